@@ -2,6 +2,9 @@ use leptos::*;
 use leptos_meta::*;
 use leptos_router::*;
 
+use crate::moderation_console::{MarketplaceModeration, TutoringModeration};
+use crate::passport_explorer::PassportExplorer;
+
 #[component]
 pub fn App() -> impl IntoView {
     provide_meta_context();
@@ -51,7 +54,7 @@ fn PassportPage() -> impl IntoView {
     view! {
         <div class="container mx-auto p-4">
             <h1 class="text-3xl font-bold mb-4">"Life Learning Passport"</h1>
-            <p>"Your verified learning journey on the blockchain."</p>
+            <PassportExplorer/>
         </div>
     }
 }
@@ -61,7 +64,7 @@ fn TutoringPage() -> impl IntoView {
     view! {
         <div class="container mx-auto p-4">
             <h1 class="text-3xl font-bold mb-4">"Tutoring Sessions"</h1>
-            <p>"Connect with human and AI tutors."</p>
+            <TutoringModeration/>
         </div>
     }
 }
@@ -71,7 +74,7 @@ fn MarketplacePage() -> impl IntoView {
     view! {
         <div class="container mx-auto p-4">
             <h1 class="text-3xl font-bold mb-4">"Safe Learning Spaces"</h1>
-            <p>"Discover community-validated learning environments."</p>
+            <MarketplaceModeration/>
         </div>
     }
 }
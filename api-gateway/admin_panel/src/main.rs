@@ -1,72 +1,380 @@
 pub mod app;
+pub mod moderation_console;
+pub mod passport_explorer;
+#[cfg(feature = "ssr")]
+pub mod services;
+
+use std::sync::Arc;
+
+use anyhow::Context as _;
 
 use app::*;
 use axum::{
     body::Body,
-    extract::{Request, State},
+    extract::{ws::WebSocketUpgrade, Extension, FromRef, Request, State},
+    middleware,
     response::{IntoResponse, Response},
     routing::{get, post},
     Router,
 };
+use governance::domain::ProposalKind;
+use governance::repository::GovernanceRepository;
+use governance::service::{GovernanceService, VocabularyExecutor};
+use identity::domain::{IssuerId, Principal};
+use identity::repository::{
+    AuthorizationRepository, HumanityRegistryRepository, IssuerRepository, ModerationAuditRepository,
+};
+use identity::service::{
+    AuthorizationService, Authorizer, FixtureProofVerifier, HumanityVerificationService, IssuerService,
+    IssuerSigner, ModerationAuditLog, PolicyEngine,
+};
+use learning_passport::domain::UnknownTermPolicy;
+use learning_passport::repository::{CompetencyRepository, LearningPassportRepository, VocabularyRepository};
+use learning_passport::service::{CompetencyService, LearningPassportService, VocabularyService};
+use marketplace::repository::{CommunityFundRepository, LearningSpaceRepository, TutoringRepository};
+use marketplace::service::{
+    CommunityFundService, LearningSpaceExecutor, LearningSpaceService, StarknetErc20Settlement, TutoringService,
+};
+use reputation::repository::{DisputeRepository, ReputationRepository};
+use reputation::service::{DisputeService, ReputationService};
+use selfstudy_guides::repository::ReviewStateRepository;
+use selfstudy_guides::service::ReviewScheduler;
+use juniper_axum::response::JuniperResponse;
 use leptos::*;
-use leptos_axum::{generate_route_list, handle_server_fns, LeptosRoutes};
-use tower_http::{cors::CorsLayer, services::ServeDir};
+use leptos_axum::{generate_route_list, handle_server_fns_with_context, LeptosRoutes};
+use tower_http::{cors::{AllowOrigin, CorsLayer}, services::ServeDir};
 use tower::util::ServiceExt; // for .oneshot
-use keiko_graphql_server::schema::create_schema;
+use keiko_graphql_server::auth::{authorization_middleware, AuthState, SessionVerifier};
+use keiko_graphql_server::backend::{Backend, InProcessBackend};
+use keiko_graphql_server::context::Context;
+use keiko_graphql_server::cache::{CacheStore, MemoryStore, RedisStore, ResponseCache};
+use keiko_graphql_server::limits::QueryLimits;
+use keiko_graphql_server::persisted::{execute_persisted, PersistedQueries, PersistedRequest};
+use keiko_graphql_server::schema::{create_schema, Schema};
+use keiko_graphql_server::subscriptions::{self, SubscriptionPolicy};
+use services::AdminServices;
+use sqlx::postgres::PgPool;
+use uuid::Uuid;
+
+/// Estado compartido por los handlers de axum
+#[cfg(feature = "ssr")]
+#[derive(Clone)]
+struct AppState {
+    leptos_options: LeptosOptions,
+    graphql_schema: Arc<Schema>,
+    authorizer: Arc<dyn Authorizer>,
+    backend: Arc<dyn Backend>,
+    query_limits: QueryLimits,
+    persisted_queries: Arc<PersistedQueries>,
+    response_cache: Option<Arc<ResponseCache>>,
+    services: AdminServices,
+}
+
+#[cfg(feature = "ssr")]
+impl FromRef<AppState> for LeptosOptions {
+    fn from_ref(state: &AppState) -> Self {
+        state.leptos_options.clone()
+    }
+}
 
 #[cfg(feature = "ssr")]
 #[tokio::main]
-async fn main() {
-    let conf = get_configuration(None).await.unwrap();
+async fn main() -> anyhow::Result<()> {
+    let conf = get_configuration(None).await.context("No se pudo leer la configuración de Leptos")?;
     let leptos_options = conf.leptos_options;
     let addr = leptos_options.site_addr;
     let routes = generate_route_list(App);
 
+    // Autorización RBAC/ABAC con auditoría de decisiones
+    let database_url = env_var("DATABASE_URL")?;
+    let pool = PgPool::connect_lazy(&database_url).context("DATABASE_URL no válida")?;
+    let authorizer: Arc<dyn Authorizer> = Arc::new(AuthorizationService::new(
+        PolicyEngine::default(),
+        AuthorizationRepository::new(pool.clone()),
+    ));
+    let auth = AuthState {
+        sessions: Arc::new(SessionVerifier::from_env().context("Tokens de sesión sin configurar")?),
+        authorizer: authorizer.clone(),
+    };
+
+    // Servicios de los módulos: resuelven el esquema GraphQL y las server functions del panel
+    let services = build_services(pool, authorizer.clone()).await?;
+    let backend: Arc<dyn Backend> = Arc::new(InProcessBackend::new(
+        services.passport.clone(),
+        services.competencies.clone(),
+        services.tutoring.clone(),
+        services.reputation.clone(),
+    ));
+
+    // Consultas persistidas y caché de respuestas, invalidada por los eventos de dominio
+    let cache_store = build_cache_store().await?;
+    let persisted_queries = Arc::new(persisted_queries_from_env(cache_store.clone())?);
+    let response_cache = response_cache_from_env(cache_store);
+    if let Some(cache) = &response_cache {
+        cache.clone().spawn_invalidation(backend.as_ref());
+    }
+
     // GraphQL Schema Integration
-    let _graphql_schema = create_schema();
+    let state = AppState {
+        leptos_options,
+        graphql_schema: Arc::new(create_schema()),
+        authorizer: authorizer.clone(),
+        backend,
+        query_limits: query_limits_from_env(),
+        persisted_queries,
+        response_cache,
+        services: services.clone(),
+    };
 
     let app = Router::new()
-        // GraphQL endpoint
-        .route("/graphql", post(graphql_handler))
+        // GraphQL endpoint (GET para consultas, POST con lotes)
+        .route("/graphql", get(graphql_handler).post(graphql_handler))
+        // Suscripciones GraphQL sobre WebSocket (graphql-transport-ws y graphql-ws)
+        .route("/subscriptions", get(subscriptions_handler))
         .route("/graphiql", get(graphiql_handler))
         // Server functions
         .route("/api/*fn_name", post(server_fn_handler))
         // Leptos routes
-        .leptos_routes(&leptos_options, routes, App)
+        .leptos_routes_with_context(&state, routes, move || provide_context(services.clone()), App)
         // Static files
         .fallback(file_and_error_handler)
-        // CORS for development
-        .layer(CorsLayer::permissive())
-        .with_state(leptos_options);
+        // Authorization for REST endpoints
+        .layer(middleware::from_fn_with_state(auth, authorization_middleware))
+        // CORS only for the configured origins
+        .layer(cors_from_env()?)
+        .with_state(state);
 
-    let listener = tokio::net::TcpListener::bind(&addr).await.unwrap();
-    println!("Listening on http://{}", &addr);
-    axum::serve(listener, app.into_make_service())
+    let listener = tokio::net::TcpListener::bind(&addr)
         .await
-        .unwrap();
+        .with_context(|| format!("No se pudo escuchar en {}", addr))?;
+    println!("Listening on http://{}", &addr);
+    axum::serve(listener, app.into_make_service()).await.context("El servidor terminó con un error")?;
+
+    Ok(())
+}
+
+/// Variable de entorno obligatoria
+#[cfg(feature = "ssr")]
+fn env_var(name: &str) -> anyhow::Result<String> {
+    std::env::var(name).with_context(|| format!("{} no configurada", name))
 }
 
+/// CORS para los orígenes de `CORS_ALLOWED_ORIGINS` (separados por comas)
+///
+/// Sin configurar no se admite ningún origen cruzado: el panel se sirve desde el propio gateway.
 #[cfg(feature = "ssr")]
-async fn server_fn_handler(
-    State(_leptos_options): State<LeptosOptions>,
-    _request: Request<Body>,
-) -> impl IntoResponse {
-    handle_server_fns(_request).await
+fn cors_from_env() -> anyhow::Result<CorsLayer> {
+    use axum::http::{header, HeaderValue, Method};
+
+    let origins = std::env::var("CORS_ALLOWED_ORIGINS")
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|origin| !origin.is_empty())
+        .map(|origin| HeaderValue::from_str(origin).with_context(|| format!("Origen CORS no válido: {}", origin)))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    Ok(CorsLayer::new()
+        .allow_origin(AllowOrigin::list(origins))
+        .allow_methods([Method::GET, Method::POST])
+        .allow_headers([header::AUTHORIZATION, header::CONTENT_TYPE]))
+}
+
+/// Límites de las consultas GraphQL; sin configurar se usan los valores por defecto
+#[cfg(feature = "ssr")]
+fn query_limits_from_env() -> QueryLimits {
+    let defaults = QueryLimits::default();
+    let var = |name: &str, default: usize| {
+        std::env::var(name).ok().and_then(|v| v.parse().ok()).unwrap_or(default)
+    };
+
+    QueryLimits {
+        max_depth: var("GRAPHQL_MAX_DEPTH", defaults.max_depth),
+        max_complexity: var("GRAPHQL_MAX_COMPLEXITY", defaults.max_complexity),
+        max_batch_size: var("GRAPHQL_MAX_BATCH_SIZE", defaults.max_batch_size),
+    }
 }
 
+/// Almacén de la caché del gateway: Redis si está configurado; si no, el del proceso (solo desarrollo)
+#[cfg(feature = "ssr")]
+async fn build_cache_store() -> anyhow::Result<Arc<dyn CacheStore>> {
+    Ok(match std::env::var("REDIS_URL") {
+        Ok(url) => Arc::new(RedisStore::connect(&url, "keiko:gql:").await.context("No se pudo conectar a Redis")?),
+        Err(_) => Arc::new(MemoryStore::default()),
+    })
+}
+
+/// Con un manifiesto solo se ejecutan sus documentos (apps móviles en producción);
+/// sin él, los clientes registran sus consultas con APQ
+#[cfg(feature = "ssr")]
+fn persisted_queries_from_env(store: Arc<dyn CacheStore>) -> anyhow::Result<PersistedQueries> {
+    match std::env::var("GRAPHQL_PERSISTED_QUERIES_MANIFEST") {
+        Ok(path) => {
+            let manifest = std::fs::read_to_string(&path)
+                .with_context(|| format!("No se pudo leer el manifiesto {}", path))?;
+            PersistedQueries::allow_list_from_manifest(&manifest)
+                .context("Manifiesto de consultas persistidas no válido")
+        }
+        Err(_) => Ok(PersistedQueries::Automatic(store)),
+    }
+}
+
+/// Caché de respuestas por campo; un TTL de 0 la desactiva
+#[cfg(feature = "ssr")]
+fn response_cache_from_env(store: Arc<dyn CacheStore>) -> Option<Arc<ResponseCache>> {
+    let ttl = std::env::var("GRAPHQL_RESPONSE_CACHE_TTL_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(300);
+
+    (ttl > 0).then(|| Arc::new(ResponseCache::new(store, std::time::Duration::from_secs(ttl))))
+}
+
+/// Construir los servicios de los módulos dentro del mismo proceso
+#[cfg(feature = "ssr")]
+async fn build_services(pool: PgPool, authorizer: Arc<dyn Authorizer>) -> anyhow::Result<AdminServices> {
+    let humanity = Arc::new(HumanityVerificationService::new(
+        Box::new(
+            FixtureProofVerifier::from_file(env_var("HUMANITY_PROOF_FIXTURES")?)
+                .context("No se pudieron cargar las pruebas de humanidad")?,
+        ),
+        HumanityRegistryRepository::new(pool.clone()),
+    ));
+    let issuers = Arc::new(IssuerService::new(IssuerRepository::new(pool.clone())));
+    let platform_issuer = Uuid::parse_str(&env_var("PLATFORM_ISSUER_ID")?).context("PLATFORM_ISSUER_ID no válido")?;
+    let platform_signer = IssuerSigner::from_hex(
+        IssuerId(platform_issuer),
+        &env_var("PLATFORM_ISSUER_KEY_ID")?,
+        &env_var("PLATFORM_ISSUER_SECRET")?,
+    )
+    .context("Clave del emisor de la plataforma no válida")?;
+    let vocabulary = Arc::new(
+        VocabularyService::load(VocabularyRepository::new(pool.clone()), UnknownTermPolicy::Warn)
+            .await
+            .context("No se pudo cargar el vocabulario xAPI")?,
+    );
+    vocabulary.clone().spawn_change_listener().await.context("No se pudo escuchar los cambios del vocabulario")?;
+
+    let passport = Arc::new(LearningPassportService::new(
+        LearningPassportRepository::new(pool.clone()),
+        humanity.clone(),
+        issuers,
+        platform_signer,
+        vocabulary.clone(),
+    ));
+    let competencies = Arc::new(CompetencyService::new(CompetencyRepository::new(pool.clone()), passport.clone()));
+    let tutoring = Arc::new(TutoringService::new(
+        TutoringRepository::new(pool.clone()),
+        humanity.clone(),
+        passport.clone(),
+    ));
+    let spaces = Arc::new(LearningSpaceService::new(LearningSpaceRepository::new(pool.clone()), humanity.clone()));
+    let reputation = Arc::new(ReputationService::new(
+        ReputationRepository::new(pool.clone()),
+        humanity.clone(),
+        tutoring.clone(),
+    ));
+    reputation.clone().spawn_passport_ingestion(passport.subscribe());
+    let reviews = Arc::new(ReviewScheduler::new(ReviewStateRepository::new(pool.clone()), passport.clone()));
+    reviews.spawn_passport_sync(passport.subscribe());
+
+    // El fondo comunitario es una cuenta propia en el token de liquidación
+    let fund_settlement = StarknetErc20Settlement::new(
+        &env_var("STARKNET_RPC_URL")?,
+        &env_var("STARKNET_CHAIN_ID")?,
+        &env_var("SETTLEMENT_TOKEN_ADDRESS")?,
+        &env_var("COMMUNITY_FUND_ACCOUNT")?,
+        &env_var("COMMUNITY_FUND_PRIVATE_KEY")?,
+        env_var("STARKNET_MAX_FEE")?.parse().context("STARKNET_MAX_FEE no válido")?,
+    )
+    .context("Configuración del fondo comunitario no válida")?;
+    let fund = Arc::new(CommunityFundService::new(
+        CommunityFundRepository::new(pool.clone()),
+        Arc::new(fund_settlement),
+    ));
+
+    let mut governance = GovernanceService::new(
+        GovernanceRepository::new(pool.clone()),
+        humanity.clone(),
+        reputation.clone(),
+    );
+    governance.register_executor(ProposalKind::AddVocabularyEntries, Arc::new(VocabularyExecutor::new(vocabulary)));
+    let space_executor = Arc::new(LearningSpaceExecutor::new(spaces.clone()));
+    governance.register_executor(ProposalKind::ApproveLearningSpace, space_executor.clone());
+    governance.register_executor(ProposalKind::SuspendLearningSpace, space_executor);
+    governance.register_community_fund(fund);
+    let governance = Arc::new(governance);
+
+    let disputes = Arc::new(DisputeService::new(
+        DisputeRepository::new(pool.clone()),
+        reputation.clone(),
+        humanity,
+        authorizer,
+    ));
+    let moderation_audit = Arc::new(ModerationAuditLog::new(ModerationAuditRepository::new(pool)));
+
+    Ok(AdminServices { passport, competencies, tutoring, spaces, reputation, disputes, governance, moderation_audit })
+}
+
+/// Ejecutar una server function con los servicios de los módulos como contexto
+#[cfg(feature = "ssr")]
+async fn server_fn_handler(State(state): State<AppState>, request: Request<Body>) -> impl IntoResponse {
+    handle_server_fns_with_context(move || provide_context(state.services.clone()), request).await
+}
+
+/// Ejecutar peticiones GraphQL (una o un lote) con el contexto del usuario autenticado
+///
+/// El `Principal` lo deja en las extensiones el middleware de autorización; sin él
+/// la petición se resuelve como anónima y cada campo decide si lo permite.
 #[cfg(feature = "ssr")]
 async fn graphql_handler(
-    State(_leptos_options): State<LeptosOptions>,
-    _request: Request<Body>,
-) -> impl IntoResponse {
-    // GraphQL request handling
-    // This would integrate with Juniper's request handling
-    "GraphQL endpoint - TODO: implement Juniper integration"
+    State(state): State<AppState>,
+    principal: Option<Extension<Principal>>,
+    PersistedRequest(request): PersistedRequest,
+) -> JuniperResponse {
+    let context = Context::new(
+        principal.map(|Extension(principal)| principal),
+        state.authorizer.clone(),
+        state.backend.clone(),
+        state.response_cache.clone(),
+    );
+
+    JuniperResponse(
+        execute_persisted(&state.graphql_schema, request, &context, &state.persisted_queries, &state.query_limits)
+            .await,
+    )
+}
+
+/// Abrir una conexión de suscripciones con el contexto del usuario autenticado
+///
+/// La autenticación llega en las cabeceras de la petición de upgrade; cada suscripción
+/// se autoriza al iniciarse y solo recibe los eventos de su usuario. Las operaciones siguen
+/// la misma política de consultas persistidas y límites que `/graphql`.
+#[cfg(feature = "ssr")]
+async fn subscriptions_handler(
+    State(state): State<AppState>,
+    principal: Option<Extension<Principal>>,
+    ws: WebSocketUpgrade,
+) -> Response {
+    let context = Context::new(
+        principal.map(|Extension(principal)| principal),
+        state.authorizer.clone(),
+        state.backend.clone(),
+        state.response_cache.clone(),
+    );
+
+    let policy = SubscriptionPolicy {
+        schema: state.graphql_schema,
+        persisted: state.persisted_queries,
+        limits: state.query_limits,
+    };
+
+    ws.protocols(subscriptions::PROTOCOLS).on_upgrade(move |socket| subscriptions::serve(socket, policy, context))
 }
 
 #[cfg(feature = "ssr")]
 async fn graphiql_handler() -> impl IntoResponse {
-    axum::response::Html(juniper::http::graphiql::graphiql_source("/graphql", None))
+    axum::response::Html(juniper::http::graphiql::graphiql_source("/graphql", Some("/subscriptions")))
 }
 
 #[cfg(feature = "ssr")]
@@ -0,0 +1,942 @@
+// Consola de moderación del panel de administración
+// Sesiones de tutoría, disputas e inasistencias abiertas, espacios reportados y tutores pendientes de verificación
+
+use leptos::*;
+use serde::{Deserialize, Serialize};
+
+/// Sesiones por página del listado de moderación
+pub const SESSIONS_PAGE_SIZE: i64 = 25;
+
+/// Acciones recientes que muestra el log de auditoría
+pub const AUDIT_LOG_LIMIT: i64 = 50;
+
+/// Estados de sesión que se pueden filtrar, con su etiqueta
+pub const SESSION_STATUSES: [(&str, &str); 6] = [
+    ("requested", "Requested"),
+    ("accepted", "Accepted"),
+    ("in_progress", "In progress"),
+    ("completed", "Completed"),
+    ("cancelled", "Cancelled"),
+    ("no_show", "No-show"),
+];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionRow {
+    pub id: String,
+    pub tutor: String,
+    pub learner: String,
+    pub subject: String,
+    pub scheduled_start: String,
+    pub status: String,
+    pub cancellation_reason: Option<String>,
+    pub requested_at: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionPage {
+    pub total_count: i64,
+    pub page: i64,
+    pub sessions: Vec<SessionRow>,
+}
+
+impl SessionPage {
+    pub fn page_count(&self) -> i64 {
+        ((self.total_count + SESSIONS_PAGE_SIZE - 1) / SESSIONS_PAGE_SIZE).max(1)
+    }
+}
+
+/// Inasistencia reportada en una sesión aceptada, a la espera de confirmación o en disputa
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NoShowRow {
+    pub session_id: String,
+    pub tutor: String,
+    pub learner: String,
+    pub scheduled_start: String,
+    pub absent_party: String,   // Parte señalada como ausente ("tutor" o "learner")
+    pub reported_at: String,
+    pub disputed: bool,
+}
+
+/// Disputa abierta sobre una calificación
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DisputeRow {
+    pub id: String,
+    pub rater: String,
+    pub ratee: String,
+    pub opened_by: String,
+    pub reason: String,
+    pub status: String,
+    pub adjudication: String,
+    pub evidence_count: usize,
+    pub opened_at: String,
+    pub can_close_evidence: bool,   // Sigue recogiendo pruebas
+    pub can_decide: bool,           // Pruebas completas y la decide un moderador
+}
+
+/// Espacio en revisión con los reportes recibidos desde su último cambio de estado
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpaceRow {
+    pub id: String,
+    pub name: String,
+    pub host: String,
+    pub address: String,
+    pub status_reason: Option<String>,
+    pub status_changed_at: String,
+    pub reports: Vec<String>,
+}
+
+/// Tutor pendiente de verificación
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TutorRow {
+    pub user_address: String,
+    pub display_name: String,
+    pub bio: String,
+    pub subjects: Vec<String>,
+    pub languages: Vec<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditRow {
+    pub moderator: String,
+    pub action: String,
+    pub resource: String,
+    pub reason: Option<String>,
+    pub succeeded: Option<bool>,
+    pub error: Option<String>,
+    pub timestamp: String,
+}
+
+#[cfg(feature = "ssr")]
+mod convert {
+    use identity::domain::ModerationAuditEntry;
+    use marketplace::domain::{LearningSpace, SpaceValidation, TutorProfile, TutoringSession, ValidationKind};
+    use reputation::domain::{Adjudication, Dispute, DisputeStatus};
+
+    use super::*;
+    use crate::services::TIMESTAMP_FORMAT;
+
+    impl From<TutoringSession> for SessionRow {
+        fn from(session: TutoringSession) -> Self {
+            Self {
+                id: session.id.0.to_string(),
+                tutor: session.tutor,
+                learner: session.learner,
+                subject: session.subject.name,
+                scheduled_start: session.scheduled_start.format(TIMESTAMP_FORMAT).to_string(),
+                status: session.status.as_str().to_string(),
+                cancellation_reason: session.cancellation_reason,
+                requested_at: session.requested_at.format(TIMESTAMP_FORMAT).to_string(),
+            }
+        }
+    }
+
+    /// Fila de la cola de inasistencias; `None` si la sesión ya no tiene reporte
+    pub fn no_show_row(session: TutoringSession) -> Option<NoShowRow> {
+        let report = session.no_show_report?;
+        Some(NoShowRow {
+            session_id: session.id.0.to_string(),
+            tutor: session.tutor,
+            learner: session.learner,
+            scheduled_start: session.scheduled_start.format(TIMESTAMP_FORMAT).to_string(),
+            absent_party: report.absent_party.as_str().to_string(),
+            reported_at: report.reported_at.format(TIMESTAMP_FORMAT).to_string(),
+            disputed: report.disputed,
+        })
+    }
+
+    impl From<Dispute> for DisputeRow {
+        fn from(dispute: Dispute) -> Self {
+            let decided_by_moderator = dispute.adjudication == Adjudication::Moderator;
+            Self {
+                id: dispute.id.0.to_string(),
+                rater: dispute.rater,
+                ratee: dispute.ratee,
+                opened_by: dispute.opened_by,
+                reason: dispute.reason,
+                status: dispute.status.as_str().to_string(),
+                adjudication: match &dispute.adjudication {
+                    Adjudication::Moderator => "moderator".to_string(),
+                    Adjudication::Jury { jurors } => format!("jury ({} votes of {})", dispute.votes.len(), jurors.len()),
+                },
+                evidence_count: dispute.evidence.len(),
+                opened_at: dispute.opened_at.format(TIMESTAMP_FORMAT).to_string(),
+                can_close_evidence: dispute.status == DisputeStatus::Flagged,
+                can_decide: decided_by_moderator && dispute.status == DisputeStatus::EvidenceComplete,
+            }
+        }
+    }
+
+    impl From<TutorProfile> for TutorRow {
+        fn from(profile: TutorProfile) -> Self {
+            Self {
+                user_address: profile.user_address,
+                display_name: profile.display_name,
+                bio: profile.bio,
+                subjects: profile.subjects.into_iter().map(|subject| subject.name).collect(),
+                languages: profile.languages,
+                created_at: profile.created_at.format(TIMESTAMP_FORMAT).to_string(),
+            }
+        }
+    }
+
+    impl From<ModerationAuditEntry> for AuditRow {
+        fn from(entry: ModerationAuditEntry) -> Self {
+            Self {
+                moderator: entry.moderator,
+                action: entry.action,
+                resource: format!("{} {}", entry.resource_kind.as_str(), entry.resource_id),
+                reason: entry.reason,
+                succeeded: entry.succeeded,
+                error: entry.error,
+                timestamp: entry.timestamp.format(TIMESTAMP_FORMAT).to_string(),
+            }
+        }
+    }
+
+    /// Espacio con los reportes que lo pusieron en revisión
+    pub fn space_row(space: LearningSpace, validations: Vec<SpaceValidation>) -> SpaceRow {
+        let reports = validations
+            .into_iter()
+            .filter(|v| v.kind == ValidationKind::Report && v.created_at >= space.status_changed_at)
+            .map(|v| format!("{}: {}", v.member, v.comment))
+            .collect();
+
+        SpaceRow {
+            id: space.id.0.to_string(),
+            name: space.details.name,
+            host: space.host,
+            address: space.details.address,
+            status_reason: space.status_reason,
+            status_changed_at: space.status_changed_at.format(TIMESTAMP_FORMAT).to_string(),
+            reports,
+        }
+    }
+}
+
+#[cfg(feature = "ssr")]
+fn parse_id(kind: &str, id: &str) -> Result<uuid::Uuid, ServerFnError> {
+    uuid::Uuid::parse_str(id).map_err(|_| ServerFnError::new(format!("ID de {} no válido: {}", kind, id)))
+}
+
+/// Página de las sesiones de tutoría de todos los usuarios, de la solicitud más reciente a la más antigua
+#[server(ListModerationSessions, "/api", endpoint = "list_moderation_sessions")]
+pub async fn list_moderation_sessions(status: Option<String>, page: i64) -> Result<SessionPage, ServerFnError> {
+    use marketplace::domain::{SessionFilter, SessionOrder, SessionOrderField};
+
+    use crate::services::{admin_services, service_error};
+
+    let tutoring = admin_services()?.tutoring;
+    let statuses = status.map(|s| s.parse()).transpose().map_err(service_error)?;
+    let filter = SessionFilter { statuses: statuses.into_iter().collect(), ..SessionFilter::default() };
+    let order = SessionOrder { field: SessionOrderField::RequestedAt, descending: true };
+    let page = page.max(0);
+
+    let total_count = tutoring.count_sessions(&filter).await.map_err(service_error)?;
+    let sessions = tutoring
+        .list_sessions(&filter, order, SESSIONS_PAGE_SIZE, page * SESSIONS_PAGE_SIZE)
+        .await
+        .map_err(service_error)?;
+
+    Ok(SessionPage { total_count, page, sessions: sessions.into_iter().map(SessionRow::from).collect() })
+}
+
+/// Disputas sin resolver: recogiendo pruebas o pendientes de decisión
+#[server(ListOpenDisputes, "/api", endpoint = "list_open_disputes")]
+pub async fn list_open_disputes() -> Result<Vec<DisputeRow>, ServerFnError> {
+    use reputation::domain::DisputeStatus;
+
+    use crate::services::{admin_services, service_error};
+
+    let disputes = admin_services()?.disputes;
+    let mut open = Vec::new();
+    for status in [DisputeStatus::EvidenceComplete, DisputeStatus::Flagged] {
+        open.extend(disputes.get_disputes_by_status(status).await.map_err(service_error)?);
+    }
+
+    Ok(open.into_iter().map(DisputeRow::from).collect())
+}
+
+/// Inasistencias reportadas sin confirmar, de la más antigua a la más reciente
+#[server(ListNoShowReports, "/api", endpoint = "list_no_show_reports")]
+pub async fn list_no_show_reports() -> Result<Vec<NoShowRow>, ServerFnError> {
+    use crate::services::{admin_services, service_error};
+
+    let sessions = admin_services()?.tutoring.get_no_show_reports().await.map_err(service_error)?;
+
+    Ok(sessions.into_iter().filter_map(convert::no_show_row).collect())
+}
+
+/// Espacios puestos en revisión por reportes de la comunidad
+#[server(ListReportedSpaces, "/api", endpoint = "list_reported_spaces")]
+pub async fn list_reported_spaces() -> Result<Vec<SpaceRow>, ServerFnError> {
+    use marketplace::domain::SpaceStatus;
+
+    use crate::services::{admin_services, service_error};
+
+    let spaces = admin_services()?.spaces;
+    let mut rows = Vec::new();
+    for space in spaces.get_spaces_by_status(SpaceStatus::UnderReview).await.map_err(service_error)? {
+        let validations = spaces.get_validations(&space.id).await.map_err(service_error)?;
+        rows.push(convert::space_row(space, validations));
+    }
+
+    Ok(rows)
+}
+
+/// Tutores pendientes de verificación, del más antiguo al más reciente
+#[server(ListPendingTutors, "/api", endpoint = "list_pending_tutors")]
+pub async fn list_pending_tutors() -> Result<Vec<TutorRow>, ServerFnError> {
+    use marketplace::domain::TutorVerification;
+
+    use crate::services::{admin_services, service_error};
+
+    let tutors = admin_services()?
+        .tutoring
+        .get_tutors_by_verification(TutorVerification::Pending)
+        .await
+        .map_err(service_error)?;
+
+    Ok(tutors.into_iter().map(TutorRow::from).collect())
+}
+
+/// Últimas acciones de moderación registradas
+#[server(ListModerationActions, "/api", endpoint = "list_moderation_actions")]
+pub async fn list_moderation_actions() -> Result<Vec<AuditRow>, ServerFnError> {
+    use crate::services::{admin_services, service_error};
+
+    let entries = admin_services()?.moderation_audit.recent(AUDIT_LOG_LIMIT).await.map_err(service_error)?;
+
+    Ok(entries.into_iter().map(AuditRow::from).collect())
+}
+
+/// Resolver una inasistencia reportada: `absent_party` cierra la sesión como inasistencia
+/// de esa parte ("tutor" o "learner"); sin ella se descarta el reporte
+#[server(ResolveNoShow, "/api", endpoint = "resolve_no_show")]
+pub async fn resolve_no_show(
+    session_id: String,
+    absent_party: Option<String>,
+    reason: Option<String>,
+) -> Result<(), ServerFnError> {
+    use identity::domain::ResourceKind;
+    use marketplace::domain::SessionId;
+
+    use crate::services::{admin_services, audited, moderator_address, service_error};
+
+    let id = SessionId(parse_id("sesión", &session_id)?);
+    let absent_party = absent_party.map(|party| party.parse()).transpose().map_err(service_error)?;
+    let moderator = moderator_address().await?;
+    let tutoring = admin_services()?.tutoring;
+    let run = tutoring.resolve_no_show(&id, absent_party, &moderator);
+    audited("resolve_no_show", ResourceKind::TutoringSession, &session_id, reason, run).await?;
+
+    Ok(())
+}
+
+/// Aprobar a un tutor: aparece en las búsquedas y puede recibir reservas
+#[server(ApproveTutor, "/api", endpoint = "approve_tutor")]
+pub async fn approve_tutor(user_address: String, reason: Option<String>) -> Result<(), ServerFnError> {
+    use identity::domain::ResourceKind;
+    use marketplace::domain::TutorVerification;
+
+    use crate::services::{admin_services, audited};
+
+    let tutoring = admin_services()?.tutoring;
+    let run = tutoring.set_tutor_verification(&user_address, TutorVerification::Approved);
+    audited("approve_tutor", ResourceKind::TutorProfile, &user_address, reason, run).await?;
+
+    Ok(())
+}
+
+/// Suspender a un tutor: deja de aparecer en las búsquedas y de recibir reservas
+#[server(SuspendTutor, "/api", endpoint = "suspend_tutor")]
+pub async fn suspend_tutor(user_address: String, reason: Option<String>) -> Result<(), ServerFnError> {
+    use identity::domain::ResourceKind;
+    use marketplace::domain::TutorVerification;
+
+    use crate::services::{admin_services, audited};
+
+    let tutoring = admin_services()?.tutoring;
+    let run = tutoring.set_tutor_verification(&user_address, TutorVerification::Suspended);
+    audited("suspend_tutor", ResourceKind::TutorProfile, &user_address, reason, run).await?;
+
+    Ok(())
+}
+
+/// Aprobar un espacio en revisión, descartando los reportes recibidos
+#[server(ApproveSpace, "/api", endpoint = "approve_space")]
+pub async fn approve_space(space_id: String, reason: Option<String>) -> Result<(), ServerFnError> {
+    use identity::domain::ResourceKind;
+    use marketplace::domain::{LearningSpaceId, SpaceStatus};
+
+    use crate::services::{admin_services, audited};
+
+    let id = LearningSpaceId(parse_id("espacio", &space_id)?);
+    let spaces = admin_services()?.spaces;
+    let run = spaces.apply_moderation_decision(&id, SpaceStatus::Verified, reason.clone());
+    audited("approve_space", ResourceKind::LearningSpace, &space_id, reason, run).await?;
+
+    Ok(())
+}
+
+/// Suspender un espacio; reactivarlo requiere una propuesta de gobernanza
+#[server(SuspendSpace, "/api", endpoint = "suspend_space")]
+pub async fn suspend_space(space_id: String, reason: Option<String>) -> Result<(), ServerFnError> {
+    use identity::domain::ResourceKind;
+    use marketplace::domain::{LearningSpaceId, SpaceStatus};
+
+    use crate::services::{admin_services, audited};
+
+    let id = LearningSpaceId(parse_id("espacio", &space_id)?);
+    let spaces = admin_services()?.spaces;
+    let run = spaces.apply_moderation_decision(&id, SpaceStatus::Suspended, reason.clone());
+    audited("suspend_space", ResourceKind::LearningSpace, &space_id, reason, run).await?;
+
+    Ok(())
+}
+
+/// Cerrar el plazo de pruebas de una disputa aunque alguna parte no haya respondido
+#[server(CloseDisputeEvidence, "/api", endpoint = "close_dispute_evidence")]
+pub async fn close_dispute_evidence(dispute_id: String, reason: Option<String>) -> Result<(), ServerFnError> {
+    use identity::domain::ResourceKind;
+    use reputation::domain::DisputeId;
+
+    use crate::services::{admin_services, audited, moderator_address};
+
+    let id = DisputeId(parse_id("disputa", &dispute_id)?);
+    let services = admin_services()?;
+    let moderator = moderator_address().await?;
+    let run = services.disputes.close_evidence(&id, &moderator);
+    audited("close_dispute_evidence", ResourceKind::Dispute, &dispute_id, reason, run).await?;
+
+    Ok(())
+}
+
+/// Resolver una disputa de moderación: `upheld`, `removed` o `reduced` con las nuevas estrellas
+#[server(ResolveDispute, "/api", endpoint = "resolve_dispute")]
+pub async fn resolve_dispute(
+    dispute_id: String,
+    outcome: String,
+    stars: Option<u8>,
+    reason: Option<String>,
+) -> Result<(), ServerFnError> {
+    use identity::domain::ResourceKind;
+    use reputation::domain::{DisputeId, DisputeOutcome};
+
+    use crate::services::{admin_services, audited, moderator_address};
+
+    let id = DisputeId(parse_id("disputa", &dispute_id)?);
+    let outcome = match (outcome.as_str(), stars) {
+        ("upheld", _) => DisputeOutcome::Upheld,
+        ("removed", _) => DisputeOutcome::Removed,
+        ("reduced", Some(stars)) => DisputeOutcome::Reduced { stars },
+        ("reduced", None) => return Err(ServerFnError::new("Una reducción debe indicar las nuevas estrellas")),
+        (other, _) => return Err(ServerFnError::new(format!("Resultado de disputa desconocido: {}", other))),
+    };
+    let services = admin_services()?;
+    let moderator = moderator_address().await?;
+    let run = services.disputes.decide(&id, &moderator, outcome);
+    audited("resolve_dispute", ResourceKind::Dispute, &dispute_id, reason, run).await?;
+
+    Ok(())
+}
+
+/// Moderación de tutorías: sesiones de todos los usuarios y disputas abiertas
+///
+/// Los listados se cargan desde el navegador (`create_local_resource`), así que siempre
+/// pasan por `/api`, donde el middleware exige el permiso de moderación.
+#[component]
+pub fn TutoringModeration() -> impl IntoView {
+    let (status, set_status) = create_signal(None::<String>);
+    let (page, set_page) = create_signal(0i64);
+    let close_evidence = create_action(|(id, reason): &(String, Option<String>)| {
+        close_dispute_evidence(id.clone(), reason.clone())
+    });
+    let resolve = create_action(|(id, outcome, stars, reason): &(String, String, Option<u8>, Option<String>)| {
+        resolve_dispute(id.clone(), outcome.clone(), *stars, reason.clone())
+    });
+    let resolve_absence = create_action(|(id, absent_party, reason): &(String, Option<String>, Option<String>)| {
+        resolve_no_show(id.clone(), absent_party.clone(), reason.clone())
+    });
+
+    let sessions = create_local_resource(
+        move || (status.get(), page.get()),
+        |(status, page)| list_moderation_sessions(status, page),
+    );
+    let disputes = create_local_resource(
+        move || (close_evidence.version().get(), resolve.version().get()),
+        |_| list_open_disputes(),
+    );
+    let no_shows = create_local_resource(move || resolve_absence.version().get(), |_| list_no_show_reports());
+
+    view! {
+        <section class="mb-10">
+            <div class="flex items-center justify-between mb-4">
+                <h2 class="text-2xl font-bold">"Sessions"</h2>
+                <select
+                    class="border rounded px-2 py-1"
+                    on:change=move |ev| {
+                        let value = event_target_value(&ev);
+                        set_page.set(0);
+                        set_status.set((!value.is_empty()).then_some(value));
+                    }
+                >
+                    <option value="">"All statuses"</option>
+                    {SESSION_STATUSES
+                        .iter()
+                        .map(|(value, label)| view! { <option value=*value>{*label}</option> })
+                        .collect_view()}
+                </select>
+            </div>
+            <Transition fallback=|| view! { <p>"Loading sessions…"</p> }>
+                {move || sessions.get().map(|result| match result {
+                    Err(err) => view! { <p class="text-red-600">{err.to_string()}</p> }.into_view(),
+                    Ok(session_page) => view! {
+                        <SessionTable session_page=session_page on_page=move |page| set_page.set(page)/>
+                    }
+                    .into_view(),
+                })}
+            </Transition>
+        </section>
+
+        <section class="mb-10">
+            <h2 class="text-2xl font-bold mb-4">"Open disputes"</h2>
+            <ActionError action_value=close_evidence.value()/>
+            <ActionError action_value=resolve.value()/>
+            <Transition fallback=|| view! { <p>"Loading disputes…"</p> }>
+                {move || disputes.get().map(|result| match result {
+                    Err(err) => view! { <p class="text-red-600">{err.to_string()}</p> }.into_view(),
+                    Ok(disputes) if disputes.is_empty() => {
+                        view! { <p class="text-gray-500">"No open disputes."</p> }.into_view()
+                    }
+                    Ok(disputes) => disputes
+                        .into_iter()
+                        .map(|dispute| view! {
+                            <DisputeCard
+                                dispute=dispute
+                                on_close_evidence=move |args| close_evidence.dispatch(args)
+                                on_resolve=move |args| resolve.dispatch(args)
+                            />
+                        })
+                        .collect_view(),
+                })}
+            </Transition>
+        </section>
+
+        <section class="mb-10">
+            <h2 class="text-2xl font-bold mb-4">"Reported no-shows"</h2>
+            <ActionError action_value=resolve_absence.value()/>
+            <Transition fallback=|| view! { <p>"Loading no-show reports…"</p> }>
+                {move || no_shows.get().map(|result| match result {
+                    Err(err) => view! { <p class="text-red-600">{err.to_string()}</p> }.into_view(),
+                    Ok(reports) if reports.is_empty() => {
+                        view! { <p class="text-gray-500">"No unconfirmed no-shows."</p> }.into_view()
+                    }
+                    Ok(reports) => reports
+                        .into_iter()
+                        .map(|report| view! {
+                            <NoShowCard report=report on_resolve=move |args| resolve_absence.dispatch(args)/>
+                        })
+                        .collect_view(),
+                })}
+            </Transition>
+        </section>
+
+        <ModerationLog refresh=Signal::derive(move || {
+            close_evidence.version().get() + resolve.version().get() + resolve_absence.version().get()
+        })/>
+    }
+}
+
+#[component]
+fn SessionTable(session_page: SessionPage, #[prop(into)] on_page: Callback<i64>) -> impl IntoView {
+    let page = session_page.page;
+    let page_count = session_page.page_count();
+
+    view! {
+        <table class="w-full text-sm">
+            <thead>
+                <tr class="text-left border-b">
+                    <th class="py-2">"Requested"</th>
+                    <th>"Scheduled"</th>
+                    <th>"Subject"</th>
+                    <th>"Tutor"</th>
+                    <th>"Learner"</th>
+                    <th>"Status"</th>
+                </tr>
+            </thead>
+            <tbody>
+                {session_page
+                    .sessions
+                    .into_iter()
+                    .map(|session| view! {
+                        <tr class="border-b">
+                            <td class="py-2 whitespace-nowrap">{session.requested_at}</td>
+                            <td class="whitespace-nowrap">{session.scheduled_start}</td>
+                            <td>{session.subject}</td>
+                            <td class="font-mono truncate">{session.tutor}</td>
+                            <td class="font-mono truncate">{session.learner}</td>
+                            <td title=session.cancellation_reason.unwrap_or_default()>{session.status}</td>
+                        </tr>
+                    })
+                    .collect_view()}
+            </tbody>
+        </table>
+        <div class="flex items-center justify-between mt-4">
+            <button
+                class="text-blue-500 hover:underline disabled:text-gray-400"
+                prop:disabled=page == 0
+                on:click=move |_| on_page.call(page - 1)
+            >
+                "Previous"
+            </button>
+            <span>{format!("Page {} of {} ({} sessions)", page + 1, page_count, session_page.total_count)}</span>
+            <button
+                class="text-blue-500 hover:underline disabled:text-gray-400"
+                prop:disabled=page + 1 >= page_count
+                on:click=move |_| on_page.call(page + 1)
+            >
+                "Next"
+            </button>
+        </div>
+    }
+}
+
+#[component]
+fn DisputeCard(
+    dispute: DisputeRow,
+    #[prop(into)] on_close_evidence: Callback<(String, Option<String>)>,
+    #[prop(into)] on_resolve: Callback<(String, String, Option<u8>, Option<String>)>,
+) -> impl IntoView {
+    let (reason, set_reason) = create_signal(String::new());
+    let (outcome, set_outcome) = create_signal("upheld".to_string());
+    let (stars, set_stars) = create_signal(None::<u8>);
+    let optional_reason = move || Some(reason.get()).filter(|r| !r.trim().is_empty());
+    let close_id = dispute.id.clone();
+    let resolve_id = dispute.id.clone();
+
+    view! {
+        <div class="border rounded p-4 mb-4">
+            <div class="flex justify-between text-sm text-gray-500">
+                <span class="font-mono">{dispute.id.clone()}</span>
+                <span>{format!("{} · {} · opened {}", dispute.status, dispute.adjudication, dispute.opened_at)}</span>
+            </div>
+            <p class="my-2">{dispute.reason}</p>
+            <p class="text-sm">
+                "Rater " <span class="font-mono">{dispute.rater}</span>
+                " · ratee " <span class="font-mono">{dispute.ratee}</span>
+                " · opened by " <span class="font-mono">{dispute.opened_by}</span>
+                {format!(" · {} evidence item(s)", dispute.evidence_count)}
+            </p>
+            <div class="flex flex-wrap gap-2 mt-3">
+                <input
+                    type="text"
+                    class="flex-1 border rounded px-2 py-1"
+                    placeholder="Reason (audit log)"
+                    on:input=move |ev| set_reason.set(event_target_value(&ev))
+                />
+                {dispute.can_close_evidence.then(|| view! {
+                    <button
+                        class="bg-gray-700 hover:bg-gray-900 text-white py-1 px-3 rounded"
+                        on:click=move |_| on_close_evidence.call((close_id.clone(), optional_reason()))
+                    >
+                        "Close evidence"
+                    </button>
+                })}
+                {dispute.can_decide.then(|| view! {
+                    <select class="border rounded px-2 py-1" on:change=move |ev| set_outcome.set(event_target_value(&ev))>
+                        <option value="upheld">"Uphold rating"</option>
+                        <option value="reduced">"Reduce stars"</option>
+                        <option value="removed">"Remove rating"</option>
+                    </select>
+                    <input
+                        type="number"
+                        min="1"
+                        max="4"
+                        class="w-20 border rounded px-2 py-1"
+                        placeholder="Stars"
+                        prop:disabled=move || outcome.get() != "reduced"
+                        on:input=move |ev| set_stars.set(event_target_value(&ev).parse().ok())
+                    />
+                    <button
+                        class="bg-blue-500 hover:bg-blue-700 text-white py-1 px-3 rounded"
+                        on:click=move |_| {
+                            on_resolve.call((resolve_id.clone(), outcome.get(), stars.get(), optional_reason()))
+                        }
+                    >
+                        "Resolve"
+                    </button>
+                })}
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn NoShowCard(
+    report: NoShowRow,
+    #[prop(into)] on_resolve: Callback<(String, Option<String>, Option<String>)>,
+) -> impl IntoView {
+    let (reason, set_reason) = create_signal(String::new());
+    let (absent_party, set_absent_party) = create_signal(report.absent_party.clone());
+    let optional_reason = move || Some(reason.get()).filter(|r| !r.trim().is_empty());
+    let session_id = report.session_id.clone();
+    let state = if report.disputed { "disputed" } else { "awaiting confirmation" };
+
+    view! {
+        <div class="border rounded p-4 mb-4">
+            <div class="flex justify-between text-sm text-gray-500">
+                <span class="font-mono">{report.session_id.clone()}</span>
+                <span>{format!("{} · reported {}", state, report.reported_at)}</span>
+            </div>
+            <p class="text-sm my-2">
+                "Tutor " <span class="font-mono">{report.tutor}</span>
+                " · learner " <span class="font-mono">{report.learner}</span>
+                {format!(" · scheduled {} · reported absent: {}", report.scheduled_start, report.absent_party)}
+            </p>
+            <div class="flex flex-wrap gap-2 mt-3">
+                <input
+                    type="text"
+                    class="flex-1 border rounded px-2 py-1"
+                    placeholder="Reason (audit log)"
+                    on:input=move |ev| set_reason.set(event_target_value(&ev))
+                />
+                <select
+                    class="border rounded px-2 py-1"
+                    prop:value=move || absent_party.get()
+                    on:change=move |ev| set_absent_party.set(event_target_value(&ev))
+                >
+                    <option value="tutor">"Tutor was absent"</option>
+                    <option value="learner">"Learner was absent"</option>
+                    <option value="">"Nobody was absent"</option>
+                </select>
+                <button
+                    class="bg-blue-500 hover:bg-blue-700 text-white py-1 px-3 rounded"
+                    on:click=move |_| {
+                        let absent_party = Some(absent_party.get()).filter(|party| !party.is_empty());
+                        on_resolve.call((session_id.clone(), absent_party, optional_reason()))
+                    }
+                >
+                    "Resolve"
+                </button>
+            </div>
+        </div>
+    }
+}
+
+/// Moderación del marketplace: tutores pendientes de verificación y espacios reportados
+#[component]
+pub fn MarketplaceModeration() -> impl IntoView {
+    let approve_tutor = create_action(|(address, reason): &(String, Option<String>)| {
+        approve_tutor(address.clone(), reason.clone())
+    });
+    let suspend_tutor = create_action(|(address, reason): &(String, Option<String>)| {
+        suspend_tutor(address.clone(), reason.clone())
+    });
+    let approve_space = create_action(|(id, reason): &(String, Option<String>)| approve_space(id.clone(), reason.clone()));
+    let suspend_space = create_action(|(id, reason): &(String, Option<String>)| suspend_space(id.clone(), reason.clone()));
+
+    let tutors = create_local_resource(
+        move || (approve_tutor.version().get(), suspend_tutor.version().get()),
+        |_| list_pending_tutors(),
+    );
+    let spaces = create_local_resource(
+        move || (approve_space.version().get(), suspend_space.version().get()),
+        |_| list_reported_spaces(),
+    );
+    let refresh = Signal::derive(move || {
+        approve_tutor.version().get()
+            + suspend_tutor.version().get()
+            + approve_space.version().get()
+            + suspend_space.version().get()
+    });
+
+    view! {
+        <section class="mb-10">
+            <h2 class="text-2xl font-bold mb-4">"Pending tutor verifications"</h2>
+            <ActionError action_value=approve_tutor.value()/>
+            <ActionError action_value=suspend_tutor.value()/>
+            <Transition fallback=|| view! { <p>"Loading tutors…"</p> }>
+                {move || tutors.get().map(|result| match result {
+                    Err(err) => view! { <p class="text-red-600">{err.to_string()}</p> }.into_view(),
+                    Ok(tutors) if tutors.is_empty() => {
+                        view! { <p class="text-gray-500">"No tutors waiting for verification."</p> }.into_view()
+                    }
+                    Ok(tutors) => tutors
+                        .into_iter()
+                        .map(|tutor| {
+                            let address = tutor.user_address.clone();
+                            let details = view! {
+                                <p class="font-bold">{tutor.display_name}</p>
+                                <p class="font-mono text-sm">{tutor.user_address}</p>
+                                <p class="text-sm my-1">{tutor.bio}</p>
+                                <p class="text-sm text-gray-500">
+                                    {format!(
+                                        "{} · {} · since {}",
+                                        tutor.subjects.join(", "),
+                                        tutor.languages.join(", "),
+                                        tutor.created_at,
+                                    )}
+                                </p>
+                            };
+                            view! {
+                                <ModerationCard
+                                    target=address
+                                    details=details.into_view()
+                                    on_approve=move |args| approve_tutor.dispatch(args)
+                                    on_suspend=move |args| suspend_tutor.dispatch(args)
+                                />
+                            }
+                        })
+                        .collect_view(),
+                })}
+            </Transition>
+        </section>
+
+        <section class="mb-10">
+            <h2 class="text-2xl font-bold mb-4">"Reported learning spaces"</h2>
+            <ActionError action_value=approve_space.value()/>
+            <ActionError action_value=suspend_space.value()/>
+            <Transition fallback=|| view! { <p>"Loading spaces…"</p> }>
+                {move || spaces.get().map(|result| match result {
+                    Err(err) => view! { <p class="text-red-600">{err.to_string()}</p> }.into_view(),
+                    Ok(spaces) if spaces.is_empty() => {
+                        view! { <p class="text-gray-500">"No spaces under review."</p> }.into_view()
+                    }
+                    Ok(spaces) => spaces
+                        .into_iter()
+                        .map(|space| {
+                            let id = space.id.clone();
+                            let details = view! {
+                                <p class="font-bold">{space.name}</p>
+                                <p class="text-sm">{space.address}</p>
+                                <p class="text-sm text-gray-500">
+                                    "Host " <span class="font-mono">{space.host}</span>
+                                    {format!(
+                                        " · under review since {} ({})",
+                                        space.status_changed_at,
+                                        space.status_reason.unwrap_or_default(),
+                                    )}
+                                </p>
+                                <ul class="list-disc ml-6 text-sm my-1">
+                                    {space.reports.into_iter().map(|report| view! { <li>{report}</li> }).collect_view()}
+                                </ul>
+                            };
+                            view! {
+                                <ModerationCard
+                                    target=id
+                                    details=details.into_view()
+                                    on_approve=move |args| approve_space.dispatch(args)
+                                    on_suspend=move |args| suspend_space.dispatch(args)
+                                />
+                            }
+                        })
+                        .collect_view(),
+                })}
+            </Transition>
+        </section>
+
+        <ModerationLog refresh=refresh/>
+    }
+}
+
+/// Elemento de una cola de moderación con el motivo y las acciones de aprobar y suspender
+#[component]
+fn ModerationCard(
+    target: String,
+    details: View,
+    #[prop(into)] on_approve: Callback<(String, Option<String>)>,
+    #[prop(into)] on_suspend: Callback<(String, Option<String>)>,
+) -> impl IntoView {
+    let (reason, set_reason) = create_signal(String::new());
+    let optional_reason = move || Some(reason.get()).filter(|r| !r.trim().is_empty());
+    let approve_target = target.clone();
+
+    view! {
+        <div class="border rounded p-4 mb-4">
+            {details}
+            <div class="flex gap-2 mt-3">
+                <input
+                    type="text"
+                    class="flex-1 border rounded px-2 py-1"
+                    placeholder="Reason (required to suspend)"
+                    on:input=move |ev| set_reason.set(event_target_value(&ev))
+                />
+                <button
+                    class="bg-green-600 hover:bg-green-800 text-white py-1 px-3 rounded"
+                    on:click=move |_| on_approve.call((approve_target.clone(), optional_reason()))
+                >
+                    "Approve"
+                </button>
+                <button
+                    class="bg-red-600 hover:bg-red-800 text-white py-1 px-3 rounded"
+                    on:click=move |_| on_suspend.call((target.clone(), optional_reason()))
+                >
+                    "Suspend"
+                </button>
+            </div>
+        </div>
+    }
+}
+
+/// Error de la última ejecución de una acción de moderación
+#[component]
+fn ActionError(action_value: RwSignal<Option<Result<(), ServerFnError>>>) -> impl IntoView {
+    move || match action_value.get() {
+        Some(Err(err)) => view! { <p class="mb-4 p-3 rounded bg-red-100">{err.to_string()}</p> }.into_view(),
+        _ => ().into_view(),
+    }
+}
+
+/// Últimas acciones del log de auditoría de moderación
+#[component]
+fn ModerationLog(#[prop(into)] refresh: Signal<usize>) -> impl IntoView {
+    let actions = create_local_resource(move || refresh.get(), |_| list_moderation_actions());
+
+    view! {
+        <section>
+            <h2 class="text-2xl font-bold mb-4">"Moderation log"</h2>
+            <Transition fallback=|| view! { <p>"Loading audit log…"</p> }>
+                {move || actions.get().map(|result| match result {
+                    Err(err) => view! { <p class="text-red-600">{err.to_string()}</p> }.into_view(),
+                    Ok(actions) => view! {
+                        <table class="w-full text-sm">
+                            <thead>
+                                <tr class="text-left border-b">
+                                    <th class="py-2">"When"</th>
+                                    <th>"Moderator"</th>
+                                    <th>"Action"</th>
+                                    <th>"Resource"</th>
+                                    <th>"Reason"</th>
+                                    <th>"Outcome"</th>
+                                </tr>
+                            </thead>
+                            <tbody>
+                                {actions
+                                    .into_iter()
+                                    .map(|action| view! {
+                                        <tr class="border-b">
+                                            <td class="py-2 whitespace-nowrap">{action.timestamp}</td>
+                                            <td class="font-mono truncate">{action.moderator}</td>
+                                            <td>{action.action}</td>
+                                            <td class="font-mono truncate">{action.resource}</td>
+                                            <td>{action.reason.unwrap_or_default()}</td>
+                                            {match action.succeeded {
+                                                Some(true) => view! { <td class="text-green-700">"Applied"</td> },
+                                                Some(false) => view! {
+                                                    <td class="text-red-700">{action.error.unwrap_or_default()}</td>
+                                                },
+                                                None => view! { <td class="text-yellow-700">"Pending"</td> },
+                                            }}
+                                        </tr>
+                                    })
+                                    .collect_view()}
+                            </tbody>
+                        </table>
+                    }
+                    .into_view(),
+                })}
+            </Transition>
+        </section>
+    }
+}
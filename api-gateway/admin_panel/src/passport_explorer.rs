@@ -0,0 +1,537 @@
+// Explorador de pasaportes del panel de administración
+// Búsqueda por dirección, historial paginado, re-verificación de firmas y estado de sincronización con Keikochain
+
+use std::collections::HashMap;
+
+use leptos::*;
+use serde::{Deserialize, Serialize};
+
+/// Pasaportes que devuelve una búsqueda como máximo
+pub const SEARCH_LIMIT: i64 = 25;
+
+/// Interacciones por página del historial
+pub const INTERACTIONS_PAGE_SIZE: i64 = 20;
+
+/// Pasaporte encontrado en una búsqueda
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PassportRow {
+    pub user_address: String,
+    pub interaction_count: i64,
+    pub anchored_interaction_count: i64,
+    pub created_at: String,
+    pub updated_at: String,
+    pub blockchain_hash: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResultDetails {
+    pub success: bool,
+    pub completion: Option<f64>,
+    pub score: Option<f64>,
+    pub duration: Option<i64>,
+    pub response: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContextDetails {
+    pub platform: String,
+    pub language: String,
+    pub instructor: Option<String>,
+    pub group: Option<String>,
+    pub extensions: Option<String>, // JSON formateado
+}
+
+/// Interacción del historial con el estado registrado de sus firmas y anclaje
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InteractionRow {
+    pub id: String,
+    pub timestamp: String,
+    pub verb: String,
+    pub object: String,
+    pub activity_type: Option<String>,
+    pub result: Option<ResultDetails>,
+    pub context: Option<ContextDetails>,
+    pub signed: bool,
+    pub issuer_id: Option<String>,
+    pub stored_in_blockchain: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InteractionPage {
+    pub total_count: i64,
+    pub page: i64,
+    pub interactions: Vec<InteractionRow>,
+}
+
+impl InteractionPage {
+    pub fn page_count(&self) -> i64 {
+        ((self.total_count + INTERACTIONS_PAGE_SIZE - 1) / INTERACTIONS_PAGE_SIZE).max(1)
+    }
+}
+
+/// Resultado de volver a verificar una interacción
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VerificationRow {
+    pub interaction_id: String,
+    pub signature_valid: Option<bool>, // `None` si el estudiante no la firmó
+    pub authority_valid: Option<bool>, // `None` si la interacción no tiene emisor
+    pub stored_in_blockchain: bool,    // Estado registrado por la sincronización, no verificado
+    pub legacy: bool,                  // Formato heredado, sin contrafirma
+}
+
+impl VerificationRow {
+    pub fn is_valid(&self) -> bool {
+        if self.legacy {
+            return self.signature_valid == Some(true) && self.authority_valid.unwrap_or(true);
+        }
+        self.signature_valid.unwrap_or(true) && self.authority_valid.unwrap_or(false)
+    }
+}
+
+#[cfg(feature = "ssr")]
+mod convert {
+    use learning_passport::domain::{InteractionVerification, LearningInteraction, PassportSummary};
+
+    use super::*;
+    use crate::services::TIMESTAMP_FORMAT;
+
+    impl From<PassportSummary> for PassportRow {
+        fn from(passport: PassportSummary) -> Self {
+            Self {
+                user_address: passport.user_address,
+                interaction_count: passport.interaction_count,
+                anchored_interaction_count: passport.anchored_interaction_count,
+                created_at: passport.created_at.format(TIMESTAMP_FORMAT).to_string(),
+                updated_at: passport.updated_at.format(TIMESTAMP_FORMAT).to_string(),
+                blockchain_hash: passport.blockchain_hash,
+            }
+        }
+    }
+
+    impl From<LearningInteraction> for InteractionRow {
+        fn from(interaction: LearningInteraction) -> Self {
+            Self {
+                id: interaction.id.0.to_string(),
+                timestamp: interaction.timestamp.format(TIMESTAMP_FORMAT).to_string(),
+                verb: interaction.verb,
+                object: interaction.object,
+                activity_type: interaction.activity_type,
+                result: interaction.result.map(|result| ResultDetails {
+                    success: result.success,
+                    completion: result.completion,
+                    score: result.score,
+                    duration: result.duration,
+                    response: result.response,
+                }),
+                context: interaction.context.map(|context| ContextDetails {
+                    platform: context.platform,
+                    language: context.language,
+                    instructor: context.instructor,
+                    group: context.group,
+                    extensions: context
+                        .extensions
+                        .map(|extensions| serde_json::to_string_pretty(&extensions).unwrap_or_default()),
+                }),
+                signed: interaction.signature.is_some()
+                    || interaction.authority.as_ref().is_some_and(|authority| authority.signature.is_some()),
+                issuer_id: interaction.authority.map(|authority| authority.issuer_id),
+                stored_in_blockchain: interaction.stored_in_blockchain,
+            }
+        }
+    }
+
+    impl From<InteractionVerification> for VerificationRow {
+        fn from(verification: InteractionVerification) -> Self {
+            Self {
+                interaction_id: verification.interaction_id.0.to_string(),
+                signature_valid: verification.signature_valid,
+                authority_valid: verification.authority_valid,
+                stored_in_blockchain: verification.stored_in_blockchain,
+                legacy: verification.legacy,
+            }
+        }
+    }
+}
+
+/// Buscar pasaportes por el inicio de la dirección del usuario
+#[server(SearchPassports, "/api", endpoint = "search_passports")]
+pub async fn search_passports(query: String) -> Result<Vec<PassportRow>, ServerFnError> {
+    use crate::services::{admin_services, service_error};
+
+    let passports = admin_services()?.passport.search_passports(&query, SEARCH_LIMIT).await.map_err(service_error)?;
+
+    Ok(passports.into_iter().map(PassportRow::from).collect())
+}
+
+/// Página del historial de un pasaporte, de la interacción más reciente a la más antigua
+#[server(ListPassportInteractions, "/api", endpoint = "list_passport_interactions")]
+pub async fn list_passport_interactions(user_address: String, page: i64) -> Result<InteractionPage, ServerFnError> {
+    use learning_passport::domain::InteractionFilter;
+
+    use crate::services::{admin_services, service_error};
+
+    let passport = admin_services()?.passport;
+    let filter = InteractionFilter::default();
+    let page = page.max(0);
+
+    let total_count = passport.count_user_interactions(&user_address, &filter).await.map_err(service_error)?;
+    let interactions = passport
+        .list_user_interactions(&user_address, &filter, true, INTERACTIONS_PAGE_SIZE, page * INTERACTIONS_PAGE_SIZE)
+        .await
+        .map_err(service_error)?;
+
+    Ok(InteractionPage {
+        total_count,
+        page,
+        interactions: interactions.into_iter().map(InteractionRow::from).collect(),
+    })
+}
+
+/// Volver a verificar la firma, la contrafirma y el anclaje de una interacción
+#[server(VerifyInteraction, "/api", endpoint = "verify_interaction")]
+pub async fn verify_interaction(interaction_id: String) -> Result<VerificationRow, ServerFnError> {
+    use crate::services::{admin_services, service_error};
+
+    let id = uuid::Uuid::parse_str(&interaction_id)
+        .map_err(|_| ServerFnError::new(format!("ID de interacción no válido: {}", interaction_id)))?;
+    let verification = admin_services()?
+        .passport
+        .verify_interactions(&[id])
+        .await
+        .map_err(service_error)?
+        .pop()
+        .ok_or_else(|| ServerFnError::new(format!("No existe la interacción {}", interaction_id)))?;
+
+    Ok(verification.into())
+}
+
+/// Volver a verificar todas las interacciones de un pasaporte
+#[server(ReverifyPassport, "/api", endpoint = "reverify_passport")]
+pub async fn reverify_passport(user_address: String) -> Result<Vec<VerificationRow>, ServerFnError> {
+    use crate::services::{admin_services, service_error};
+
+    let verifications =
+        admin_services()?.passport.verify_user_interactions(&user_address).await.map_err(service_error)?;
+
+    Ok(verifications.into_iter().map(VerificationRow::from).collect())
+}
+
+/// Explorador de pasaportes
+///
+/// Los datos solo se piden tras una acción del administrador, así que se resuelven
+/// siempre a través de `/api`, donde el middleware exige el permiso de moderación.
+#[component]
+pub fn PassportExplorer() -> impl IntoView {
+    let (query, set_query) = create_signal(String::new());
+    let (selected, set_selected) = create_signal(None::<String>);
+    let (page, set_page) = create_signal(0i64);
+    let verifications = create_rw_signal(HashMap::<String, VerificationRow>::new());
+
+    let search = create_action(|query: &String| search_passports(query.clone()));
+    let interactions = create_resource(
+        move || (selected.get(), page.get()),
+        |(user_address, page)| async move {
+            match user_address {
+                Some(user_address) => list_passport_interactions(user_address, page).await.map(Some),
+                None => Ok(None),
+            }
+        },
+    );
+    let verify = create_action(|interaction_id: &String| verify_interaction(interaction_id.clone()));
+    let reverify = create_action(|user_address: &String| reverify_passport(user_address.clone()));
+
+    create_effect(move |_| {
+        if let Some(Ok(verification)) = verify.value().get() {
+            verifications.update(|v| {
+                v.insert(verification.interaction_id.clone(), verification);
+            });
+        }
+    });
+    create_effect(move |_| {
+        if let Some(Ok(results)) = reverify.value().get() {
+            verifications.update(|v| {
+                v.extend(results.into_iter().map(|r| (r.interaction_id.clone(), r)));
+            });
+        }
+    });
+
+    let select = move |user_address: String| {
+        verifications.set(HashMap::new());
+        reverify.value().set(None);
+        set_page.set(0);
+        set_selected.set(Some(user_address));
+    };
+
+    view! {
+        <form
+            class="flex gap-2 mb-6"
+            on:submit=move |ev| {
+                ev.prevent_default();
+                search.dispatch(query.get());
+            }
+        >
+            <input
+                type="text"
+                class="flex-1 border rounded px-3 py-2 font-mono"
+                placeholder="User address (prefix)"
+                prop:value=query
+                on:input=move |ev| set_query.set(event_target_value(&ev))
+            />
+            <button type="submit" class="bg-blue-500 hover:bg-blue-700 text-white font-bold py-2 px-4 rounded">
+                "Search"
+            </button>
+        </form>
+
+        {move || match search.value().get() {
+            None => view! { <p class="text-gray-500">"Search passports by user address."</p> }.into_view(),
+            Some(Err(err)) => view! { <p class="text-red-600">{err.to_string()}</p> }.into_view(),
+            Some(Ok(passports)) if passports.is_empty() => {
+                view! { <p class="text-gray-500">"No passports match this address."</p> }.into_view()
+            }
+            Some(Ok(passports)) => view! {
+                <table class="w-full text-sm mb-8">
+                    <thead>
+                        <tr class="text-left border-b">
+                            <th class="py-2">"User address"</th>
+                            <th>"Interactions"</th>
+                            <th>"Anchored"</th>
+                            <th>"Created"</th>
+                            <th>"Passport hash"</th>
+                            <th></th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {passports
+                            .into_iter()
+                            .map(|passport| {
+                                let user_address = passport.user_address.clone();
+                                view! {
+                                    <tr class="border-b">
+                                        <td class="py-2 font-mono">{passport.user_address}</td>
+                                        <td>{passport.interaction_count}</td>
+                                        <td>{passport.anchored_interaction_count}</td>
+                                        <td>{passport.created_at}</td>
+                                        <td class="font-mono truncate">
+                                            {passport.blockchain_hash.unwrap_or_else(|| "—".to_string())}
+                                        </td>
+                                        <td>
+                                            <button
+                                                class="text-blue-500 hover:underline"
+                                                on:click=move |_| select(user_address.clone())
+                                            >
+                                                "Explore"
+                                            </button>
+                                        </td>
+                                    </tr>
+                                }
+                            })
+                            .collect_view()}
+                    </tbody>
+                </table>
+            }
+            .into_view(),
+        }}
+
+        {move || selected.get().map(|user_address| {
+            let bulk_address = user_address.clone();
+            view! {
+                <section>
+                    <div class="flex items-center justify-between mb-4">
+                        <h2 class="text-2xl font-bold font-mono">{user_address}</h2>
+                        <button
+                            class="bg-gray-700 hover:bg-gray-900 text-white py-2 px-4 rounded disabled:opacity-50"
+                            prop:disabled=move || reverify.pending().get()
+                            on:click=move |_| reverify.dispatch(bulk_address.clone())
+                        >
+                            {move || if reverify.pending().get() { "Verifying…" } else { "Re-verify all" }}
+                        </button>
+                    </div>
+                    <BulkSummary results=reverify.value()/>
+                    <Transition fallback=|| view! { <p>"Loading interactions…"</p> }>
+                        {move || interactions.get().map(|page_result| match page_result {
+                            Err(err) => view! { <p class="text-red-600">{err.to_string()}</p> }.into_view(),
+                            Ok(None) => ().into_view(),
+                            Ok(Some(interaction_page)) => view! {
+                                <InteractionTable
+                                    interaction_page=interaction_page
+                                    verifications=verifications
+                                    on_verify=move |id| verify.dispatch(id)
+                                    on_page=move |page| set_page.set(page)
+                                />
+                            }
+                            .into_view(),
+                        })}
+                    </Transition>
+                </section>
+            }
+        })}
+    }
+}
+
+/// Resumen de la última re-verificación del pasaporte
+#[component]
+fn BulkSummary(results: RwSignal<Option<Result<Vec<VerificationRow>, ServerFnError>>>) -> impl IntoView {
+    move || match results.get() {
+        None => ().into_view(),
+        Some(Err(err)) => view! { <p class="text-red-600 mb-4">{err.to_string()}</p> }.into_view(),
+        Some(Ok(results)) => {
+            let invalid = results.iter().filter(|r| !r.is_valid()).count();
+            let unsynced = results.iter().filter(|r| !r.stored_in_blockchain).count();
+            let class = if invalid > 0 { "mb-4 p-3 rounded bg-red-100" } else { "mb-4 p-3 rounded bg-green-100" };
+            view! {
+                <p class=class>
+                    {format!(
+                        "{} interactions verified: {} with invalid signatures, {} not yet synced to Keikochain.",
+                        results.len(),
+                        invalid,
+                        unsynced,
+                    )}
+                </p>
+            }
+            .into_view()
+        }
+    }
+}
+
+#[component]
+fn InteractionTable(
+    interaction_page: InteractionPage,
+    verifications: RwSignal<HashMap<String, VerificationRow>>,
+    #[prop(into)] on_verify: Callback<String>,
+    #[prop(into)] on_page: Callback<i64>,
+) -> impl IntoView {
+    let page = interaction_page.page;
+    let page_count = interaction_page.page_count();
+
+    view! {
+        <table class="w-full text-sm">
+            <thead>
+                <tr class="text-left border-b">
+                    <th class="py-2">"Timestamp"</th>
+                    <th>"Statement"</th>
+                    <th>"Result"</th>
+                    <th>"Context"</th>
+                    <th>"Signature"</th>
+                    <th>"Keikochain"</th>
+                    <th></th>
+                </tr>
+            </thead>
+            <tbody>
+                {interaction_page
+                    .interactions
+                    .into_iter()
+                    .map(|interaction| view! {
+                        <InteractionRowView interaction=interaction verifications=verifications on_verify=on_verify/>
+                    })
+                    .collect_view()}
+            </tbody>
+        </table>
+        <div class="flex items-center justify-between mt-4">
+            <button
+                class="text-blue-500 hover:underline disabled:text-gray-400"
+                prop:disabled=page == 0
+                on:click=move |_| on_page.call(page - 1)
+            >
+                "Previous"
+            </button>
+            <span>{format!("Page {} of {} ({} interactions)", page + 1, page_count, interaction_page.total_count)}</span>
+            <button
+                class="text-blue-500 hover:underline disabled:text-gray-400"
+                prop:disabled=page + 1 >= page_count
+                on:click=move |_| on_page.call(page + 1)
+            >
+                "Next"
+            </button>
+        </div>
+    }
+}
+
+#[component]
+fn InteractionRowView(
+    interaction: InteractionRow,
+    verifications: RwSignal<HashMap<String, VerificationRow>>,
+    on_verify: Callback<String>,
+) -> impl IntoView {
+    let id = interaction.id.clone();
+    let verification = {
+        let id = id.clone();
+        move || verifications.with(|v| v.get(&id).cloned())
+    };
+    let signed = interaction.signed;
+    let stored = interaction.stored_in_blockchain;
+    let verification_for_chain = verification.clone();
+
+    let result = match interaction.result {
+        None => view! { <span class="text-gray-400">"—"</span> }.into_view(),
+        Some(result) => view! {
+            <details>
+                <summary class=if result.success { "text-green-700" } else { "text-red-700" }>
+                    {if result.success { "Success" } else { "Failed" }}
+                </summary>
+                <dl class="text-xs">
+                    <dt>"Completion"</dt><dd>{format_optional(result.completion)}</dd>
+                    <dt>"Score"</dt><dd>{format_optional(result.score)}</dd>
+                    <dt>"Duration (s)"</dt><dd>{format_optional(result.duration)}</dd>
+                    <dt>"Response"</dt><dd>{result.response.unwrap_or_else(|| "—".to_string())}</dd>
+                </dl>
+            </details>
+        }
+        .into_view(),
+    };
+    let context = match interaction.context {
+        None => view! { <span class="text-gray-400">"—"</span> }.into_view(),
+        Some(context) => view! {
+            <details>
+                <summary>{format!("{} ({})", context.platform, context.language)}</summary>
+                <dl class="text-xs">
+                    <dt>"Instructor"</dt><dd class="font-mono">{context.instructor.unwrap_or_else(|| "—".to_string())}</dd>
+                    <dt>"Group"</dt><dd>{context.group.unwrap_or_else(|| "—".to_string())}</dd>
+                </dl>
+                {context.extensions.map(|extensions| view! { <pre class="text-xs">{extensions}</pre> })}
+            </details>
+        }
+        .into_view(),
+    };
+
+    view! {
+        <tr class="border-b align-top">
+            <td class="py-2 whitespace-nowrap">{interaction.timestamp}</td>
+            <td>
+                <span class="font-bold">{interaction.verb}</span>" "{interaction.object}
+                {interaction.activity_type.map(|activity| view! { <div class="text-xs text-gray-500">{activity}</div> })}
+                {interaction.issuer_id.map(|issuer| view! { <div class="text-xs text-gray-500">"Issuer: "{issuer}</div> })}
+            </td>
+            <td>{result}</td>
+            <td>{context}</td>
+            <td>
+                {move || match verification() {
+                    Some(v) if v.is_valid() && v.legacy => view! { <span class="text-green-700">"Valid (legacy)"</span> }.into_view(),
+                    Some(v) if v.is_valid() => view! { <span class="text-green-700">"Valid"</span> }.into_view(),
+                    Some(v) if v.legacy || v.signature_valid == Some(false) => view! { <span class="text-red-700">"Invalid"</span> }.into_view(),
+                    Some(_) => view! { <span class="text-red-700">"Invalid countersignature"</span> }.into_view(),
+                    None if signed => view! { <span class="text-gray-500">"Signed, not verified"</span> }.into_view(),
+                    None => view! { <span class="text-red-700">"Unsigned"</span> }.into_view(),
+                }}
+            </td>
+            <td>
+                {move || {
+                    let synced = verification_for_chain().map_or(stored, |v| v.stored_in_blockchain);
+                    if synced {
+                        view! { <span class="text-green-700">"Synced"</span> }
+                    } else {
+                        view! { <span class="text-yellow-700">"Pending"</span> }
+                    }
+                }}
+            </td>
+            <td>
+                <button class="text-blue-500 hover:underline" on:click=move |_| on_verify.call(id.clone())>
+                    "Verify"
+                </button>
+            </td>
+        </tr>
+    }
+}
+
+fn format_optional<T: ToString>(value: Option<T>) -> String {
+    value.map_or_else(|| "—".to_string(), |value| value.to_string())
+}
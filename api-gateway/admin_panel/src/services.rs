@@ -0,0 +1,92 @@
+// Servicios de los módulos disponibles para las server functions del panel
+// Se registran como contexto de Leptos en cada petición de página o de server function
+
+use std::fmt::Display;
+use std::future::Future;
+use std::sync::Arc;
+
+use axum::Extension;
+use governance::service::GovernanceService;
+use identity::domain::{ModerationAuditEntry, Principal, ResourceKind};
+use identity::service::ModerationAuditLog;
+use leptos::*;
+use learning_passport::service::{CompetencyService, LearningPassportService};
+use marketplace::service::{LearningSpaceService, TutoringService};
+use reputation::service::{DisputeService, ReputationService};
+
+/// Formato de las fechas que muestra el panel
+pub const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S UTC";
+
+/// Servicios de aplicación compartidos con el backend GraphQL
+#[derive(Clone)]
+pub struct AdminServices {
+    pub passport: Arc<LearningPassportService>,
+    pub competencies: Arc<CompetencyService>,
+    pub tutoring: Arc<TutoringService>,
+    pub spaces: Arc<LearningSpaceService>,
+    pub reputation: Arc<ReputationService>,
+    pub disputes: Arc<DisputeService>,
+    pub governance: Arc<GovernanceService>,
+    pub moderation_audit: Arc<ModerationAuditLog>,
+}
+
+/// Servicios del contexto de la petición en curso
+pub fn admin_services() -> Result<AdminServices, ServerFnError> {
+    use_context::<AdminServices>().ok_or_else(|| ServerFnError::new("Servicios del panel no disponibles"))
+}
+
+/// Error de un servicio, con su cadena de causas, tal como lo ve el panel
+pub fn service_error(err: impl Display) -> ServerFnError {
+    ServerFnError::new(format!("{:#}", err))
+}
+
+/// Dirección del moderador que hace la petición
+///
+/// El middleware de autorización deja su `Principal` en las extensiones de la petición
+/// tras verificar el token de sesión; sin token válido no hay moderador.
+pub async fn moderator_address() -> Result<String, ServerFnError> {
+    let Extension(principal) = leptos_axum::extract::<Extension<Principal>>()
+        .await
+        .map_err(|_| ServerFnError::new("Acción de moderación sin usuario autenticado"))?;
+
+    Ok(principal.user_address)
+}
+
+/// Ejecutar una acción de moderación y registrarla en el log de auditoría
+///
+/// Se registran también las acciones que el servicio rechaza. Si la auditoría falla,
+/// el moderador recibe el error aunque la acción ya se haya aplicado.
+pub async fn audited<T, E: Display>(
+    action: &str,
+    resource_kind: ResourceKind,
+    resource_id: &str,
+    reason: Option<String>,
+    run: impl Future<Output = Result<T, E>>,
+) -> Result<T, ServerFnError> {
+    let moderator = moderator_address().await?;
+    let services = admin_services()?;
+
+    let reason = reason.filter(|reason| !reason.trim().is_empty());
+    let entry = ModerationAuditEntry::new(&moderator, action, resource_kind, resource_id, reason);
+    // Sin registro previo la acción no se ejecuta
+    services.moderation_audit.begin(&entry).await.map_err(|err| {
+        ServerFnError::new(format!("No se pudo registrar la acción en la auditoría: {:#}", err))
+    })?;
+
+    let result = run.await;
+    let entry = match &result {
+        Ok(_) => entry.applied(),
+        Err(err) => entry.failed(format!("{:#}", err)),
+    };
+
+    // Si falla, la entrada queda pendiente en el log y la acción sigue auditada
+    if let Err(err) = services.moderation_audit.complete(&entry).await {
+        return Err(ServerFnError::new(format!(
+            "No se pudo registrar el resultado en la auditoría (aplicada: {}): {:#}",
+            result.is_ok(),
+            err
+        )));
+    }
+
+    result.map_err(service_error)
+}
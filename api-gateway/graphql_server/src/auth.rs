@@ -0,0 +1,231 @@
+use std::sync::Arc;
+
+use anyhow::{anyhow, bail, Context as _};
+use axum::{
+    extract::{Request, State},
+    http::{header::AUTHORIZATION, HeaderMap, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use identity::domain::{Action, Principal, Resource, ResourceKind};
+use identity::service::Authorizer;
+use jsonwebtoken::{decode, Algorithm, DecodingKey, Validation};
+use serde::Deserialize;
+
+/// Longitud mínima del secreto compartido con el proxy de autenticación
+pub const MIN_SESSION_SECRET_LEN: usize = 32;
+
+/// Claims del token de sesión; `sub` es la dirección del usuario autenticado
+#[derive(Debug, Deserialize)]
+struct SessionClaims {
+    sub: String,
+}
+
+/// Verificador de los tokens de sesión (JWT HS256) que emite el proxy de autenticación
+///
+/// El proxy los firma con un secreto compartido tras validar la sesión FIDO2; el gateway
+/// sólo acepta la identidad que lleva un token válido, vigente y de ese emisor.
+pub struct SessionVerifier {
+    key: DecodingKey,
+    validation: Validation,
+}
+
+impl SessionVerifier {
+    pub fn new(secret: &[u8], issuer: &str) -> anyhow::Result<Self> {
+        if secret.len() < MIN_SESSION_SECRET_LEN {
+            bail!("El secreto de sesión debe tener al menos {} bytes", MIN_SESSION_SECRET_LEN);
+        }
+
+        let mut validation = Validation::new(Algorithm::HS256);
+        validation.set_issuer(&[issuer]);
+        validation.set_required_spec_claims(&["exp", "iss", "sub"]);
+        Ok(Self { key: DecodingKey::from_secret(secret), validation })
+    }
+
+    /// Configurar desde `JWT_SECRET` y `JWT_ISSUER`
+    pub fn from_env() -> anyhow::Result<Self> {
+        let secret = std::env::var("JWT_SECRET").context("JWT_SECRET no configurada")?;
+        let issuer = std::env::var("JWT_ISSUER").context("JWT_ISSUER no configurada")?;
+        Self::new(secret.as_bytes(), &issuer)
+    }
+
+    /// Dirección del usuario autenticado; `None` si la petición no trae token
+    ///
+    /// Un token presente pero inválido o caducado es un error, no una petición anónima.
+    pub fn user_address(&self, headers: &HeaderMap) -> anyhow::Result<Option<String>> {
+        let Some(value) = headers.get(AUTHORIZATION) else {
+            return Ok(None);
+        };
+        let token = value
+            .to_str()
+            .ok()
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .ok_or_else(|| anyhow!("La cabecera Authorization debe ser `Bearer <token>`"))?;
+
+        let claims = decode::<SessionClaims>(token, &self.key, &self.validation)
+            .context("Token de sesión no válido")?
+            .claims;
+        if claims.sub.is_empty() {
+            bail!("El token de sesión no identifica al usuario");
+        }
+
+        Ok(Some(claims.sub))
+    }
+}
+
+/// Estado del middleware de autorización
+#[derive(Clone)]
+pub struct AuthState {
+    pub sessions: Arc<SessionVerifier>,
+    pub authorizer: Arc<dyn Authorizer>,
+}
+
+/// Permiso que exige cada server function del panel (`/api/<endpoint>`)
+const REST_PERMISSIONS: &[(&str, Action, ResourceKind)] = &[
+    ("search_passports", Action::Read, ResourceKind::Passport),
+    ("list_passport_interactions", Action::Read, ResourceKind::LearningInteraction),
+    ("verify_interaction", Action::Read, ResourceKind::LearningInteraction),
+    ("reverify_passport", Action::Read, ResourceKind::Passport),
+    ("list_moderation_sessions", Action::Read, ResourceKind::TutoringSession),
+    ("list_no_show_reports", Action::Read, ResourceKind::TutoringSession),
+    ("list_open_disputes", Action::Read, ResourceKind::Dispute),
+    ("list_reported_spaces", Action::Read, ResourceKind::LearningSpace),
+    ("list_pending_tutors", Action::Read, ResourceKind::TutorProfile),
+    ("list_moderation_actions", Action::Read, ResourceKind::AdminPanel),
+    ("approve_tutor", Action::Moderate, ResourceKind::TutorProfile),
+    ("suspend_tutor", Action::Moderate, ResourceKind::TutorProfile),
+    ("approve_space", Action::Moderate, ResourceKind::LearningSpace),
+    ("suspend_space", Action::Moderate, ResourceKind::LearningSpace),
+    ("close_dispute_evidence", Action::Moderate, ResourceKind::Dispute),
+    ("resolve_dispute", Action::Moderate, ResourceKind::Dispute),
+    ("resolve_no_show", Action::Moderate, ResourceKind::TutoringSession),
+];
+
+/// Acción y recurso que protege cada ruta REST
+///
+/// Las rutas sin entrada (GraphQL, páginas, estáticos) se autorizan en su propio handler.
+/// Una server function sin permiso declarado sólo la puede usar un administrador.
+pub fn rest_permission(path: &str) -> Option<(Action, Resource)> {
+    let endpoint = path.strip_prefix("/api/")?;
+    let (action, kind) = REST_PERMISSIONS
+        .iter()
+        .find(|(name, _, _)| *name == endpoint)
+        .map(|(_, action, kind)| (*action, *kind))
+        .unwrap_or((Action::Administer, ResourceKind::AdminPanel));
+
+    Some((action, Resource::new(kind).with_id(endpoint)))
+}
+
+/// Middleware de autorización para las rutas REST
+///
+/// Autentica el token de sesión, deja el `Principal` en las extensiones de la petición para
+/// los handlers posteriores y rechaza las rutas protegidas que no autorice el motor de políticas.
+pub async fn authorization_middleware(State(auth): State<AuthState>, mut request: Request, next: Next) -> Response {
+    let user_address = match auth.sessions.user_address(request.headers()) {
+        Ok(user_address) => user_address,
+        Err(err) => return (StatusCode::UNAUTHORIZED, format!("{:#}", err)).into_response(),
+    };
+    let principal = match user_address {
+        Some(user_address) => match auth.authorizer.load_principal(&user_address).await {
+            Ok(principal) => Some(principal),
+            Err(err) => return (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()).into_response(),
+        },
+        None => None,
+    };
+
+    if let Some((action, resource)) = rest_permission(request.uri().path()) {
+        match auth.authorizer.authorize(principal.as_ref(), action, &resource).await {
+            Ok(decision) if decision.allowed => {}
+            Ok(decision) => {
+                let status = if principal.is_some() {
+                    StatusCode::FORBIDDEN
+                } else {
+                    StatusCode::UNAUTHORIZED
+                };
+                return (status, decision.reason).into_response();
+            }
+            Err(err) => return (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()).into_response(),
+        }
+    }
+
+    if let Some(principal) = principal {
+        request.extensions_mut().insert::<Principal>(principal);
+    }
+
+    next.run(request).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+    use jsonwebtoken::{encode, EncodingKey, Header};
+    use serde::Serialize;
+
+    const SECRET: &[u8] = b"0123456789abcdef0123456789abcdef";
+    const ISSUER: &str = "keiko-auth";
+
+    #[derive(Serialize)]
+    struct Claims<'a> {
+        sub: &'a str,
+        iss: &'a str,
+        exp: i64,
+    }
+
+    fn headers(secret: &[u8], claims: &Claims) -> HeaderMap {
+        let token = encode(&Header::new(Algorithm::HS256), claims, &EncodingKey::from_secret(secret)).unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, HeaderValue::from_str(&format!("Bearer {token}")).unwrap());
+        headers
+    }
+
+    fn expires_in(seconds: i64) -> i64 {
+        chrono::Utc::now().timestamp() + seconds
+    }
+
+    #[test]
+    fn only_tokens_signed_with_the_shared_secret_authenticate() {
+        let sessions = SessionVerifier::new(SECRET, ISSUER).unwrap();
+        let claims = Claims { sub: "0xabc", iss: ISSUER, exp: expires_in(300) };
+
+        assert_eq!(sessions.user_address(&headers(SECRET, &claims)).unwrap().as_deref(), Some("0xabc"));
+        assert!(sessions.user_address(&headers(b"fedcba9876543210fedcba9876543210", &claims)).is_err());
+        assert!(sessions.user_address(&HeaderMap::new()).unwrap().is_none());
+    }
+
+    #[test]
+    fn expired_or_foreign_tokens_are_rejected() {
+        let sessions = SessionVerifier::new(SECRET, ISSUER).unwrap();
+
+        let expired = Claims { sub: "0xabc", iss: ISSUER, exp: expires_in(-3600) };
+        let foreign = Claims { sub: "0xabc", iss: "otro", exp: expires_in(300) };
+        assert!(sessions.user_address(&headers(SECRET, &expired)).is_err());
+        assert!(sessions.user_address(&headers(SECRET, &foreign)).is_err());
+    }
+
+    #[test]
+    fn the_user_address_header_is_not_trusted() {
+        let sessions = SessionVerifier::new(SECRET, ISSUER).unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert("x-keiko-user-address", HeaderValue::from_static("0xadmin"));
+
+        assert!(sessions.user_address(&headers).unwrap().is_none());
+    }
+
+    #[test]
+    fn each_server_function_has_its_own_permission() {
+        let (action, resource) = rest_permission("/api/approve_tutor").unwrap();
+        assert_eq!((action, resource.kind), (Action::Moderate, ResourceKind::TutorProfile));
+
+        let (action, resource) = rest_permission("/api/search_passports").unwrap();
+        assert_eq!((action, resource.kind), (Action::Read, ResourceKind::Passport));
+
+        let (action, resource) = rest_permission("/api/resolve_no_show").unwrap();
+        assert_eq!((action, resource.kind), (Action::Moderate, ResourceKind::TutoringSession));
+
+        let (action, resource) = rest_permission("/api/unknown").unwrap();
+        assert_eq!((action, resource.kind), (Action::Administer, ResourceKind::AdminPanel));
+
+        assert!(rest_permission("/graphql").is_none());
+    }
+}
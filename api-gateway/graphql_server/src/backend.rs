@@ -0,0 +1,259 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::stream::{self, BoxStream, StreamExt};
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use learning_passport::domain::{
+    CompetencyFramework, CompetencyProfile, FrameworkId, InteractionFilter, LearningContext, LearningInteraction, LearningInteractionId,
+    LearningPassportEvent, LearningResult, LifeLearningPassport, PassportStatistics,
+};
+use learning_passport::service::{CompetencyService, LearningPassportService};
+use marketplace::domain::{SessionFilter, SessionOrder, TutorProfile, TutoringEvent, TutoringSession};
+use marketplace::service::TutoringService;
+use reputation::domain::{RatedRole, ReputationScore};
+use reputation::service::ReputationService;
+
+/// Servicios del backend que resuelven el esquema GraphQL
+///
+/// El gateway no conoce el despliegue de los módulos: `InProcessBackend` los llama
+/// directamente dentro del monolito; un cliente remoto (gRPC) implementaría el mismo
+/// trait cuando los módulos se separen.
+#[async_trait]
+pub trait Backend: Send + Sync {
+    async fn get_passport(&self, user_address: &str) -> Result<Option<LifeLearningPassport>>;
+
+    async fn get_passport_statistics(&self, user_address: &str) -> Result<Option<PassportStatistics>>;
+
+    /// Pasaportes de varios usuarios en una sola llamada
+    async fn get_passports(&self, user_addresses: &[String]) -> Result<Vec<LifeLearningPassport>>;
+
+    /// Interacciones por ID en una sola llamada
+    async fn get_interactions(&self, interaction_ids: &[Uuid]) -> Result<Vec<LearningInteraction>>;
+
+    async fn count_learning_interactions(&self, user_address: &str, filter: &InteractionFilter) -> Result<i64>;
+
+    /// Página del historial de un usuario, ordenado por fecha
+    async fn list_learning_interactions(
+        &self,
+        user_address: &str,
+        filter: &InteractionFilter,
+        descending: bool,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<LearningInteraction>>;
+
+    /// Registrar una interacción autodeclarada; se firma y contrafirma en el pasaporte
+    async fn add_learning_interaction(
+        &self,
+        user_address: &str,
+        verb: &str,
+        object: &str,
+        activity_type: Option<&str>,
+        result: Option<LearningResult>,
+        context: Option<LearningContext>,
+    ) -> Result<LearningInteraction>;
+
+    async fn get_tutor_profile(&self, user_address: &str) -> Result<Option<TutorProfile>>;
+
+    /// Perfiles de tutor de varios usuarios en una sola llamada
+    async fn get_tutor_profiles(&self, user_addresses: &[String]) -> Result<Vec<TutorProfile>>;
+
+    async fn count_tutoring_sessions(&self, user_address: &str, filter: &SessionFilter) -> Result<i64>;
+
+    /// Página de las sesiones en las que participa un usuario
+    async fn list_tutoring_sessions(
+        &self,
+        user_address: &str,
+        filter: &SessionFilter,
+        order: SessionOrder,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<TutoringSession>>;
+
+    async fn request_tutoring_session(
+        &self,
+        learner: &str,
+        tutor: &str,
+        subject_code: &str,
+        scheduled_start: DateTime<Utc>,
+        scheduled_end: DateTime<Utc>,
+        message: Option<String>,
+    ) -> Result<TutoringSession>;
+
+    async fn get_reputation(&self, user_address: &str, role: RatedRole) -> Result<Option<ReputationScore>>;
+
+    async fn get_reputation_history(&self, user_address: &str, role: RatedRole) -> Result<Vec<ReputationScore>>;
+
+    async fn get_competency_frameworks(&self) -> Result<Vec<CompetencyFramework>>;
+
+    /// Perfil de competencias; `None` si el marco no existe
+    async fn get_competency_profile(
+        &self,
+        user_address: &str,
+        framework_id: &FrameworkId,
+    ) -> Result<Option<CompetencyProfile>>;
+
+    /// Eventos de pasaportes emitidos desde el momento de la suscripción
+    fn passport_events(&self) -> BoxStream<'static, LearningPassportEvent>;
+
+    /// Eventos de sesiones de tutoría emitidos desde el momento de la suscripción
+    fn tutoring_events(&self) -> BoxStream<'static, TutoringEvent>;
+}
+
+/// Backend con los servicios de los módulos en el mismo proceso
+pub struct InProcessBackend {
+    passport: Arc<LearningPassportService>,
+    competencies: Arc<CompetencyService>,
+    tutoring: Arc<TutoringService>,
+    reputation: Arc<ReputationService>,
+}
+
+impl InProcessBackend {
+    pub fn new(
+        passport: Arc<LearningPassportService>,
+        competencies: Arc<CompetencyService>,
+        tutoring: Arc<TutoringService>,
+        reputation: Arc<ReputationService>,
+    ) -> Self {
+        Self { passport, competencies, tutoring, reputation }
+    }
+}
+
+#[async_trait]
+impl Backend for InProcessBackend {
+    async fn get_passport(&self, user_address: &str) -> Result<Option<LifeLearningPassport>> {
+        self.passport.get_passport_by_user(user_address).await
+    }
+
+    async fn get_passport_statistics(&self, user_address: &str) -> Result<Option<PassportStatistics>> {
+        self.passport.get_passport_statistics(user_address).await
+    }
+
+    async fn get_passports(&self, user_addresses: &[String]) -> Result<Vec<LifeLearningPassport>> {
+        self.passport.get_passports_by_users(user_addresses).await
+    }
+
+    async fn get_interactions(&self, interaction_ids: &[Uuid]) -> Result<Vec<LearningInteraction>> {
+        let ids: Vec<LearningInteractionId> = interaction_ids.iter().copied().map(LearningInteractionId).collect();
+        self.passport.get_interactions(&ids).await
+    }
+
+    async fn count_learning_interactions(&self, user_address: &str, filter: &InteractionFilter) -> Result<i64> {
+        self.passport.count_user_interactions(user_address, filter).await
+    }
+
+    async fn list_learning_interactions(
+        &self,
+        user_address: &str,
+        filter: &InteractionFilter,
+        descending: bool,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<LearningInteraction>> {
+        self.passport.list_user_interactions(user_address, filter, descending, limit, offset).await
+    }
+
+    async fn add_learning_interaction(
+        &self,
+        user_address: &str,
+        verb: &str,
+        object: &str,
+        activity_type: Option<&str>,
+        result: Option<LearningResult>,
+        context: Option<LearningContext>,
+    ) -> Result<LearningInteraction> {
+        self.passport
+            .add_self_reported_interaction(user_address, verb, object, activity_type, result, context)
+            .await
+    }
+
+    async fn get_tutor_profile(&self, user_address: &str) -> Result<Option<TutorProfile>> {
+        self.tutoring.get_tutor_profile(user_address).await
+    }
+
+    async fn get_tutor_profiles(&self, user_addresses: &[String]) -> Result<Vec<TutorProfile>> {
+        self.tutoring.get_tutor_profiles(user_addresses).await
+    }
+
+    async fn count_tutoring_sessions(&self, user_address: &str, filter: &SessionFilter) -> Result<i64> {
+        self.tutoring.count_sessions_for_user(user_address, filter).await
+    }
+
+    async fn list_tutoring_sessions(
+        &self,
+        user_address: &str,
+        filter: &SessionFilter,
+        order: SessionOrder,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<TutoringSession>> {
+        self.tutoring.list_sessions_for_user(user_address, filter, order, limit, offset).await
+    }
+
+    async fn request_tutoring_session(
+        &self,
+        learner: &str,
+        tutor: &str,
+        subject_code: &str,
+        scheduled_start: DateTime<Utc>,
+        scheduled_end: DateTime<Utc>,
+        message: Option<String>,
+    ) -> Result<TutoringSession> {
+        self.tutoring
+            .request_booking(learner, tutor, subject_code, scheduled_start, scheduled_end, message)
+            .await
+    }
+
+    async fn get_reputation(&self, user_address: &str, role: RatedRole) -> Result<Option<ReputationScore>> {
+        self.reputation.get_reputation(user_address, role).await
+    }
+
+    async fn get_reputation_history(&self, user_address: &str, role: RatedRole) -> Result<Vec<ReputationScore>> {
+        self.reputation.get_reputation_history(user_address, role).await
+    }
+
+    async fn get_competency_frameworks(&self) -> Result<Vec<CompetencyFramework>> {
+        self.competencies.get_frameworks().await
+    }
+
+    async fn get_competency_profile(
+        &self,
+        user_address: &str,
+        framework_id: &FrameworkId,
+    ) -> Result<Option<CompetencyProfile>> {
+        if self.competencies.get_framework(framework_id).await?.is_none() {
+            return Ok(None);
+        }
+
+        Ok(Some(self.competencies.compute_profile(user_address, framework_id).await?))
+    }
+
+    fn passport_events(&self) -> BoxStream<'static, LearningPassportEvent> {
+        broadcast_stream(self.passport.subscribe())
+    }
+
+    fn tutoring_events(&self) -> BoxStream<'static, TutoringEvent> {
+        broadcast_stream(self.tutoring.subscribe())
+    }
+}
+
+/// Convertir un canal de eventos en stream
+///
+/// Un suscriptor que se queda atrás pierde los eventos más antiguos en lugar de
+/// cortar la suscripción.
+fn broadcast_stream<T: Clone + Send + 'static>(receiver: broadcast::Receiver<T>) -> BoxStream<'static, T> {
+    stream::unfold(receiver, |mut receiver| async move {
+        loop {
+            match receiver.recv().await {
+                Ok(event) => return Some((event, receiver)),
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    })
+    .boxed()
+}
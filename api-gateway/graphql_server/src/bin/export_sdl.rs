@@ -0,0 +1,53 @@
+// Escribir el SDL del esquema en la instantánea versionada, o comprobarla con `--check`
+//
+// Uso: export_sdl [--check] [ruta]   (por defecto, schema.graphql en la raíz del crate)
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use keiko_graphql_server::sdl::{breaking_changes, schema_sdl, SNAPSHOT_PATH};
+
+fn main() -> ExitCode {
+    let mut check = false;
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(SNAPSHOT_PATH);
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "--check" => check = true,
+            _ => path = PathBuf::from(arg),
+        }
+    }
+
+    let current = schema_sdl();
+    let snapshot = std::fs::read_to_string(&path).ok();
+
+    let changes = match snapshot.as_deref().map(|snapshot| breaking_changes(snapshot, &current)) {
+        Some(Ok(changes)) => changes,
+        Some(Err(err)) => {
+            eprintln!("{:#}", err);
+            return ExitCode::FAILURE;
+        }
+        None => Vec::new(),
+    };
+    if !changes.is_empty() {
+        eprintln!("Cambios incompatibles con {}:", path.display());
+        for change in &changes {
+            eprintln!("  - {}", change);
+        }
+    }
+
+    if check {
+        if snapshot.as_deref() != Some(current.as_str()) {
+            eprintln!("{} está desactualizada; regenérala con `cargo run -p keiko-graphql-server --bin export_sdl`", path.display());
+            return ExitCode::FAILURE;
+        }
+        println!("{} coincide con el esquema", path.display());
+        return ExitCode::SUCCESS;
+    }
+
+    if let Err(err) = std::fs::write(&path, &current) {
+        eprintln!("No se pudo escribir {}: {}", path.display(), err);
+        return ExitCode::FAILURE;
+    }
+    println!("SDL escrito en {}", path.display());
+    ExitCode::SUCCESS
+}
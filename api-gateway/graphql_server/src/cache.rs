@@ -0,0 +1,278 @@
+// Caché del gateway sobre Redis: respuestas por campo y documentos de consultas persistidas
+// Un fallo de la caché nunca rompe una petición: se registra y se resuelve contra el backend
+
+use std::collections::HashMap;
+use std::future::{self, Future};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use futures::stream::{self, StreamExt};
+use redis::aio::MultiplexedConnection;
+use redis::AsyncCommands;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use tokio::task::JoinHandle;
+
+use marketplace::domain::TutoringEvent;
+
+use crate::backend::Backend;
+
+/// Almacén clave-valor de la caché
+#[async_trait]
+pub trait CacheStore: Send + Sync {
+    /// Valores de varias claves, en el mismo orden; `None` si no están
+    async fn get_many(&self, keys: &[String]) -> Result<Vec<Option<String>>>;
+
+    /// Guardar un valor; sin `ttl` no expira
+    async fn set(&self, key: &str, value: &str, ttl: Option<Duration>) -> Result<()>;
+
+    async fn delete(&self, keys: &[String]) -> Result<()>;
+}
+
+/// Caché compartida por todas las instancias del gateway
+pub struct RedisStore {
+    connection: MultiplexedConnection,
+    prefix: String,   // Espacio de nombres de las claves (ej: "keiko:gql:")
+}
+
+impl RedisStore {
+    pub async fn connect(url: &str, prefix: &str) -> Result<Self> {
+        let client = redis::Client::open(url)?;
+        let connection = client.get_multiplexed_tokio_connection().await?;
+
+        Ok(Self { connection, prefix: prefix.to_string() })
+    }
+
+    fn key(&self, key: &str) -> String {
+        format!("{}{}", self.prefix, key)
+    }
+}
+
+#[async_trait]
+impl CacheStore for RedisStore {
+    async fn get_many(&self, keys: &[String]) -> Result<Vec<Option<String>>> {
+        if keys.is_empty() {
+            return Ok(Vec::new());
+        }
+        let keys: Vec<String> = keys.iter().map(|k| self.key(k)).collect();
+
+        Ok(self.connection.clone().mget(keys).await?)
+    }
+
+    async fn set(&self, key: &str, value: &str, ttl: Option<Duration>) -> Result<()> {
+        let mut connection = self.connection.clone();
+        match ttl {
+            Some(ttl) => connection.set_ex::<_, _, ()>(self.key(key), value, ttl.as_secs().max(1)).await?,
+            None => connection.set::<_, _, ()>(self.key(key), value).await?,
+        }
+
+        Ok(())
+    }
+
+    async fn delete(&self, keys: &[String]) -> Result<()> {
+        if keys.is_empty() {
+            return Ok(());
+        }
+        let keys: Vec<String> = keys.iter().map(|k| self.key(k)).collect();
+        self.connection.clone().del::<_, ()>(keys).await?;
+
+        Ok(())
+    }
+}
+
+/// Caché local del proceso, para desarrollo sin Redis y para pruebas
+#[derive(Default)]
+pub struct MemoryStore {
+    entries: Mutex<HashMap<String, (String, Option<Instant>)>>,
+}
+
+#[async_trait]
+impl CacheStore for MemoryStore {
+    async fn get_many(&self, keys: &[String]) -> Result<Vec<Option<String>>> {
+        let entries = self.entries.lock().unwrap();
+        let now = Instant::now();
+
+        Ok(keys
+            .iter()
+            .map(|key| {
+                entries
+                    .get(key)
+                    .filter(|(_, expires_at)| expires_at.is_none_or(|at| at > now))
+                    .map(|(value, _)| value.clone())
+            })
+            .collect())
+    }
+
+    async fn set(&self, key: &str, value: &str, ttl: Option<Duration>) -> Result<()> {
+        let expires_at = ttl.map(|ttl| Instant::now() + ttl);
+        self.entries.lock().unwrap().insert(key.to_string(), (value.to_string(), expires_at));
+
+        Ok(())
+    }
+
+    async fn delete(&self, keys: &[String]) -> Result<()> {
+        let mut entries = self.entries.lock().unwrap();
+        for key in keys {
+            entries.remove(key);
+        }
+
+        Ok(())
+    }
+}
+
+/// Campos públicos cuya respuesta se cachea, por usuario
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CachedField {
+    Passport,
+    PassportStatistics,
+    TutorProfile,
+}
+
+impl CachedField {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CachedField::Passport => "passport",
+            CachedField::PassportStatistics => "passport_statistics",
+            CachedField::TutorProfile => "tutor_profile",
+        }
+    }
+}
+
+/// Caché de respuestas por campo
+///
+/// Guarda el valor de dominio que resuelve cada campo, no el JSON de la respuesta, para
+/// que consultas con selecciones distintas compartan entrada. La invalidación la dirigen
+/// los eventos de dominio; el TTL acota lo que dure un valor escrito justo después de
+/// que su evento lo borrara.
+pub struct ResponseCache {
+    store: Arc<dyn CacheStore>,
+    ttl: Duration,
+}
+
+impl ResponseCache {
+    pub fn new(store: Arc<dyn CacheStore>, ttl: Duration) -> Self {
+        Self { store, ttl }
+    }
+
+    fn key(field: CachedField, key: &str) -> String {
+        format!("field:{}:{}", field.as_str(), key)
+    }
+
+    /// Valores de un campo para varias claves: los que faltan se cargan con `load` y se guardan
+    pub async fn load_many<T, F, Fut>(&self, field: CachedField, keys: &[String], load: F) -> Result<HashMap<String, T>>
+    where
+        T: Serialize + DeserializeOwned,
+        F: FnOnce(Vec<String>) -> Fut,
+        Fut: Future<Output = Result<HashMap<String, T>>>,
+    {
+        let cache_keys: Vec<String> = keys.iter().map(|key| Self::key(field, key)).collect();
+        let cached = self.store.get_many(&cache_keys).await.unwrap_or_else(|err| {
+            tracing::warn!("Caché de respuestas no disponible: {:#}", err);
+            vec![None; keys.len()]
+        });
+
+        let mut values = HashMap::new();
+        let mut missing = Vec::new();
+        for (key, value) in keys.iter().zip(cached) {
+            match value.and_then(|json| serde_json::from_str(&json).ok()) {
+                Some(value) => {
+                    values.insert(key.clone(), value);
+                }
+                None => missing.push(key.clone()),
+            }
+        }
+
+        if !missing.is_empty() {
+            for (key, value) in load(missing).await? {
+                match serde_json::to_string(&value) {
+                    Ok(json) => {
+                        if let Err(err) = self.store.set(&Self::key(field, &key), &json, Some(self.ttl)).await {
+                            tracing::warn!("No se pudo guardar en la caché de respuestas: {:#}", err);
+                        }
+                    }
+                    Err(err) => tracing::warn!("Valor no serializable para la caché: {}", err),
+                }
+                values.insert(key, value);
+            }
+        }
+
+        Ok(values)
+    }
+
+    /// Valor de un campo para una clave
+    pub async fn load<T, F, Fut>(&self, field: CachedField, key: &str, load: F) -> Result<Option<T>>
+    where
+        T: Serialize + DeserializeOwned,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<Option<T>>>,
+    {
+        let key = key.to_string();
+        let mut values = self
+            .load_many(field, std::slice::from_ref(&key), |keys| async move {
+                Ok(load().await?.map(|value| (keys[0].clone(), value)).into_iter().collect())
+            })
+            .await?;
+
+        Ok(values.remove(&key))
+    }
+
+    pub async fn invalidate(&self, entries: &[(CachedField, String)]) {
+        let keys: Vec<String> = entries.iter().map(|(field, key)| Self::key(*field, key)).collect();
+        if let Err(err) = self.store.delete(&keys).await {
+            tracing::warn!("No se pudo invalidar la caché de respuestas: {:#}", err);
+        }
+    }
+
+    /// Invalidar las entradas afectadas por cada evento de dominio del backend
+    pub fn spawn_invalidation(self: Arc<Self>, backend: &dyn Backend) -> JoinHandle<()> {
+        let passports = backend.passport_events().map(|event| {
+            let user_address = event.user_address().to_string();
+            vec![(CachedField::Passport, user_address.clone()), (CachedField::PassportStatistics, user_address)]
+        });
+        let tutors = backend.tutoring_events().filter_map(|event| {
+            future::ready(match event {
+                TutoringEvent::TutorProfileUpdated { user_address, .. } => {
+                    Some(vec![(CachedField::TutorProfile, user_address)])
+                }
+                _ => None,
+            })
+        });
+        let mut entries = stream::select(passports, tutors);
+
+        tokio::spawn(async move {
+            while let Some(entries) = entries.next().await {
+                self.invalidate(&entries).await;
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn caches_loaded_values_until_invalidated() {
+        let cache = ResponseCache::new(Arc::new(MemoryStore::default()), Duration::from_secs(60));
+        let loads = AtomicUsize::new(0);
+        let load = |keys: Vec<String>| {
+            loads.fetch_add(keys.len(), Ordering::SeqCst);
+            async move { Ok(keys.into_iter().filter(|k| k != "0x3").map(|k| (k.clone(), k.len())).collect()) }
+        };
+        let keys = ["0x1".to_string(), "0x3".to_string()];
+
+        let values = cache.load_many(CachedField::TutorProfile, &keys, load).await.unwrap();
+        assert_eq!(values, HashMap::from([("0x1".to_string(), 3)]));
+        cache.load_many(CachedField::TutorProfile, &keys, load).await.unwrap();
+        // Los ausentes no se cachean y se vuelven a pedir
+        assert_eq!(loads.load(Ordering::SeqCst), 3);
+
+        cache.invalidate(&[(CachedField::TutorProfile, "0x1".to_string())]).await;
+        cache.load_many(CachedField::TutorProfile, &keys, load).await.unwrap();
+        assert_eq!(loads.load(Ordering::SeqCst), 5);
+    }
+}
@@ -0,0 +1,152 @@
+// Conexiones Relay sobre la paginación por desplazamiento de los repositorios
+// El cursor es opaco para el cliente: codifica la posición del elemento en el listado filtrado
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use juniper::FieldResult;
+
+use crate::context::{error_code, field_error};
+use crate::schema::PageInfo;
+
+/// Elementos por página cuando no se indica `first` ni `last`
+pub const DEFAULT_PAGE_SIZE: i32 = 20;
+
+/// Máximo de elementos que se pueden pedir en una página
+pub const MAX_PAGE_SIZE: i32 = 100;
+
+const CURSOR_PREFIX: &str = "offset:";
+
+/// Argumentos de paginación de Relay
+#[derive(Debug, Clone, Default)]
+pub struct PageArgs {
+    pub first: Option<i32>,
+    pub after: Option<String>,
+    pub last: Option<i32>,
+    pub before: Option<String>,
+}
+
+/// Tramo del listado que corresponde a unos argumentos de paginación
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageWindow {
+    pub offset: i64,
+    pub limit: i64,
+    pub has_previous_page: bool,
+    pub has_next_page: bool,
+}
+
+impl PageArgs {
+    /// Calcular el tramo sobre un listado de `total_count` elementos
+    ///
+    /// `after` y `before` acotan el listado; `first` se aplica desde el inicio del
+    /// tramo y `last` desde el final, como indica la especificación de Relay.
+    pub fn window(&self, total_count: i64) -> FieldResult<PageWindow> {
+        let first = page_size("first", self.first)?;
+        let last = page_size("last", self.last)?;
+
+        let mut end = match &self.before {
+            Some(cursor) => decode_cursor(cursor)?.min(total_count),
+            None => total_count,
+        };
+        let mut start = match &self.after {
+            Some(cursor) => decode_cursor(cursor)?.saturating_add(1).min(end),
+            None => 0,
+        };
+
+        if let Some(first) = first {
+            end = end.min(start + first);
+        }
+        if let Some(last) = last {
+            start = start.max(end - last);
+        }
+        if first.is_none() && last.is_none() {
+            end = end.min(start + i64::from(DEFAULT_PAGE_SIZE));
+        }
+
+        Ok(PageWindow {
+            offset: start,
+            limit: end - start,
+            has_previous_page: start > 0,
+            has_next_page: end < total_count,
+        })
+    }
+}
+
+impl PageWindow {
+    /// Cursor del elemento `index` de la página
+    pub fn cursor(&self, index: usize) -> String {
+        encode_cursor(self.offset + index as i64)
+    }
+
+    /// Información de página para `len` elementos devueltos
+    pub fn page_info(&self, len: usize) -> PageInfo {
+        PageInfo {
+            has_next_page: self.has_next_page,
+            has_previous_page: self.has_previous_page,
+            start_cursor: (len > 0).then(|| self.cursor(0)),
+            end_cursor: len.checked_sub(1).map(|last| self.cursor(last)),
+        }
+    }
+}
+
+fn page_size(argument: &str, size: Option<i32>) -> FieldResult<Option<i64>> {
+    match size {
+        Some(size) if !(0..=MAX_PAGE_SIZE).contains(&size) => Err(field_error(
+            format!("`{argument}` debe estar entre 0 y {MAX_PAGE_SIZE}"),
+            error_code::BAD_USER_INPUT,
+        )),
+        size => Ok(size.map(i64::from)),
+    }
+}
+
+pub fn encode_cursor(offset: i64) -> String {
+    URL_SAFE_NO_PAD.encode(format!("{CURSOR_PREFIX}{offset}"))
+}
+
+pub fn decode_cursor(cursor: &str) -> FieldResult<i64> {
+    URL_SAFE_NO_PAD
+        .decode(cursor)
+        .ok()
+        .and_then(|bytes| String::from_utf8(bytes).ok())
+        .and_then(|s| s.strip_prefix(CURSOR_PREFIX)?.parse::<i64>().ok())
+        .filter(|offset| *offset >= 0)
+        .ok_or_else(|| field_error(format!("Cursor no válido: {cursor}"), error_code::BAD_USER_INPUT))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(first: Option<i32>, after: Option<i64>, last: Option<i32>, before: Option<i64>) -> PageArgs {
+        PageArgs { first, after: after.map(encode_cursor), last, before: before.map(encode_cursor) }
+    }
+
+    #[test]
+    fn windows_follow_relay_slicing() {
+        let page = |args: PageArgs| {
+            let window = args.window(50).unwrap();
+            (window.offset, window.limit, window.has_previous_page, window.has_next_page)
+        };
+
+        assert_eq!(page(args(None, None, None, None)), (0, 20, false, true));
+        assert_eq!(page(args(Some(10), Some(9), None, None)), (10, 10, true, true));
+        assert_eq!(page(args(Some(10), Some(45), None, None)), (46, 4, true, false));
+        assert_eq!(page(args(None, None, Some(5), None)), (45, 5, true, false));
+        assert_eq!(page(args(None, None, Some(5), Some(3))), (0, 3, false, true));
+        assert_eq!(page(args(None, Some(80), None, None)), (50, 0, true, false));
+    }
+
+    #[test]
+    fn the_largest_after_cursor_yields_an_empty_page() {
+        let window = args(Some(10), Some(i64::MAX), None, None).window(50).unwrap();
+
+        assert_eq!((window.offset, window.limit, window.has_next_page), (50, 0, false));
+    }
+
+    #[test]
+    fn rejects_malformed_cursors_and_oversized_pages() {
+        assert_eq!(decode_cursor(&encode_cursor(42)).unwrap(), 42);
+        assert!(decode_cursor("b2Zmc2V0Oi0x").is_err());
+        assert!(decode_cursor("not a cursor").is_err());
+        assert!(args(Some(MAX_PAGE_SIZE + 1), None, None, None).window(10).is_err());
+        assert!(args(None, None, Some(-1), None).window(10).is_err());
+    }
+}
@@ -1,39 +1,409 @@
-use juniper::FieldResult;
+use std::str::FromStr;
+use std::sync::Arc;
 
-pub struct Context;
+use chrono::{DateTime, Utc};
+use futures::future;
+use futures::stream::{BoxStream, StreamExt};
+use identity::domain::{Action, Principal, Resource};
+use identity::service::Authorizer;
+use juniper::{graphql_value, FieldError, FieldResult};
+use learning_passport::domain::{FrameworkId, InteractionFilter, LearningInteraction, LifeLearningPassport};
+use marketplace::domain::{SessionFilter, SessionOrder, TutorProfile, TutoringSession};
+use reputation::domain::{RatedRole, ReputationScore};
+use uuid::Uuid;
+
+use crate::backend::Backend;
+use crate::cache::{CachedField, ResponseCache};
+use crate::connection::PageArgs;
+use crate::loader::Loader;
+use crate::schema;
+
+pub struct Context {
+    pub principal: Option<Principal>,
+    authorizer: Arc<dyn Authorizer>,
+    backend: Arc<dyn Backend>,
+    cache: Option<Arc<ResponseCache>>,
+    loaders: Loaders,
+}
+
+/// Cargadores por lotes de una petición; su caché vive lo mismo que el contexto
+struct Loaders {
+    passports: Loader<String, LifeLearningPassport>,
+    tutor_profiles: Loader<String, TutorProfile>,
+    interactions: Loader<Uuid, LearningInteraction>,
+}
+
+impl Loaders {
+    fn new(backend: &Arc<dyn Backend>, cache: Option<Arc<ResponseCache>>) -> Self {
+        let passports = backend.clone();
+        let tutor_profiles = backend.clone();
+        let interactions = backend.clone();
+        let passport_cache = cache.clone();
+        let tutor_profile_cache = cache;
+
+        Self {
+            passports: Loader::new(move |addresses: Vec<String>| {
+                let backend = passports.clone();
+                let cache = passport_cache.clone();
+                async move {
+                    let load = |addresses: Vec<String>| async move {
+                        let passports = backend.get_passports(&addresses).await?;
+                        Ok(passports.into_iter().map(|p| (p.user_address.clone(), p)).collect())
+                    };
+                    match cache {
+                        Some(cache) => cache.load_many(CachedField::Passport, &addresses, load).await,
+                        None => load(addresses).await,
+                    }
+                }
+            }),
+            tutor_profiles: Loader::new(move |addresses: Vec<String>| {
+                let backend = tutor_profiles.clone();
+                let cache = tutor_profile_cache.clone();
+                async move {
+                    let load = |addresses: Vec<String>| async move {
+                        let profiles = backend.get_tutor_profiles(&addresses).await?;
+                        Ok(profiles.into_iter().map(|p| (p.user_address.clone(), p)).collect())
+                    };
+                    match cache {
+                        Some(cache) => cache.load_many(CachedField::TutorProfile, &addresses, load).await,
+                        None => load(addresses).await,
+                    }
+                }
+            }),
+            interactions: Loader::new(move |ids: Vec<Uuid>| {
+                let backend = interactions.clone();
+                async move {
+                    let interactions = backend.get_interactions(&ids).await?;
+                    Ok(interactions.into_iter().map(|i| (i.id.0, i)).collect())
+                }
+            }),
+        }
+    }
+}
 
 impl juniper::Context for Context {}
 
+/// Códigos de error expuestos en `extensions.code`
+pub mod error_code {
+    pub const UNAUTHENTICATED: &str = "UNAUTHENTICATED";
+    pub const FORBIDDEN: &str = "FORBIDDEN";
+    pub const NOT_FOUND: &str = "NOT_FOUND";
+    pub const BAD_USER_INPUT: &str = "BAD_USER_INPUT";
+    pub const QUERY_TOO_COMPLEX: &str = "QUERY_TOO_COMPLEX";
+    pub const PERSISTED_QUERY_NOT_FOUND: &str = "PERSISTED_QUERY_NOT_FOUND";
+    pub const PERSISTED_QUERY_NOT_ALLOWED: &str = "PERSISTED_QUERY_NOT_ALLOWED";
+    pub const INTERNAL: &str = "INTERNAL";
+}
+
+/// Error GraphQL con su código en las extensiones
+pub fn field_error(message: impl std::fmt::Display, code: &str) -> FieldError {
+    FieldError::new(message, graphql_value!({ "code": code }))
+}
+
+/// Error inesperado de un servicio del backend
+pub fn internal_error(err: anyhow::Error) -> FieldError {
+    field_error(err, error_code::INTERNAL)
+}
+
 impl Context {
-    pub async fn get_user(&self, _id: &str) -> FieldResult<Option<crate::schema::User>> {
-        Ok(None)
+    /// Sin `cache` los campos públicos se resuelven siempre contra el backend
+    pub fn new(
+        principal: Option<Principal>,
+        authorizer: Arc<dyn Authorizer>,
+        backend: Arc<dyn Backend>,
+        cache: Option<Arc<ResponseCache>>,
+    ) -> Self {
+        let loaders = Loaders::new(&backend, cache.clone());
+        Self { principal, authorizer, backend, cache, loaders }
     }
 
-    pub async fn get_learning_interactions(&self, _user_id: &str) -> FieldResult<Vec<crate::schema::LearningInteraction>> {
-        Ok(Vec::new())
+    /// Exigir autorización antes de resolver un campo
+    pub async fn authorize(&self, action: Action, resource: Resource) -> FieldResult<()> {
+        let decision = self
+            .authorizer
+            .authorize(self.principal.as_ref(), action, &resource)
+            .await
+            .map_err(internal_error)?;
+
+        if decision.allowed {
+            Ok(())
+        } else if self.principal.is_none() {
+            Err(field_error(decision.reason, error_code::UNAUTHENTICATED))
+        } else {
+            Err(field_error(decision.reason, error_code::FORBIDDEN))
+        }
     }
 
-    pub async fn get_tutoring_sessions(&self, _user_id: &str) -> FieldResult<Vec<crate::schema::TutoringSession>> {
-        Ok(Vec::new())
+    /// Usuario con pasaporte; su nombre es el de su perfil de tutor o, si no tiene, su dirección
+    pub async fn get_user(&self, id: &str) -> FieldResult<Option<schema::User>> {
+        let (passport, profile) = futures::try_join!(
+            self.loaders.passports.load(id.to_string()),
+            self.loaders.tutor_profiles.load(id.to_string()),
+        )
+        .map_err(internal_error)?;
+
+        Ok(passport.map(|passport| schema::User {
+            name: profile.map(|p| p.display_name).unwrap_or_else(|| passport.user_address.clone()),
+            id: passport.user_address,
+            passport_id: Some(passport.id.0),
+        }))
     }
 
+    /// Varios usuarios, en el orden pedido; los que no existen se omiten
+    pub async fn get_users(&self, ids: &[String]) -> FieldResult<Vec<schema::User>> {
+        let users = futures::future::try_join_all(ids.iter().map(|id| self.get_user(id))).await?;
+
+        Ok(users.into_iter().flatten().collect())
+    }
+
+    /// Pasaporte cargado por lotes junto al de otros usuarios de la misma petición
+    pub async fn load_passport(&self, user_id: &str) -> FieldResult<Option<schema::LifeLearningPassport>> {
+        let passport = self.loaders.passports.load(user_id.to_string()).await.map_err(internal_error)?;
+
+        Ok(passport.map(Into::into))
+    }
+
+    /// Interacción cargada por lotes junto a otras de la misma petición
+    pub async fn load_interaction(&self, interaction_id: Uuid) -> FieldResult<Option<schema::LearningInteraction>> {
+        let interaction = self.loaders.interactions.load(interaction_id).await.map_err(internal_error)?;
+
+        Ok(interaction.map(Into::into))
+    }
+
+    pub async fn get_passport(&self, user_id: &str) -> FieldResult<Option<schema::LifeLearningPassport>> {
+        let passport = self.backend.get_passport(user_id).await.map_err(internal_error)?;
+
+        Ok(passport.map(Into::into))
+    }
+
+    pub async fn get_passport_statistics(&self, user_id: &str) -> FieldResult<Option<schema::PassportStatistics>> {
+        let load = || self.backend.get_passport_statistics(user_id);
+        let statistics = match &self.cache {
+            Some(cache) => cache.load(CachedField::PassportStatistics, user_id, load).await,
+            None => load().await,
+        }
+        .map_err(internal_error)?;
+
+        Ok(statistics.map(Into::into))
+    }
+
+    pub async fn get_learning_interactions(
+        &self,
+        user_id: &str,
+        filter: &InteractionFilter,
+        descending: bool,
+        page: &PageArgs,
+    ) -> FieldResult<schema::LearningInteractionConnection> {
+        let total_count = self.backend.count_learning_interactions(user_id, filter).await.map_err(internal_error)?;
+        let window = page.window(total_count)?;
+        let interactions = self
+            .backend
+            .list_learning_interactions(user_id, filter, descending, window.limit, window.offset)
+            .await
+            .map_err(internal_error)?;
+
+        Ok(schema::LearningInteractionConnection {
+            page_info: window.page_info(interactions.len()),
+            edges: interactions
+                .into_iter()
+                .enumerate()
+                .map(|(i, interaction)| schema::LearningInteractionEdge {
+                    node: interaction.into(),
+                    cursor: window.cursor(i),
+                })
+                .collect(),
+            total_count: i32::try_from(total_count).unwrap_or(i32::MAX),
+        })
+    }
+
+    pub async fn get_tutoring_sessions(
+        &self,
+        user_id: &str,
+        filter: &SessionFilter,
+        order: SessionOrder,
+        page: &PageArgs,
+    ) -> FieldResult<schema::TutoringSessionConnection> {
+        let total_count = self.backend.count_tutoring_sessions(user_id, filter).await.map_err(internal_error)?;
+        let window = page.window(total_count)?;
+        let sessions = self
+            .backend
+            .list_tutoring_sessions(user_id, filter, order, window.limit, window.offset)
+            .await
+            .map_err(internal_error)?;
+
+        Ok(schema::TutoringSessionConnection {
+            page_info: window.page_info(sessions.len()),
+            edges: sessions
+                .into_iter()
+                .enumerate()
+                .map(|(i, session)| schema::TutoringSessionEdge { node: to_schema_session(session), cursor: window.cursor(i) })
+                .collect(),
+            total_count: i32::try_from(total_count).unwrap_or(i32::MAX),
+        })
+    }
+
+    pub async fn get_reputation(&self, user_id: &str, role: &str) -> FieldResult<Option<schema::ReputationScore>> {
+        let role = parse_role(role)?;
+        let score = self.backend.get_reputation(user_id, role).await.map_err(internal_error)?;
+
+        Ok(score.map(to_schema_reputation))
+    }
+
+    pub async fn get_reputation_history(&self, user_id: &str, role: &str) -> FieldResult<Vec<schema::ReputationScore>> {
+        let role = parse_role(role)?;
+        let history = self.backend.get_reputation_history(user_id, role).await.map_err(internal_error)?;
+
+        Ok(history.into_iter().map(to_schema_reputation).collect())
+    }
+
+    pub async fn get_competency_frameworks(&self) -> FieldResult<Vec<schema::CompetencyFramework>> {
+        let frameworks = self.backend.get_competency_frameworks().await.map_err(internal_error)?;
+
+        Ok(frameworks
+            .into_iter()
+            .map(|f| schema::CompetencyFramework {
+                id: f.id.0,
+                name: f.name,
+                source: f.source.as_str().to_string(),
+                source_uri: f.source_uri,
+                version: f.version,
+                language: f.language,
+            })
+            .collect())
+    }
+
+    pub async fn get_competency_profile(&self, user_id: &str, framework_id: Uuid) -> FieldResult<Option<schema::CompetencyProfile>> {
+        let framework_id = FrameworkId(framework_id);
+        let profile = self.backend.get_competency_profile(user_id, &framework_id).await.map_err(internal_error)?;
+
+        Ok(profile.map(|p| schema::CompetencyProfile {
+            user_id: p.user_address,
+            framework_id: p.framework_id.0,
+            framework_name: p.framework_name,
+            proficiencies: p
+                .proficiencies
+                .into_iter()
+                .map(|c| schema::CompetencyProficiency {
+                    competency_uri: c.competency_uri,
+                    code: c.code,
+                    title: c.title,
+                    parent_uri: c.parent_uri,
+                    level: c.level,
+                    coverage: c.coverage,
+                    evidence: c
+                        .evidence
+                        .into_iter()
+                        .map(|e| schema::CompetencyEvidence {
+                            interaction_id: e.interaction_id,
+                            activity_iri: e.activity_iri,
+                            verb: e.verb,
+                            value: e.value,
+                            weight: e.weight,
+                            via_competency_uri: e.via_competency_uri,
+                            timestamp: e.timestamp,
+                        })
+                        .collect(),
+                })
+                .collect(),
+            computed_at: p.computed_at,
+        }))
+    }
+
+    /// Eventos del pasaporte de un usuario
+    pub fn passport_events(&self, user_id: &str) -> BoxStream<'static, FieldResult<schema::PassportEvent>> {
+        let user_id = user_id.to_string();
+
+        self.backend
+            .passport_events()
+            .filter(move |event| future::ready(event.user_address() == user_id))
+            .map(|event| Ok(event.into()))
+            .boxed()
+    }
+
+    /// Eventos de las sesiones de tutoría en las que participa un usuario
+    pub fn tutoring_session_events(&self, user_id: &str) -> BoxStream<'static, FieldResult<schema::TutoringSessionEvent>> {
+        let user_id = user_id.to_string();
+
+        self.backend
+            .tutoring_events()
+            .filter(move |event| future::ready(event.involves(&user_id)))
+            .filter_map(|event| future::ready(schema::TutoringSessionEvent::try_from(event).ok().map(Ok)))
+            .boxed()
+    }
+
+    /// Registrar una interacción autodeclarada
     pub async fn create_learning_interaction(
         &self,
-        user_id: String,
-        activity_type: String,
-        content: String,
-    ) -> FieldResult<crate::schema::LearningInteraction> {
-        Ok(crate::schema::LearningInteraction { id: String::new(), user_id, activity_type, content, timestamp: String::new() })
+        input: schema::LearningInteractionInput,
+    ) -> FieldResult<schema::LearningInteraction> {
+        let verb = input.verb.unwrap_or_else(|| DEFAULT_VERB.to_string());
+        let interaction = self
+            .backend
+            .add_learning_interaction(
+                &input.user_id,
+                &verb,
+                &input.object,
+                input.activity_type.as_deref(),
+                input.result.map(Into::into),
+                input.context.map(Into::into),
+            )
+            .await
+            .map_err(|e| field_error(e, error_code::BAD_USER_INPUT))?;
+
+        Ok(interaction.into())
     }
 
+    /// Solicitar una reserva de tutoría en nombre del estudiante
     pub async fn start_tutoring_session(
         &self,
         tutor_id: String,
         student_id: String,
         subject: String,
-    ) -> FieldResult<crate::schema::TutoringSession> {
-        Ok(crate::schema::TutoringSession { id: String::new(), tutor_id, student_id, subject, status: String::from("pending") })
+        scheduled_start: String,
+        scheduled_end: String,
+        message: Option<String>,
+    ) -> FieldResult<schema::TutoringSession> {
+        let scheduled_start = parse_datetime(&scheduled_start)?;
+        let scheduled_end = parse_datetime(&scheduled_end)?;
+        let session = self
+            .backend
+            .request_tutoring_session(&student_id, &tutor_id, &subject, scheduled_start, scheduled_end, message)
+            .await
+            .map_err(|e| field_error(e, error_code::BAD_USER_INPUT))?;
+
+        Ok(to_schema_session(session))
     }
 }
 
+/// Verbo xAPI de las interacciones creadas sin verbo explícito
+const DEFAULT_VERB: &str = "http://adlnet.gov/expapi/verbs/experienced";
 
+fn parse_role(role: &str) -> FieldResult<RatedRole> {
+    RatedRole::from_str(role).map_err(|e| field_error(e, error_code::BAD_USER_INPUT))
+}
+
+fn parse_datetime(value: &str) -> FieldResult<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|d| d.with_timezone(&Utc))
+        .map_err(|_| field_error(format!("Fecha RFC 3339 no válida: {}", value), error_code::BAD_USER_INPUT))
+}
+
+fn to_schema_session(session: TutoringSession) -> schema::TutoringSession {
+    schema::TutoringSession {
+        id: session.id.0,
+        tutor_id: session.tutor,
+        student_id: session.learner,
+        subject: session.subject.code,
+        status: session.status.as_str().to_string(),
+    }
+}
+
+fn to_schema_reputation(score: ReputationScore) -> schema::ReputationScore {
+    schema::ReputationScore {
+        user_id: score.user_address,
+        role: score.role.as_str().to_string(),
+        score: score.score,
+        rating_count: i32::try_from(score.rating_count).unwrap_or(i32::MAX),
+        computed_at: score.computed_at,
+    }
+}
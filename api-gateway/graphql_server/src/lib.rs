@@ -1,4 +1,15 @@
+
+
 pub mod schema;
+pub mod scalars;
 pub mod context;
-
+pub mod auth;
+pub mod backend;
+pub mod cache;
+pub mod connection;
+pub mod limits;
+pub mod loader;
+pub mod persisted;
+pub mod sdl;
+pub mod subscriptions;
 
@@ -0,0 +1,199 @@
+// Límites de profundidad y complejidad de las consultas GraphQL
+// Se comprueban sobre el documento antes de ejecutarlo, con los fragmentos expandidos
+
+use std::collections::HashMap;
+
+use juniper::http::{GraphQLRequest, GraphQLResponse};
+use juniper::{parser::parse_document_source, Definition, FieldResult, Selection};
+
+use crate::context::{error_code, field_error, Context};
+use crate::schema::Schema;
+
+/// Límites configurables de una consulta
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueryLimits {
+    pub max_depth: usize,       // Niveles de selección anidados
+    pub max_complexity: usize,  // Campos seleccionados en total
+    pub max_batch_size: usize,  // Operaciones por petición en lote
+}
+
+impl Default for QueryLimits {
+    fn default() -> Self {
+        Self { max_depth: 10, max_complexity: 250, max_batch_size: 10 }
+    }
+}
+
+/// Profundidad y complejidad medidas de una operación
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QueryCost {
+    pub depth: usize,
+    pub complexity: usize,
+}
+
+type Selections<'a> = [Selection<'a, juniper::DefaultScalarValue>];
+
+struct Analyzer<'d, 'a> {
+    fragments: HashMap<&'a str, &'d Selections<'a>>,
+}
+
+impl<'d, 'a> Analyzer<'d, 'a> {
+    /// Los campos de introspección (`__schema`, `__type`...) no cuentan
+    fn measure(&self, selections: &'d Selections<'a>, visiting: &mut Vec<&'a str>) -> QueryCost {
+        let mut cost = QueryCost::default();
+
+        for selection in selections {
+            let nested = match selection {
+                Selection::Field(field) => {
+                    if field.item.name.item.starts_with("__") {
+                        continue;
+                    }
+                    let children = field
+                        .item
+                        .selection_set
+                        .as_deref()
+                        .map(|children| self.measure(children, visiting))
+                        .unwrap_or_default();
+                    QueryCost { depth: children.depth + 1, complexity: children.complexity + 1 }
+                }
+                Selection::FragmentSpread(spread) => {
+                    let name = spread.item.name.item;
+                    // Los ciclos de fragmentos los rechaza la validación; aquí solo se cortan
+                    let Some(fragment) = self.fragments.get(name).filter(|_| !visiting.contains(&name)) else {
+                        continue;
+                    };
+                    visiting.push(name);
+                    let nested = self.measure(fragment, visiting);
+                    visiting.pop();
+                    nested
+                }
+                Selection::InlineFragment(fragment) => self.measure(&fragment.item.selection_set, visiting),
+            };
+
+            cost.depth = cost.depth.max(nested.depth);
+            cost.complexity += nested.complexity;
+        }
+
+        cost
+    }
+}
+
+/// Medir las operaciones de una consulta; `None` si no se puede analizar
+///
+/// Con `operation_name` solo se mide esa operación. Los errores de sintaxis se dejan
+/// para la ejecución, que los devuelve con su posición.
+pub fn measure_query(schema: &Schema, query: &str, operation_name: Option<&str>) -> Option<QueryCost> {
+    let document = parse_document_source(query, &schema.schema).ok()?;
+
+    let analyzer = Analyzer {
+        fragments: document
+            .iter()
+            .filter_map(|definition| match definition {
+                Definition::Fragment(fragment) => {
+                    Some((fragment.item.name.item, fragment.item.selection_set.as_slice()))
+                }
+                Definition::Operation(_) => None,
+            })
+            .collect(),
+    };
+
+    let cost = document
+        .iter()
+        .filter_map(|definition| match definition {
+            Definition::Operation(operation) => Some(&operation.item),
+            Definition::Fragment(_) => None,
+        })
+        .filter(|operation| match operation_name {
+            Some(name) => operation.name.as_ref().map(|n| n.item) == Some(name),
+            None => true,
+        })
+        .map(|operation| analyzer.measure(&operation.selection_set, &mut Vec::new()))
+        .fold(QueryCost::default(), |acc, cost| QueryCost {
+            depth: acc.depth.max(cost.depth),
+            complexity: acc.complexity.max(cost.complexity),
+        });
+
+    Some(cost)
+}
+
+/// Rechazar la consulta si supera los límites
+pub fn check_query(schema: &Schema, request: &GraphQLRequest, limits: &QueryLimits) -> FieldResult<()> {
+    let Some(cost) = measure_query(schema, &request.query, request.operation_name.as_deref()) else {
+        return Ok(());
+    };
+
+    if cost.depth > limits.max_depth {
+        return Err(field_error(
+            format!("La consulta supera la profundidad máxima ({} > {})", cost.depth, limits.max_depth),
+            error_code::QUERY_TOO_COMPLEX,
+        ));
+    }
+    if cost.complexity > limits.max_complexity {
+        return Err(field_error(
+            format!("La consulta supera la complejidad máxima ({} > {})", cost.complexity, limits.max_complexity),
+            error_code::QUERY_TOO_COMPLEX,
+        ));
+    }
+
+    Ok(())
+}
+
+/// Rechazar un lote con demasiadas operaciones
+///
+/// Los límites se comprueban por operación: sin tope, un lote los multiplicaría.
+pub fn check_batch_size(size: usize, limits: &QueryLimits) -> FieldResult<()> {
+    if size > limits.max_batch_size {
+        return Err(field_error(
+            format!("El lote supera el máximo de operaciones ({} > {})", size, limits.max_batch_size),
+            error_code::QUERY_TOO_COMPLEX,
+        ));
+    }
+
+    Ok(())
+}
+
+/// Ejecutar una petición rechazándola antes si supera los límites
+pub async fn execute_checked(
+    schema: &Schema,
+    request: &GraphQLRequest,
+    context: &Context,
+    limits: &QueryLimits,
+) -> GraphQLResponse {
+    match check_query(schema, request, limits) {
+        Ok(()) => request.execute(schema, context).await,
+        Err(err) => GraphQLResponse::error(err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::create_schema;
+
+    #[test]
+    fn fragments_are_expanded_and_introspection_is_free() {
+        let schema = create_schema();
+        let query = r#"
+            query Deep {
+                user(id: "0x1") { ...Profile passport { interactions { instructor { ...Profile } } } }
+                __schema { types { name fields { name type { ofType { ofType { name } } } } } }
+            }
+            fragment Profile on User { id name }
+        "#;
+
+        let cost = measure_query(&schema, query, Some("Deep")).unwrap();
+        assert_eq!(cost, QueryCost { depth: 5, complexity: 8 });
+
+        let request = GraphQLRequest::new(query.to_string(), None, None);
+        let limits = QueryLimits { max_depth: 4, max_complexity: 100, ..QueryLimits::default() };
+        assert!(check_query(&schema, &request, &limits).is_err());
+        assert!(check_query(&schema, &request, &QueryLimits::default()).is_ok());
+    }
+
+    #[test]
+    fn batches_are_capped() {
+        let limits = QueryLimits { max_batch_size: 2, ..QueryLimits::default() };
+
+        assert!(check_batch_size(2, &limits).is_ok());
+        assert!(check_batch_size(3, &limits).is_err());
+    }
+}
@@ -0,0 +1,145 @@
+// Carga por lotes al estilo DataLoader, con caché por petición
+// Las claves pedidas por resolvers hermanos se agrupan en una sola llamada al backend
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::hash::Hash;
+use std::mem;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context as TaskContext, Poll};
+
+use futures::future::{BoxFuture, FutureExt, Shared};
+
+type BatchResult<K, V> = Result<Arc<HashMap<K, V>>, Arc<anyhow::Error>>;
+type BatchFuture<K, V> = Shared<BoxFuture<'static, BatchResult<K, V>>>;
+type BatchFn<K, V> = dyn Fn(Vec<K>) -> BoxFuture<'static, anyhow::Result<HashMap<K, V>>> + Send + Sync;
+
+struct LoaderState<K, V> {
+    cache: HashMap<K, Option<V>>,
+    pending: Vec<K>,
+    in_flight: HashMap<K, BatchFuture<K, V>>,
+}
+
+/// Cargador por lotes de una petición
+///
+/// Cada `load` registra su clave y cede el turno una vez; el primer resolver que
+/// vuelve lanza el lote con todas las claves registradas y el resto espera ese mismo
+/// resultado. Las claves ya resueltas (también las ausentes) se sirven desde la caché.
+pub struct Loader<K, V> {
+    batch: Arc<BatchFn<K, V>>,
+    state: Mutex<LoaderState<K, V>>,
+}
+
+impl<K, V> Loader<K, V>
+where
+    K: Eq + Hash + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    pub fn new<F, Fut>(batch: F) -> Self
+    where
+        F: Fn(Vec<K>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = anyhow::Result<HashMap<K, V>>> + Send + 'static,
+    {
+        Self {
+            batch: Arc::new(move |keys| batch(keys).boxed()),
+            state: Mutex::new(LoaderState { cache: HashMap::new(), pending: Vec::new(), in_flight: HashMap::new() }),
+        }
+    }
+
+    pub async fn load(&self, key: K) -> anyhow::Result<Option<V>> {
+        {
+            let mut state = self.state.lock().unwrap();
+            if let Some(value) = state.cache.get(&key) {
+                return Ok(value.clone());
+            }
+            if !state.in_flight.contains_key(&key) && !state.pending.contains(&key) {
+                state.pending.push(key.clone());
+            }
+        }
+
+        // Dejar que los resolvers hermanos registren sus claves antes de lanzar el lote
+        YieldNow(false).await;
+
+        let (batch, dispatched) = {
+            let mut state = self.state.lock().unwrap();
+            if let Some(value) = state.cache.get(&key) {
+                return Ok(value.clone());
+            }
+            match state.in_flight.get(&key) {
+                Some(batch) => (batch.clone(), Vec::new()),
+                None => {
+                    let keys = mem::take(&mut state.pending);
+                    let batch = (self.batch)(keys.clone()).map(|r| r.map(Arc::new).map_err(Arc::new)).boxed().shared();
+                    for key in &keys {
+                        state.in_flight.insert(key.clone(), batch.clone());
+                    }
+                    (batch, keys)
+                }
+            }
+        };
+
+        let result = batch.await;
+
+        if !dispatched.is_empty() {
+            let mut state = self.state.lock().unwrap();
+            for key in dispatched {
+                state.in_flight.remove(&key);
+                // Tras un error no se cachea nada y la siguiente carga lo reintenta
+                if let Ok(values) = &result {
+                    let value = values.get(&key).cloned();
+                    state.cache.insert(key, value);
+                }
+            }
+        }
+
+        match result {
+            Ok(values) => Ok(values.get(&key).cloned()),
+            Err(err) => Err(anyhow::anyhow!("{:#}", err)),
+        }
+    }
+}
+
+/// Ceder el turno una sola vez al ejecutor
+struct YieldNow(bool);
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<()> {
+        if self.0 {
+            return Poll::Ready(());
+        }
+        self.0 = true;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use futures::future::join_all;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn sibling_loads_share_one_batch_and_cache_misses() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let loader = Loader::new(move |keys: Vec<u32>| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            async move { Ok(keys.into_iter().filter(|k| k % 2 == 0).map(|k| (k, k * 10)).collect()) }
+        });
+
+        let values = join_all([1, 2, 3, 4, 2].map(|k| loader.load(k))).await;
+        let values: Vec<Option<u32>> = values.into_iter().map(Result::unwrap).collect();
+        assert_eq!(values, vec![None, Some(20), None, Some(40), Some(20)]);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        assert_eq!(loader.load(3).await.unwrap(), None);
+        assert_eq!(loader.load(4).await.unwrap(), Some(40));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,377 @@
+// Consultas persistidas: el cliente envía el hash SHA-256 del documento en lugar del texto
+// Protocolo APQ de Apollo (`extensions.persistedQuery`), con registro automático o lista cerrada
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{bail, Result};
+use axum::{
+    async_trait,
+    body::Body,
+    extract::{FromRequest, Query},
+    http::{header, Method, Request, StatusCode},
+    response::{IntoResponse, Response},
+    Json, RequestExt,
+};
+use juniper::http::{GraphQLBatchResponse, GraphQLRequest, GraphQLResponse};
+use juniper::{parser::parse_document_source, Definition, FieldResult, InputValue, OperationType};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+use crate::cache::CacheStore;
+use crate::context::{error_code, field_error, Context};
+use crate::limits::{check_batch_size, check_query, QueryLimits};
+use crate::schema::Schema;
+
+/// Única versión del protocolo APQ
+pub const PERSISTED_QUERY_VERSION: u32 = 1;
+
+/// Vida de un documento registrado por APQ; los clientes lo reenvían si ha expirado
+pub const AUTOMATIC_QUERY_TTL: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Hash con el que se registra un documento: SHA-256 en hexadecimal
+pub fn query_hash(query: &str) -> String {
+    hex::encode(Sha256::digest(query.as_bytes()))
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PersistedQueryExtension {
+    pub version: u32,
+    #[serde(rename = "sha256Hash")]
+    pub sha256_hash: String,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RequestExtensions {
+    #[serde(rename = "persistedQuery")]
+    pub persisted_query: Option<PersistedQueryExtension>,
+}
+
+/// Petición GraphQL del gateway: como la de juniper, pero sin documento si llega su hash
+#[derive(Debug, Clone, Deserialize)]
+pub struct GatewayRequest {
+    pub query: Option<String>,
+    #[serde(rename = "operationName")]
+    pub operation_name: Option<String>,
+    pub variables: Option<InputValue>,
+    #[serde(default)]
+    pub extensions: RequestExtensions,
+    /// Las peticiones GET no pueden cambiar estado: solo ejecutan consultas
+    #[serde(skip)]
+    pub queries_only: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum GatewayBatchRequest {
+    Single(GatewayRequest),
+    Batch(Vec<GatewayRequest>),
+}
+
+/// Parámetros de una petición GET; `variables` y `extensions` llegan como JSON
+#[derive(Debug, Deserialize)]
+struct GetParams {
+    query: Option<String>,
+    #[serde(rename = "operationName")]
+    operation_name: Option<String>,
+    variables: Option<String>,
+    extensions: Option<String>,
+}
+
+impl TryFrom<GetParams> for GatewayRequest {
+    type Error = serde_json::Error;
+
+    fn try_from(params: GetParams) -> Result<Self, Self::Error> {
+        Ok(Self {
+            query: params.query,
+            operation_name: params.operation_name,
+            variables: params.variables.map(|v| serde_json::from_str(&v)).transpose()?,
+            extensions: params.extensions.map(|e| serde_json::from_str(&e)).transpose()?.unwrap_or_default(),
+            queries_only: true,
+        })
+    }
+}
+
+/// Extractor de axum para `/graphql`: GET con parámetros, o POST JSON (uno o un lote) o `application/graphql`
+pub struct PersistedRequest(pub GatewayBatchRequest);
+
+#[async_trait]
+impl<S: Send + Sync> FromRequest<S> for PersistedRequest {
+    type Rejection = Response;
+
+    async fn from_request(mut request: Request<Body>, state: &S) -> Result<Self, Self::Rejection> {
+        let bad_request = |message: String| (StatusCode::BAD_REQUEST, message).into_response();
+        let content_type = request
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+
+        match *request.method() {
+            Method::GET => {
+                let Query(params) = request
+                    .extract_parts::<Query<GetParams>>()
+                    .await
+                    .map_err(|e| bad_request(format!("Parámetros de consulta no válidos: {e}")))?;
+                let request = params.try_into().map_err(|e| bad_request(format!("JSON no válido: {e}")))?;
+                Ok(Self(GatewayBatchRequest::Single(request)))
+            }
+            Method::POST if content_type.starts_with("application/json") => {
+                let Json(request) = Json::<GatewayBatchRequest>::from_request(request, state)
+                    .await
+                    .map_err(|e| bad_request(format!("Cuerpo JSON no válido: {e}")))?;
+                Ok(Self(request))
+            }
+            Method::POST if content_type.starts_with("application/graphql") => {
+                let query = String::from_request(request, state)
+                    .await
+                    .map_err(|_| bad_request("El cuerpo no es UTF-8 válido".to_string()))?;
+                Ok(Self(GatewayBatchRequest::Single(GatewayRequest {
+                    query: Some(query),
+                    operation_name: None,
+                    variables: None,
+                    extensions: RequestExtensions::default(),
+                    queries_only: false,
+                })))
+            }
+            Method::POST => Err((
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                "Se espera `Content-Type` `application/json` o `application/graphql`",
+            )
+                .into_response()),
+            _ => Err((StatusCode::METHOD_NOT_ALLOWED, "Solo se admiten GET y POST").into_response()),
+        }
+    }
+}
+
+/// Política de consultas persistidas del gateway
+pub enum PersistedQueries {
+    /// APQ: un hash desconocido pide al cliente que reenvíe el documento, que queda registrado
+    Automatic(Arc<dyn CacheStore>),
+    /// Solo se ejecutan los documentos del manifiesto que se publica con cada versión de las apps
+    AllowList(HashMap<String, String>),
+}
+
+impl PersistedQueries {
+    /// Lista cerrada a partir de un manifiesto JSON `{ "<sha256>": "<documento>" }`
+    pub fn allow_list_from_manifest(manifest: &str) -> Result<Self> {
+        let documents: HashMap<String, String> = serde_json::from_str(manifest)?;
+        for (hash, query) in &documents {
+            if query_hash(query) != hash.to_ascii_lowercase() {
+                bail!("El hash {} del manifiesto no corresponde a su documento", hash);
+            }
+        }
+
+        Ok(Self::AllowList(documents.into_iter().map(|(hash, query)| (hash.to_ascii_lowercase(), query)).collect()))
+    }
+
+    /// Obtener el documento a ejecutar
+    pub async fn resolve(&self, request: GatewayRequest) -> FieldResult<GraphQLRequest> {
+        let GatewayRequest { query, operation_name, variables, extensions, .. } = request;
+        let persisted = extensions.persisted_query;
+
+        if let Some(persisted) = &persisted {
+            if persisted.version != PERSISTED_QUERY_VERSION {
+                return Err(field_error(
+                    format!("Versión de consulta persistida no soportada: {}", persisted.version),
+                    error_code::BAD_USER_INPUT,
+                ));
+            }
+        }
+        let hash = match (&query, &persisted) {
+            (Some(query), Some(persisted)) => {
+                let hash = query_hash(query);
+                if hash != persisted.sha256_hash.to_ascii_lowercase() {
+                    return Err(field_error("El hash no corresponde a la consulta", error_code::BAD_USER_INPUT));
+                }
+                hash
+            }
+            (Some(query), None) => query_hash(query),
+            (None, Some(persisted)) => persisted.sha256_hash.to_ascii_lowercase(),
+            (None, None) => return Err(field_error("Falta la consulta", error_code::BAD_USER_INPUT)),
+        };
+
+        let query = match self {
+            PersistedQueries::Automatic(store) => {
+                let key = format!("apq:{hash}");
+                match query {
+                    Some(query) => {
+                        if persisted.is_some() {
+                            if let Err(err) = store.set(&key, &query, Some(AUTOMATIC_QUERY_TTL)).await {
+                                tracing::warn!("No se pudo registrar la consulta persistida: {:#}", err);
+                            }
+                        }
+                        query
+                    }
+                    None => {
+                        let stored = store.get_many(&[key]).await.unwrap_or_else(|err| {
+                            tracing::warn!("Registro de consultas persistidas no disponible: {:#}", err);
+                            vec![None]
+                        });
+                        // El mensaje lo reconocen los clientes APQ para reenviar el documento
+                        stored.into_iter().next().flatten().ok_or_else(|| {
+                            field_error("PersistedQueryNotFound", error_code::PERSISTED_QUERY_NOT_FOUND)
+                        })?
+                    }
+                }
+            }
+            PersistedQueries::AllowList(documents) => documents.get(&hash).cloned().ok_or_else(|| {
+                field_error("La consulta no está en la lista permitida", error_code::PERSISTED_QUERY_NOT_ALLOWED)
+            })?,
+        };
+
+        Ok(GraphQLRequest::new(query, operation_name, variables))
+    }
+}
+
+/// Tipo de la operación que se ejecutaría; `None` si el documento no la identifica
+fn operation_type(schema: &Schema, query: &str, operation_name: Option<&str>) -> Option<OperationType> {
+    let document = parse_document_source(query, &schema.schema).ok()?;
+    let mut operations = document.iter().filter_map(|definition| match definition {
+        Definition::Operation(operation) => Some(&operation.item),
+        Definition::Fragment(_) => None,
+    });
+
+    let operation = match operation_name {
+        Some(name) => operations.find(|operation| operation.name.as_ref().map(|n| n.item) == Some(name))?,
+        None => {
+            let operation = operations.next()?;
+            if operations.next().is_some() {
+                return None;
+            }
+            operation
+        }
+    };
+    Some(operation.operation_type)
+}
+
+/// Ejecutar una petición (o un lote) del gateway: documento persistido y límites de consulta
+pub async fn execute_persisted(
+    schema: &Schema,
+    request: GatewayBatchRequest,
+    context: &Context,
+    persisted: &PersistedQueries,
+    limits: &QueryLimits,
+) -> GraphQLBatchResponse {
+    match request {
+        GatewayBatchRequest::Single(request) => {
+            GraphQLBatchResponse::Single(execute_one(schema, request, context, persisted, limits).await)
+        }
+        GatewayBatchRequest::Batch(requests) => {
+            if let Err(err) = check_batch_size(requests.len(), limits) {
+                return GraphQLBatchResponse::Single(GraphQLResponse::error(err));
+            }
+            let mut responses = Vec::with_capacity(requests.len());
+            for request in requests {
+                responses.push(execute_one(schema, request, context, persisted, limits).await);
+            }
+            GraphQLBatchResponse::Batch(responses)
+        }
+    }
+}
+
+async fn execute_one(
+    schema: &Schema,
+    request: GatewayRequest,
+    context: &Context,
+    persisted: &PersistedQueries,
+    limits: &QueryLimits,
+) -> GraphQLResponse {
+    match resolve_checked(schema, request, persisted, limits).await {
+        Ok(request) => request.execute(schema, context).await,
+        Err(err) => GraphQLResponse::error(err),
+    }
+}
+
+/// Documento a ejecutar, ya comprobado contra la política de la petición y los límites
+pub async fn resolve_checked(
+    schema: &Schema,
+    request: GatewayRequest,
+    persisted: &PersistedQueries,
+    limits: &QueryLimits,
+) -> FieldResult<GraphQLRequest> {
+    let queries_only = request.queries_only;
+    let request = persisted.resolve(request).await?;
+
+    if queries_only
+        && operation_type(schema, &request.query, request.operation_name.as_deref()) != Some(OperationType::Query)
+    {
+        return Err(field_error("Las peticiones GET solo admiten consultas", error_code::BAD_USER_INPUT));
+    }
+    check_query(schema, &request, limits)?;
+
+    Ok(request)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cache::MemoryStore;
+
+    use super::*;
+
+    const QUERY: &str = "{ competencyFrameworks { id } }";
+
+    fn request(query: Option<&str>, hash: Option<&str>) -> GatewayRequest {
+        GatewayRequest {
+            query: query.map(str::to_string),
+            operation_name: None,
+            variables: None,
+            extensions: RequestExtensions {
+                persisted_query: hash.map(|hash| PersistedQueryExtension {
+                    version: PERSISTED_QUERY_VERSION,
+                    sha256_hash: hash.to_string(),
+                }),
+            },
+            queries_only: false,
+        }
+    }
+
+    fn code(err: juniper::FieldError) -> String {
+        let code = err.extensions().as_object_value().and_then(|o| o.get_field_value("code"));
+        code.and_then(|c| c.as_string_value()).unwrap().to_string()
+    }
+
+    #[tokio::test]
+    async fn automatic_mode_registers_documents_sent_with_their_hash() {
+        let queries = PersistedQueries::Automatic(Arc::new(MemoryStore::default()));
+        let hash = query_hash(QUERY);
+
+        let err = queries.resolve(request(None, Some(&hash))).await.unwrap_err();
+        assert_eq!(code(err), error_code::PERSISTED_QUERY_NOT_FOUND);
+
+        assert!(queries.resolve(request(Some("{ other }"), Some(&hash))).await.is_err());
+        queries.resolve(request(Some(QUERY), Some(&hash))).await.unwrap();
+        assert_eq!(queries.resolve(request(None, Some(&hash))).await.unwrap().query, QUERY);
+    }
+
+    #[tokio::test]
+    async fn allow_list_only_runs_manifest_documents() {
+        let manifest = serde_json::json!({ query_hash(QUERY): QUERY }).to_string();
+        let queries = PersistedQueries::allow_list_from_manifest(&manifest).unwrap();
+
+        assert_eq!(queries.resolve(request(None, Some(&query_hash(QUERY)))).await.unwrap().query, QUERY);
+        assert_eq!(queries.resolve(request(Some(QUERY), None)).await.unwrap().query, QUERY);
+
+        let err = queries.resolve(request(Some("{ __typename }"), None)).await.unwrap_err();
+        assert_eq!(code(err), error_code::PERSISTED_QUERY_NOT_ALLOWED);
+
+        assert!(PersistedQueries::allow_list_from_manifest(r#"{ "abc": "{ __typename }" }"#).is_err());
+    }
+
+    #[tokio::test]
+    async fn get_requests_only_run_queries() {
+        let schema = crate::schema::create_schema();
+        let queries = PersistedQueries::Automatic(Arc::new(MemoryStore::default()));
+        let limits = QueryLimits::default();
+        let get = |query: &str| GatewayRequest { queries_only: true, ..request(Some(query), None) };
+
+        assert!(resolve_checked(&schema, get(QUERY), &queries, &limits).await.is_ok());
+
+        let mutation = r#"mutation { startTutoringSession(tutorId: "0x1", studentId: "0x2") { id } }"#;
+        let err = resolve_checked(&schema, get(mutation), &queries, &limits).await.unwrap_err();
+        assert_eq!(code(err), error_code::BAD_USER_INPUT);
+        assert!(resolve_checked(&schema, request(Some(mutation), None), &queries, &limits).await.is_ok());
+    }
+}
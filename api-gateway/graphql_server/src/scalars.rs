@@ -0,0 +1,126 @@
+// Escalares propios del esquema: JSON libre y elementos de campo (felt) de Starknet
+// DateTime y UUID los aportan las integraciones chrono y uuid de juniper
+
+use juniper::{GraphQLScalar, InputValue, Object, ScalarValue, Value};
+
+/// Arbitrary JSON value, used for xAPI context extensions
+#[derive(Debug, Clone, PartialEq, GraphQLScalar)]
+#[graphql(name = "JSON", with = json_scalar, parse_token(String, i32, f64, bool))]
+pub struct Json(pub serde_json::Value);
+
+mod json_scalar {
+    use super::*;
+
+    pub(super) fn to_output<S: ScalarValue>(v: &Json) -> Value<S> {
+        to_value(&v.0)
+    }
+
+    pub(super) fn from_input<S: ScalarValue>(v: &InputValue<S>) -> Result<Json, String> {
+        from_input_value(v).map(Json)
+    }
+
+    fn to_value<S: ScalarValue>(json: &serde_json::Value) -> Value<S> {
+        match json {
+            serde_json::Value::Null => Value::null(),
+            serde_json::Value::Bool(b) => Value::scalar(*b),
+            serde_json::Value::Number(n) => match n.as_i64().and_then(|i| i32::try_from(i).ok()) {
+                Some(i) => Value::scalar(i),
+                None => Value::scalar(n.as_f64().unwrap_or_default()),
+            },
+            serde_json::Value::String(s) => Value::scalar(s.clone()),
+            serde_json::Value::Array(items) => Value::list(items.iter().map(to_value).collect()),
+            serde_json::Value::Object(map) => {
+                let mut object = Object::with_capacity(map.len());
+                for (key, value) in map {
+                    object.add_field(key.as_str(), to_value(value));
+                }
+                Value::object(object)
+            }
+        }
+    }
+
+    fn from_input_value<S: ScalarValue>(v: &InputValue<S>) -> Result<serde_json::Value, String> {
+        match v {
+            InputValue::Null => Ok(serde_json::Value::Null),
+            InputValue::Scalar(s) => {
+                if let Some(b) = s.as_bool() {
+                    Ok(b.into())
+                } else if let Some(i) = s.as_int() {
+                    Ok(i.into())
+                } else if let Some(f) = s.as_float() {
+                    serde_json::Number::from_f64(f)
+                        .map(serde_json::Value::Number)
+                        .ok_or_else(|| format!("Número JSON no válido: {f}"))
+                } else if let Some(s) = s.as_str() {
+                    Ok(s.into())
+                } else {
+                    Err(format!("Escalar JSON no reconocido: {v}"))
+                }
+            }
+            InputValue::Enum(e) => Ok(e.clone().into()),
+            InputValue::Variable(name) => Err(format!("Variable sin resolver en JSON: ${name}")),
+            InputValue::List(items) => items.iter().map(|i| from_input_value(&i.item)).collect(),
+            InputValue::Object(fields) => fields
+                .iter()
+                .map(|(key, value)| Ok((key.item.clone(), from_input_value(&value.item)?)))
+                .collect::<Result<serde_json::Map<_, _>, String>>()
+                .map(serde_json::Value::Object),
+        }
+    }
+}
+
+/// Starknet field element (felt252) as a 0x-prefixed hex string: account addresses and on-chain hashes
+#[derive(Debug, Clone, PartialEq, Eq, GraphQLScalar)]
+#[graphql(with = felt_scalar, parse_token(String))]
+pub struct Felt(pub String);
+
+mod felt_scalar {
+    use super::*;
+
+    pub(super) fn to_output<S: ScalarValue>(v: &Felt) -> Value<S> {
+        Value::scalar(v.0.clone())
+    }
+
+    pub(super) fn from_input<S: ScalarValue>(v: &InputValue<S>) -> Result<Felt, String> {
+        let s = v.as_string_value().ok_or_else(|| format!("Se esperaba `String`, recibido: {v}"))?;
+        let digits = s.strip_prefix("0x").ok_or_else(|| format!("Felt sin prefijo 0x: {s}"))?;
+
+        // Se admite el relleno a 64 cifras; sin ceros a la izquierda, 252 bits caben en 63
+        let significant = digits.trim_start_matches('0');
+        if digits.is_empty()
+            || digits.len() > 64
+            || significant.len() > 63
+            || !digits.chars().all(|c| c.is_ascii_hexdigit())
+        {
+            return Err(format!("Felt no válido: {s}"));
+        }
+
+        Ok(Felt(format!("0x{}", digits.to_ascii_lowercase())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use juniper::{DefaultScalarValue, FromInputValue, ToInputValue};
+
+    use super::*;
+
+    fn felt(s: String) -> Result<Felt, juniper::FieldError> {
+        Felt::from_input_value(&InputValue::<DefaultScalarValue>::scalar(s))
+    }
+
+    #[test]
+    fn felt_accepts_padded_hex_and_rejects_overflow() {
+        assert_eq!(felt(format!("0x{:0>64}", "ABC")).unwrap().0, format!("0x{:0>64}", "abc"));
+        assert!(felt("abc".to_string()).is_err());
+        assert!(felt(format!("0x{}", "f".repeat(64))).is_err());
+    }
+
+    #[test]
+    fn json_round_trips_nested_values() {
+        let json = Json(serde_json::json!({ "tags": ["xapi", 1, 2.5, true], "nested": { "empty": null } }));
+        let input: InputValue<DefaultScalarValue> = json.to_input_value();
+
+        assert_eq!(Json::from_input_value(&input).unwrap(), json);
+    }
+}
@@ -1,32 +1,715 @@
-use juniper::{EmptySubscription, FieldResult, GraphQLObject, RootNode, graphql_object};
+use chrono::{DateTime, Utc};
+use futures::stream::BoxStream;
+use juniper::{
+    graphql_object, graphql_subscription, FieldResult, GraphQLEnum, GraphQLInputObject, GraphQLObject, RootNode,
+};
+use uuid::Uuid;
+use crate::connection::PageArgs;
 use crate::context::Context;
+use crate::scalars::{Felt, Json};
+use identity::domain::{Action, Resource, ResourceKind};
+use learning_passport::domain;
+use marketplace::domain::{SessionFilter, SessionOrder, SessionOrderField, SessionStatus, TutoringEvent};
 
 // GraphQL Types
-#[derive(GraphQLObject)]
 pub struct User {
     pub id: String,
     pub name: String,
-    pub passport_id: Option<String>,
+    pub passport_id: Option<Uuid>,
+}
+
+#[graphql_object(context = Context)]
+impl User {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn passport_id(&self) -> Option<Uuid> {
+        self.passport_id
+    }
+
+    /// The user's learning passport, batch-loaded with the other passports in the request
+    async fn passport(&self, context: &Context) -> FieldResult<Option<LifeLearningPassport>> {
+        context.authorize(Action::Read, Resource::owned_by(ResourceKind::Passport, &self.id)).await?;
+        context.load_passport(&self.id).await
+    }
+}
+
+/// Whether an interaction has already been anchored on Keikochain
+#[derive(GraphQLEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChainStatus {
+    Pending,
+    Stored,
+}
+
+/// Outcome of a learning interaction (xAPI `result`)
+#[derive(GraphQLObject)]
+pub struct LearningResult {
+    pub success: bool,
+    /// Completion in [0, 1]
+    pub completion: Option<f64>,
+    pub score: Option<f64>,
+    /// Duration in seconds
+    pub duration: Option<i32>,
+    pub response: Option<String>,
+}
+
+/// Context of a learning interaction (xAPI `context`)
+#[derive(GraphQLObject)]
+pub struct LearningContext {
+    pub platform: String,
+    pub language: String,
+    pub instructor: Option<String>,
+    pub group: Option<String>,
+    pub extensions: Option<Json>,
 }
 
+/// Issuer that countersigns an interaction (xAPI `authority`)
 #[derive(GraphQLObject)]
+pub struct InteractionAuthority {
+    pub issuer_id: String,
+    pub key_id: String,
+    pub signature: Option<String>,
+}
+
 pub struct LearningInteraction {
-    pub id: String,
+    pub id: Uuid,
+    pub passport_id: Uuid,
+    pub actor: Felt,
+    pub verb: String,
+    pub object: String,
+    pub activity_type: Option<String>,
+    pub result: Option<LearningResult>,
+    pub context: Option<LearningContext>,
+    pub timestamp: DateTime<Utc>,
+    pub verifying_key: Option<String>,
+    pub signature: Option<String>,
+    pub authority: Option<InteractionAuthority>,
+    pub chain_status: ChainStatus,
+}
+
+/// Atomic xAPI learning interaction recorded in a passport
+#[graphql_object(context = Context)]
+impl LearningInteraction {
+    fn id(&self) -> Uuid {
+        self.id
+    }
+
+    fn passport_id(&self) -> Uuid {
+        self.passport_id
+    }
+
+    fn actor(&self) -> &Felt {
+        &self.actor
+    }
+
+    /// xAPI verb IRI
+    fn verb(&self) -> &str {
+        &self.verb
+    }
+
+    /// Activity IRI
+    fn object(&self) -> &str {
+        &self.object
+    }
+
+    fn activity_type(&self) -> Option<&str> {
+        self.activity_type.as_deref()
+    }
+
+    fn result(&self) -> Option<&LearningResult> {
+        self.result.as_ref()
+    }
+
+    fn context(&self) -> Option<&LearningContext> {
+        self.context.as_ref()
+    }
+
+    fn timestamp(&self) -> DateTime<Utc> {
+        self.timestamp
+    }
+
+    /// Learner's Ed25519 public key, which verifies `signature`
+    fn verifying_key(&self) -> Option<&str> {
+        self.verifying_key.as_deref()
+    }
+
+    /// Same value as `verifyingKey`, kept while clients migrate
+    #[graphql(deprecated = "Use `verifyingKey`; the humanity commitment is no longer exposed")]
+    fn humanity_proof_key(&self) -> &str {
+        self.verifying_key.as_deref().unwrap_or_default()
+    }
+
+    /// Learner's Ed25519 signature
+    fn signature(&self) -> Option<&str> {
+        self.signature.as_deref()
+    }
+
+    fn authority(&self) -> Option<&InteractionAuthority> {
+        self.authority.as_ref()
+    }
+
+    fn chain_status(&self) -> ChainStatus {
+        self.chain_status
+    }
+
+    /// Instructor or tutor of the interaction, when they are a Keiko user
+    async fn instructor(&self, context: &Context) -> FieldResult<Option<User>> {
+        match self.context.as_ref().and_then(|c| c.instructor.as_deref()) {
+            Some(instructor) => context.get_user(instructor).await,
+            None => Ok(None),
+        }
+    }
+}
+
+#[derive(GraphQLObject)]
+pub struct PassportStatistics {
+    pub total_interactions: i32,
+    pub successful_interactions: i32,
+    /// Percentage of successful interactions
+    pub completion_rate: f64,
+    /// Total duration in seconds
+    pub total_duration: i32,
+    pub last_activity: Option<DateTime<Utc>>,
+}
+
+/// Life Learning Passport: every interaction of a user, in chronological order
+#[derive(GraphQLObject)]
+#[graphql(context = Context)]
+pub struct LifeLearningPassport {
+    pub id: Uuid,
+    pub user_address: Felt,
+    /// Learner's Ed25519 public key
+    pub verifying_key: Option<String>,
+    /// Same value as `verifyingKey`, kept while clients migrate
+    #[graphql(deprecated = "Use `verifyingKey`; the humanity commitment is no longer exposed")]
+    pub humanity_proof_key: String,
+    pub interactions: Vec<LearningInteraction>,
+    pub statistics: PassportStatistics,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub blockchain_hash: Option<Felt>,
+}
+
+#[derive(GraphQLInputObject)]
+pub struct LearningResultInput {
+    pub success: bool,
+    pub completion: Option<f64>,
+    pub score: Option<f64>,
+    pub duration: Option<i32>,
+    pub response: Option<String>,
+}
+
+#[derive(GraphQLInputObject)]
+pub struct LearningContextInput {
+    pub platform: String,
+    pub language: String,
+    pub instructor: Option<String>,
+    pub group: Option<String>,
+    pub extensions: Option<Json>,
+}
+
+/// Self-reported learning interaction; `verb` defaults to xAPI "experienced"
+#[derive(GraphQLInputObject)]
+pub struct LearningInteractionInput {
     pub user_id: String,
-    pub activity_type: String,
-    pub content: String,
-    pub timestamp: String,
+    pub verb: Option<String>,
+    pub object: String,
+    pub activity_type: Option<String>,
+    pub result: Option<LearningResultInput>,
+    pub context: Option<LearningContextInput>,
+    /// Institution on whose behalf a tutor or institution records it; `context.group` sets the group
+    pub institution_id: Option<String>,
+}
+
+#[derive(GraphQLEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PassportEventKind {
+    PassportCreated,
+    InteractionAdded,
+    /// The interaction was anchored on Keikochain
+    InteractionStoredInBlockchain,
+    PassportUpdated,
+}
+
+/// Change in a user's passport, pushed to subscribers
+#[derive(GraphQLObject)]
+pub struct PassportEvent {
+    pub kind: PassportEventKind,
+    pub passport_id: Uuid,
+    pub user_address: Felt,
+    pub interaction_id: Option<Uuid>,
+    pub blockchain_hash: Option<Felt>,
+    pub timestamp: DateTime<Utc>,
+}
+
+#[derive(GraphQLEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TutoringSessionEventKind {
+    BookingRequested,
+    StatusChanged,
+    SessionCompleted,
+}
+
+/// Change in a tutoring session, pushed to both participants
+#[derive(GraphQLObject)]
+pub struct TutoringSessionEvent {
+    pub kind: TutoringSessionEventKind,
+    pub session_id: Uuid,
+    pub tutor_id: String,
+    pub student_id: String,
+    /// Status before the change, for `STATUS_CHANGED`
+    pub previous_status: Option<String>,
+    pub status: String,
+    pub changed_by: Option<String>,
+    pub tutor_interaction_id: Option<Uuid>,
+    pub learner_interaction_id: Option<Uuid>,
+    pub timestamp: DateTime<Utc>,
 }
 
 #[derive(GraphQLObject)]
 pub struct TutoringSession {
-    pub id: String,
+    pub id: Uuid,
     pub tutor_id: String,
     pub student_id: String,
     pub subject: String,
     pub status: String,
 }
 
+/// Relay pagination info
+#[derive(GraphQLObject)]
+pub struct PageInfo {
+    pub has_next_page: bool,
+    pub has_previous_page: bool,
+    pub start_cursor: Option<String>,
+    pub end_cursor: Option<String>,
+}
+
+#[derive(GraphQLObject)]
+#[graphql(context = Context)]
+pub struct LearningInteractionEdge {
+    pub node: LearningInteraction,
+    pub cursor: String,
+}
+
+/// Page of a user's learning interactions
+#[derive(GraphQLObject)]
+#[graphql(context = Context)]
+pub struct LearningInteractionConnection {
+    pub edges: Vec<LearningInteractionEdge>,
+    pub page_info: PageInfo,
+    /// Interactions matching the filter, across all pages
+    pub total_count: i32,
+}
+
+#[derive(GraphQLObject)]
+pub struct TutoringSessionEdge {
+    pub node: TutoringSession,
+    pub cursor: String,
+}
+
+/// Page of the tutoring sessions a user takes part in
+#[derive(GraphQLObject)]
+pub struct TutoringSessionConnection {
+    pub edges: Vec<TutoringSessionEdge>,
+    pub page_info: PageInfo,
+    /// Sessions matching the filter, across all pages
+    pub total_count: i32,
+}
+
+#[derive(GraphQLEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OrderDirection {
+    Asc,
+    Desc,
+}
+
+#[derive(GraphQLInputObject)]
+pub struct LearningInteractionFilter {
+    /// xAPI verb IRI
+    pub verb: Option<String>,
+    /// Inclusive lower bound of the interaction timestamp
+    pub since: Option<DateTime<Utc>>,
+    /// Exclusive upper bound of the interaction timestamp
+    pub until: Option<DateTime<Utc>>,
+    /// Interactions without a result count as unsuccessful
+    pub success: Option<bool>,
+}
+
+/// Interactions are ordered by timestamp; ascending by default
+#[derive(GraphQLInputObject)]
+pub struct LearningInteractionOrder {
+    pub direction: OrderDirection,
+}
+
+#[derive(GraphQLEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TutoringSessionStatus {
+    Requested,
+    Accepted,
+    InProgress,
+    Completed,
+    Cancelled,
+    NoShow,
+}
+
+#[derive(GraphQLInputObject)]
+pub struct TutoringSessionFilter {
+    /// Any of these statuses
+    pub status: Option<Vec<TutoringSessionStatus>>,
+    /// Subject code, e.g. `math.algebra`
+    pub subject: Option<String>,
+    /// Inclusive lower bound of the scheduled start
+    pub scheduled_after: Option<DateTime<Utc>>,
+    /// Exclusive upper bound of the scheduled start
+    pub scheduled_before: Option<DateTime<Utc>>,
+}
+
+#[derive(GraphQLEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TutoringSessionOrderField {
+    ScheduledStart,
+    RequestedAt,
+}
+
+/// Sessions are ordered by scheduled start, most recent first, by default
+#[derive(GraphQLInputObject)]
+pub struct TutoringSessionOrder {
+    pub field: TutoringSessionOrderField,
+    pub direction: OrderDirection,
+}
+
+#[derive(GraphQLObject)]
+pub struct ReputationScore {
+    pub user_id: String,
+    pub role: String,
+    pub score: f64,
+    pub rating_count: i32,
+    pub computed_at: DateTime<Utc>,
+}
+
+#[derive(GraphQLObject)]
+pub struct CompetencyFramework {
+    pub id: Uuid,
+    pub name: String,
+    pub source: String,
+    pub source_uri: String,
+    pub version: Option<String>,
+    pub language: String,
+}
+
+pub struct CompetencyEvidence {
+    pub interaction_id: Uuid,
+    pub activity_iri: String,
+    pub verb: String,
+    pub value: f64,
+    pub weight: f64,
+    pub via_competency_uri: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+#[graphql_object(context = Context)]
+impl CompetencyEvidence {
+    fn interaction_id(&self) -> Uuid {
+        self.interaction_id
+    }
+
+    fn activity_iri(&self) -> &str {
+        &self.activity_iri
+    }
+
+    fn verb(&self) -> &str {
+        &self.verb
+    }
+
+    fn value(&self) -> f64 {
+        self.value
+    }
+
+    fn weight(&self) -> f64 {
+        self.weight
+    }
+
+    fn via_competency_uri(&self) -> &str {
+        &self.via_competency_uri
+    }
+
+    fn timestamp(&self) -> DateTime<Utc> {
+        self.timestamp
+    }
+
+    /// The interaction behind this evidence, batch-loaded with the rest of the profile
+    async fn interaction(&self, context: &Context) -> FieldResult<Option<LearningInteraction>> {
+        context.load_interaction(self.interaction_id).await
+    }
+}
+
+#[derive(GraphQLObject)]
+#[graphql(context = Context)]
+pub struct CompetencyProficiency {
+    pub competency_uri: String,
+    pub code: Option<String>,
+    pub title: String,
+    pub parent_uri: Option<String>,
+    pub level: f64,
+    pub coverage: f64,
+    pub evidence: Vec<CompetencyEvidence>,
+}
+
+#[derive(GraphQLObject)]
+#[graphql(context = Context)]
+pub struct CompetencyProfile {
+    pub user_id: String,
+    pub framework_id: Uuid,
+    pub framework_name: String,
+    pub proficiencies: Vec<CompetencyProficiency>,
+    pub computed_at: DateTime<Utc>,
+}
+
+// Conversions from the domain
+fn saturating_i32<T: TryInto<i32>>(value: T) -> i32 {
+    value.try_into().unwrap_or(i32::MAX)
+}
+
+impl From<domain::LearningResult> for LearningResult {
+    fn from(r: domain::LearningResult) -> Self {
+        Self {
+            success: r.success,
+            completion: r.completion,
+            score: r.score,
+            duration: r.duration.map(saturating_i32),
+            response: r.response,
+        }
+    }
+}
+
+impl From<domain::LearningContext> for LearningContext {
+    fn from(c: domain::LearningContext) -> Self {
+        Self {
+            platform: c.platform,
+            language: c.language,
+            instructor: c.instructor,
+            group: c.group,
+            extensions: c.extensions.map(Json),
+        }
+    }
+}
+
+impl From<domain::LearningInteraction> for LearningInteraction {
+    fn from(i: domain::LearningInteraction) -> Self {
+        Self {
+            id: i.id.0,
+            passport_id: i.passport_id.0,
+            actor: Felt(i.actor),
+            verb: i.verb,
+            object: i.object,
+            activity_type: i.activity_type,
+            result: i.result.map(Into::into),
+            context: i.context.map(Into::into),
+            timestamp: i.timestamp,
+            verifying_key: Some(i.verifying_key).filter(|key| !key.is_empty()),
+            signature: i.signature,
+            authority: i.authority.map(|a| InteractionAuthority {
+                issuer_id: a.issuer_id,
+                key_id: a.key_id,
+                signature: a.signature,
+            }),
+            chain_status: if i.stored_in_blockchain { ChainStatus::Stored } else { ChainStatus::Pending },
+        }
+    }
+}
+
+impl From<domain::PassportStatistics> for PassportStatistics {
+    fn from(s: domain::PassportStatistics) -> Self {
+        Self {
+            total_interactions: saturating_i32(s.total_interactions),
+            successful_interactions: saturating_i32(s.successful_interactions),
+            completion_rate: s.completion_rate,
+            total_duration: saturating_i32(s.total_duration),
+            last_activity: s.last_activity,
+        }
+    }
+}
+
+impl From<domain::LifeLearningPassport> for LifeLearningPassport {
+    fn from(p: domain::LifeLearningPassport) -> Self {
+        let statistics = p.get_statistics().into();
+        let interactions = p.get_interactions_chronological().into_iter().cloned().map(Into::into).collect();
+
+        Self {
+            id: p.id.0,
+            user_address: Felt(p.user_address),
+            humanity_proof_key: p.verifying_key.clone(),
+            verifying_key: Some(p.verifying_key).filter(|key| !key.is_empty()),
+            interactions,
+            statistics,
+            created_at: p.created_at,
+            updated_at: p.updated_at,
+            blockchain_hash: p.blockchain_hash.map(Felt),
+        }
+    }
+}
+
+impl From<domain::LearningPassportEvent> for PassportEvent {
+    fn from(event: domain::LearningPassportEvent) -> Self {
+        use domain::LearningPassportEvent as E;
+
+        match event {
+            E::PassportCreated { passport_id, user_address, timestamp } => Self {
+                kind: PassportEventKind::PassportCreated,
+                passport_id: passport_id.0,
+                user_address: Felt(user_address),
+                interaction_id: None,
+                blockchain_hash: None,
+                timestamp,
+            },
+            E::InteractionAdded { passport_id, user_address, interaction_id, timestamp } => Self {
+                kind: PassportEventKind::InteractionAdded,
+                passport_id: passport_id.0,
+                user_address: Felt(user_address),
+                interaction_id: Some(interaction_id.0),
+                blockchain_hash: None,
+                timestamp,
+            },
+            E::InteractionStoredInBlockchain { passport_id, user_address, interaction_id, timestamp } => Self {
+                kind: PassportEventKind::InteractionStoredInBlockchain,
+                passport_id: passport_id.0,
+                user_address: Felt(user_address),
+                interaction_id: Some(interaction_id.0),
+                blockchain_hash: None,
+                timestamp,
+            },
+            E::PassportUpdated { passport_id, user_address, blockchain_hash, timestamp } => Self {
+                kind: PassportEventKind::PassportUpdated,
+                passport_id: passport_id.0,
+                user_address: Felt(user_address),
+                interaction_id: None,
+                blockchain_hash: Some(Felt(blockchain_hash)),
+                timestamp,
+            },
+        }
+    }
+}
+
+/// Solo los eventos de sesión tienen representación; los de perfil se devuelven tal cual
+impl TryFrom<TutoringEvent> for TutoringSessionEvent {
+    type Error = TutoringEvent;
+
+    fn try_from(event: TutoringEvent) -> Result<Self, Self::Error> {
+        Ok(match event {
+            TutoringEvent::BookingRequested { session_id, tutor, learner, timestamp } => Self {
+                kind: TutoringSessionEventKind::BookingRequested,
+                session_id: session_id.0,
+                tutor_id: tutor,
+                student_id: learner.clone(),
+                previous_status: None,
+                status: SessionStatus::Requested.as_str().to_string(),
+                changed_by: Some(learner),
+                tutor_interaction_id: None,
+                learner_interaction_id: None,
+                timestamp,
+            },
+            TutoringEvent::SessionStatusChanged { session_id, tutor, learner, from, to, changed_by, timestamp } => Self {
+                kind: TutoringSessionEventKind::StatusChanged,
+                session_id: session_id.0,
+                tutor_id: tutor,
+                student_id: learner,
+                previous_status: Some(from.as_str().to_string()),
+                status: to.as_str().to_string(),
+                changed_by: Some(changed_by),
+                tutor_interaction_id: None,
+                learner_interaction_id: None,
+                timestamp,
+            },
+            TutoringEvent::SessionCompleted {
+                session_id,
+                tutor,
+                learner,
+                tutor_interaction_id,
+                learner_interaction_id,
+                timestamp,
+            } => Self {
+                kind: TutoringSessionEventKind::SessionCompleted,
+                session_id: session_id.0,
+                tutor_id: tutor.clone(),
+                student_id: learner,
+                previous_status: None,
+                status: SessionStatus::Completed.as_str().to_string(),
+                changed_by: Some(tutor),
+                tutor_interaction_id: Some(tutor_interaction_id),
+                learner_interaction_id: Some(learner_interaction_id),
+                timestamp,
+            },
+            TutoringEvent::TutorProfileUpdated { .. } => return Err(event),
+        })
+    }
+}
+
+impl From<LearningResultInput> for domain::LearningResult {
+    fn from(r: LearningResultInput) -> Self {
+        Self {
+            success: r.success,
+            completion: r.completion,
+            score: r.score,
+            duration: r.duration.map(i64::from),
+            response: r.response,
+        }
+    }
+}
+
+impl From<LearningContextInput> for domain::LearningContext {
+    fn from(c: LearningContextInput) -> Self {
+        Self {
+            platform: c.platform,
+            language: c.language,
+            instructor: c.instructor,
+            group: c.group,
+            extensions: c.extensions.map(|e| e.0),
+        }
+    }
+}
+
+impl From<LearningInteractionFilter> for domain::InteractionFilter {
+    fn from(filter: LearningInteractionFilter) -> Self {
+        Self { verb: filter.verb, since: filter.since, until: filter.until, success: filter.success }
+    }
+}
+
+impl From<TutoringSessionStatus> for SessionStatus {
+    fn from(status: TutoringSessionStatus) -> Self {
+        match status {
+            TutoringSessionStatus::Requested => SessionStatus::Requested,
+            TutoringSessionStatus::Accepted => SessionStatus::Accepted,
+            TutoringSessionStatus::InProgress => SessionStatus::InProgress,
+            TutoringSessionStatus::Completed => SessionStatus::Completed,
+            TutoringSessionStatus::Cancelled => SessionStatus::Cancelled,
+            TutoringSessionStatus::NoShow => SessionStatus::NoShow,
+        }
+    }
+}
+
+impl From<TutoringSessionFilter> for SessionFilter {
+    fn from(filter: TutoringSessionFilter) -> Self {
+        Self {
+            statuses: filter.status.unwrap_or_default().into_iter().map(Into::into).collect(),
+            subject_code: filter.subject,
+            scheduled_after: filter.scheduled_after,
+            scheduled_before: filter.scheduled_before,
+        }
+    }
+}
+
+impl From<TutoringSessionOrder> for SessionOrder {
+    fn from(order: TutoringSessionOrder) -> Self {
+        Self {
+            field: match order.field {
+                TutoringSessionOrderField::ScheduledStart => SessionOrderField::ScheduledStart,
+                TutoringSessionOrderField::RequestedAt => SessionOrderField::RequestedAt,
+            },
+            descending: order.direction == OrderDirection::Desc,
+        }
+    }
+}
+
 // Query Root
 pub struct Query;
 
@@ -35,23 +718,107 @@ pub struct Query;
 impl Query {
     /// Get user by ID
     async fn user(context: &Context, id: String) -> FieldResult<Option<User>> {
+        context.authorize(Action::Read, Resource::owned_by(ResourceKind::UserProfile, &id)).await?;
         context.get_user(&id).await
     }
 
-    /// Get learning interactions for a user
+    /// Get several users at once; unknown IDs are skipped
+    async fn users(context: &Context, ids: Vec<String>) -> FieldResult<Vec<User>> {
+        for id in &ids {
+            context.authorize(Action::Read, Resource::owned_by(ResourceKind::UserProfile, id)).await?;
+        }
+        context.get_users(&ids).await
+    }
+
+    /// Get the learning passport of a user, with its interactions and statistics
+    async fn passport(context: &Context, user_id: String) -> FieldResult<Option<LifeLearningPassport>> {
+        context.authorize(Action::Read, Resource::owned_by(ResourceKind::Passport, &user_id)).await?;
+        context.load_passport(&user_id).await
+    }
+
+    /// Get the aggregated statistics of a user's passport
+    async fn passport_statistics(context: &Context, user_id: String) -> FieldResult<Option<PassportStatistics>> {
+        context.authorize(Action::Read, Resource::owned_by(ResourceKind::Passport, &user_id)).await?;
+        context.get_passport_statistics(&user_id).await
+    }
+
+    /// Get a page of a user's learning interactions
+    ///
+    /// Tutors and institutions read their learners' interactions through `group` and `institutionId`.
+    #[allow(clippy::too_many_arguments)]
     async fn learning_interactions(
         context: &Context, 
-        user_id: String
-    ) -> FieldResult<Vec<LearningInteraction>> {
-        context.get_learning_interactions(&user_id).await
+        user_id: String,
+        filter: Option<LearningInteractionFilter>,
+        order_by: Option<LearningInteractionOrder>,
+        first: Option<i32>,
+        after: Option<String>,
+        last: Option<i32>,
+        before: Option<String>,
+        group: Option<String>,
+        institution_id: Option<String>,
+    ) -> FieldResult<LearningInteractionConnection> {
+        let resource = Resource::owned_by(ResourceKind::LearningInteraction, &user_id)
+            .scoped(institution_id.as_deref(), group.as_deref());
+        context.authorize(Action::Read, resource).await?;
+        let descending = order_by.is_some_and(|order| order.direction == OrderDirection::Desc);
+        let filter = filter.map(Into::into).unwrap_or_default();
+        context
+            .get_learning_interactions(&user_id, &filter, descending, &PageArgs { first, after, last, before })
+            .await
     }
 
-    /// Get active tutoring sessions
+    /// Get a page of the tutoring sessions a user takes part in, as tutor or learner
+    #[allow(clippy::too_many_arguments)]
     async fn tutoring_sessions(
         context: &Context,
-        user_id: String
-    ) -> FieldResult<Vec<TutoringSession>> {
-        context.get_tutoring_sessions(&user_id).await
+        user_id: String,
+        filter: Option<TutoringSessionFilter>,
+        order_by: Option<TutoringSessionOrder>,
+        first: Option<i32>,
+        after: Option<String>,
+        last: Option<i32>,
+        before: Option<String>,
+    ) -> FieldResult<TutoringSessionConnection> {
+        context.authorize(Action::Read, Resource::owned_by(ResourceKind::TutoringSession, &user_id)).await?;
+        let order = order_by.map(Into::into).unwrap_or_default();
+        let filter = filter.map(Into::into).unwrap_or_default();
+        context
+            .get_tutoring_sessions(&user_id, &filter, order, &PageArgs { first, after, last, before })
+            .await
+    }
+
+    /// Get the current public reputation of a user as tutor or learner
+    async fn reputation(
+        context: &Context,
+        user_id: String,
+        role: String,
+    ) -> FieldResult<Option<ReputationScore>> {
+        context.get_reputation(&user_id, &role).await
+    }
+
+    /// Get the reputation history of a user as tutor or learner
+    async fn reputation_history(
+        context: &Context,
+        user_id: String,
+        role: String,
+    ) -> FieldResult<Vec<ReputationScore>> {
+        context.get_reputation_history(&user_id, &role).await
+    }
+
+    /// List the imported competency frameworks (ESCO, CASE, custom)
+    async fn competency_frameworks(context: &Context) -> FieldResult<Vec<CompetencyFramework>> {
+        context.get_competency_frameworks().await
+    }
+
+    /// Get the per-competency proficiency of a user in a framework, with its evidence trail
+    async fn competency_profile(
+        context: &Context,
+        user_id: String,
+        framework_id: Uuid,
+    ) -> FieldResult<Option<CompetencyProfile>> {
+        context.authorize(Action::Read, Resource::owned_by(ResourceKind::Passport, &user_id)).await?;
+        context.get_competency_profile(&user_id, framework_id).await
     }
 }
 
@@ -61,30 +828,61 @@ pub struct Mutation;
 #[graphql_object]
 #[graphql(context = Context)]
 impl Mutation {
-    /// Create a new learning interaction
+    /// Record a self-reported learning interaction, countersigned by the platform
     async fn create_learning_interaction(
         context: &Context,
-        user_id: String,
-        activity_type: String,
-        content: String,
+        input: LearningInteractionInput,
     ) -> FieldResult<LearningInteraction> {
-        context.create_learning_interaction(user_id, activity_type, content).await
+        let group = input.context.as_ref().and_then(|c| c.group.as_deref());
+        let resource = Resource::owned_by(ResourceKind::LearningInteraction, &input.user_id)
+            .scoped(input.institution_id.as_deref(), group);
+        context.authorize(Action::Create, resource).await?;
+        context.create_learning_interaction(input).await
     }
 
-    /// Start a tutoring session
+    /// Request a tutoring session booking; times are RFC 3339 and `subject` is the subject code
     async fn start_tutoring_session(
         context: &Context,
         tutor_id: String,
         student_id: String,
         subject: String,
+        scheduled_start: String,
+        scheduled_end: String,
+        message: Option<String>,
     ) -> FieldResult<TutoringSession> {
-        context.start_tutoring_session(tutor_id, student_id, subject).await
+        // Solo el estudiante reserva: el tutor lo elige quien llama y no concede permisos
+        let resource = Resource::owned_by(ResourceKind::TutoringSession, &student_id);
+        context.authorize(Action::Create, resource).await?;
+        context
+            .start_tutoring_session(tutor_id, student_id, subject, scheduled_start, scheduled_end, message)
+            .await
+    }
+}
+
+// Subscription Root
+pub struct Subscription;
+
+pub type PassportEventStream = BoxStream<'static, FieldResult<PassportEvent>>;
+pub type TutoringSessionEventStream = BoxStream<'static, FieldResult<TutoringSessionEvent>>;
+
+#[graphql_subscription(context = Context)]
+impl Subscription {
+    /// Live changes in a user's passport: new interactions and Keikochain sync confirmations
+    async fn passport_events(context: &Context, user_id: String) -> FieldResult<PassportEventStream> {
+        context.authorize(Action::Read, Resource::owned_by(ResourceKind::Passport, &user_id)).await?;
+        Ok(context.passport_events(&user_id))
+    }
+
+    /// Live state changes of the tutoring sessions a user takes part in
+    async fn tutoring_session_events(context: &Context, user_id: String) -> FieldResult<TutoringSessionEventStream> {
+        context.authorize(Action::Read, Resource::owned_by(ResourceKind::TutoringSession, &user_id)).await?;
+        Ok(context.tutoring_session_events(&user_id))
     }
 }
 
 // Schema Definition
-pub type Schema = RootNode<'static, Query, Mutation, EmptySubscription<Context>>;
+pub type Schema = RootNode<'static, Query, Mutation, Subscription>;
 
 pub fn create_schema() -> Schema {
-    Schema::new(Query, Mutation, EmptySubscription::new())
+    Schema::new(Query, Mutation, Subscription)
 }
\ No newline at end of file
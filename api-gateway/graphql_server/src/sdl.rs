@@ -0,0 +1,272 @@
+// Exportación del esquema en SDL y detección de cambios incompatibles con los clientes
+// La instantánea versionada (`schema.graphql`) es el contrato con las apps Flutter
+
+use std::collections::HashMap;
+use std::fmt;
+
+use anyhow::{anyhow, Result};
+use graphql_parser::schema::{
+    parse_schema, Definition, Document, EnumType, Field, InputValue, Type, TypeDefinition,
+};
+
+use crate::schema::create_schema;
+
+/// Ruta de la instantánea, relativa a la raíz del crate
+pub const SNAPSHOT_PATH: &str = "schema.graphql";
+
+/// SDL del esquema actual
+pub fn schema_sdl() -> String {
+    create_schema().as_sdl()
+}
+
+/// Cambio del esquema que puede romper a un cliente ya publicado
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BreakingChange {
+    pub path: String,         // Tipo, campo o argumento afectado (ej: "Query.passport(userId)")
+    pub description: String,
+}
+
+impl fmt::Display for BreakingChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.description)
+    }
+}
+
+/// Posición de un tipo: lo que el servidor devuelve o lo que el cliente envía
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Position {
+    Output,
+    Input,
+}
+
+type Types<'a> = HashMap<&'a str, &'a TypeDefinition<'a, &'a str>>;
+
+/// Cambios incompatibles entre la instantánea y el esquema actual
+///
+/// Se consideran incompatibles los tipos, campos, argumentos y valores de enum eliminados,
+/// los cambios de tipo, los campos de salida que pasan a admitir null y las entradas que
+/// pasan a ser obligatorias. Los añadidos opcionales no rompen nada y no se informan.
+pub fn breaking_changes(previous_sdl: &str, current_sdl: &str) -> Result<Vec<BreakingChange>> {
+    let previous = parse_schema::<&str>(previous_sdl).map_err(|e| anyhow!("Instantánea no válida: {}", e))?;
+    let current = parse_schema::<&str>(current_sdl).map_err(|e| anyhow!("Esquema actual no válido: {}", e))?;
+    let previous_types = types(&previous);
+    let current_types = types(&current);
+
+    let mut names: Vec<&str> = previous_types.keys().copied().collect();
+    names.sort_unstable();
+
+    let mut changes = Vec::new();
+    for name in names {
+        let change = |description: String| BreakingChange { path: name.to_string(), description };
+        let Some(current) = current_types.get(name) else {
+            changes.push(change("tipo eliminado".to_string()));
+            continue;
+        };
+
+        match (previous_types[name], current) {
+            (TypeDefinition::Object(old), TypeDefinition::Object(new)) => {
+                compare_fields(name, &old.fields, &new.fields, &mut changes);
+            }
+            (TypeDefinition::Interface(old), TypeDefinition::Interface(new)) => {
+                compare_fields(name, &old.fields, &new.fields, &mut changes);
+            }
+            (TypeDefinition::InputObject(old), TypeDefinition::InputObject(new)) => {
+                let path = |field: &str| format!("{name}.{field}");
+                compare_inputs(path, &old.fields, &new.fields, "campo", &mut changes);
+            }
+            (TypeDefinition::Enum(old), TypeDefinition::Enum(new)) => compare_enum(name, old, new, &mut changes),
+            (TypeDefinition::Union(old), TypeDefinition::Union(new)) => {
+                for member in old.types.iter().filter(|member| !new.types.contains(member)) {
+                    changes.push(change(format!("`{member}` ya no forma parte de la unión")));
+                }
+            }
+            (TypeDefinition::Scalar(_), TypeDefinition::Scalar(_)) => {}
+            (old, new) => changes.push(change(format!("pasa de {} a {}", kind(old), kind(new)))),
+        }
+    }
+
+    Ok(changes)
+}
+
+fn types<'a>(document: &'a Document<'a, &'a str>) -> Types<'a> {
+    document
+        .definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            Definition::TypeDefinition(definition) => Some((type_name(definition), definition)),
+            _ => None,
+        })
+        .collect()
+}
+
+fn type_name<'a>(definition: &TypeDefinition<'a, &'a str>) -> &'a str {
+    match definition {
+        TypeDefinition::Scalar(t) => t.name,
+        TypeDefinition::Object(t) => t.name,
+        TypeDefinition::Interface(t) => t.name,
+        TypeDefinition::Union(t) => t.name,
+        TypeDefinition::Enum(t) => t.name,
+        TypeDefinition::InputObject(t) => t.name,
+    }
+}
+
+fn kind<'a>(definition: &TypeDefinition<'a, &'a str>) -> &'static str {
+    match definition {
+        TypeDefinition::Scalar(_) => "escalar",
+        TypeDefinition::Object(_) => "objeto",
+        TypeDefinition::Interface(_) => "interfaz",
+        TypeDefinition::Union(_) => "unión",
+        TypeDefinition::Enum(_) => "enum",
+        TypeDefinition::InputObject(_) => "input",
+    }
+}
+
+fn compare_fields<'a>(
+    type_name: &str,
+    previous: &[Field<'a, &'a str>],
+    current: &[Field<'a, &'a str>],
+    changes: &mut Vec<BreakingChange>,
+) {
+    for old in previous {
+        let path = format!("{}.{}", type_name, old.name);
+        let Some(new) = current.iter().find(|field| field.name == old.name) else {
+            changes.push(BreakingChange { path, description: "campo eliminado".to_string() });
+            continue;
+        };
+
+        if let Some(description) = type_change(&old.field_type, &new.field_type, Position::Output) {
+            changes.push(BreakingChange { path: path.clone(), description });
+        }
+        let argument_path = |argument: &str| format!("{path}({argument})");
+        compare_inputs(argument_path, &old.arguments, &new.arguments, "argumento", changes);
+    }
+}
+
+/// Argumentos de un campo o campos de un input: el cliente los envía
+fn compare_inputs<'a>(
+    path: impl Fn(&str) -> String,
+    previous: &[InputValue<'a, &'a str>],
+    current: &[InputValue<'a, &'a str>],
+    what: &str,
+    changes: &mut Vec<BreakingChange>,
+) {
+    for old in previous {
+        let input_path = path(old.name);
+        match current.iter().find(|input| input.name == old.name) {
+            Some(new) => {
+                if let Some(description) = type_change(&old.value_type, &new.value_type, Position::Input) {
+                    changes.push(BreakingChange { path: input_path, description });
+                }
+            }
+            None => changes.push(BreakingChange { path: input_path, description: format!("{what} eliminado") }),
+        }
+    }
+
+    for new in current.iter().filter(|new| !previous.iter().any(|old| old.name == new.name)) {
+        if is_required(new) {
+            changes.push(BreakingChange {
+                path: path(new.name),
+                description: format!("nuevo {what} obligatorio"),
+            });
+        }
+    }
+}
+
+fn compare_enum<'a>(
+    type_name: &str,
+    previous: &EnumType<'a, &'a str>,
+    current: &EnumType<'a, &'a str>,
+    changes: &mut Vec<BreakingChange>,
+) {
+    for old in previous.values.iter().filter(|old| !current.values.iter().any(|new| new.name == old.name)) {
+        changes.push(BreakingChange {
+            path: format!("{}.{}", type_name, old.name),
+            description: "valor de enum eliminado".to_string(),
+        });
+    }
+}
+
+fn is_required<'a>(input: &InputValue<'a, &'a str>) -> bool {
+    matches!(input.value_type, Type::NonNullType(_)) && input.default_value.is_none()
+}
+
+/// Descripción del cambio de tipo si rompe a los clientes
+fn type_change<'a>(previous: &Type<'a, &'a str>, current: &Type<'a, &'a str>, position: Position) -> Option<String> {
+    if compatible(previous, current, position) {
+        return None;
+    }
+
+    let nullability = match (previous, current, position) {
+        (Type::NonNullType(old), new, Position::Output) if old.as_ref() == new => Some("ahora admite null"),
+        (old, Type::NonNullType(new), Position::Input) if old == new.as_ref() => Some("ahora es obligatorio"),
+        _ => None,
+    };
+
+    Some(match nullability {
+        Some(description) => format!("{description} (`{previous}` → `{current}`)"),
+        None => format!("cambia de tipo (`{previous}` → `{current}`)"),
+    })
+}
+
+/// ¿Sigue funcionando un cliente escrito contra `previous`?
+///
+/// En la salida se puede endurecer la nulabilidad (el cliente ya maneja el null que
+/// no llegará); en la entrada se puede relajar (el cliente ya envía el valor).
+fn compatible<'a>(previous: &Type<'a, &'a str>, current: &Type<'a, &'a str>, position: Position) -> bool {
+    match (previous, current) {
+        (Type::NamedType(old), Type::NamedType(new)) => old == new,
+        (Type::ListType(old), Type::ListType(new)) => compatible(old, new, position),
+        (Type::NonNullType(old), Type::NonNullType(new)) => compatible(old, new, position),
+        (Type::NonNullType(old), new) => position == Position::Input && compatible(old, new, position),
+        (old, Type::NonNullType(new)) => position == Position::Output && compatible(old, new, position),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_removals_type_changes_and_nullability() {
+        let previous = r#"
+            type Query { user(id: String!): User passports(first: Int): [Passport!]! }
+            type User { id: String! name: String! score: Float }
+            type Passport { id: ID! }
+            enum Status { OPEN CLOSED }
+        "#;
+        let current = r#"
+            type Query { user(id: String!, tenant: String!): User passports(first: Int!, after: String): [Passport!]! }
+            type User { id: String! name: String score: Float! }
+            type Passport { id: String! }
+            enum Status { OPEN }
+        "#;
+
+        let changes: Vec<String> =
+            breaking_changes(previous, current).unwrap().iter().map(ToString::to_string).collect();
+        assert_eq!(
+            changes,
+            vec![
+                "Passport.id: cambia de tipo (`ID!` → `String!`)",
+                "Query.user(tenant): nuevo argumento obligatorio",
+                "Query.passports(first): ahora es obligatorio (`Int` → `Int!`)",
+                "Status.CLOSED: valor de enum eliminado",
+                "User.name: ahora admite null (`String!` → `String`)",
+            ]
+        );
+    }
+
+    #[test]
+    fn current_schema_keeps_the_snapshot_contract() {
+        let snapshot = include_str!("../schema.graphql");
+        let changes = breaking_changes(snapshot, &schema_sdl()).unwrap();
+
+        assert!(
+            changes.is_empty(),
+            "Cambios incompatibles con {}:\n{}\nSi son intencionados, regenera la instantánea con \
+             `cargo run -p keiko-graphql-server --bin export_sdl`",
+            SNAPSHOT_PATH,
+            changes.iter().map(|c| format!("  - {c}")).collect::<Vec<_>>().join("\n")
+        );
+    }
+}
@@ -0,0 +1,221 @@
+// Suscripciones GraphQL sobre WebSocket (graphql-transport-ws y graphql-ws)
+// Cada operación que inicia el cliente pasa por las consultas persistidas y los límites de `/graphql`
+
+use std::convert::Infallible;
+use std::sync::Arc;
+
+use axum::extract::ws::{CloseFrame, Message, WebSocket};
+use futures::{channel::mpsc, future, future::BoxFuture, stream, FutureExt, Sink, SinkExt, Stream, StreamExt};
+use juniper::http::GraphQLResponse;
+use juniper::DefaultScalarValue;
+use juniper_graphql_ws::{graphql_transport_ws, graphql_ws, ConnectionConfig};
+use serde_json::{json, Value};
+
+use crate::context::{error_code, field_error, Context};
+use crate::limits::QueryLimits;
+use crate::persisted::{resolve_checked, GatewayRequest, PersistedQueries};
+use crate::schema::Schema;
+
+/// Subprotocolos admitidos, en orden de preferencia
+pub const PROTOCOLS: [&str; 2] = ["graphql-transport-ws", "graphql-ws"];
+
+/// Política que se aplica a las operaciones de una conexión
+#[derive(Clone)]
+pub struct SubscriptionPolicy {
+    pub schema: Arc<Schema>,
+    pub persisted: Arc<PersistedQueries>,
+    pub limits: QueryLimits,
+}
+
+impl SubscriptionPolicy {
+    /// Revisar un mensaje del cliente antes de entregarlo a juniper
+    ///
+    /// Si inicia una operación, su documento se sustituye por el persistido y se comprueban
+    /// los límites; si se rechaza, devuelve el mensaje de error para el cliente. El resto de
+    /// mensajes pasan sin cambios.
+    pub async fn check_message(&self, text: String, legacy: bool) -> Result<String, String> {
+        let Ok(mut message) = serde_json::from_str::<Value>(&text) else {
+            return Ok(text);
+        };
+        let start = if legacy { "start" } else { "subscribe" };
+        if message.get("type").and_then(Value::as_str) != Some(start) {
+            return Ok(text);
+        }
+
+        let id = message.get("id").and_then(Value::as_str).unwrap_or_default().to_string();
+        let payload = message.get("payload").cloned().unwrap_or(Value::Null);
+        let checked = match serde_json::from_value::<GatewayRequest>(payload) {
+            Ok(request) => resolve_checked(&self.schema, request, &self.persisted, &self.limits).await,
+            Err(err) => Err(field_error(format!("Operación no válida: {err}"), error_code::BAD_USER_INPUT)),
+        };
+
+        match checked {
+            Ok(request) => {
+                message["payload"]["query"] = Value::String(request.query);
+                Ok(message.to_string())
+            }
+            Err(err) => {
+                let response = serde_json::to_value(GraphQLResponse::<DefaultScalarValue>::error(err))
+                    .unwrap_or(Value::Null);
+                let errors = response.get("errors").cloned().unwrap_or(Value::Null);
+                // graphql-ws envía un único error; graphql-transport-ws, la lista
+                let payload = if legacy { errors.get(0).cloned().unwrap_or(Value::Null) } else { errors };
+                Err(json!({ "type": "error", "id": id, "payload": payload }).to_string())
+            }
+        }
+    }
+}
+
+/// Mensaje del cliente ya revisado, listo para la conexión de juniper
+enum ClientFrame {
+    Text(String),
+    Close,
+}
+
+impl TryFrom<ClientFrame> for graphql_transport_ws::Input<DefaultScalarValue> {
+    type Error = serde_json::Error;
+
+    fn try_from(frame: ClientFrame) -> Result<Self, Self::Error> {
+        match frame {
+            ClientFrame::Text(text) => serde_json::from_str(&text).map(Self::Message),
+            ClientFrame::Close => Ok(Self::Close),
+        }
+    }
+}
+
+impl TryFrom<ClientFrame> for graphql_ws::ClientMessage<DefaultScalarValue> {
+    type Error = serde_json::Error;
+
+    fn try_from(frame: ClientFrame) -> Result<Self, Self::Error> {
+        match frame {
+            ClientFrame::Text(text) => serde_json::from_str(&text),
+            ClientFrame::Close => Ok(Self::ConnectionTerminate),
+        }
+    }
+}
+
+/// Servir una conexión ya aceptada con el subprotocolo que negoció el cliente
+pub fn serve(socket: WebSocket, policy: SubscriptionPolicy, context: Context) -> BoxFuture<'static, ()> {
+    let schema = policy.schema.clone();
+    let config = ConnectionConfig::new(context);
+
+    if socket.protocol().map(|protocol| protocol.as_bytes()) == Some(b"graphql-ws") {
+        let connection = graphql_ws::Connection::new(schema, config);
+        serve_connection(socket, policy, true, connection, |message| encode(&message)).boxed()
+    } else {
+        let connection = graphql_transport_ws::Connection::new(schema, config);
+        serve_connection(socket, policy, false, connection, |output| match output {
+            graphql_transport_ws::Output::Message(message) => encode(&message),
+            graphql_transport_ws::Output::Close { code, message } => {
+                Message::Close(Some(CloseFrame { code, reason: message.into() }))
+            }
+        })
+        .boxed()
+    }
+}
+
+async fn serve_connection<C, O>(
+    socket: WebSocket,
+    policy: SubscriptionPolicy,
+    legacy: bool,
+    connection: C,
+    to_message: fn(O) -> Message,
+) where
+    C: Sink<ClientFrame, Error = Infallible> + Stream<Item = O> + Send,
+{
+    let (ws_tx, ws_rx) = socket.split();
+    let (s_tx, s_rx) = connection.split();
+    // Los rechazos se responden sin pasar por juniper
+    let (rejections_tx, rejections_rx) = mpsc::unbounded();
+
+    let input = ws_rx
+        .filter_map(move |frame| {
+            let policy = policy.clone();
+            let rejections = rejections_tx.clone();
+            async move {
+                let text = match frame {
+                    Ok(Message::Text(text)) => text,
+                    Ok(Message::Binary(bytes)) => match String::from_utf8(bytes) {
+                        Ok(text) => text,
+                        Err(_) => return Some(ClientFrame::Close),
+                    },
+                    Ok(Message::Ping(_) | Message::Pong(_)) => return None,
+                    Ok(Message::Close(_)) | Err(_) => return Some(ClientFrame::Close),
+                };
+                match policy.check_message(text, legacy).await {
+                    Ok(text) => Some(ClientFrame::Text(text)),
+                    Err(rejection) => {
+                        let _ = rejections.unbounded_send(Message::Text(rejection));
+                        None
+                    }
+                }
+            }
+        })
+        .map(Ok)
+        .forward(s_tx.sink_map_err(|e| match e {}));
+
+    let output = stream::select(s_rx.map(to_message), rejections_rx).map(Ok).forward(ws_tx);
+
+    // Termina en cuanto se cierra cualquiera de los dos sentidos
+    let _ = future::select(Box::pin(input), Box::pin(output)).await;
+}
+
+fn encode(message: &impl serde::Serialize) -> Message {
+    serde_json::to_string(message).map(Message::Text).unwrap_or_else(|e| {
+        let reason = format!("No se pudo serializar la respuesta: {e}");
+        Message::Close(Some(CloseFrame { code: 1011, reason: reason.into() }))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::persisted::query_hash;
+    use crate::schema::create_schema;
+
+    const SUBSCRIPTION: &str = r#"subscription { passportEvents(userId: "0x1") { kind } }"#;
+
+    fn policy(limits: QueryLimits) -> SubscriptionPolicy {
+        let documents = HashMap::from([(query_hash(SUBSCRIPTION), SUBSCRIPTION.to_string())]);
+        SubscriptionPolicy {
+            schema: Arc::new(create_schema()),
+            persisted: Arc::new(PersistedQueries::AllowList(documents)),
+            limits,
+        }
+    }
+
+    fn subscribe(payload: Value) -> String {
+        json!({ "type": "subscribe", "id": "1", "payload": payload }).to_string()
+    }
+
+    #[tokio::test]
+    async fn subscriptions_follow_the_allow_list() {
+        let policy = policy(QueryLimits::default());
+
+        let hash = query_hash(SUBSCRIPTION);
+        let persisted = json!({ "extensions": { "persistedQuery": { "version": 1, "sha256Hash": hash } } });
+        let checked = policy.check_message(subscribe(persisted), false).await.unwrap();
+        let checked: Value = serde_json::from_str(&checked).unwrap();
+        assert_eq!(checked["payload"]["query"], SUBSCRIPTION);
+
+        let other = json!({ "query": r#"subscription { tutoringSessionEvents(userId: "0x1") { kind } }"# });
+        let rejection = policy.check_message(subscribe(other), false).await.unwrap_err();
+        let rejection: Value = serde_json::from_str(&rejection).unwrap();
+        assert_eq!(rejection["type"], "error");
+        assert_eq!(rejection["payload"][0]["extensions"]["code"], error_code::PERSISTED_QUERY_NOT_ALLOWED);
+
+        let ping = json!({ "type": "ping" }).to_string();
+        assert_eq!(policy.check_message(ping.clone(), false).await.unwrap(), ping);
+    }
+
+    #[tokio::test]
+    async fn subscriptions_respect_query_limits() {
+        let policy = policy(QueryLimits { max_depth: 1, max_complexity: 100, ..QueryLimits::default() });
+        let start = json!({ "type": "start", "id": "1", "payload": { "query": SUBSCRIPTION } }).to_string();
+
+        let rejection: Value = serde_json::from_str(&policy.check_message(start, true).await.unwrap_err()).unwrap();
+        assert_eq!(rejection["payload"]["extensions"]["code"], error_code::QUERY_TOO_COMPLEX);
+    }
+}
@@ -0,0 +1,30 @@
+-- Propuestas de gobernanza y votos
+-- Un voto retirado conserva su fila con `withdrawn_at`
+
+CREATE TABLE governance_proposals (
+    id UUID PRIMARY KEY,
+    proposer TEXT NOT NULL,
+    title TEXT NOT NULL,
+    description TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    payload JSONB NOT NULL,
+    rules JSONB NOT NULL,
+    status TEXT NOT NULL,
+    voting_starts_at TIMESTAMPTZ NOT NULL,
+    voting_ends_at TIMESTAMPTZ NOT NULL,
+    tally JSONB,
+    executed_at TIMESTAMPTZ,
+    execution_error TEXT,
+    created_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE TABLE governance_votes (
+    proposal_id UUID NOT NULL REFERENCES governance_proposals(id),
+    voter TEXT NOT NULL,
+    choice TEXT NOT NULL,
+    weight DOUBLE PRECISION NOT NULL,
+    credits DOUBLE PRECISION NOT NULL,
+    cast_at TIMESTAMPTZ NOT NULL,
+    withdrawn_at TIMESTAMPTZ,
+    PRIMARY KEY (proposal_id, voter)
+);
@@ -0,0 +1,319 @@
+// Entidades de dominio para el módulo governance
+// Propuestas comunitarias con contenido tipado, votación ponderada y ejecución automática
+
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use learning_passport::domain::VocabularyEntry;
+use reputation::domain::ReputationParameters;
+
+pub mod strategy;
+
+pub use strategy::*;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ProposalId(pub Uuid);
+
+impl ProposalId {
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+/// Tipo de propuesta, usado para elegir reglas de votación y ejecutor
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ProposalKind {
+    AddVocabularyEntries,
+    ChangeReputationParameters,
+    ApproveLearningSpace,
+    SuspendLearningSpace,
+    FundLearningInitiative,
+}
+
+impl ProposalKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProposalKind::AddVocabularyEntries => "add_vocabulary_entries",
+            ProposalKind::ChangeReputationParameters => "change_reputation_parameters",
+            ProposalKind::ApproveLearningSpace => "approve_learning_space",
+            ProposalKind::SuspendLearningSpace => "suspend_learning_space",
+            ProposalKind::FundLearningInitiative => "fund_learning_initiative",
+        }
+    }
+}
+
+impl fmt::Display for ProposalKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ProposalKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "add_vocabulary_entries" => Ok(ProposalKind::AddVocabularyEntries),
+            "change_reputation_parameters" => Ok(ProposalKind::ChangeReputationParameters),
+            "approve_learning_space" => Ok(ProposalKind::ApproveLearningSpace),
+            "suspend_learning_space" => Ok(ProposalKind::SuspendLearningSpace),
+            "fund_learning_initiative" => Ok(ProposalKind::FundLearningInitiative),
+            other => Err(anyhow!("Tipo de propuesta desconocido: {}", other)),
+        }
+    }
+}
+
+/// Contenido tipado de una propuesta, aplicado por el módulo destino si se aprueba
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ProposalPayload {
+    AddVocabularyEntries {
+        entries: Vec<VocabularyEntry>, // Verbos y tipos de actividad xAPI aceptados
+    },
+    ChangeReputationParameters {
+        parameters: ReputationParameters,
+    },
+    ApproveLearningSpace {
+        space_id: Uuid,
+    },
+    SuspendLearningSpace {
+        space_id: Uuid,
+        reason: String,
+    },
+    FundLearningInitiative {
+        recipient: String,             // Dirección que recibe los fondos
+        requested_amount: u64,         // En la unidad mínima del token
+    },
+}
+
+impl ProposalPayload {
+    pub fn kind(&self) -> ProposalKind {
+        match self {
+            ProposalPayload::AddVocabularyEntries { .. } => ProposalKind::AddVocabularyEntries,
+            ProposalPayload::ChangeReputationParameters { .. } => ProposalKind::ChangeReputationParameters,
+            ProposalPayload::ApproveLearningSpace { .. } => ProposalKind::ApproveLearningSpace,
+            ProposalPayload::SuspendLearningSpace { .. } => ProposalKind::SuspendLearningSpace,
+            ProposalPayload::FundLearningInitiative { .. } => ProposalKind::FundLearningInitiative,
+        }
+    }
+}
+
+/// Cómo se pondera cada voto
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VoteWeighting {
+    OneHumanOneVote,    // Un voto por humano verificado (PoH)
+    ReputationWeighted, // Peso igual a la mejor reputación del votante en [0, 1]
+}
+
+/// Reglas de votación de una propuesta
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VotingRules {
+    pub strategy: VotingStrategyKind,
+    pub weighting: VoteWeighting,
+    pub voting_period_hours: i64,
+    pub quorum_min_voters: usize,      // Votantes distintos necesarios (incluidas abstenciones)
+    pub quorum_min_weight: f64,        // Peso total necesario (incluidas abstenciones)
+    pub approval_threshold: f64,       // Fracción de peso a favor sobre (a favor + en contra) que debe superarse
+}
+
+impl VotingRules {
+    /// Reglas por defecto según el tipo de propuesta
+    pub fn default_for(kind: ProposalKind) -> Self {
+        match kind {
+            ProposalKind::AddVocabularyEntries => Self {
+                strategy: VotingStrategyKind::SimpleMajority,
+                weighting: VoteWeighting::OneHumanOneVote,
+                voting_period_hours: 72,
+                quorum_min_voters: 10,
+                quorum_min_weight: 10.0,
+                approval_threshold: 0.5,
+            },
+            // Cambia cómo se mide a todos: voto cuadrático para evitar la captura
+            // por unos pocos actores, mayoría cualificada y periodo largo
+            ProposalKind::ChangeReputationParameters => Self {
+                strategy: VotingStrategyKind::Quadratic,
+                weighting: VoteWeighting::OneHumanOneVote,
+                voting_period_hours: 168,
+                quorum_min_voters: 25,
+                quorum_min_weight: 25.0,
+                approval_threshold: 2.0 / 3.0,
+            },
+            // La seguridad de un espacio la valoran mejor quienes tienen trayectoria
+            ProposalKind::ApproveLearningSpace | ProposalKind::SuspendLearningSpace => Self {
+                strategy: VotingStrategyKind::SimpleMajority,
+                weighting: VoteWeighting::ReputationWeighted,
+                voting_period_hours: 72,
+                quorum_min_voters: 5,
+                quorum_min_weight: 3.0,
+                approval_threshold: 0.5,
+            },
+            // Financiación continua: la propuesta sigue abierta hasta acumular convicción
+            ProposalKind::FundLearningInitiative => Self {
+                strategy: VotingStrategyKind::Conviction,
+                weighting: VoteWeighting::OneHumanOneVote,
+                voting_period_hours: 24 * 90,
+                quorum_min_voters: 5,
+                quorum_min_weight: 0.0,
+                approval_threshold: 0.0,
+            },
+        }
+    }
+}
+
+/// Estado de una propuesta
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProposalStatus {
+    Active,
+    Passed,            // Aprobada, pendiente de ejecución
+    Rejected,
+    Executed,
+    ExecutionFailed,
+}
+
+impl ProposalStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProposalStatus::Active => "active",
+            ProposalStatus::Passed => "passed",
+            ProposalStatus::Rejected => "rejected",
+            ProposalStatus::Executed => "executed",
+            ProposalStatus::ExecutionFailed => "execution_failed",
+        }
+    }
+}
+
+impl FromStr for ProposalStatus {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "active" => Ok(ProposalStatus::Active),
+            "passed" => Ok(ProposalStatus::Passed),
+            "rejected" => Ok(ProposalStatus::Rejected),
+            "executed" => Ok(ProposalStatus::Executed),
+            "execution_failed" => Ok(ProposalStatus::ExecutionFailed),
+            other => Err(anyhow!("Estado de propuesta desconocido: {}", other)),
+        }
+    }
+}
+
+/// Resultado del recuento de una votación
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TallyResult {
+    pub yes: f64,
+    pub no: f64,
+    pub abstain: f64,
+    pub voters: usize,
+    pub quorum_reached: bool,
+    pub passed: bool,
+}
+
+/// Propuesta de gobernanza
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Proposal {
+    pub id: ProposalId,
+    pub proposer: String,
+    pub title: String,
+    pub description: String,
+    pub payload: ProposalPayload,
+    pub rules: VotingRules,
+    pub status: ProposalStatus,
+    pub voting_starts_at: DateTime<Utc>,
+    pub voting_ends_at: DateTime<Utc>,
+    pub tally: Option<TallyResult>,
+    pub executed_at: Option<DateTime<Utc>>,
+    pub execution_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Proposal {
+    /// Comprobar si la votación está abierta en un instante dado
+    pub fn is_open_at(&self, at: DateTime<Utc>) -> bool {
+        self.status == ProposalStatus::Active && at >= self.voting_starts_at && at < self.voting_ends_at
+    }
+}
+
+/// Opción de voto
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VoteChoice {
+    Yes,
+    No,
+    Abstain,
+}
+
+impl VoteChoice {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VoteChoice::Yes => "yes",
+            VoteChoice::No => "no",
+            VoteChoice::Abstain => "abstain",
+        }
+    }
+}
+
+impl FromStr for VoteChoice {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "yes" => Ok(VoteChoice::Yes),
+            "no" => Ok(VoteChoice::No),
+            "abstain" => Ok(VoteChoice::Abstain),
+            other => Err(anyhow!("Opción de voto desconocida: {}", other)),
+        }
+    }
+}
+
+/// Voto emitido sobre una propuesta
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Vote {
+    pub proposal_id: ProposalId,
+    pub voter: String,
+    pub choice: VoteChoice,
+    pub weight: f64,                         // Peso fijado al emitir el voto
+    pub credits: f64,                        // Créditos de voz gastados (voto cuadrático)
+    pub cast_at: DateTime<Utc>,
+    pub withdrawn_at: Option<DateTime<Utc>>, // Retirada del apoyo (votación continua)
+}
+
+/// Eventos de dominio de gobernanza
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum GovernanceEvent {
+    ProposalSubmitted {
+        proposal_id: ProposalId,
+        proposer: String,
+        kind: ProposalKind,
+        timestamp: DateTime<Utc>,
+    },
+    VoteCast {
+        proposal_id: ProposalId,
+        voter: String,
+        choice: VoteChoice,
+        weight: f64,
+        credits: f64,
+        timestamp: DateTime<Utc>,
+    },
+    VoteWithdrawn {
+        proposal_id: ProposalId,
+        voter: String,
+        timestamp: DateTime<Utc>,
+    },
+    ProposalFinalized {
+        proposal_id: ProposalId,
+        tally: TallyResult,
+        timestamp: DateTime<Utc>,
+    },
+    ProposalExecuted {
+        proposal_id: ProposalId,
+        timestamp: DateTime<Utc>,
+    },
+    ProposalExecutionFailed {
+        proposal_id: ProposalId,
+        error: String,
+        timestamp: DateTime<Utc>,
+    },
+}
@@ -0,0 +1,452 @@
+// Estrategias de recuento de votos
+// Funciones puras: mismos votos y contexto producen siempre el mismo resultado
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{Proposal, ProposalPayload, TallyResult, Vote, VoteChoice, VotingRules};
+
+/// Estrategia de recuento usada por una propuesta
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum VotingStrategyKind {
+    SimpleMajority,
+    Quadratic,
+    Conviction,
+}
+
+impl VotingStrategyKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VotingStrategyKind::SimpleMajority => "simple_majority",
+            VotingStrategyKind::Quadratic => "quadratic",
+            VotingStrategyKind::Conviction => "conviction",
+        }
+    }
+}
+
+/// Presupuesto de créditos de voz por humano verificado y época
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VoiceCreditBudget {
+    pub credits_per_epoch: f64,
+    pub epoch_days: i64,
+}
+
+impl VoiceCreditBudget {
+    /// Inicio y fin de la época que contiene un instante (épocas alineadas con el epoch Unix)
+    pub fn epoch_bounds(&self, at: DateTime<Utc>) -> (DateTime<Utc>, DateTime<Utc>) {
+        let epoch_seconds = self.epoch_days.max(1) * 86_400;
+        let start_seconds = at.timestamp().div_euclid(epoch_seconds) * epoch_seconds;
+        let start = Utc.timestamp_opt(start_seconds, 0).single().unwrap_or(at);
+
+        (start, start + Duration::seconds(epoch_seconds))
+    }
+}
+
+/// Datos ajenos a la propuesta que intervienen en un recuento
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TallyContext {
+    pub now: DateTime<Utc>,
+    pub fund_balance: Option<u64>, // Saldo del fondo comunitario al hacer el recuento, si la propuesta lo usa
+}
+
+impl TallyContext {
+    pub fn at(now: DateTime<Utc>) -> Self {
+        Self { now, fund_balance: None }
+    }
+
+    pub fn with_fund_balance(mut self, fund_balance: u64) -> Self {
+        self.fund_balance = Some(fund_balance);
+        self
+    }
+}
+
+/// Estrategia de recuento de votos
+pub trait VotingStrategy: Send + Sync {
+    fn kind(&self) -> VotingStrategyKind;
+
+    /// Presupuesto de créditos de voz, si la estrategia los usa
+    fn voice_credits(&self) -> Option<&VoiceCreditBudget> {
+        None
+    }
+
+    /// Votación continua: los votos se pueden retirar y la propuesta se cierra
+    /// en cuanto se aprueba, sin esperar al fin del periodo
+    fn is_continuous(&self) -> bool {
+        false
+    }
+
+    /// Contar los votos de una propuesta en el instante del contexto
+    fn tally(&self, proposal: &Proposal, votes: &[Vote], context: &TallyContext) -> TallyResult;
+}
+
+/// Recuento por mayoría sobre pesos efectivos, con quórum de votantes y de peso
+fn majority_tally(rules: &VotingRules, weighted: impl Iterator<Item = (VoteChoice, f64)>) -> TallyResult {
+    let mut result = TallyResult {
+        yes: 0.0,
+        no: 0.0,
+        abstain: 0.0,
+        voters: 0,
+        quorum_reached: false,
+        passed: false,
+    };
+
+    for (choice, weight) in weighted {
+        result.voters += 1;
+        match choice {
+            VoteChoice::Yes => result.yes += weight,
+            VoteChoice::No => result.no += weight,
+            VoteChoice::Abstain => result.abstain += weight,
+        }
+    }
+
+    let total_weight = result.yes + result.no + result.abstain;
+    result.quorum_reached = result.voters >= rules.quorum_min_voters && total_weight >= rules.quorum_min_weight;
+
+    let decisive = result.yes + result.no;
+    result.passed = result.quorum_reached && decisive > 0.0 && result.yes / decisive > rules.approval_threshold;
+
+    result
+}
+
+/// Mayoría simple: cada voto cuenta con su peso
+pub struct SimpleMajority;
+
+impl VotingStrategy for SimpleMajority {
+    fn kind(&self) -> VotingStrategyKind {
+        VotingStrategyKind::SimpleMajority
+    }
+
+    fn tally(&self, proposal: &Proposal, votes: &[Vote], _context: &TallyContext) -> TallyResult {
+        majority_tally(&proposal.rules, votes.iter().map(|v| (v.choice, v.weight)))
+    }
+}
+
+/// Voto cuadrático: gastar n créditos de voz otorga √n votos
+///
+/// Los créditos se reparten por época entre todas las propuestas cuadráticas,
+/// de modo que concentrar el apoyo en una sola tiene un coste creciente.
+pub struct QuadraticVoting {
+    pub budget: VoiceCreditBudget,
+}
+
+impl Default for QuadraticVoting {
+    fn default() -> Self {
+        Self {
+            budget: VoiceCreditBudget {
+                credits_per_epoch: 100.0,
+                epoch_days: 30,
+            },
+        }
+    }
+}
+
+impl VotingStrategy for QuadraticVoting {
+    fn kind(&self) -> VotingStrategyKind {
+        VotingStrategyKind::Quadratic
+    }
+
+    fn voice_credits(&self) -> Option<&VoiceCreditBudget> {
+        Some(&self.budget)
+    }
+
+    fn tally(&self, proposal: &Proposal, votes: &[Vote], _context: &TallyContext) -> TallyResult {
+        majority_tally(
+            &proposal.rules,
+            votes.iter().map(|v| (v.choice, v.weight * v.credits.max(0.0).sqrt())),
+        )
+    }
+}
+
+/// Votación por convicción para financiación continua
+///
+/// El apoyo de cada voto crece con el tiempo que permanece hacia su peso
+/// (mitad en `half_life_hours`) y decae igual tras retirarse. La propuesta se
+/// aprueba cuando la convicción neta a favor alcanza `rho / (beta - solicitado/fondo)²`,
+/// con el saldo del fondo en el momento del recuento; una solicitud de `beta` o
+/// más del fondo nunca se aprueba. El umbral de aprobación de las reglas no se usa.
+pub struct ConvictionVoting {
+    pub half_life_hours: f64,
+    pub beta: f64,  // Fracción máxima del fondo que puede solicitarse
+    pub rho: f64,   // Escala del umbral, en unidades de peso
+}
+
+impl Default for ConvictionVoting {
+    fn default() -> Self {
+        Self {
+            half_life_hours: 72.0,
+            beta: 0.2,
+            rho: 0.4,
+        }
+    }
+}
+
+impl ConvictionVoting {
+    /// Convicción acumulada por un voto en un instante dado
+    pub fn conviction(&self, vote: &Vote, now: DateTime<Utc>) -> f64 {
+        let hours = |from: DateTime<Utc>, to: DateTime<Utc>| (to - from).num_seconds().max(0) as f64 / 3_600.0;
+        let supported_until = vote.withdrawn_at.map(|w| w.min(now)).unwrap_or(now);
+
+        let grown = vote.weight * (1.0 - 0.5_f64.powf(hours(vote.cast_at, supported_until) / self.half_life_hours));
+        grown * 0.5_f64.powf(hours(supported_until, now) / self.half_life_hours)
+    }
+
+    /// Convicción necesaria para aprobar una propuesta con el saldo del fondo dado
+    ///
+    /// Sin saldo conocido una solicitud de fondos nunca se aprueba.
+    pub fn threshold(&self, proposal: &Proposal, fund_balance: Option<u64>) -> f64 {
+        let requested_ratio = match (&proposal.payload, fund_balance) {
+            (ProposalPayload::FundLearningInitiative { requested_amount, .. }, Some(balance)) if balance > 0 => {
+                *requested_amount as f64 / balance as f64
+            }
+            (ProposalPayload::FundLearningInitiative { .. }, _) => return f64::INFINITY,
+            _ => 0.0,
+        };
+
+        if requested_ratio >= self.beta {
+            return f64::INFINITY;
+        }
+
+        self.rho / (self.beta - requested_ratio).powi(2)
+    }
+}
+
+impl VotingStrategy for ConvictionVoting {
+    fn kind(&self) -> VotingStrategyKind {
+        VotingStrategyKind::Conviction
+    }
+
+    fn is_continuous(&self) -> bool {
+        true
+    }
+
+    fn tally(&self, proposal: &Proposal, votes: &[Vote], context: &TallyContext) -> TallyResult {
+        let mut result = TallyResult {
+            yes: 0.0,
+            no: 0.0,
+            abstain: 0.0,
+            voters: 0,
+            quorum_reached: false,
+            passed: false,
+        };
+
+        for vote in votes {
+            if vote.withdrawn_at.is_none() {
+                result.voters += 1;
+            }
+            let conviction = self.conviction(vote, context.now);
+            match vote.choice {
+                VoteChoice::Yes => result.yes += conviction,
+                VoteChoice::No => result.no += conviction,
+                VoteChoice::Abstain => result.abstain += conviction,
+            }
+        }
+
+        result.quorum_reached = result.voters >= proposal.rules.quorum_min_voters
+            && result.yes + result.no + result.abstain >= proposal.rules.quorum_min_weight;
+        result.passed = result.quorum_reached && result.yes - result.no >= self.threshold(proposal, context.fund_balance);
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{ProposalId, ProposalStatus, VoteWeighting};
+
+    fn at(hours: i64) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap() + Duration::hours(hours)
+    }
+
+    fn proposal(payload: ProposalPayload, rules: VotingRules) -> Proposal {
+        Proposal {
+            id: ProposalId::new(),
+            proposer: "proposer".to_string(),
+            title: "Propuesta".to_string(),
+            description: String::new(),
+            payload,
+            rules,
+            status: ProposalStatus::Active,
+            voting_starts_at: at(0),
+            voting_ends_at: at(72),
+            tally: None,
+            executed_at: None,
+            execution_error: None,
+            created_at: at(0),
+        }
+    }
+
+    fn rules(strategy: VotingStrategyKind, quorum_min_voters: usize) -> VotingRules {
+        VotingRules {
+            strategy,
+            weighting: VoteWeighting::OneHumanOneVote,
+            voting_period_hours: 72,
+            quorum_min_voters,
+            quorum_min_weight: 0.0,
+            approval_threshold: 0.5,
+        }
+    }
+
+    fn vote(proposal: &Proposal, voter: usize, choice: VoteChoice, credits: f64, cast_at: DateTime<Utc>) -> Vote {
+        Vote {
+            proposal_id: proposal.id.clone(),
+            voter: format!("voter-{voter}"),
+            choice,
+            weight: 1.0,
+            credits,
+            cast_at,
+            withdrawn_at: None,
+        }
+    }
+
+    fn space() -> ProposalPayload {
+        ProposalPayload::ApproveLearningSpace { space_id: uuid::Uuid::nil() }
+    }
+
+    fn funding(requested_amount: u64) -> ProposalPayload {
+        ProposalPayload::FundLearningInitiative {
+            recipient: "initiative".to_string(),
+            requested_amount,
+        }
+    }
+
+    fn with_fund(hours: i64, fund_balance: u64) -> TallyContext {
+        TallyContext::at(at(hours)).with_fund_balance(fund_balance)
+    }
+
+    #[test]
+    fn simple_majority_passes_with_quorum_and_majority() {
+        let p = proposal(space(), rules(VotingStrategyKind::SimpleMajority, 10));
+        let mut votes: Vec<Vote> = (0..6).map(|i| vote(&p, i, VoteChoice::Yes, 0.0, at(1))).collect();
+        votes.extend((6..9).map(|i| vote(&p, i, VoteChoice::No, 0.0, at(1))));
+        votes.push(vote(&p, 9, VoteChoice::Abstain, 0.0, at(1)));
+
+        let tally = SimpleMajority.tally(&p, &votes, &TallyContext::at(at(72)));
+
+        assert_eq!(tally.voters, 10);
+        assert_eq!((tally.yes, tally.no, tally.abstain), (6.0, 3.0, 1.0));
+        assert!(tally.quorum_reached);
+        assert!(tally.passed);
+    }
+
+    #[test]
+    fn simple_majority_fails_without_quorum_or_on_tie() {
+        let p = proposal(space(), rules(VotingStrategyKind::SimpleMajority, 10));
+
+        let short: Vec<Vote> = (0..9).map(|i| vote(&p, i, VoteChoice::Yes, 0.0, at(1))).collect();
+        let tally = SimpleMajority.tally(&p, &short, &TallyContext::at(at(72)));
+        assert!(!tally.quorum_reached);
+        assert!(!tally.passed);
+
+        let mut tie: Vec<Vote> = (0..5).map(|i| vote(&p, i, VoteChoice::Yes, 0.0, at(1))).collect();
+        tie.extend((5..10).map(|i| vote(&p, i, VoteChoice::No, 0.0, at(1))));
+        let tally = SimpleMajority.tally(&p, &tie, &TallyContext::at(at(72)));
+        assert!(tally.quorum_reached);
+        assert!(!tally.passed);
+    }
+
+    #[test]
+    fn quadratic_voting_uses_square_root_of_credits() {
+        let p = proposal(space(), rules(VotingStrategyKind::Quadratic, 1));
+        let votes = vec![
+            vote(&p, 0, VoteChoice::Yes, 100.0, at(1)),
+            vote(&p, 1, VoteChoice::No, 16.0, at(1)),
+            vote(&p, 2, VoteChoice::No, 25.0, at(1)),
+        ];
+
+        let tally = QuadraticVoting::default().tally(&p, &votes, &TallyContext::at(at(72)));
+
+        assert_eq!(tally.yes, 10.0);
+        assert_eq!(tally.no, 9.0);
+        assert!(tally.passed);
+    }
+
+    #[test]
+    fn quadratic_voting_lets_many_small_voters_outweigh_one_large_one() {
+        let p = proposal(space(), rules(VotingStrategyKind::Quadratic, 1));
+        let mut votes = vec![vote(&p, 0, VoteChoice::Yes, 100.0, at(1))];
+        votes.extend((1..=6).map(|i| vote(&p, i, VoteChoice::No, 4.0, at(1))));
+
+        let tally = QuadraticVoting::default().tally(&p, &votes, &TallyContext::at(at(72)));
+
+        assert_eq!(tally.yes, 10.0);
+        assert_eq!(tally.no, 12.0);
+        assert!(!tally.passed);
+    }
+
+    #[test]
+    fn voice_credit_epochs_are_aligned_and_contiguous() {
+        let budget = VoiceCreditBudget {
+            credits_per_epoch: 100.0,
+            epoch_days: 30,
+        };
+
+        let (start, end) = budget.epoch_bounds(at(0));
+        assert!(start <= at(0) && at(0) < end);
+        assert_eq!(end - start, Duration::days(30));
+        assert_eq!(budget.epoch_bounds(end).0, end);
+        assert_eq!(budget.epoch_bounds(end - Duration::seconds(1)).0, start);
+    }
+
+    #[test]
+    fn conviction_grows_towards_vote_weight() {
+        let strategy = ConvictionVoting::default();
+        let p = proposal(funding(0), rules(VotingStrategyKind::Conviction, 1));
+        let v = vote(&p, 0, VoteChoice::Yes, 0.0, at(0));
+
+        assert_eq!(strategy.conviction(&v, at(0)), 0.0);
+        assert_eq!(strategy.conviction(&v, at(72)), 0.5);
+        assert_eq!(strategy.conviction(&v, at(144)), 0.75);
+    }
+
+    #[test]
+    fn conviction_decays_after_withdrawal() {
+        let strategy = ConvictionVoting::default();
+        let p = proposal(funding(0), rules(VotingStrategyKind::Conviction, 1));
+        let mut v = vote(&p, 0, VoteChoice::Yes, 0.0, at(0));
+        v.withdrawn_at = Some(at(144));
+
+        assert_eq!(strategy.conviction(&v, at(144)), 0.75);
+        assert_eq!(strategy.conviction(&v, at(216)), 0.375);
+    }
+
+    #[test]
+    fn conviction_passes_once_threshold_is_reached() {
+        let strategy = ConvictionVoting::default();
+        // 5% del fondo: umbral = 0.4 / (0.2 - 0.05)² ≈ 17.8
+        let p = proposal(funding(50), rules(VotingStrategyKind::Conviction, 5));
+        let votes: Vec<Vote> = (0..24).map(|i| vote(&p, i, VoteChoice::Yes, 0.0, at(0))).collect();
+
+        assert!((strategy.threshold(&p, Some(1_000)) - 0.4 / 0.0225).abs() < 1e-9);
+        // 24 votos a una vida media acumulan 12 de convicción: aún no basta
+        assert!(!strategy.tally(&p, &votes, &with_fund(72, 1_000)).passed);
+        // A dos vidas medias acumulan 18
+        let tally = strategy.tally(&p, &votes, &with_fund(144, 1_000));
+        assert_eq!(tally.yes, 18.0);
+        assert!(tally.passed);
+    }
+
+    #[test]
+    fn conviction_never_passes_requests_above_beta() {
+        let strategy = ConvictionVoting::default();
+        let p = proposal(funding(200), rules(VotingStrategyKind::Conviction, 1));
+        let votes: Vec<Vote> = (0..1_000).map(|i| vote(&p, i, VoteChoice::Yes, 0.0, at(0))).collect();
+
+        assert_eq!(strategy.threshold(&p, Some(1_000)), f64::INFINITY);
+        assert!(!strategy.tally(&p, &votes, &with_fund(24 * 365, 1_000)).passed);
+    }
+
+    #[test]
+    fn conviction_threshold_follows_the_fund_balance_at_tally_time() {
+        let strategy = ConvictionVoting::default();
+        let p = proposal(funding(50), rules(VotingStrategyKind::Conviction, 5));
+        let votes: Vec<Vote> = (0..24).map(|i| vote(&p, i, VoteChoice::Yes, 0.0, at(0))).collect();
+
+        // Con 1000 en el fondo basta; si el fondo baja a 400 la solicitud supera beta
+        assert!(strategy.tally(&p, &votes, &with_fund(144, 1_000)).passed);
+        assert!(!strategy.tally(&p, &votes, &with_fund(144, 400)).passed);
+        // Sin saldo conocido nunca se aprueba
+        assert_eq!(strategy.threshold(&p, None), f64::INFINITY);
+        assert!(!strategy.tally(&p, &votes, &TallyContext::at(at(144))).passed);
+    }
+}
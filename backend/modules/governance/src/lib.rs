@@ -0,0 +1,28 @@
+use anyhow::Result;
+
+pub mod domain;
+pub mod repository;
+pub mod service;
+
+/// Inicializar el módulo governance
+pub async fn init() -> Result<()> {
+    tracing::info!("Inicializando módulo governance");
+    
+    // TODO: Implementar inicialización del módulo
+    // - Conexión a base de datos
+    // - Registro de ejecutores de propuestas de los módulos destino
+    // - Tarea periódica de cierre y ejecución de votaciones vencidas
+    
+    Ok(())
+}
+
+/// Cerrar el módulo governance
+pub async fn shutdown() -> Result<()> {
+    tracing::info!("Cerrando módulo governance");
+    
+    // TODO: Implementar limpieza del módulo
+    // - Cerrar conexiones
+    // - Detener la tarea de cierre de votaciones
+    
+    Ok(())
+}
@@ -0,0 +1,299 @@
+// Repositorios para persistencia del módulo governance
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+use crate::domain::{Proposal, ProposalId, ProposalStatus, Vote};
+
+pub struct GovernanceRepository {
+    pool: PgPool,
+}
+
+impl GovernanceRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Crear una nueva propuesta
+    pub async fn create_proposal(&self, proposal: &Proposal) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO governance_proposals (
+                id, proposer, title, description, kind, payload, rules, status,
+                voting_starts_at, voting_ends_at, created_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            "#,
+            proposal.id.0,
+            proposal.proposer,
+            proposal.title,
+            proposal.description,
+            proposal.payload.kind().as_str(),
+            serde_json::to_value(&proposal.payload)?,
+            serde_json::to_value(&proposal.rules)?,
+            proposal.status.as_str(),
+            proposal.voting_starts_at,
+            proposal.voting_ends_at,
+            proposal.created_at
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Actualizar estado, recuento y resultado de ejecución de una propuesta
+    ///
+    /// Sólo se actualiza si sigue en el estado `expected`; devuelve si se actualizó, de modo
+    /// que de dos cierres o ejecuciones concurrentes sólo uno gana.
+    pub async fn update_proposal_outcome(&self, proposal: &Proposal, expected: ProposalStatus) -> Result<bool> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE governance_proposals
+            SET status = $2, tally = $3, executed_at = $4, execution_error = $5
+            WHERE id = $1 AND status = $6
+            "#,
+            proposal.id.0,
+            proposal.status.as_str(),
+            proposal.tally.as_ref().map(serde_json::to_value).transpose()?,
+            proposal.executed_at,
+            proposal.execution_error,
+            expected.as_str()
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Obtener una propuesta por ID
+    pub async fn get_proposal(&self, proposal_id: &ProposalId) -> Result<Option<Proposal>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT id, proposer, title, description, payload, rules, status,
+                   voting_starts_at, voting_ends_at, tally, executed_at, execution_error, created_at
+            FROM governance_proposals
+            WHERE id = $1
+            "#,
+            proposal_id.0
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match row {
+            Some(row) => Ok(Some(Proposal {
+                id: ProposalId(row.id),
+                proposer: row.proposer,
+                title: row.title,
+                description: row.description,
+                payload: serde_json::from_value(row.payload)?,
+                rules: serde_json::from_value(row.rules)?,
+                status: row.status.parse()?,
+                voting_starts_at: row.voting_starts_at,
+                voting_ends_at: row.voting_ends_at,
+                tally: row.tally.map(serde_json::from_value).transpose()?,
+                executed_at: row.executed_at,
+                execution_error: row.execution_error,
+                created_at: row.created_at,
+            })),
+            None => Ok(None),
+        }
+    }
+
+    /// Listar propuestas en un estado, de la más reciente a la más antigua
+    pub async fn get_proposals_by_status(&self, status: ProposalStatus) -> Result<Vec<ProposalId>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT id
+            FROM governance_proposals
+            WHERE status = $1
+            ORDER BY created_at DESC
+            "#,
+            status.as_str()
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(|row| ProposalId(row.id)).collect())
+    }
+
+    /// Obtener las propuestas activas cuya votación ya terminó
+    pub async fn get_expired_active_proposals(&self, now: DateTime<Utc>) -> Result<Vec<ProposalId>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT id
+            FROM governance_proposals
+            WHERE status = $1 AND voting_ends_at <= $2
+            ORDER BY voting_ends_at ASC
+            "#,
+            ProposalStatus::Active.as_str(),
+            now
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(|row| ProposalId(row.id)).collect())
+    }
+
+    /// Registrar un voto (uno por votante y propuesta)
+    pub async fn insert_vote(&self, vote: &Vote) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO governance_votes (proposal_id, voter, choice, weight, credits, cast_at, withdrawn_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#,
+            vote.proposal_id.0,
+            vote.voter,
+            vote.choice.as_str(),
+            vote.weight,
+            vote.credits,
+            vote.cast_at,
+            vote.withdrawn_at
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Marcar como retirado el voto vigente de un usuario
+    pub async fn withdraw_vote(&self, proposal_id: &ProposalId, voter: &str, at: DateTime<Utc>) -> Result<bool> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE governance_votes
+            SET withdrawn_at = $3
+            WHERE proposal_id = $1 AND voter = $2 AND withdrawn_at IS NULL
+            "#,
+            proposal_id.0,
+            voter,
+            at
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Registrar un voto cuadrático si cabe en el presupuesto de créditos de la época
+    ///
+    /// La suma de créditos y la inserción van en una transacción con un bloqueo por
+    /// votante, para que dos votos simultáneos no gasten el mismo saldo. Devuelve
+    /// `false` sin registrar el voto si excede el presupuesto.
+    pub async fn insert_vote_within_budget(
+        &self,
+        vote: &Vote,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        credits_per_epoch: f64,
+    ) -> Result<bool> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query!("SELECT pg_advisory_xact_lock(hashtextextended($1, 0))", vote.voter)
+            .execute(&mut *tx)
+            .await?;
+
+        let row = sqlx::query!(
+            r#"
+            SELECT COALESCE(SUM(credits), 0) AS "spent!"
+            FROM governance_votes
+            WHERE voter = $1 AND cast_at >= $2 AND cast_at < $3
+            "#,
+            vote.voter,
+            from,
+            to
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if row.spent + vote.credits > credits_per_epoch {
+            return Ok(false);
+        }
+
+        sqlx::query!(
+            r#"
+            INSERT INTO governance_votes (proposal_id, voter, choice, weight, credits, cast_at, withdrawn_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#,
+            vote.proposal_id.0,
+            vote.voter,
+            vote.choice.as_str(),
+            vote.weight,
+            vote.credits,
+            vote.cast_at,
+            vote.withdrawn_at
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(true)
+    }
+
+    /// Créditos de voz gastados por un usuario en un intervalo
+    pub async fn get_credits_spent(&self, voter: &str, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<f64> {
+        let row = sqlx::query!(
+            r#"
+            SELECT COALESCE(SUM(credits), 0) AS "spent!"
+            FROM governance_votes
+            WHERE voter = $1 AND cast_at >= $2 AND cast_at < $3
+            "#,
+            voter,
+            from,
+            to
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(row.spent)
+    }
+
+    /// Comprobar si un usuario ya votó una propuesta
+    pub async fn has_voted(&self, proposal_id: &ProposalId, voter: &str) -> Result<bool> {
+        let row = sqlx::query!(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM governance_votes WHERE proposal_id = $1 AND voter = $2
+            ) AS "exists!"
+            "#,
+            proposal_id.0,
+            voter
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(row.exists)
+    }
+
+    /// Obtener los votos de una propuesta
+    pub async fn get_votes(&self, proposal_id: &ProposalId) -> Result<Vec<Vote>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT proposal_id, voter, choice, weight, credits, cast_at, withdrawn_at
+            FROM governance_votes
+            WHERE proposal_id = $1
+            ORDER BY cast_at ASC
+            "#,
+            proposal_id.0
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut votes = Vec::new();
+
+        for row in rows {
+            votes.push(Vote {
+                proposal_id: ProposalId(row.proposal_id),
+                voter: row.voter,
+                choice: row.choice.parse()?,
+                weight: row.weight,
+                credits: row.credits,
+                cast_at: row.cast_at,
+                withdrawn_at: row.withdrawn_at,
+            });
+        }
+
+        Ok(votes)
+    }
+}
@@ -0,0 +1,565 @@
+// Servicios de aplicación para el módulo governance
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::{anyhow, bail, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+
+use identity::service::HumanityVerifier;
+use learning_passport::service::VocabularyService;
+use reputation::domain::RatedRole;
+use reputation::service::ReputationService;
+
+use crate::domain::{
+    ConvictionVoting, Proposal, ProposalId, ProposalKind, ProposalPayload, ProposalStatus, QuadraticVoting,
+    SimpleMajority, TallyContext, Vote, VoteChoice, VoteWeighting, VotingRules, VotingStrategy, VotingStrategyKind,
+};
+use crate::repository::GovernanceRepository;
+
+/// Aplica una propuesta aprobada en su módulo destino
+///
+/// Cada módulo registra el ejecutor de los tipos de propuesta que le afectan.
+#[async_trait]
+pub trait ProposalExecutor: Send + Sync {
+    async fn execute(&self, proposal: &Proposal) -> Result<()>;
+}
+
+/// Ejecutor de cambios de parámetros del motor de reputación
+pub struct ReputationParametersExecutor {
+    reputation: Arc<ReputationService>,
+}
+
+impl ReputationParametersExecutor {
+    pub fn new(reputation: Arc<ReputationService>) -> Self {
+        Self { reputation }
+    }
+}
+
+#[async_trait]
+impl ProposalExecutor for ReputationParametersExecutor {
+    async fn execute(&self, proposal: &Proposal) -> Result<()> {
+        let ProposalPayload::ChangeReputationParameters { parameters } = &proposal.payload else {
+            bail!("La propuesta no cambia parámetros de reputación");
+        };
+
+        self.reputation.update_parameters(parameters).await?;
+        self.reputation.recompute().await?;
+
+        Ok(())
+    }
+}
+
+/// Ejecutor de altas y cambios en el vocabulario xAPI
+pub struct VocabularyExecutor {
+    vocabulary: Arc<VocabularyService>,
+}
+
+impl VocabularyExecutor {
+    pub fn new(vocabulary: Arc<VocabularyService>) -> Self {
+        Self { vocabulary }
+    }
+}
+
+#[async_trait]
+impl ProposalExecutor for VocabularyExecutor {
+    async fn execute(&self, proposal: &Proposal) -> Result<()> {
+        let ProposalPayload::AddVocabularyEntries { entries } = &proposal.payload else {
+            bail!("La propuesta no modifica el vocabulario");
+        };
+
+        self.vocabulary.add_entries(entries.clone(), Some(proposal.id.0)).await
+    }
+}
+
+/// Fondo comunitario del que salen las iniciativas de aprendizaje aprobadas
+///
+/// Lo implementa el módulo que custodia los fondos (marketplace).
+#[async_trait]
+pub trait CommunityFund: Send + Sync {
+    /// Saldo disponible del fondo, en la unidad mínima del token
+    async fn balance(&self) -> Result<u64>;
+
+    /// Transferir los fondos de una propuesta aprobada
+    ///
+    /// Debe ser idempotente por propuesta: reintentar la ejecución no paga dos veces.
+    async fn disburse(&self, proposal_id: &ProposalId, recipient: &str, amount: u64) -> Result<()>;
+}
+
+/// Ejecutor de la financiación de iniciativas de aprendizaje con el fondo comunitario
+pub struct LearningInitiativeExecutor {
+    fund: Arc<dyn CommunityFund>,
+}
+
+impl LearningInitiativeExecutor {
+    pub fn new(fund: Arc<dyn CommunityFund>) -> Self {
+        Self { fund }
+    }
+}
+
+#[async_trait]
+impl ProposalExecutor for LearningInitiativeExecutor {
+    async fn execute(&self, proposal: &Proposal) -> Result<()> {
+        let ProposalPayload::FundLearningInitiative { recipient, requested_amount, .. } = &proposal.payload else {
+            bail!("La propuesta no financia una iniciativa de aprendizaje");
+        };
+
+        self.fund.disburse(&proposal.id, recipient, *requested_amount).await
+    }
+}
+
+pub struct GovernanceService {
+    repository: GovernanceRepository,
+    humanity_verifier: Arc<dyn HumanityVerifier>,
+    reputation: Arc<ReputationService>,
+    executors: HashMap<ProposalKind, Arc<dyn ProposalExecutor>>,
+    strategies: HashMap<VotingStrategyKind, Arc<dyn VotingStrategy>>,
+    fund: Option<Arc<dyn CommunityFund>>, // Fondo de las iniciativas de aprendizaje, si está configurado
+}
+
+impl GovernanceService {
+    pub fn new(
+        repository: GovernanceRepository,
+        humanity_verifier: Arc<dyn HumanityVerifier>,
+        reputation: Arc<ReputationService>,
+    ) -> Self {
+        let mut executors: HashMap<ProposalKind, Arc<dyn ProposalExecutor>> = HashMap::new();
+        executors.insert(
+            ProposalKind::ChangeReputationParameters,
+            Arc::new(ReputationParametersExecutor::new(reputation.clone())),
+        );
+
+        let mut strategies: HashMap<VotingStrategyKind, Arc<dyn VotingStrategy>> = HashMap::new();
+        strategies.insert(VotingStrategyKind::SimpleMajority, Arc::new(SimpleMajority));
+        strategies.insert(VotingStrategyKind::Quadratic, Arc::new(QuadraticVoting::default()));
+        strategies.insert(VotingStrategyKind::Conviction, Arc::new(ConvictionVoting::default()));
+
+        Self { repository, humanity_verifier, reputation, executors, strategies, fund: None }
+    }
+
+    /// Registrar el ejecutor de un tipo de propuesta
+    pub fn register_executor(&mut self, kind: ProposalKind, executor: Arc<dyn ProposalExecutor>) {
+        self.executors.insert(kind, executor);
+    }
+
+    /// Configurar el fondo comunitario: habilita las propuestas de financiación
+    ///
+    /// Su saldo en el momento de cada recuento fija el umbral de esas propuestas.
+    pub fn register_community_fund(&mut self, fund: Arc<dyn CommunityFund>) {
+        self.executors
+            .insert(ProposalKind::FundLearningInitiative, Arc::new(LearningInitiativeExecutor::new(fund.clone())));
+        self.fund = Some(fund);
+    }
+
+    /// Registrar (o reemplazar) una estrategia de recuento
+    pub fn register_strategy(&mut self, strategy: Arc<dyn VotingStrategy>) {
+        self.strategies.insert(strategy.kind(), strategy);
+    }
+
+    /// Presentar una propuesta; la votación empieza de inmediato
+    pub async fn submit_proposal(
+        &self,
+        proposer: &str,
+        title: &str,
+        description: &str,
+        payload: ProposalPayload,
+    ) -> Result<Proposal> {
+        if !self.humanity_verifier.is_verified_address(proposer).await? {
+            bail!("Sólo humanos verificados pueden presentar propuestas");
+        }
+
+        if title.trim().is_empty() {
+            bail!("La propuesta debe tener un título");
+        }
+
+        let kind = payload.kind();
+        if !self.executors.contains_key(&kind) {
+            bail!("No hay ejecutor registrado para propuestas de tipo {}", kind);
+        }
+
+        let rules = VotingRules::default_for(kind);
+        self.strategy(&rules)?;
+        let now = Utc::now();
+
+        let proposal = Proposal {
+            id: ProposalId::new(),
+            proposer: proposer.to_string(),
+            title: title.to_string(),
+            description: description.to_string(),
+            payload,
+            voting_starts_at: now,
+            voting_ends_at: now + Duration::hours(rules.voting_period_hours),
+            rules,
+            status: ProposalStatus::Active,
+            tally: None,
+            executed_at: None,
+            execution_error: None,
+            created_at: now,
+        };
+
+        self.repository.create_proposal(&proposal).await?;
+
+        // TODO: Emitir evento de dominio
+        // self.emit_event(GovernanceEvent::ProposalSubmitted { ... }).await?;
+
+        Ok(proposal)
+    }
+
+    /// Votar una propuesta activa
+    ///
+    /// En voto cuadrático `credits` indica los créditos de voz gastados; en el
+    /// resto de estrategias debe omitirse.
+    pub async fn cast_vote(
+        &self,
+        proposal_id: &ProposalId,
+        voter: &str,
+        choice: VoteChoice,
+        credits: Option<f64>,
+    ) -> Result<Vote> {
+        let proposal = self.load(proposal_id).await?;
+        let strategy = self.strategy(&proposal.rules)?;
+        let now = Utc::now();
+
+        if !proposal.is_open_at(now) {
+            bail!("La votación de la propuesta no está abierta");
+        }
+
+        // Ambas ponderaciones exigen humanidad verificada para evitar votos Sybil
+        if !self.humanity_verifier.is_verified_address(voter).await? {
+            bail!("Sólo humanos verificados pueden votar");
+        }
+
+        if self.repository.has_voted(proposal_id, voter).await? {
+            bail!("{} ya votó esta propuesta", voter);
+        }
+
+        let weight = match proposal.rules.weighting {
+            VoteWeighting::OneHumanOneVote => 1.0,
+            VoteWeighting::ReputationWeighted => self.reputation_weight(voter).await?,
+        };
+
+        if weight <= 0.0 {
+            bail!("{} no tiene reputación suficiente para votar esta propuesta", voter);
+        }
+
+        let budget = match (strategy.voice_credits(), credits) {
+            (Some(budget), Some(credits)) => {
+                if credits <= 0.0 {
+                    bail!("Hay que gastar créditos de voz para votar");
+                }
+                Some(budget)
+            }
+            (Some(_), None) => bail!("El voto cuadrático requiere indicar los créditos de voz"),
+            (None, Some(_)) => bail!("La estrategia {} no usa créditos de voz", strategy.kind().as_str()),
+            (None, None) => None,
+        };
+
+        let vote = Vote {
+            proposal_id: proposal_id.clone(),
+            voter: voter.to_string(),
+            choice,
+            weight,
+            credits: credits.unwrap_or(0.0),
+            cast_at: now,
+            withdrawn_at: None,
+        };
+
+        match budget {
+            Some(budget) => {
+                let (epoch_start, epoch_end) = budget.epoch_bounds(now);
+                let recorded = self
+                    .repository
+                    .insert_vote_within_budget(&vote, epoch_start, epoch_end, budget.credits_per_epoch)
+                    .await?;
+
+                if !recorded {
+                    let spent = self.repository.get_credits_spent(voter, epoch_start, epoch_end).await?;
+                    bail!(
+                        "Créditos de voz insuficientes: quedan {} de {} en esta época",
+                        budget.credits_per_epoch - spent,
+                        budget.credits_per_epoch
+                    );
+                }
+            }
+            None => self.repository.insert_vote(&vote).await?,
+        }
+
+        // TODO: Emitir evento de dominio
+        // self.emit_event(GovernanceEvent::VoteCast { ... }).await?;
+
+        Ok(vote)
+    }
+
+    /// Retirar el apoyo a una propuesta de votación continua
+    pub async fn withdraw_vote(&self, proposal_id: &ProposalId, voter: &str) -> Result<()> {
+        let proposal = self.load(proposal_id).await?;
+        let now = Utc::now();
+
+        if !self.strategy(&proposal.rules)?.is_continuous() {
+            bail!("Sólo se pueden retirar votos en votaciones continuas");
+        }
+
+        if !proposal.is_open_at(now) {
+            bail!("La votación de la propuesta no está abierta");
+        }
+
+        if !self.repository.withdraw_vote(proposal_id, voter, now).await? {
+            bail!("{} no tiene un voto vigente en esta propuesta", voter);
+        }
+
+        // TODO: Emitir evento de dominio
+        // self.emit_event(GovernanceEvent::VoteWithdrawn { ... }).await?;
+
+        Ok(())
+    }
+
+    /// Cerrar la votación de una propuesta y ejecutarla si se aprobó
+    ///
+    /// Las votaciones continuas pueden cerrarse antes del fin del periodo en
+    /// cuanto se aprueban; el resto sólo al vencer.
+    pub async fn finalize_proposal(&self, proposal_id: &ProposalId) -> Result<Proposal> {
+        let mut proposal = self.load(proposal_id).await?;
+        let strategy = self.strategy(&proposal.rules)?;
+        let now = Utc::now();
+
+        if proposal.status != ProposalStatus::Active {
+            bail!("La propuesta ya fue cerrada");
+        }
+
+        let votes = self.repository.get_votes(proposal_id).await?;
+        let tally = strategy.tally(&proposal, &votes, &self.tally_context(&proposal, now).await?);
+
+        if now < proposal.voting_ends_at && !(strategy.is_continuous() && tally.passed) {
+            bail!("La votación de la propuesta sigue abierta");
+        }
+
+        proposal.status = if tally.passed { ProposalStatus::Passed } else { ProposalStatus::Rejected };
+        proposal.tally = Some(tally);
+        if !self.repository.update_proposal_outcome(&proposal, ProposalStatus::Active).await? {
+            bail!("La propuesta ya fue cerrada");
+        }
+
+        // TODO: Emitir evento de dominio
+        // self.emit_event(GovernanceEvent::ProposalFinalized { ... }).await?;
+
+        if proposal.status == ProposalStatus::Passed {
+            self.execute(&mut proposal).await?;
+        }
+
+        Ok(proposal)
+    }
+
+    /// Cerrar las votaciones vencidas y las continuas ya aprobadas (invocado periódicamente)
+    pub async fn finalize_expired_proposals(&self) -> Result<Vec<Proposal>> {
+        let mut finalized = Vec::new();
+
+        for proposal_id in self.repository.get_expired_active_proposals(Utc::now()).await? {
+            match self.finalize_proposal(&proposal_id).await {
+                Ok(proposal) => finalized.push(proposal),
+                Err(e) => tracing::error!("Error cerrando la propuesta {:?}: {}", proposal_id, e),
+            }
+        }
+
+        for proposal in self.get_proposals_by_status(ProposalStatus::Active).await? {
+            if !self.strategy(&proposal.rules)?.is_continuous() {
+                continue;
+            }
+
+            let votes = self.repository.get_votes(&proposal.id).await?;
+            let context = self.tally_context(&proposal, Utc::now()).await?;
+            if self.strategy(&proposal.rules)?.tally(&proposal, &votes, &context).passed {
+                match self.finalize_proposal(&proposal.id).await {
+                    Ok(proposal) => finalized.push(proposal),
+                    Err(e) => tracing::error!("Error cerrando la propuesta {:?}: {}", proposal.id, e),
+                }
+            }
+        }
+
+        Ok(finalized)
+    }
+
+    /// Reintentar la ejecución de una propuesta aprobada cuya ejecución falló
+    pub async fn retry_execution(&self, proposal_id: &ProposalId) -> Result<Proposal> {
+        let mut proposal = self.load(proposal_id).await?;
+
+        if proposal.status != ProposalStatus::ExecutionFailed {
+            bail!("Sólo se pueden reintentar propuestas cuya ejecución falló");
+        }
+
+        // Volver a aprobada reserva el reintento: otro reintento concurrente no la ejecuta
+        proposal.status = ProposalStatus::Passed;
+        if !self.repository.update_proposal_outcome(&proposal, ProposalStatus::ExecutionFailed).await? {
+            bail!("La ejecución de la propuesta ya se está reintentando");
+        }
+
+        self.execute(&mut proposal).await?;
+
+        Ok(proposal)
+    }
+
+    /// Obtener una propuesta por ID
+    pub async fn get_proposal(&self, proposal_id: &ProposalId) -> Result<Option<Proposal>> {
+        self.repository.get_proposal(proposal_id).await
+    }
+
+    /// Listar propuestas en un estado
+    pub async fn get_proposals_by_status(&self, status: ProposalStatus) -> Result<Vec<Proposal>> {
+        let mut proposals = Vec::new();
+
+        for proposal_id in self.repository.get_proposals_by_status(status).await? {
+            if let Some(proposal) = self.repository.get_proposal(&proposal_id).await? {
+                proposals.push(proposal);
+            }
+        }
+
+        Ok(proposals)
+    }
+
+    /// Obtener los votos emitidos sobre una propuesta
+    pub async fn get_votes(&self, proposal_id: &ProposalId) -> Result<Vec<Vote>> {
+        self.repository.get_votes(proposal_id).await
+    }
+
+    fn strategy(&self, rules: &VotingRules) -> Result<&Arc<dyn VotingStrategy>> {
+        self.strategies
+            .get(&rules.strategy)
+            .ok_or_else(|| anyhow!("Estrategia de votación no registrada: {}", rules.strategy.as_str()))
+    }
+
+    /// Contexto de recuento: las solicitudes de fondos se miden contra el saldo actual del fondo
+    async fn tally_context(&self, proposal: &Proposal, now: DateTime<Utc>) -> Result<TallyContext> {
+        let context = TallyContext::at(now);
+
+        match (&proposal.payload, &self.fund) {
+            (ProposalPayload::FundLearningInitiative { .. }, Some(fund)) => {
+                Ok(context.with_fund_balance(fund.balance().await?))
+            }
+            _ => Ok(context),
+        }
+    }
+
+    async fn load(&self, proposal_id: &ProposalId) -> Result<Proposal> {
+        self.repository
+            .get_proposal(proposal_id)
+            .await?
+            .ok_or_else(|| anyhow!("Propuesta no encontrada: {:?}", proposal_id))
+    }
+
+    /// Aplicar una propuesta aprobada en el módulo destino y registrar el resultado
+    ///
+    /// Quien llama ya pasó la propuesta a `Passed` con una actualización condicional,
+    /// así que sólo una llamada llega a ejecutarla.
+    async fn execute(&self, proposal: &mut Proposal) -> Result<()> {
+        let result = match self.executors.get(&proposal.payload.kind()) {
+            Some(executor) => executor.execute(proposal).await,
+            None => Err(anyhow!("No hay ejecutor registrado para {}", proposal.payload.kind())),
+        };
+
+        match result {
+            Ok(()) => {
+                proposal.status = ProposalStatus::Executed;
+                proposal.executed_at = Some(Utc::now());
+                proposal.execution_error = None;
+
+                // TODO: Emitir evento de dominio
+                // self.emit_event(GovernanceEvent::ProposalExecuted { ... }).await?;
+            }
+            Err(e) => {
+                tracing::error!("Error ejecutando la propuesta {:?}: {}", proposal.id, e);
+                proposal.status = ProposalStatus::ExecutionFailed;
+                proposal.execution_error = Some(e.to_string());
+
+                // TODO: Emitir evento de dominio
+                // self.emit_event(GovernanceEvent::ProposalExecutionFailed { ... }).await?;
+            }
+        }
+
+        if !self.repository.update_proposal_outcome(proposal, ProposalStatus::Passed).await? {
+            bail!("La propuesta {:?} ya no estaba pendiente de ejecución", proposal.id);
+        }
+
+        Ok(())
+    }
+
+    /// Mejor reputación del votante entre sus roles, en [0, 1]
+    async fn reputation_weight(&self, voter: &str) -> Result<f64> {
+        let mut best: f64 = 0.0;
+
+        for role in [RatedRole::Tutor, RatedRole::Learner] {
+            if let Some(score) = self.reputation.get_reputation(voter, role).await? {
+                best = best.max(score.score / 100.0);
+            }
+        }
+
+        Ok(best)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// Fondo en memoria que anota los desembolsos solicitados
+    #[derive(Default)]
+    struct Fund {
+        disbursed: Mutex<Vec<(ProposalId, String, u64)>>,
+    }
+
+    #[async_trait]
+    impl CommunityFund for Fund {
+        async fn balance(&self) -> Result<u64> {
+            Ok(1_000)
+        }
+
+        async fn disburse(&self, proposal_id: &ProposalId, recipient: &str, amount: u64) -> Result<()> {
+            self.disbursed.lock().unwrap().push((proposal_id.clone(), recipient.to_string(), amount));
+            Ok(())
+        }
+    }
+
+    fn proposal(payload: ProposalPayload) -> Proposal {
+        let now = Utc::now();
+        Proposal {
+            id: ProposalId::new(),
+            proposer: "proposer".to_string(),
+            title: "Propuesta".to_string(),
+            description: String::new(),
+            rules: VotingRules::default_for(payload.kind()),
+            payload,
+            status: ProposalStatus::Passed,
+            voting_starts_at: now,
+            voting_ends_at: now,
+            tally: None,
+            executed_at: None,
+            execution_error: None,
+            created_at: now,
+        }
+    }
+
+    #[tokio::test]
+    async fn learning_initiative_executor_disburses_the_requested_amount() {
+        let fund = Arc::new(Fund::default());
+        let executor = LearningInitiativeExecutor::new(fund.clone());
+        let funded = proposal(ProposalPayload::FundLearningInitiative {
+            recipient: "initiative".to_string(),
+            requested_amount: 400,
+        });
+
+        executor.execute(&funded).await.unwrap();
+
+        let disbursed = fund.disbursed.lock().unwrap().clone();
+        assert_eq!(disbursed, vec![(funded.id.clone(), "initiative".to_string(), 400)]);
+    }
+
+    #[tokio::test]
+    async fn learning_initiative_executor_rejects_other_proposals() {
+        let fund = Arc::new(Fund::default());
+        let executor = LearningInitiativeExecutor::new(fund.clone());
+        let space = proposal(ProposalPayload::ApproveLearningSpace { space_id: uuid::Uuid::nil() });
+
+        assert!(executor.execute(&space).await.is_err());
+        assert!(fund.disbursed.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,13 @@
+-- Registro de Proof-of-Humanity
+-- Solo se guardan el compromiso, el nullifier y la clave pública de firma; nunca la clave secreta
+
+CREATE TABLE humanity_registry (
+    id UUID PRIMARY KEY,
+    user_address TEXT NOT NULL UNIQUE,
+    commitment TEXT NOT NULL UNIQUE,
+    nullifier TEXT NOT NULL UNIQUE,
+    verifying_key TEXT NOT NULL UNIQUE,
+    proof_backend TEXT NOT NULL,
+    verified_at TIMESTAMPTZ NOT NULL,
+    revoked_at TIMESTAMPTZ
+);
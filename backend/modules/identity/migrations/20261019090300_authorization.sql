@@ -0,0 +1,28 @@
+-- Roles, pertenencia a instituciones y auditoría de las decisiones de autorización
+
+CREATE TABLE user_roles (
+    user_address TEXT NOT NULL,
+    role TEXT NOT NULL,
+    PRIMARY KEY (user_address, role)
+);
+
+CREATE TABLE institution_memberships (
+    user_address TEXT NOT NULL,
+    institution_id TEXT NOT NULL,
+    role TEXT NOT NULL,
+    group_name TEXT
+);
+
+CREATE INDEX institution_memberships_user_address_idx ON institution_memberships (user_address);
+
+CREATE TABLE authorization_audit_log (
+    id UUID PRIMARY KEY,
+    principal TEXT,
+    action TEXT NOT NULL,
+    resource_kind TEXT NOT NULL,
+    resource_id TEXT,
+    allowed BOOLEAN NOT NULL,
+    rule TEXT,
+    reason TEXT NOT NULL,
+    timestamp TIMESTAMPTZ NOT NULL
+);
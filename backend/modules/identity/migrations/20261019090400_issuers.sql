@@ -0,0 +1,22 @@
+-- Emisores institucionales y sus claves de firma
+-- Las claves rotan: cada una tiene su periodo de validez y puede revocarse
+
+CREATE TABLE issuers (
+    id UUID PRIMARY KEY,
+    name TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    home_page TEXT NOT NULL,
+    institution_id TEXT,
+    created_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE TABLE issuer_keys (
+    key_id TEXT PRIMARY KEY,
+    issuer_id UUID NOT NULL REFERENCES issuers(id),
+    public_key TEXT NOT NULL,
+    valid_from TIMESTAMPTZ NOT NULL,
+    valid_until TIMESTAMPTZ,
+    revoked_at TIMESTAMPTZ
+);
+
+CREATE INDEX issuer_keys_issuer_id_idx ON issuer_keys (issuer_id);
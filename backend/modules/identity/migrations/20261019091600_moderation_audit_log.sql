@@ -0,0 +1,14 @@
+-- Auditoría de las acciones de moderación
+-- `succeeded` queda a NULL mientras la acción está en curso; se completa al terminar
+
+CREATE TABLE moderation_audit_log (
+    id UUID PRIMARY KEY,
+    moderator TEXT NOT NULL,
+    action TEXT NOT NULL,
+    resource_kind TEXT NOT NULL,
+    resource_id TEXT NOT NULL,
+    reason TEXT,
+    succeeded BOOLEAN,
+    error TEXT,
+    timestamp TIMESTAMPTZ NOT NULL
+);
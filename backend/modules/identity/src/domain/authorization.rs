@@ -0,0 +1,270 @@
+// Entidades de dominio para autorización basada en roles y atributos (RBAC/ABAC)
+
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Roles de Keiko
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Role {
+    Learner,
+    Tutor,
+    Institution,
+    Moderator,
+    Admin,
+}
+
+impl Role {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Learner => "learner",
+            Role::Tutor => "tutor",
+            Role::Institution => "institution",
+            Role::Moderator => "moderator",
+            Role::Admin => "admin",
+        }
+    }
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Role {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "learner" => Ok(Role::Learner),
+            "tutor" => Ok(Role::Tutor),
+            "institution" => Ok(Role::Institution),
+            "moderator" => Ok(Role::Moderator),
+            "admin" => Ok(Role::Admin),
+            other => Err(anyhow!("Rol desconocido: {}", other)),
+        }
+    }
+}
+
+/// Pertenencia de un usuario a una institución, opcionalmente dentro de un grupo
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InstitutionMembership {
+    pub institution_id: String,
+    pub role: Role,              // Rol dentro de la institución
+    pub group: Option<String>,   // Grupo o cohorte
+}
+
+/// Usuario autenticado sobre el que se toman decisiones de autorización
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Principal {
+    pub user_address: String,
+    pub roles: Vec<Role>,                          // Roles globales
+    pub memberships: Vec<InstitutionMembership>,
+}
+
+impl Principal {
+    pub fn has_role(&self, role: Role) -> bool {
+        self.roles.contains(&role)
+    }
+
+    /// Comprobar si el usuario tiene un rol dentro de un grupo de una institución
+    pub fn has_group_role(&self, institution_id: &str, group: &str, role: Role) -> bool {
+        self.memberships
+            .iter()
+            .any(|m| m.role == role && m.group.as_deref() == Some(group) && m.institution_id == institution_id)
+    }
+
+    /// Comprobar si el usuario tiene un rol dentro de una institución
+    pub fn has_institution_role(&self, institution_id: &str, role: Role) -> bool {
+        self.memberships.iter().any(|m| m.role == role && m.institution_id == institution_id)
+    }
+}
+
+/// Acciones sobre recursos de Keiko
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Action {
+    Read,
+    Create,
+    Update,
+    Delete,
+    Moderate,
+    Administer,
+}
+
+impl Action {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Action::Read => "read",
+            Action::Create => "create",
+            Action::Update => "update",
+            Action::Delete => "delete",
+            Action::Moderate => "moderate",
+            Action::Administer => "administer",
+        }
+    }
+}
+
+/// Tipos de recursos protegidos
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ResourceKind {
+    UserProfile,
+    Passport,
+    LearningInteraction,
+    TutoringSession,
+    LearningSpace,
+    Institution,
+    AdminPanel,
+    TutorProfile,
+    Dispute,
+}
+
+impl ResourceKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ResourceKind::UserProfile => "user_profile",
+            ResourceKind::Passport => "passport",
+            ResourceKind::LearningInteraction => "learning_interaction",
+            ResourceKind::TutoringSession => "tutoring_session",
+            ResourceKind::LearningSpace => "learning_space",
+            ResourceKind::Institution => "institution",
+            ResourceKind::AdminPanel => "admin_panel",
+            ResourceKind::TutorProfile => "tutor_profile",
+            ResourceKind::Dispute => "dispute",
+        }
+    }
+}
+
+impl FromStr for ResourceKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "user_profile" => Ok(ResourceKind::UserProfile),
+            "passport" => Ok(ResourceKind::Passport),
+            "learning_interaction" => Ok(ResourceKind::LearningInteraction),
+            "tutoring_session" => Ok(ResourceKind::TutoringSession),
+            "learning_space" => Ok(ResourceKind::LearningSpace),
+            "institution" => Ok(ResourceKind::Institution),
+            "admin_panel" => Ok(ResourceKind::AdminPanel),
+            "tutor_profile" => Ok(ResourceKind::TutorProfile),
+            "dispute" => Ok(ResourceKind::Dispute),
+            other => Err(anyhow!("Tipo de recurso desconocido: {}", other)),
+        }
+    }
+}
+
+/// Recurso protegido junto con los atributos usados por las políticas
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Resource {
+    pub kind: ResourceKind,
+    pub id: Option<String>,
+    pub owner: Option<String>,           // Dirección del titular (p.ej. dueño del pasaporte)
+    pub participants: Vec<String>,       // Otros usuarios implicados (p.ej. tutor de una sesión)
+    pub institution_id: Option<String>,
+    pub group: Option<String>,
+}
+
+impl Resource {
+    pub fn new(kind: ResourceKind) -> Self {
+        Self {
+            kind,
+            id: None,
+            owner: None,
+            participants: Vec::new(),
+            institution_id: None,
+            group: None,
+        }
+    }
+
+    /// Recurso perteneciente a un usuario
+    pub fn owned_by(kind: ResourceKind, owner: &str) -> Self {
+        Self {
+            owner: Some(owner.to_string()),
+            ..Self::new(kind)
+        }
+    }
+
+    pub fn with_id(mut self, id: &str) -> Self {
+        self.id = Some(id.to_string());
+        self
+    }
+
+    pub fn with_participant(mut self, user_address: &str) -> Self {
+        self.participants.push(user_address.to_string());
+        self
+    }
+
+    pub fn in_group(mut self, institution_id: Option<&str>, group: &str) -> Self {
+        self.institution_id = institution_id.map(str::to_string);
+        self.group = Some(group.to_string());
+        self
+    }
+
+    pub fn in_institution(mut self, institution_id: &str) -> Self {
+        self.institution_id = Some(institution_id.to_string());
+        self
+    }
+
+    /// Grupo o institución a través de los que se accede al recurso de otro usuario
+    pub fn scoped(self, institution_id: Option<&str>, group: Option<&str>) -> Self {
+        match (institution_id, group) {
+            (institution_id, Some(group)) => self.in_group(institution_id, group),
+            (Some(institution_id), None) => self.in_institution(institution_id),
+            (None, None) => self,
+        }
+    }
+}
+
+/// Solicitud de autorización evaluada por el motor de políticas
+#[derive(Debug, Clone)]
+pub struct AuthorizationRequest {
+    pub principal: Principal,
+    pub action: Action,
+    pub resource: Resource,
+    pub owner_memberships: Vec<InstitutionMembership>, // Pertenencias del titular del recurso
+}
+
+/// Decisión de autorización
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthorizationDecision {
+    pub allowed: bool,
+    pub rule: Option<String>,   // Regla que tomó la decisión
+    pub reason: String,
+}
+
+impl AuthorizationDecision {
+    pub fn allow(rule: &str, reason: impl Into<String>) -> Self {
+        Self {
+            allowed: true,
+            rule: Some(rule.to_string()),
+            reason: reason.into(),
+        }
+    }
+
+    pub fn deny(reason: impl Into<String>) -> Self {
+        Self {
+            allowed: false,
+            rule: None,
+            reason: reason.into(),
+        }
+    }
+}
+
+/// Entrada del registro de auditoría de decisiones de autorización
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthorizationAuditEntry {
+    pub id: Uuid,
+    pub principal: Option<String>,   // None para peticiones anónimas
+    pub action: Action,
+    pub resource_kind: ResourceKind,
+    pub resource_id: Option<String>,
+    pub allowed: bool,
+    pub rule: Option<String>,
+    pub reason: String,
+    pub timestamp: DateTime<Utc>,
+}
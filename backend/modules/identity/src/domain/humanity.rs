@@ -0,0 +1,255 @@
+// Entidades de dominio para Proof-of-Humanity
+// humanity_proof_key = sha256(iris_hash || genome_hash || salt), verificada con una prueba STARK
+// La clave Ed25519 del usuario se deriva en su cartera; el registro sólo guarda la clave pública
+
+use anyhow::{anyhow, bail, Result};
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use hkdf::Hkdf;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+/// Contexto HKDF de la clave de firma de humanidad
+pub const HUMANITY_KEY_INFO: &[u8] = b"keiko/humanity-signing-key/v1";
+
+/// Longitud mínima del secreto de la cartera del que se deriva la clave
+pub const MIN_WALLET_SECRET_LEN: usize = 32;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HumanityRecordId(pub Uuid);
+
+impl HumanityRecordId {
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+/// Compromiso de humanidad (humanity_proof_key) en hexadecimal
+///
+/// Es público: identifica a la persona en el registro anti-Sybil, pero no es una clave.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct HumanityCommitment(pub String);
+
+impl HumanityCommitment {
+    /// Calcular la clave compuesta sha256(iris_hash || genome_hash || salt)
+    pub fn compute(iris_hash: &[u8], genome_hash: &[u8], salt: &[u8]) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(iris_hash);
+        hasher.update(genome_hash);
+        hasher.update(salt);
+        Self(hex::encode(hasher.finalize()))
+    }
+
+    /// Derivar en la cartera la clave Ed25519 con la que el usuario firma
+    ///
+    /// HKDF-SHA256 sobre el secreto de la cartera, con el compromiso como sal. El secreto
+    /// nunca sale de la cartera: sin él, conocer el compromiso no permite firmar.
+    pub fn derive_signing_key(&self, wallet_secret: &[u8]) -> Result<SigningKey> {
+        if wallet_secret.len() < MIN_WALLET_SECRET_LEN {
+            bail!("El secreto de la cartera debe tener al menos {} bytes", MIN_WALLET_SECRET_LEN);
+        }
+
+        let salt = hex::decode(&self.0)?;
+        let mut seed = [0u8; 32];
+        Hkdf::<Sha256>::new(Some(&salt), wallet_secret)
+            .expand(HUMANITY_KEY_INFO, &mut seed)
+            .map_err(|_| anyhow!("No se pudo derivar la clave de humanidad"))?;
+        Ok(SigningKey::from_bytes(&seed))
+    }
+
+    /// Clave pública con la que se firmaron las interacciones heredadas
+    ///
+    /// Antes de derivar la clave en la cartera, el propio compromiso era la semilla Ed25519.
+    /// Como esa semilla es pública, sólo sirve para verificar registros históricos.
+    pub fn legacy_verifying_key(&self) -> Result<VerifyingKey> {
+        let seed: [u8; 32] = hex::decode(&self.0)?
+            .get(..32)
+            .and_then(|seed| seed.try_into().ok())
+            .ok_or_else(|| anyhow!("El compromiso debe tener al menos 32 bytes"))?;
+        Ok(SigningKey::from_bytes(&seed).verifying_key())
+    }
+}
+
+/// Interpretar una clave pública Ed25519 en hexadecimal
+pub fn parse_verifying_key(public_key: &str) -> Result<VerifyingKey> {
+    let key_bytes: [u8; 32] = hex::decode(public_key)?
+        .try_into()
+        .map_err(|_| anyhow!("La clave pública debe tener 32 bytes"))?;
+    Ok(VerifyingKey::from_bytes(&key_bytes)?)
+}
+
+/// Verificar una firma Ed25519 en hexadecimal; una firma malformada no es válida
+pub fn verify_signature(verifying_key: &VerifyingKey, message: &[u8], signature: &str) -> bool {
+    let Ok(signature_bytes) = hex::decode(signature) else {
+        return false;
+    };
+    let Ok(signature_bytes): Result<[u8; 64], _> = signature_bytes.try_into() else {
+        return false;
+    };
+
+    verifying_key.verify(message, &Signature::from_bytes(&signature_bytes)).is_ok()
+}
+
+/// Nullifier anti-Sybil sha256(iris_hash || genome_hash)
+///
+/// No depende del salt, por lo que una misma persona siempre produce el mismo
+/// nullifier aunque genere varias humanity_proof_key distintas.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct HumanityNullifier(pub String);
+
+impl HumanityNullifier {
+    pub fn compute(iris_hash: &[u8], genome_hash: &[u8]) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(iris_hash);
+        hasher.update(genome_hash);
+        Self(hex::encode(hasher.finalize()))
+    }
+}
+
+/// Prueba de humanidad presentada por un usuario
+///
+/// La prueba STARK demuestra que `commitment` y `nullifier` se derivan de los
+/// mismos datos biométricos sin revelarlos. `key_signature` demuestra que quien
+/// registra la clave pública posee la clave privada correspondiente.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HumanityProof {
+    pub commitment: HumanityCommitment,
+    pub nullifier: HumanityNullifier,
+    pub stark_proof: String,   // Prueba STARK serializada en hexadecimal
+    pub verifying_key: String, // Clave pública Ed25519 derivada en la cartera
+    pub key_signature: String, // Firma de `registration_payload` con esa clave
+}
+
+impl HumanityProof {
+    /// Construir la prueba en la cartera, firmando el registro con la clave derivada
+    pub fn new(
+        user_address: &str,
+        commitment: HumanityCommitment,
+        nullifier: HumanityNullifier,
+        stark_proof: String,
+        signing_key: &SigningKey,
+    ) -> Self {
+        let mut proof = Self {
+            commitment,
+            nullifier,
+            stark_proof,
+            verifying_key: hex::encode(signing_key.verifying_key().to_bytes()),
+            key_signature: String::new(),
+        };
+        proof.key_signature = hex::encode(signing_key.sign(&proof.registration_payload(user_address)).to_bytes());
+        proof
+    }
+
+    /// Mensaje que firma la cartera: vincula la clave con el usuario y el compromiso
+    pub fn registration_payload(&self, user_address: &str) -> Vec<u8> {
+        format!(
+            "keiko-humanity-registration|{}|{}|{}|{}",
+            user_address, self.commitment.0, self.nullifier.0, self.verifying_key
+        )
+        .into_bytes()
+    }
+
+    /// Comprobar que `key_signature` es de la clave registrada para este usuario
+    pub fn proves_key_possession(&self, user_address: &str) -> Result<bool> {
+        let verifying_key = parse_verifying_key(&self.verifying_key)?;
+        Ok(verify_signature(&verifying_key, &self.registration_payload(user_address), &self.key_signature))
+    }
+}
+
+/// Compromiso de humanidad verificado y registrado
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HumanityRecord {
+    pub id: HumanityRecordId,
+    pub user_address: String,             // Dirección blockchain del usuario
+    pub commitment: HumanityCommitment,
+    pub nullifier: HumanityNullifier,
+    pub verifying_key: String,            // Clave pública Ed25519 de la cartera
+    pub proof_backend: String,            // Backend que verificó la prueba
+    pub verified_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl HumanityRecord {
+    /// Un registro revocado deja de acreditar humanidad
+    pub fn is_active(&self) -> bool {
+        self.revoked_at.is_none()
+    }
+}
+
+/// Eventos de dominio de Proof-of-Humanity
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum HumanityEvent {
+    HumanityVerified {
+        record_id: HumanityRecordId,
+        user_address: String,
+        timestamp: DateTime<Utc>,
+    },
+    SybilAttemptRejected {
+        user_address: String,
+        nullifier: HumanityNullifier,
+        timestamp: DateTime<Utc>,
+    },
+    HumanityRevoked {
+        record_id: HumanityRecordId,
+        timestamp: DateTime<Utc>,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WALLET_SECRET: [u8; 32] = [7; 32];
+
+    fn commitment() -> HumanityCommitment {
+        HumanityCommitment::compute(b"iris", b"genome", b"salt")
+    }
+
+    fn proof(user_address: &str, signing_key: &SigningKey) -> HumanityProof {
+        let nullifier = HumanityNullifier::compute(b"iris", b"genome");
+        HumanityProof::new(user_address, commitment(), nullifier, "stark".to_string(), signing_key)
+    }
+
+    #[test]
+    fn signing_key_comes_from_the_wallet_secret_not_the_commitment() {
+        let key = commitment().derive_signing_key(&WALLET_SECRET).unwrap();
+
+        assert_eq!(key.to_bytes(), commitment().derive_signing_key(&WALLET_SECRET).unwrap().to_bytes());
+        assert_ne!(key.to_bytes(), commitment().derive_signing_key(&[8; 32]).unwrap().to_bytes());
+        assert_ne!(key.to_bytes().to_vec(), hex::decode(&commitment().0).unwrap());
+    }
+
+    #[test]
+    fn short_wallet_secrets_are_rejected() {
+        assert!(commitment().derive_signing_key(&[7; 16]).is_err());
+    }
+
+    #[test]
+    fn registration_proves_key_possession_for_its_user_only() {
+        let key = commitment().derive_signing_key(&WALLET_SECRET).unwrap();
+        let proof = proof("0xabc", &key);
+
+        assert!(proof.proves_key_possession("0xabc").unwrap());
+        assert!(!proof.proves_key_possession("0xdef").unwrap());
+    }
+
+    #[test]
+    fn registering_someone_elses_public_key_fails() {
+        let key = commitment().derive_signing_key(&WALLET_SECRET).unwrap();
+        let victim = commitment().derive_signing_key(&[9; 32]).unwrap();
+        let mut proof = proof("0xabc", &key);
+        proof.verifying_key = hex::encode(victim.verifying_key().to_bytes());
+
+        assert!(!proof.proves_key_possession("0xabc").unwrap());
+    }
+
+    #[test]
+    fn malformed_keys_and_signatures_do_not_verify() {
+        let key = commitment().derive_signing_key(&WALLET_SECRET).unwrap();
+
+        assert!(parse_verifying_key("abcd").is_err());
+        assert!(!verify_signature(&key.verifying_key(), b"mensaje", "abcd"));
+        assert!(!verify_signature(&key.verifying_key(), b"mensaje", "no-hex"));
+    }
+}
@@ -0,0 +1,109 @@
+// Entidades de dominio para emisores institucionales (xAPI `authority`)
+// Universidades, instancias de Moodle y la propia plataforma contrafirman interacciones
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IssuerId(pub Uuid);
+
+impl IssuerId {
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+/// Tipo de emisor
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IssuerKind {
+    University,
+    Lms,        // Moodle u otro LMS que envía interacciones en nombre de los estudiantes
+    Platform,   // La propia plataforma Keiko (interacciones autodeclaradas)
+}
+
+impl IssuerKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IssuerKind::University => "university",
+            IssuerKind::Lms => "lms",
+            IssuerKind::Platform => "platform",
+        }
+    }
+}
+
+impl FromStr for IssuerKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "university" => Ok(IssuerKind::University),
+            "lms" => Ok(IssuerKind::Lms),
+            "platform" => Ok(IssuerKind::Platform),
+            other => Err(anyhow!("Tipo de emisor desconocido: {}", other)),
+        }
+    }
+}
+
+/// Identidad de un emisor registrado
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Issuer {
+    pub id: IssuerId,
+    pub name: String,
+    pub kind: IssuerKind,
+    pub home_page: String,                 // IRI usada como `authority.account.homePage` en xAPI
+    pub institution_id: Option<String>,    // Institución asociada para las políticas de autorización
+    pub created_at: DateTime<Utc>,
+}
+
+/// Clave pública de firma de un emisor
+///
+/// Las claves no se borran: rotar o revocar sólo acota su ventana de validez,
+/// de modo que las contrafirmas anteriores siguen siendo verificables.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssuerKey {
+    pub key_id: String,                     // Identificador estable de la clave (issuer_id#n)
+    pub issuer_id: IssuerId,
+    pub public_key: String,                 // Clave pública Ed25519 en hexadecimal
+    pub valid_from: DateTime<Utc>,
+    pub valid_until: Option<DateTime<Utc>>, // Fijado al rotar la clave
+    pub revoked_at: Option<DateTime<Utc>>,  // Momento desde el que la clave se considera comprometida
+}
+
+impl IssuerKey {
+    /// Comprobar si la clave podía firmar en un instante dado
+    pub fn was_valid_at(&self, at: DateTime<Utc>) -> bool {
+        at >= self.valid_from
+            && self.valid_until.map(|until| at < until).unwrap_or(true)
+            && self.revoked_at.map(|revoked| at < revoked).unwrap_or(true)
+    }
+
+    /// Comprobar si la clave puede usarse para nuevas firmas
+    pub fn is_current(&self) -> bool {
+        self.valid_until.is_none() && self.revoked_at.is_none()
+    }
+}
+
+/// Eventos de dominio de emisores
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum IssuerEvent {
+    IssuerRegistered {
+        issuer_id: IssuerId,
+        key_id: String,
+        timestamp: DateTime<Utc>,
+    },
+    IssuerKeyRotated {
+        issuer_id: IssuerId,
+        previous_key_id: String,
+        new_key_id: String,
+        timestamp: DateTime<Utc>,
+    },
+    IssuerKeyRevoked {
+        issuer_id: IssuerId,
+        key_id: String,
+        effective_from: DateTime<Utc>,
+        timestamp: DateTime<Utc>,
+    },
+}
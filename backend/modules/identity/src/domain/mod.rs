@@ -0,0 +1,13 @@
+// Entidades de dominio para el módulo identity
+// Gestiona la identidad de los usuarios, su verificación de humanidad, su autorización
+// los emisores institucionales que contrafirman interacciones y la auditoría de moderación
+
+pub mod authorization;
+pub mod humanity;
+pub mod issuer;
+pub mod moderation;
+
+pub use authorization::*;
+pub use humanity::*;
+pub use issuer::*;
+pub use moderation::*;
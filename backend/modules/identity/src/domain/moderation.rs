@@ -0,0 +1,56 @@
+// Auditoría de las acciones de moderación del panel de administración
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::ResourceKind;
+
+/// Acción de moderación ejecutada (o intentada) por un moderador
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModerationAuditEntry {
+    pub id: Uuid,
+    pub moderator: String,
+    pub action: String,              // Acción del panel (ej: "approve_tutor", "resolve_dispute")
+    pub resource_kind: ResourceKind,
+    pub resource_id: String,
+    pub reason: Option<String>,      // Motivo que indica el moderador
+    pub succeeded: Option<bool>,     // None mientras la acción está en curso
+    pub error: Option<String>,       // Motivo del fallo si el servicio rechazó la acción
+    pub timestamp: DateTime<Utc>,
+}
+
+impl ModerationAuditEntry {
+    pub fn new(
+        moderator: &str,
+        action: &str,
+        resource_kind: ResourceKind,
+        resource_id: &str,
+        reason: Option<String>,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            moderator: moderator.to_string(),
+            action: action.to_string(),
+            resource_kind,
+            resource_id: resource_id.to_string(),
+            reason,
+            succeeded: None,
+            error: None,
+            timestamp: Utc::now(),
+        }
+    }
+
+    /// Registrar que el servicio aplicó la acción
+    pub fn applied(mut self) -> Self {
+        self.succeeded = Some(true);
+        self
+    }
+
+    /// Registrar que el servicio rechazó la acción
+    pub fn failed(mut self, error: impl Into<String>) -> Self {
+        self.succeeded = Some(false);
+        self.error = Some(error.into());
+        self
+    }
+}
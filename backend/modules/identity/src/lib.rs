@@ -0,0 +1,28 @@
+use anyhow::Result;
+
+pub mod domain;
+pub mod repository;
+pub mod service;
+
+/// Inicializar el módulo identity
+pub async fn init() -> Result<()> {
+    tracing::info!("Inicializando módulo identity");
+    
+    // TODO: Implementar inicialización del módulo
+    // - Conexión a base de datos
+    // - Carga del backend de verificación de pruebas STARK
+    // - Registro de eventos de dominio de Proof-of-Humanity
+    
+    Ok(())
+}
+
+/// Cerrar el módulo identity
+pub async fn shutdown() -> Result<()> {
+    tracing::info!("Cerrando módulo identity");
+    
+    // TODO: Implementar limpieza del módulo
+    // - Cerrar conexiones
+    // - Finalizar streams
+    
+    Ok(())
+}
@@ -0,0 +1,133 @@
+// Persistencia de roles, pertenencias institucionales y auditoría de autorización
+
+use anyhow::Result;
+use sqlx::PgPool;
+
+use crate::domain::{AuthorizationAuditEntry, InstitutionMembership, Role};
+
+pub struct AuthorizationRepository {
+    pool: PgPool,
+}
+
+impl AuthorizationRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Obtener roles globales de un usuario
+    pub async fn get_roles(&self, user_address: &str) -> Result<Vec<Role>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT role
+            FROM user_roles
+            WHERE user_address = $1
+            "#,
+            user_address
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(|row| row.role.parse()).collect()
+    }
+
+    /// Asignar un rol global a un usuario
+    pub async fn assign_role(&self, user_address: &str, role: Role) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO user_roles (user_address, role)
+            VALUES ($1, $2)
+            ON CONFLICT DO NOTHING
+            "#,
+            user_address,
+            role.as_str()
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Retirar un rol global a un usuario
+    pub async fn revoke_role(&self, user_address: &str, role: Role) -> Result<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM user_roles
+            WHERE user_address = $1 AND role = $2
+            "#,
+            user_address,
+            role.as_str()
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Obtener pertenencias institucionales de un usuario
+    pub async fn get_memberships(&self, user_address: &str) -> Result<Vec<InstitutionMembership>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT institution_id, role, group_name
+            FROM institution_memberships
+            WHERE user_address = $1
+            "#,
+            user_address
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut memberships = Vec::new();
+
+        for row in rows {
+            memberships.push(InstitutionMembership {
+                institution_id: row.institution_id,
+                role: row.role.parse()?,
+                group: row.group_name,
+            });
+        }
+
+        Ok(memberships)
+    }
+
+    /// Agregar pertenencia institucional
+    pub async fn add_membership(&self, user_address: &str, membership: &InstitutionMembership) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO institution_memberships (user_address, institution_id, role, group_name)
+            VALUES ($1, $2, $3, $4)
+            "#,
+            user_address,
+            membership.institution_id,
+            membership.role.as_str(),
+            membership.group
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Registrar una decisión de autorización en el log de auditoría
+    pub async fn insert_audit_entry(&self, entry: &AuthorizationAuditEntry) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO authorization_audit_log (
+                id, principal, action, resource_kind, resource_id, allowed, rule, reason, timestamp
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            "#,
+            entry.id,
+            entry.principal,
+            entry.action.as_str(),
+            entry.resource_kind.as_str(),
+            entry.resource_id,
+            entry.allowed,
+            entry.rule,
+            entry.reason,
+            entry.timestamp
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,186 @@
+// Registro persistente de compromisos de humanidad verificados
+
+use anyhow::Result;
+use chrono::Utc;
+use sqlx::PgPool;
+
+use crate::domain::{
+    HumanityCommitment, HumanityNullifier, HumanityRecord, HumanityRecordId,
+};
+
+pub struct HumanityRegistryRepository {
+    pool: PgPool,
+}
+
+impl HumanityRegistryRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Registrar un compromiso de humanidad verificado
+    ///
+    /// Las restricciones UNIQUE sobre `commitment`, `nullifier`, `verifying_key` y `user_address`
+    /// respaldan en base de datos las comprobaciones anti-Sybil del servicio.
+    pub async fn insert_record(&self, record: &HumanityRecord) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO humanity_registry (
+                id, user_address, commitment, nullifier, verifying_key,
+                proof_backend, verified_at, revoked_at
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            "#,
+            record.id.0,
+            record.user_address,
+            record.commitment.0,
+            record.nullifier.0,
+            record.verifying_key,
+            record.proof_backend,
+            record.verified_at,
+            record.revoked_at
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Obtener registro por compromiso (humanity_proof_key)
+    pub async fn get_by_commitment(&self, commitment: &HumanityCommitment) -> Result<Option<HumanityRecord>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT id, user_address, commitment, nullifier, verifying_key,
+                   proof_backend, verified_at, revoked_at
+            FROM humanity_registry
+            WHERE commitment = $1
+            "#,
+            commitment.0
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|row| HumanityRecord {
+            id: HumanityRecordId(row.id),
+            user_address: row.user_address,
+            commitment: HumanityCommitment(row.commitment),
+            nullifier: HumanityNullifier(row.nullifier),
+            verifying_key: row.verifying_key,
+            proof_backend: row.proof_backend,
+            verified_at: row.verified_at,
+            revoked_at: row.revoked_at,
+        }))
+    }
+
+    /// Obtener registro por nullifier anti-Sybil
+    pub async fn get_by_nullifier(&self, nullifier: &HumanityNullifier) -> Result<Option<HumanityRecord>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT id, user_address, commitment, nullifier, verifying_key,
+                   proof_backend, verified_at, revoked_at
+            FROM humanity_registry
+            WHERE nullifier = $1
+            "#,
+            nullifier.0
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|row| HumanityRecord {
+            id: HumanityRecordId(row.id),
+            user_address: row.user_address,
+            commitment: HumanityCommitment(row.commitment),
+            nullifier: HumanityNullifier(row.nullifier),
+            verifying_key: row.verifying_key,
+            proof_backend: row.proof_backend,
+            verified_at: row.verified_at,
+            revoked_at: row.revoked_at,
+        }))
+    }
+
+    /// Obtener registro por clave pública Ed25519
+    pub async fn get_by_verifying_key(&self, verifying_key: &str) -> Result<Option<HumanityRecord>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT id, user_address, commitment, nullifier, verifying_key,
+                   proof_backend, verified_at, revoked_at
+            FROM humanity_registry
+            WHERE verifying_key = $1
+            "#,
+            verifying_key
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|row| HumanityRecord {
+            id: HumanityRecordId(row.id),
+            user_address: row.user_address,
+            commitment: HumanityCommitment(row.commitment),
+            nullifier: HumanityNullifier(row.nullifier),
+            verifying_key: row.verifying_key,
+            proof_backend: row.proof_backend,
+            verified_at: row.verified_at,
+            revoked_at: row.revoked_at,
+        }))
+    }
+
+    /// Obtener registro por dirección de usuario
+    pub async fn get_by_user_address(&self, user_address: &str) -> Result<Option<HumanityRecord>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT id, user_address, commitment, nullifier, verifying_key,
+                   proof_backend, verified_at, revoked_at
+            FROM humanity_registry
+            WHERE user_address = $1
+            "#,
+            user_address
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|row| HumanityRecord {
+            id: HumanityRecordId(row.id),
+            user_address: row.user_address,
+            commitment: HumanityCommitment(row.commitment),
+            nullifier: HumanityNullifier(row.nullifier),
+            verifying_key: row.verifying_key,
+            proof_backend: row.proof_backend,
+            verified_at: row.verified_at,
+            revoked_at: row.revoked_at,
+        }))
+    }
+
+    /// Elegir al azar usuarios con humanidad vigente, excluyendo las direcciones indicadas
+    pub async fn sample_active_users(&self, limit: i64, exclude: &[String]) -> Result<Vec<String>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT user_address
+            FROM humanity_registry
+            WHERE revoked_at IS NULL AND user_address <> ALL($1)
+            ORDER BY random()
+            LIMIT $2
+            "#,
+            exclude,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(|row| row.user_address).collect())
+    }
+
+    /// Revocar un registro de humanidad
+    pub async fn revoke_record(&self, record_id: &HumanityRecordId) -> Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE humanity_registry
+            SET revoked_at = $1
+            WHERE id = $2
+            "#,
+            Utc::now(),
+            record_id.0
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
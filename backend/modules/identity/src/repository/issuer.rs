@@ -0,0 +1,167 @@
+// Persistencia de emisores institucionales y su historial de claves
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+use crate::domain::{Issuer, IssuerId, IssuerKey};
+
+pub struct IssuerRepository {
+    pool: PgPool,
+}
+
+impl IssuerRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Registrar un emisor
+    pub async fn create_issuer(&self, issuer: &Issuer) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO issuers (id, name, kind, home_page, institution_id, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+            issuer.id.0,
+            issuer.name,
+            issuer.kind.as_str(),
+            issuer.home_page,
+            issuer.institution_id,
+            issuer.created_at
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Obtener emisor por ID
+    pub async fn get_issuer(&self, issuer_id: &IssuerId) -> Result<Option<Issuer>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT id, name, kind, home_page, institution_id, created_at
+            FROM issuers
+            WHERE id = $1
+            "#,
+            issuer_id.0
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match row {
+            Some(row) => Ok(Some(Issuer {
+                id: IssuerId(row.id),
+                name: row.name,
+                kind: row.kind.parse()?,
+                home_page: row.home_page,
+                institution_id: row.institution_id,
+                created_at: row.created_at,
+            })),
+            None => Ok(None),
+        }
+    }
+
+    /// Agregar una clave al historial del emisor
+    pub async fn insert_key(&self, key: &IssuerKey) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO issuer_keys (key_id, issuer_id, public_key, valid_from, valid_until, revoked_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+            key.key_id,
+            key.issuer_id.0,
+            key.public_key,
+            key.valid_from,
+            key.valid_until,
+            key.revoked_at
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Obtener clave por su identificador
+    pub async fn get_key(&self, key_id: &str) -> Result<Option<IssuerKey>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT key_id, issuer_id, public_key, valid_from, valid_until, revoked_at
+            FROM issuer_keys
+            WHERE key_id = $1
+            "#,
+            key_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|row| IssuerKey {
+            key_id: row.key_id,
+            issuer_id: IssuerId(row.issuer_id),
+            public_key: row.public_key,
+            valid_from: row.valid_from,
+            valid_until: row.valid_until,
+            revoked_at: row.revoked_at,
+        }))
+    }
+
+    /// Obtener el historial completo de claves de un emisor
+    pub async fn get_keys_by_issuer(&self, issuer_id: &IssuerId) -> Result<Vec<IssuerKey>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT key_id, issuer_id, public_key, valid_from, valid_until, revoked_at
+            FROM issuer_keys
+            WHERE issuer_id = $1
+            ORDER BY valid_from ASC
+            "#,
+            issuer_id.0
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| IssuerKey {
+                key_id: row.key_id,
+                issuer_id: IssuerId(row.issuer_id),
+                public_key: row.public_key,
+                valid_from: row.valid_from,
+                valid_until: row.valid_until,
+                revoked_at: row.revoked_at,
+            })
+            .collect())
+    }
+
+    /// Cerrar la ventana de validez de una clave (rotación)
+    pub async fn close_key(&self, key_id: &str, valid_until: DateTime<Utc>) -> Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE issuer_keys
+            SET valid_until = $1
+            WHERE key_id = $2 AND valid_until IS NULL
+            "#,
+            valid_until,
+            key_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Revocar una clave desde un instante dado
+    pub async fn revoke_key(&self, key_id: &str, effective_from: DateTime<Utc>) -> Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE issuer_keys
+            SET revoked_at = $1
+            WHERE key_id = $2
+            "#,
+            effective_from,
+            key_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,11 @@
+// Repositorios para persistencia del módulo identity
+
+pub mod authorization;
+pub mod humanity;
+pub mod issuer;
+pub mod moderation;
+
+pub use authorization::AuthorizationRepository;
+pub use humanity::HumanityRegistryRepository;
+pub use issuer::IssuerRepository;
+pub use moderation::ModerationAuditRepository;
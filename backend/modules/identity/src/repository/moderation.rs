@@ -0,0 +1,93 @@
+// Persistencia del log de auditoría de moderación
+
+use anyhow::Result;
+use sqlx::PgPool;
+
+use crate::domain::ModerationAuditEntry;
+
+pub struct ModerationAuditRepository {
+    pool: PgPool,
+}
+
+impl ModerationAuditRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Registrar una acción de moderación antes de ejecutarla
+    pub async fn insert_entry(&self, entry: &ModerationAuditEntry) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO moderation_audit_log (
+                id, moderator, action, resource_kind, resource_id, reason, succeeded, error, timestamp
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            "#,
+            entry.id,
+            entry.moderator,
+            entry.action,
+            entry.resource_kind.as_str(),
+            entry.resource_id,
+            entry.reason,
+            entry.succeeded,
+            entry.error,
+            entry.timestamp
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Anotar el resultado de una acción registrada como pendiente
+    pub async fn complete_entry(&self, entry: &ModerationAuditEntry) -> Result<()> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE moderation_audit_log
+            SET succeeded = $2, error = $3
+            WHERE id = $1 AND succeeded IS NULL
+            "#,
+            entry.id,
+            entry.succeeded,
+            entry.error
+        )
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            anyhow::bail!("La acción {} no está pendiente en la auditoría", entry.id);
+        }
+
+        Ok(())
+    }
+
+    /// Últimas acciones registradas, de la más reciente a la más antigua
+    pub async fn get_recent_entries(&self, limit: i64) -> Result<Vec<ModerationAuditEntry>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT id, moderator, action, resource_kind, resource_id, reason, succeeded, error, timestamp
+            FROM moderation_audit_log
+            ORDER BY timestamp DESC
+            LIMIT $1
+            "#,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(ModerationAuditEntry {
+                    id: row.id,
+                    moderator: row.moderator,
+                    action: row.action,
+                    resource_kind: row.resource_kind.parse()?,
+                    resource_id: row.resource_id,
+                    reason: row.reason,
+                    succeeded: row.succeeded,
+                    error: row.error,
+                    timestamp: row.timestamp,
+                })
+            })
+            .collect()
+    }
+}
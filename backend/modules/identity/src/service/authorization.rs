@@ -0,0 +1,432 @@
+// Motor de políticas RBAC/ABAC y servicio de autorización con auditoría
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::domain::{
+    Action, AuthorizationAuditEntry, AuthorizationDecision, AuthorizationRequest,
+    InstitutionMembership, Principal, Resource, ResourceKind, Role,
+};
+use crate::repository::AuthorizationRepository;
+
+/// Regla del motor de políticas
+pub trait PolicyRule: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// Devolver una decisión si la regla aplica a la solicitud, o `None` para delegar en la siguiente
+    fn evaluate(&self, request: &AuthorizationRequest) -> Option<AuthorizationDecision>;
+}
+
+/// Motor de políticas: la primera regla que decide gana y, si ninguna decide, se deniega
+pub struct PolicyEngine {
+    rules: Vec<Box<dyn PolicyRule>>,
+}
+
+impl PolicyEngine {
+    pub fn new(rules: Vec<Box<dyn PolicyRule>>) -> Self {
+        Self { rules }
+    }
+
+    /// Agregar una regla al final de la cadena
+    pub fn with_rule(mut self, rule: Box<dyn PolicyRule>) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    pub fn evaluate(&self, request: &AuthorizationRequest) -> AuthorizationDecision {
+        self.rules
+            .iter()
+            .find_map(|rule| rule.evaluate(request))
+            .unwrap_or_else(|| {
+                AuthorizationDecision::deny(format!(
+                    "Ninguna política permite {} sobre {}",
+                    request.action.as_str(),
+                    request.resource.kind.as_str()
+                ))
+            })
+    }
+}
+
+impl Default for PolicyEngine {
+    fn default() -> Self {
+        Self::new(vec![
+            Box::new(AdminRule),
+            Box::new(OwnerRule),
+            Box::new(ParticipantRule),
+            Box::new(TutorGroupRule),
+            Box::new(InstitutionRule),
+            Box::new(ModeratorRule),
+        ])
+    }
+}
+
+/// Los administradores pueden realizar cualquier acción
+pub struct AdminRule;
+
+impl PolicyRule for AdminRule {
+    fn name(&self) -> &str {
+        "admin"
+    }
+
+    fn evaluate(&self, request: &AuthorizationRequest) -> Option<AuthorizationDecision> {
+        request
+            .principal
+            .has_role(Role::Admin)
+            .then(|| AuthorizationDecision::allow(self.name(), "Rol admin"))
+    }
+}
+
+/// El titular de un recurso personal puede leerlo, crearlo y actualizarlo
+pub struct OwnerRule;
+
+impl PolicyRule for OwnerRule {
+    fn name(&self) -> &str {
+        "owner"
+    }
+
+    fn evaluate(&self, request: &AuthorizationRequest) -> Option<AuthorizationDecision> {
+        let personal = matches!(
+            request.resource.kind,
+            ResourceKind::UserProfile
+                | ResourceKind::Passport
+                | ResourceKind::LearningInteraction
+                | ResourceKind::TutoringSession
+        );
+        let action_allowed = matches!(request.action, Action::Read | Action::Create | Action::Update);
+        let is_owner = request.resource.owner.as_deref() == Some(request.principal.user_address.as_str());
+
+        (personal && action_allowed && is_owner)
+            .then(|| AuthorizationDecision::allow(self.name(), "Titular del recurso"))
+    }
+}
+
+/// Los participantes de una sesión de tutoría existente (p.ej. el tutor) pueden consultarla y gestionarla.
+/// No concede la creación: al reservar, los participantes los indica quien llama
+pub struct ParticipantRule;
+
+impl PolicyRule for ParticipantRule {
+    fn name(&self) -> &str {
+        "participant"
+    }
+
+    fn evaluate(&self, request: &AuthorizationRequest) -> Option<AuthorizationDecision> {
+        let is_session = request.resource.kind == ResourceKind::TutoringSession;
+        let action_allowed = matches!(request.action, Action::Read | Action::Update);
+        let is_participant = request.resource.participants.contains(&request.principal.user_address);
+
+        (is_session && action_allowed && is_participant)
+            .then(|| AuthorizationDecision::allow(self.name(), "Participante de la sesión"))
+    }
+}
+
+/// Un tutor puede consultar pasaportes y agregar interacciones de sus estudiantes del mismo grupo
+pub struct TutorGroupRule;
+
+impl PolicyRule for TutorGroupRule {
+    fn name(&self) -> &str {
+        "tutor_group"
+    }
+
+    fn evaluate(&self, request: &AuthorizationRequest) -> Option<AuthorizationDecision> {
+        let applies = match request.resource.kind {
+            ResourceKind::LearningInteraction => matches!(request.action, Action::Read | Action::Create),
+            ResourceKind::Passport => request.action == Action::Read,
+            _ => false,
+        };
+        if !applies {
+            return None;
+        }
+
+        let group = request.resource.group.as_deref()?;
+
+        // Sin institución explícita se prueba cada institución donde el usuario es tutor del grupo;
+        // tutor y estudiante deben pertenecer al grupo en la misma institución
+        let institutions: Vec<&str> = match request.resource.institution_id.as_deref() {
+            Some(institution_id) => vec![institution_id],
+            None => request
+                .principal
+                .memberships
+                .iter()
+                .filter(|m| m.role == Role::Tutor && m.group.as_deref() == Some(group))
+                .map(|m| m.institution_id.as_str())
+                .collect(),
+        };
+
+        let institution_id = institutions.into_iter().find(|institution_id| {
+            request.principal.has_group_role(institution_id, group, Role::Tutor)
+                && is_group_learner(&request.owner_memberships, institution_id, group)
+        })?;
+
+        Some(AuthorizationDecision::allow(
+            self.name(),
+            format!("Tutor del grupo {} en {}", group, institution_id),
+        ))
+    }
+}
+
+fn is_group_learner(memberships: &[InstitutionMembership], institution_id: &str, group: &str) -> bool {
+    memberships
+        .iter()
+        .any(|m| m.role == Role::Learner && m.group.as_deref() == Some(group) && m.institution_id == institution_id)
+}
+
+/// Una institución puede consultar pasaportes y agregar interacciones de sus miembros
+pub struct InstitutionRule;
+
+impl PolicyRule for InstitutionRule {
+    fn name(&self) -> &str {
+        "institution"
+    }
+
+    fn evaluate(&self, request: &AuthorizationRequest) -> Option<AuthorizationDecision> {
+        let applies = match request.resource.kind {
+            ResourceKind::LearningInteraction => matches!(request.action, Action::Read | Action::Create),
+            ResourceKind::Passport => request.action == Action::Read,
+            _ => false,
+        };
+        if !applies {
+            return None;
+        }
+
+        let institution_id = request.resource.institution_id.as_deref()?;
+
+        let is_institution = request.principal.has_institution_role(institution_id, Role::Institution);
+        let owner_is_member = request
+            .owner_memberships
+            .iter()
+            .any(|m| m.institution_id == institution_id);
+
+        (is_institution && owner_is_member).then(|| {
+            AuthorizationDecision::allow(self.name(), format!("Institución {}", institution_id))
+        })
+    }
+}
+
+/// Los moderadores pueden leer y moderar, pero no administrar ni eliminar
+pub struct ModeratorRule;
+
+impl PolicyRule for ModeratorRule {
+    fn name(&self) -> &str {
+        "moderator"
+    }
+
+    fn evaluate(&self, request: &AuthorizationRequest) -> Option<AuthorizationDecision> {
+        let action_allowed = matches!(request.action, Action::Read | Action::Moderate);
+
+        (request.principal.has_role(Role::Moderator) && action_allowed)
+            .then(|| AuthorizationDecision::allow(self.name(), "Rol moderator"))
+    }
+}
+
+/// Autorización expuesta a los middlewares del API Gateway
+#[async_trait]
+pub trait Authorizer: Send + Sync {
+    /// Cargar roles y pertenencias de un usuario autenticado
+    async fn load_principal(&self, user_address: &str) -> Result<Principal>;
+
+    /// Decidir si un usuario (o una petición anónima) puede realizar una acción sobre un recurso
+    async fn authorize(
+        &self,
+        principal: Option<&Principal>,
+        action: Action,
+        resource: &Resource,
+    ) -> Result<AuthorizationDecision>;
+}
+
+pub struct AuthorizationService {
+    engine: PolicyEngine,
+    repository: AuthorizationRepository,
+}
+
+impl AuthorizationService {
+    pub fn new(engine: PolicyEngine, repository: AuthorizationRepository) -> Self {
+        Self { engine, repository }
+    }
+
+    /// Asignar un rol global a un usuario
+    pub async fn assign_role(&self, user_address: &str, role: Role) -> Result<()> {
+        self.repository.assign_role(user_address, role).await
+    }
+
+    /// Retirar un rol global a un usuario
+    pub async fn revoke_role(&self, user_address: &str, role: Role) -> Result<()> {
+        self.repository.revoke_role(user_address, role).await
+    }
+
+    /// Registrar a un usuario en una institución o grupo
+    pub async fn add_membership(&self, user_address: &str, membership: &InstitutionMembership) -> Result<()> {
+        self.repository.add_membership(user_address, membership).await
+    }
+}
+
+#[async_trait]
+impl Authorizer for AuthorizationService {
+    async fn load_principal(&self, user_address: &str) -> Result<Principal> {
+        Ok(Principal {
+            user_address: user_address.to_string(),
+            roles: self.repository.get_roles(user_address).await?,
+            memberships: self.repository.get_memberships(user_address).await?,
+        })
+    }
+
+    async fn authorize(
+        &self,
+        principal: Option<&Principal>,
+        action: Action,
+        resource: &Resource,
+    ) -> Result<AuthorizationDecision> {
+        let decision = match principal {
+            None => AuthorizationDecision::deny("Usuario no autenticado"),
+            Some(principal) => {
+                // Las reglas ABAC de grupo e institución necesitan las pertenencias del titular
+                let needs_owner_memberships = resource.group.is_some() || resource.institution_id.is_some();
+                let owner_memberships = match (&resource.owner, needs_owner_memberships) {
+                    (Some(owner), true) => self.repository.get_memberships(owner).await?,
+                    _ => Vec::new(),
+                };
+
+                self.engine.evaluate(&AuthorizationRequest {
+                    principal: principal.clone(),
+                    action,
+                    resource: resource.clone(),
+                    owner_memberships,
+                })
+            }
+        };
+
+        let entry = AuthorizationAuditEntry {
+            id: Uuid::new_v4(),
+            principal: principal.map(|p| p.user_address.clone()),
+            action,
+            resource_kind: resource.kind,
+            resource_id: resource.id.clone(),
+            allowed: decision.allowed,
+            rule: decision.rule.clone(),
+            reason: decision.reason.clone(),
+            timestamp: Utc::now(),
+        };
+
+        // Si no se puede auditar la decisión, la petición falla en lugar de continuar sin registro
+        self.repository.insert_audit_entry(&entry).await?;
+
+        if !decision.allowed {
+            tracing::info!(
+                "Autorización denegada: {:?} {} {} ({})",
+                entry.principal,
+                action.as_str(),
+                resource.kind.as_str(),
+                decision.reason
+            );
+        }
+
+        Ok(decision)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn principal(user_address: &str, roles: Vec<Role>, memberships: Vec<InstitutionMembership>) -> Principal {
+        Principal { user_address: user_address.to_string(), roles, memberships }
+    }
+
+    fn membership(role: Role, group: Option<&str>) -> InstitutionMembership {
+        InstitutionMembership { institution_id: "uni".to_string(), role, group: group.map(str::to_string) }
+    }
+
+    fn evaluate(principal: Principal, action: Action, resource: Resource, owner: Vec<InstitutionMembership>) -> AuthorizationDecision {
+        PolicyEngine::default().evaluate(&AuthorizationRequest {
+            principal,
+            action,
+            resource,
+            owner_memberships: owner,
+        })
+    }
+
+    #[test]
+    fn tutor_adds_interactions_only_for_students_in_their_group() {
+        let tutor = principal("0xtutor", vec![Role::Tutor], vec![membership(Role::Tutor, Some("1A"))]);
+        let interaction = |group| Resource::owned_by(ResourceKind::LearningInteraction, "0xstudent").in_group(Some("uni"), group);
+
+        let decision = evaluate(tutor.clone(), Action::Create, interaction("1A"), vec![membership(Role::Learner, Some("1A"))]);
+        assert!(decision.allowed);
+        assert_eq!(decision.rule.as_deref(), Some("tutor_group"));
+
+        assert!(!evaluate(tutor.clone(), Action::Create, interaction("1B"), vec![membership(Role::Learner, Some("1B"))]).allowed);
+        assert!(!evaluate(tutor.clone(), Action::Create, interaction("1A"), vec![membership(Role::Learner, Some("1B"))]).allowed);
+
+        let without_group = Resource::owned_by(ResourceKind::LearningInteraction, "0xstudent");
+        assert!(!evaluate(tutor, Action::Create, without_group, vec![membership(Role::Learner, Some("1A"))]).allowed);
+    }
+
+    #[test]
+    fn tutor_group_access_requires_the_same_institution() {
+        let tutor = principal("0xtutor", vec![Role::Tutor], vec![membership(Role::Tutor, Some("1A"))]);
+        let passport = Resource::owned_by(ResourceKind::Passport, "0xstudent").in_group(None, "1A");
+        let elsewhere = InstitutionMembership {
+            institution_id: "other-uni".to_string(),
+            role: Role::Learner,
+            group: Some("1A".to_string()),
+        };
+
+        assert!(!evaluate(tutor.clone(), Action::Read, passport.clone(), vec![elsewhere]).allowed);
+        assert!(evaluate(tutor, Action::Read, passport, vec![membership(Role::Learner, Some("1A"))]).allowed);
+    }
+
+    #[test]
+    fn naming_oneself_as_participant_does_not_allow_booking_for_someone_else() {
+        let tutor = principal("0xtutor", vec![Role::Tutor], Vec::new());
+        let booking = Resource::owned_by(ResourceKind::TutoringSession, "0xstudent").with_participant("0xtutor");
+
+        assert!(!evaluate(tutor.clone(), Action::Create, booking.clone(), Vec::new()).allowed);
+        assert!(evaluate(tutor, Action::Read, booking, Vec::new()).allowed);
+    }
+
+    #[test]
+    fn institution_reads_its_members_passports_but_cannot_update_them() {
+        let institution = principal("0xuni", vec![Role::Institution], vec![membership(Role::Institution, None)]);
+        let passport = Resource::owned_by(ResourceKind::Passport, "0xstudent").in_institution("uni");
+        let member = vec![membership(Role::Learner, None)];
+
+        let decision = evaluate(institution.clone(), Action::Read, passport.clone(), member.clone());
+        assert_eq!(decision.rule.as_deref(), Some("institution"));
+        assert!(!evaluate(institution.clone(), Action::Update, passport.clone(), member).allowed);
+        assert!(!evaluate(institution, Action::Read, passport, Vec::new()).allowed);
+    }
+
+    #[test]
+    fn owners_manage_their_own_records_only() {
+        let learner = principal("0xstudent", vec![Role::Learner], Vec::new());
+        let own = Resource::owned_by(ResourceKind::LearningInteraction, "0xstudent");
+        let other = Resource::owned_by(ResourceKind::LearningInteraction, "0xother");
+
+        assert!(evaluate(learner.clone(), Action::Create, own.clone(), Vec::new()).allowed);
+        assert!(!evaluate(learner.clone(), Action::Delete, own, Vec::new()).allowed);
+        assert!(!evaluate(learner, Action::Read, other, Vec::new()).allowed);
+    }
+
+    #[test]
+    fn moderators_moderate_but_do_not_administer() {
+        let moderator = principal("0xmod", vec![Role::Moderator], Vec::new());
+        let dispute = Resource::new(ResourceKind::Dispute);
+
+        assert!(evaluate(moderator.clone(), Action::Moderate, dispute.clone(), Vec::new()).allowed);
+        assert!(!evaluate(moderator.clone(), Action::Delete, dispute, Vec::new()).allowed);
+        assert!(!evaluate(moderator, Action::Administer, Resource::new(ResourceKind::AdminPanel), Vec::new()).allowed);
+
+        let admin = principal("0xadmin", vec![Role::Admin], Vec::new());
+        assert!(evaluate(admin, Action::Administer, Resource::new(ResourceKind::AdminPanel), Vec::new()).allowed);
+    }
+
+    #[test]
+    fn scoping_sets_group_or_institution() {
+        let resource = Resource::new(ResourceKind::Passport).scoped(Some("uni"), Some("1A"));
+        assert_eq!((resource.institution_id.as_deref(), resource.group.as_deref()), (Some("uni"), Some("1A")));
+
+        let resource = Resource::new(ResourceKind::Passport).scoped(Some("uni"), None);
+        assert_eq!((resource.institution_id.as_deref(), resource.group.as_deref()), (Some("uni"), None));
+    }
+}
@@ -0,0 +1,284 @@
+// Verificación de humanidad: backend de pruebas intercambiable y registro anti-Sybil
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use anyhow::{bail, Result};
+use async_trait::async_trait;
+use chrono::Utc;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+use crate::domain::{
+    HumanityCommitment, HumanityNullifier, HumanityProof, HumanityRecord, HumanityRecordId,
+};
+use crate::repository::HumanityRegistryRepository;
+
+/// Ruta por defecto de los fixtures PoH del gRPC Gateway (relativa a la raíz del repositorio)
+pub const DEFAULT_POH_FIXTURES_PATH: &str = "grpc-gateway/server/fixtures/poh/humanity_proof_samples.json";
+
+/// Backend de verificación de pruebas STARK de humanidad
+pub trait ProofVerifier: Send + Sync {
+    /// Nombre del backend, guardado junto a cada registro verificado
+    fn backend_name(&self) -> &str;
+
+    /// Verificar que la prueba vincula el compromiso y el nullifier con datos biométricos reales
+    fn verify_proof(&self, proof: &HumanityProof) -> Result<bool>;
+}
+
+/// Verificación de humanidad expuesta al resto de módulos
+#[async_trait]
+pub trait HumanityVerifier: Send + Sync {
+    /// Verificar una prueba de humanidad y registrar su compromiso si es único
+    async fn register_humanity(&self, user_address: &str, proof: &HumanityProof) -> Result<HumanityRecord>;
+
+    /// Comprobar si una clave pública pertenece a un humano verificado y no revocado
+    async fn is_verified_human(&self, verifying_key: &str) -> Result<bool>;
+
+    /// Obtener el registro de humanidad de un usuario
+    async fn get_humanity_record(&self, user_address: &str) -> Result<Option<HumanityRecord>>;
+
+    /// Comprobar si un usuario tiene una humanidad verificada y no revocada
+    async fn is_verified_address(&self, user_address: &str) -> Result<bool> {
+        Ok(self.get_humanity_record(user_address).await?.is_some_and(|record| record.is_active()))
+    }
+
+    /// Elegir al azar hasta `count` humanos verificados que no estén en `exclude`
+    async fn sample_verified_humans(&self, count: usize, exclude: &[String]) -> Result<Vec<String>>;
+}
+
+pub struct HumanityVerificationService {
+    proof_verifier: Box<dyn ProofVerifier>,
+    registry: HumanityRegistryRepository,
+}
+
+impl HumanityVerificationService {
+    pub fn new(proof_verifier: Box<dyn ProofVerifier>, registry: HumanityRegistryRepository) -> Self {
+        Self { proof_verifier, registry }
+    }
+
+    /// Revocar la humanidad verificada de un usuario
+    pub async fn revoke_humanity(&self, user_address: &str) -> Result<()> {
+        let Some(record) = self.registry.get_by_user_address(user_address).await? else {
+            bail!("El usuario {} no tiene humanidad verificada", user_address);
+        };
+
+        self.registry.revoke_record(&record.id).await?;
+
+        // TODO: Emitir evento de dominio
+        // self.emit_event(HumanityEvent::HumanityRevoked { ... }).await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl HumanityVerifier for HumanityVerificationService {
+    async fn register_humanity(&self, user_address: &str, proof: &HumanityProof) -> Result<HumanityRecord> {
+        if !self.proof_verifier.verify_proof(proof)? {
+            bail!("Prueba de humanidad inválida");
+        }
+
+        if !proof.proves_key_possession(user_address)? {
+            bail!("La firma del registro no corresponde a la clave pública presentada");
+        }
+
+        // Anti-Sybil: una persona, una identidad de humanidad
+        if self.registry.get_by_nullifier(&proof.nullifier).await?.is_some() {
+            tracing::warn!("Intento Sybil rechazado para {}", user_address);
+
+            // TODO: Emitir evento de dominio
+            // self.emit_event(HumanityEvent::SybilAttemptRejected { ... }).await?;
+
+            bail!("Esta persona ya tiene una identidad de humanidad registrada");
+        }
+
+        if self.registry.get_by_commitment(&proof.commitment).await?.is_some() {
+            bail!("La humanity_proof_key ya está registrada");
+        }
+
+        if self.registry.get_by_user_address(user_address).await?.is_some() {
+            bail!("El usuario {} ya tiene humanidad verificada", user_address);
+        }
+
+        if self.registry.get_by_verifying_key(&proof.verifying_key).await?.is_some() {
+            bail!("La clave pública ya está registrada");
+        }
+
+        let record = HumanityRecord {
+            id: HumanityRecordId::new(),
+            user_address: user_address.to_string(),
+            commitment: proof.commitment.clone(),
+            nullifier: proof.nullifier.clone(),
+            verifying_key: proof.verifying_key.clone(),
+            proof_backend: self.proof_verifier.backend_name().to_string(),
+            verified_at: Utc::now(),
+            revoked_at: None,
+        };
+
+        self.registry.insert_record(&record).await?;
+
+        // TODO: Emitir evento de dominio
+        // self.emit_event(HumanityEvent::HumanityVerified { ... }).await?;
+
+        Ok(record)
+    }
+
+    async fn is_verified_human(&self, verifying_key: &str) -> Result<bool> {
+        let record = self.registry.get_by_verifying_key(verifying_key).await?;
+
+        Ok(record.map(|r| r.is_active()).unwrap_or(false))
+    }
+
+    async fn get_humanity_record(&self, user_address: &str) -> Result<Option<HumanityRecord>> {
+        self.registry.get_by_user_address(user_address).await
+    }
+
+    async fn sample_verified_humans(&self, count: usize, exclude: &[String]) -> Result<Vec<String>> {
+        self.registry.sample_active_users(count as i64, exclude).await
+    }
+}
+
+/// Entrada del fichero de fixtures PoH
+#[derive(Debug, Clone, Deserialize)]
+struct PohFixture {
+    user_id: String,
+    iris_hash_hex: String,
+    genome_hash_hex: String,
+    salt_hex: String,
+    humanity_proof_key_hex: String,
+    nullifier_hex: String,
+    stark_proof_hex: String,
+}
+
+/// Verificador simulado respaldado por los fixtures PoH del gRPC Gateway
+///
+/// Sólo acepta pruebas cuyo compromiso figura en los fixtures y cuya prueba
+/// coincide con `mock_proof`. Pensado para desarrollo local y devnet.
+pub struct FixtureProofVerifier {
+    fixtures: HashMap<String, PohFixture>,
+}
+
+impl FixtureProofVerifier {
+    /// Cargar fixtures desde un fichero JSON
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let json = std::fs::read_to_string(path)?;
+        Self::from_json(&json)
+    }
+
+    /// Cargar fixtures desde una cadena JSON
+    pub fn from_json(json: &str) -> Result<Self> {
+        let entries: Vec<PohFixture> = serde_json::from_str(json)?;
+        let fixtures = entries
+            .into_iter()
+            .map(|f| (f.humanity_proof_key_hex.clone(), f))
+            .collect();
+
+        Ok(Self { fixtures })
+    }
+
+    /// Prueba simulada: sha256("keiko-poh-mock" || commitment || nullifier)
+    pub fn mock_proof(commitment: &HumanityCommitment, nullifier: &HumanityNullifier) -> Result<String> {
+        let mut hasher = Sha256::new();
+        hasher.update(b"keiko-poh-mock");
+        hasher.update(hex::decode(&commitment.0)?);
+        hasher.update(hex::decode(&nullifier.0)?);
+        Ok(hex::encode(hasher.finalize()))
+    }
+
+    /// Construir la prueba de un usuario de los fixtures (útil para sembrar la devnet)
+    ///
+    /// Los fixtures no incluyen carteras: la clave se deriva del secreto que se indique.
+    pub fn proof_for_user(&self, user_id: &str, user_address: &str, wallet_secret: &[u8]) -> Result<Option<HumanityProof>> {
+        let Some(fixture) = self.fixtures.values().find(|f| f.user_id == user_id) else {
+            return Ok(None);
+        };
+
+        let commitment = HumanityCommitment(fixture.humanity_proof_key_hex.clone());
+        let signing_key = commitment.derive_signing_key(wallet_secret)?;
+        Ok(Some(HumanityProof::new(
+            user_address,
+            commitment,
+            HumanityNullifier(fixture.nullifier_hex.clone()),
+            fixture.stark_proof_hex.clone(),
+            &signing_key,
+        )))
+    }
+}
+
+impl ProofVerifier for FixtureProofVerifier {
+    fn backend_name(&self) -> &str {
+        "fixture-mock"
+    }
+
+    fn verify_proof(&self, proof: &HumanityProof) -> Result<bool> {
+        let Some(fixture) = self.fixtures.get(&proof.commitment.0) else {
+            return Ok(false);
+        };
+
+        let iris_hash = hex::decode(&fixture.iris_hash_hex)?;
+        let genome_hash = hex::decode(&fixture.genome_hash_hex)?;
+        let salt = hex::decode(&fixture.salt_hex)?;
+
+        let commitment_valid = HumanityCommitment::compute(&iris_hash, &genome_hash, &salt) == proof.commitment;
+        let nullifier_valid = HumanityNullifier::compute(&iris_hash, &genome_hash) == proof.nullifier;
+        let proof_valid = Self::mock_proof(&proof.commitment, &proof.nullifier)? == proof.stark_proof;
+
+        Ok(commitment_valid && nullifier_valid && proof_valid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WALLET_SECRET: [u8; 32] = [7; 32];
+
+    fn verifier() -> FixtureProofVerifier {
+        let (iris, genome, salt) = ([1u8; 32], [2u8; 32], [3u8; 16]);
+        let commitment = HumanityCommitment::compute(&iris, &genome, &salt);
+        let nullifier = HumanityNullifier::compute(&iris, &genome);
+        let fixtures = serde_json::json!([{
+            "user_id": "user_001",
+            "iris_hash_hex": hex::encode(iris),
+            "genome_hash_hex": hex::encode(genome),
+            "salt_hex": hex::encode(salt),
+            "humanity_proof_key_hex": commitment.0,
+            "nullifier_hex": nullifier.0,
+            "stark_proof_hex": FixtureProofVerifier::mock_proof(&commitment, &nullifier).unwrap(),
+        }]);
+
+        FixtureProofVerifier::from_json(&fixtures.to_string()).unwrap()
+    }
+
+    fn fixture_proof(verifier: &FixtureProofVerifier) -> HumanityProof {
+        verifier.proof_for_user("user_001", "0xabc", &WALLET_SECRET).unwrap().unwrap()
+    }
+
+    #[test]
+    fn fixture_proofs_verify_and_prove_key_possession() {
+        let verifier = verifier();
+        let proof = fixture_proof(&verifier);
+
+        assert!(verifier.verify_proof(&proof).unwrap());
+        assert!(proof.proves_key_possession("0xabc").unwrap());
+        assert!(verifier.proof_for_user("user_999", "0xabc", &WALLET_SECRET).unwrap().is_none());
+    }
+
+    #[test]
+    fn tampered_proofs_are_rejected() {
+        let verifier = verifier();
+
+        let mut other_nullifier = fixture_proof(&verifier);
+        other_nullifier.nullifier = HumanityNullifier::compute(&[4; 32], &[2; 32]);
+        assert!(!verifier.verify_proof(&other_nullifier).unwrap());
+
+        let mut forged_stark = fixture_proof(&verifier);
+        forged_stark.stark_proof = "00".repeat(32);
+        assert!(!verifier.verify_proof(&forged_stark).unwrap());
+
+        let mut unknown_commitment = fixture_proof(&verifier);
+        unknown_commitment.commitment = HumanityCommitment::compute(&[1; 32], &[2; 32], &[5; 16]);
+        assert!(!verifier.verify_proof(&unknown_commitment).unwrap());
+    }
+}
@@ -0,0 +1,207 @@
+// Registro de emisores institucionales, rotación de claves y verificación de contrafirmas
+
+use anyhow::{anyhow, bail, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signer, SigningKey};
+use uuid::Uuid;
+
+use crate::domain::{parse_verifying_key, verify_signature, Issuer, IssuerId, IssuerKey, IssuerKind};
+use crate::repository::IssuerRepository;
+
+/// Verificación de contrafirmas de emisores expuesta al resto de módulos
+#[async_trait]
+pub trait IssuerVerifier: Send + Sync {
+    /// Verificar una contrafirma con la clave del emisor que estaba vigente en `signed_at`
+    async fn verify_countersignature(
+        &self,
+        issuer_id: &str,
+        key_id: &str,
+        message: &[u8],
+        signature: &str,
+        signed_at: DateTime<Utc>,
+    ) -> Result<bool>;
+
+    /// Comprobar que un emisor registrado actúa en nombre de una institución
+    async fn issuer_belongs_to(&self, issuer_id: &str, institution_id: &str) -> Result<bool>;
+}
+
+/// Clave privada con la que un emisor contrafirma interacciones (p.ej. la de la plataforma)
+pub struct IssuerSigner {
+    issuer_id: IssuerId,
+    key_id: String,
+    signing_key: SigningKey,
+}
+
+impl IssuerSigner {
+    pub fn new(issuer_id: IssuerId, key_id: &str, signing_key: SigningKey) -> Self {
+        Self {
+            issuer_id,
+            key_id: key_id.to_string(),
+            signing_key,
+        }
+    }
+
+    /// Construir el firmante a partir de la semilla Ed25519 en hexadecimal
+    pub fn from_hex(issuer_id: IssuerId, key_id: &str, secret_hex: &str) -> Result<Self> {
+        let seed: [u8; 32] = hex::decode(secret_hex)?
+            .try_into()
+            .map_err(|_| anyhow!("La clave del emisor debe tener 32 bytes"))?;
+        Ok(Self::new(issuer_id, key_id, SigningKey::from_bytes(&seed)))
+    }
+
+    pub fn issuer_id(&self) -> &IssuerId {
+        &self.issuer_id
+    }
+
+    pub fn key_id(&self) -> &str {
+        &self.key_id
+    }
+
+    /// Firmar un mensaje y devolver la firma en hexadecimal
+    pub fn sign(&self, message: &[u8]) -> String {
+        hex::encode(self.signing_key.sign(message).to_bytes())
+    }
+}
+
+pub struct IssuerService {
+    repository: IssuerRepository,
+}
+
+impl IssuerService {
+    pub fn new(repository: IssuerRepository) -> Self {
+        Self { repository }
+    }
+
+    /// Registrar un emisor junto con su primera clave pública
+    pub async fn register_issuer(
+        &self,
+        name: &str,
+        kind: IssuerKind,
+        home_page: &str,
+        institution_id: Option<&str>,
+        public_key: &str,
+    ) -> Result<(Issuer, IssuerKey)> {
+        parse_verifying_key(public_key)?;
+
+        let issuer = Issuer {
+            id: IssuerId::new(),
+            name: name.to_string(),
+            kind,
+            home_page: home_page.to_string(),
+            institution_id: institution_id.map(str::to_string),
+            created_at: Utc::now(),
+        };
+
+        let key = IssuerKey {
+            key_id: format!("{}#1", issuer.id.0),
+            issuer_id: issuer.id.clone(),
+            public_key: public_key.to_string(),
+            valid_from: issuer.created_at,
+            valid_until: None,
+            revoked_at: None,
+        };
+
+        self.repository.create_issuer(&issuer).await?;
+        self.repository.insert_key(&key).await?;
+
+        // TODO: Emitir evento de dominio
+        // self.emit_event(IssuerEvent::IssuerRegistered { ... }).await?;
+
+        Ok((issuer, key))
+    }
+
+    /// Obtener emisor por ID
+    pub async fn get_issuer(&self, issuer_id: &IssuerId) -> Result<Option<Issuer>> {
+        self.repository.get_issuer(issuer_id).await
+    }
+
+    /// Obtener el historial de claves de un emisor
+    pub async fn get_issuer_keys(&self, issuer_id: &IssuerId) -> Result<Vec<IssuerKey>> {
+        self.repository.get_keys_by_issuer(issuer_id).await
+    }
+
+    /// Rotar la clave vigente de un emisor
+    ///
+    /// La clave anterior deja de aceptar nuevas firmas pero sigue verificando
+    /// las contrafirmas realizadas antes de la rotación.
+    pub async fn rotate_key(&self, issuer_id: &IssuerId, new_public_key: &str) -> Result<IssuerKey> {
+        parse_verifying_key(new_public_key)?;
+
+        let keys = self.repository.get_keys_by_issuer(issuer_id).await?;
+        let Some(current) = keys.iter().find(|k| k.is_current()) else {
+            bail!("El emisor {} no tiene una clave vigente", issuer_id.0);
+        };
+
+        let now = Utc::now();
+        self.repository.close_key(&current.key_id, now).await?;
+
+        let new_key = IssuerKey {
+            key_id: format!("{}#{}", issuer_id.0, keys.len() + 1),
+            issuer_id: issuer_id.clone(),
+            public_key: new_public_key.to_string(),
+            valid_from: now,
+            valid_until: None,
+            revoked_at: None,
+        };
+
+        self.repository.insert_key(&new_key).await?;
+
+        // TODO: Emitir evento de dominio
+        // self.emit_event(IssuerEvent::IssuerKeyRotated { ... }).await?;
+
+        Ok(new_key)
+    }
+
+    /// Revocar una clave a partir del momento en que se considera comprometida
+    ///
+    /// Las contrafirmas anteriores a `effective_from` siguen siendo válidas.
+    pub async fn revoke_key(&self, key_id: &str, effective_from: DateTime<Utc>) -> Result<()> {
+        if self.repository.get_key(key_id).await?.is_none() {
+            bail!("Clave de emisor desconocida: {}", key_id);
+        }
+
+        self.repository.revoke_key(key_id, effective_from).await?;
+
+        // TODO: Emitir evento de dominio
+        // self.emit_event(IssuerEvent::IssuerKeyRevoked { ... }).await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl IssuerVerifier for IssuerService {
+    async fn verify_countersignature(
+        &self,
+        issuer_id: &str,
+        key_id: &str,
+        message: &[u8],
+        signature: &str,
+        signed_at: DateTime<Utc>,
+    ) -> Result<bool> {
+        let Some(key) = self.repository.get_key(key_id).await? else {
+            return Ok(false);
+        };
+
+        if key.issuer_id.0.to_string() != issuer_id || !key.was_valid_at(signed_at) {
+            return Ok(false);
+        }
+
+        // Una contrafirma malformada no es válida, pero no interrumpe la verificación
+        let verifying_key = parse_verifying_key(&key.public_key)?;
+        Ok(verify_signature(&verifying_key, message, signature))
+    }
+
+    async fn issuer_belongs_to(&self, issuer_id: &str, institution_id: &str) -> Result<bool> {
+        let Ok(issuer_id) = Uuid::parse_str(issuer_id) else {
+            return Ok(false);
+        };
+
+        Ok(self
+            .repository
+            .get_issuer(&IssuerId(issuer_id))
+            .await?
+            .is_some_and(|issuer| issuer.institution_id.as_deref() == Some(institution_id)))
+    }
+}
@@ -0,0 +1,13 @@
+// Servicios de aplicación para el módulo identity
+
+pub mod authorization;
+pub mod humanity;
+pub mod issuer;
+pub mod moderation;
+
+pub use authorization::{AuthorizationService, Authorizer, PolicyEngine, PolicyRule};
+pub use humanity::{
+    FixtureProofVerifier, HumanityVerificationService, HumanityVerifier, ProofVerifier,
+};
+pub use issuer::{IssuerService, IssuerSigner, IssuerVerifier};
+pub use moderation::ModerationAuditLog;
@@ -0,0 +1,42 @@
+// Log de auditoría de las acciones de moderación
+
+use anyhow::Result;
+
+use crate::domain::ModerationAuditEntry;
+use crate::repository::ModerationAuditRepository;
+
+pub struct ModerationAuditLog {
+    repository: ModerationAuditRepository,
+}
+
+impl ModerationAuditLog {
+    pub fn new(repository: ModerationAuditRepository) -> Self {
+        Self { repository }
+    }
+
+    /// Registrar una acción de moderación antes de ejecutarla, para que ninguna
+    /// acción aplicada quede fuera de la auditoría
+    pub async fn begin(&self, entry: &ModerationAuditEntry) -> Result<()> {
+        self.repository.insert_entry(entry).await
+    }
+
+    /// Anotar el resultado de una acción registrada con `begin`
+    pub async fn complete(&self, entry: &ModerationAuditEntry) -> Result<()> {
+        self.repository.complete_entry(entry).await?;
+
+        tracing::info!(
+            "Moderación: {} {} {} {} ({})",
+            entry.moderator,
+            entry.action,
+            entry.resource_kind.as_str(),
+            entry.resource_id,
+            if entry.succeeded == Some(true) { "aplicada" } else { "rechazada" }
+        );
+
+        Ok(())
+    }
+
+    pub async fn recent(&self, limit: i64) -> Result<Vec<ModerationAuditEntry>> {
+        self.repository.get_recent_entries(limit).await
+    }
+}
@@ -0,0 +1,27 @@
+-- Pasaportes de aprendizaje e interacciones, tal como los usaba el módulo antes de las migraciones
+-- IF NOT EXISTS: las bases de datos ya desplegadas tienen estas tablas
+
+CREATE TABLE IF NOT EXISTS learning_passports (
+    id UUID PRIMARY KEY,
+    user_address TEXT NOT NULL UNIQUE,
+    humanity_proof_key TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL,
+    blockchain_hash TEXT
+);
+
+CREATE TABLE IF NOT EXISTS learning_interactions (
+    id UUID PRIMARY KEY,
+    passport_id UUID NOT NULL REFERENCES learning_passports(id),
+    actor TEXT NOT NULL,
+    verb TEXT NOT NULL,
+    object TEXT NOT NULL,
+    result JSONB,
+    context JSONB,
+    timestamp TIMESTAMPTZ NOT NULL,
+    humanity_proof_key TEXT NOT NULL,
+    signature TEXT,
+    stored_in_blockchain BOOLEAN NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS learning_interactions_passport_id_idx ON learning_interactions (passport_id, timestamp);
@@ -0,0 +1,5 @@
+-- Los pasaportes y las interacciones guardan la clave pública de firma del usuario,
+-- no la clave de humanidad
+
+ALTER TABLE learning_passports RENAME COLUMN humanity_proof_key TO verifying_key;
+ALTER TABLE learning_interactions RENAME COLUMN humanity_proof_key TO verifying_key;
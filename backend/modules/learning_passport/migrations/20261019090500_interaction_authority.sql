@@ -0,0 +1,5 @@
+-- Contrafirma del emisor y versión del contenido firmado de cada interacción
+-- Las interacciones ya guardadas firmaron el formato anterior (versión 0)
+
+ALTER TABLE learning_interactions ADD COLUMN authority JSONB;
+ALTER TABLE learning_interactions ADD COLUMN payload_version SMALLINT NOT NULL DEFAULT 0;
@@ -0,0 +1,13 @@
+-- Vocabulario xAPI curado por gobernanza y tipo de actividad normalizado de cada interacción
+
+CREATE TABLE vocabulary_entries (
+    iri TEXT PRIMARY KEY,
+    kind TEXT NOT NULL,
+    aliases JSONB NOT NULL,
+    display_names JSONB NOT NULL,
+    description TEXT,
+    proposal_id UUID,
+    added_at TIMESTAMPTZ NOT NULL
+);
+
+ALTER TABLE learning_interactions ADD COLUMN activity_type TEXT;
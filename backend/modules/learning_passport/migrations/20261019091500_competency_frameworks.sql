@@ -0,0 +1,25 @@
+-- Marcos de competencias importados (ESCO/CASE) y su correspondencia con actividades
+-- Las interacciones autodeclaradas no cuentan como evidencia de competencia
+
+CREATE TABLE competency_frameworks (
+    id UUID PRIMARY KEY,
+    name TEXT NOT NULL,
+    source TEXT NOT NULL,
+    source_uri TEXT NOT NULL UNIQUE,
+    version TEXT,
+    language TEXT NOT NULL,
+    competencies JSONB NOT NULL,
+    imported_at TIMESTAMPTZ NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE TABLE competency_mappings (
+    framework_id UUID NOT NULL REFERENCES competency_frameworks(id),
+    activity_iri TEXT NOT NULL,
+    competency_uri TEXT NOT NULL,
+    weight DOUBLE PRECISION NOT NULL,
+    PRIMARY KEY (framework_id, activity_iri, competency_uri)
+);
+
+-- Las interacciones anteriores no se marcaron como autodeclaradas
+ALTER TABLE learning_interactions ADD COLUMN self_reported BOOLEAN NOT NULL DEFAULT FALSE;
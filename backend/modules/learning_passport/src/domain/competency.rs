@@ -0,0 +1,433 @@
+// Marcos de competencias (ESCO, CASE) y competencia demostrada a partir de las interacciones
+// Las actividades xAPI se relacionan con competencias mediante correspondencias ponderadas
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{LearningInteraction, LearningResult};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FrameworkId(pub Uuid);
+
+impl FrameworkId {
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+/// Origen de un marco de competencias
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FrameworkSource {
+    Esco,    // Clasificación europea de capacidades (API o volcado JSON de ESCO)
+    Case,    // Paquete CFPackage de 1EdTech CASE
+    Custom,  // Marco propio de la comunidad
+}
+
+impl FrameworkSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FrameworkSource::Esco => "esco",
+            FrameworkSource::Case => "case",
+            FrameworkSource::Custom => "custom",
+        }
+    }
+}
+
+impl fmt::Display for FrameworkSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for FrameworkSource {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "esco" => Ok(FrameworkSource::Esco),
+            "case" => Ok(FrameworkSource::Case),
+            "custom" => Ok(FrameworkSource::Custom),
+            other => Err(anyhow!("Origen de marco desconocido: {}", other)),
+        }
+    }
+}
+
+/// Competencia dentro de un marco
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Competency {
+    pub uri: String,                    // URI estable en el marco de origen
+    pub code: Option<String>,           // Código legible (ej: humanCodingScheme de CASE)
+    pub title: String,
+    pub description: Option<String>,
+    pub parent_uri: Option<String>,     // Competencia más amplia en el grafo
+}
+
+/// Marco de competencias importado
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompetencyFramework {
+    pub id: FrameworkId,
+    pub name: String,
+    pub source: FrameworkSource,
+    pub source_uri: String,             // URI del documento de origen; identifica reimportaciones
+    pub version: Option<String>,
+    pub language: String,
+    pub competencies: Vec<Competency>,
+    pub imported_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl CompetencyFramework {
+    pub fn competency(&self, uri: &str) -> Option<&Competency> {
+        self.competencies.iter().find(|c| c.uri == uri)
+    }
+
+    /// Validar el grafo: URIs únicas y padres existentes, sin ciclos
+    pub fn validate(&self) -> Result<()> {
+        if self.competencies.is_empty() {
+            bail!("El marco {} no contiene competencias", self.name);
+        }
+
+        let mut uris = HashSet::new();
+        for competency in &self.competencies {
+            if competency.title.trim().is_empty() {
+                bail!("La competencia {} necesita un título", competency.uri);
+            }
+            if !uris.insert(competency.uri.as_str()) {
+                bail!("Competencia repetida en el marco: {}", competency.uri);
+            }
+        }
+
+        for competency in &self.competencies {
+            if let Some(parent) = &competency.parent_uri {
+                if !uris.contains(parent.as_str()) {
+                    bail!("La competencia {} tiene un padre inexistente: {}", competency.uri, parent);
+                }
+            }
+            if self.ancestors(&competency.uri).contains(&competency.uri.as_str()) {
+                bail!("La jerarquía de competencias forma un ciclo en {}", competency.uri);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Competencias más amplias de una competencia, de la más cercana a la raíz
+    pub fn ancestors(&self, uri: &str) -> Vec<&str> {
+        let mut ancestors = Vec::new();
+        let mut seen = HashSet::new();
+        let mut current = self.competency(uri).and_then(|c| c.parent_uri.as_deref());
+
+        while let Some(parent) = current {
+            if !seen.insert(parent) {
+                break;
+            }
+            ancestors.push(parent);
+            current = self.competency(parent).and_then(|c| c.parent_uri.as_deref());
+        }
+
+        ancestors
+    }
+}
+
+/// Correspondencia entre una actividad xAPI y una competencia
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActivityMapping {
+    pub framework_id: FrameworkId,
+    pub activity_iri: String,
+    pub competency_uri: String,
+    pub weight: f64,                    // (0, 1]: cuánto evidencia la actividad la competencia
+}
+
+/// Valor de evidencia [0, 1] de un resultado; `None` si no evalúa nada
+pub fn evidence_value(result: &LearningResult) -> Option<f64> {
+    match (result.score, result.completion) {
+        (Some(score), _) if score.is_finite() => Some(score.clamp(0.0, 1.0)),
+        _ if result.success => Some(1.0),
+        (_, Some(completion)) if completion.is_finite() => Some(completion.clamp(0.0, 1.0)),
+        _ => None,
+    }
+}
+
+/// Interacción que evidencia una competencia
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompetencyEvidence {
+    pub interaction_id: Uuid,
+    pub activity_iri: String,
+    pub verb: String,
+    pub value: f64,
+    pub weight: f64,
+    pub via_competency_uri: String,     // Competencia mapeada (la misma o una más específica)
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Competencia demostrada en una competencia del marco
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompetencyProficiency {
+    pub competency_uri: String,
+    pub code: Option<String>,
+    pub title: String,
+    pub parent_uri: Option<String>,
+    pub level: f64,                     // Media ponderada del mejor valor por actividad evidenciada
+    pub coverage: f64,                  // Peso evidenciado sobre el peso de todas sus actividades
+    pub evidence: Vec<CompetencyEvidence>,
+}
+
+/// Perfil de competencias de un usuario en un marco
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompetencyProfile {
+    pub user_address: String,
+    pub framework_id: FrameworkId,
+    pub framework_name: String,
+    pub proficiencies: Vec<CompetencyProficiency>,
+    pub computed_at: DateTime<Utc>,
+}
+
+impl CompetencyProfile {
+    /// Calcular el perfil a partir de las interacciones del pasaporte
+    ///
+    /// La evidencia de una competencia se acumula también en las más amplias que la
+    /// contienen. Solo se incluyen competencias con alguna evidencia, y solo cuentan las
+    /// interacciones respaldadas por terceros: las autodeclaradas no demuestran nada.
+    pub fn compute(
+        user_address: &str,
+        framework: &CompetencyFramework,
+        mappings: &[ActivityMapping],
+        interactions: &[LearningInteraction],
+    ) -> Self {
+        // Peso total y evidencia por competencia, propagados hacia los ancestros
+        let mut total_weight: HashMap<&str, HashMap<&str, f64>> = HashMap::new();
+        let mut evidence: BTreeMap<&str, Vec<CompetencyEvidence>> = BTreeMap::new();
+
+        for mapping in mappings.iter().filter(|m| framework.competency(&m.competency_uri).is_some()) {
+            let targets: Vec<&str> = std::iter::once(mapping.competency_uri.as_str())
+                .chain(framework.ancestors(&mapping.competency_uri))
+                .collect();
+
+            for target in &targets {
+                let weights = total_weight.entry(target).or_default();
+                let weight = weights.entry(mapping.activity_iri.as_str()).or_insert(0.0);
+                *weight = weight.max(mapping.weight);
+            }
+
+            for interaction in interactions.iter().filter(|i| i.is_attested() && i.object == mapping.activity_iri) {
+                let Some(value) = interaction.result.as_ref().and_then(evidence_value) else {
+                    continue;
+                };
+                for target in &targets {
+                    evidence.entry(target).or_default().push(CompetencyEvidence {
+                        interaction_id: interaction.id.0,
+                        activity_iri: interaction.object.clone(),
+                        verb: interaction.verb.clone(),
+                        value,
+                        weight: mapping.weight,
+                        via_competency_uri: mapping.competency_uri.clone(),
+                        timestamp: interaction.timestamp,
+                    });
+                }
+            }
+        }
+
+        let mut proficiencies = Vec::new();
+
+        for competency in &framework.competencies {
+            let Some(mut trail) = evidence.remove(competency.uri.as_str()) else {
+                continue;
+            };
+            trail.sort_by_key(|e| std::cmp::Reverse(e.timestamp));
+
+            // Mejor valor por actividad, con el mayor peso con que se mapeó
+            let mut best: HashMap<&str, (f64, f64)> = HashMap::new();
+            for item in &trail {
+                let entry = best.entry(item.activity_iri.as_str()).or_insert((0.0, 0.0));
+                entry.0 = entry.0.max(item.value);
+                entry.1 = entry.1.max(item.weight);
+            }
+
+            let evidenced_weight: f64 = best.values().map(|(_, weight)| weight).sum();
+            let level = if evidenced_weight > 0.0 {
+                best.values().map(|(value, weight)| value * weight).sum::<f64>() / evidenced_weight
+            } else {
+                0.0
+            };
+            let mapped_weight: f64 = total_weight.get(competency.uri.as_str()).map(|w| w.values().sum()).unwrap_or(0.0);
+            let coverage = if mapped_weight > 0.0 { (evidenced_weight / mapped_weight).min(1.0) } else { 0.0 };
+
+            proficiencies.push(CompetencyProficiency {
+                competency_uri: competency.uri.clone(),
+                code: competency.code.clone(),
+                title: competency.title.clone(),
+                parent_uri: competency.parent_uri.clone(),
+                level,
+                coverage,
+                evidence: trail,
+            });
+        }
+
+        Self {
+            user_address: user_address.to_string(),
+            framework_id: framework.id.clone(),
+            framework_name: framework.name.clone(),
+            proficiencies,
+            computed_at: Utc::now(),
+        }
+    }
+
+    /// Alineaciones para incluir en credenciales exportadas (Open Badges 3.0 / CLR 2.0)
+    pub fn credential_alignments(&self, framework: &CompetencyFramework) -> serde_json::Value {
+        let alignments: Vec<serde_json::Value> = self
+            .proficiencies
+            .iter()
+            .map(|p| {
+                serde_json::json!({
+                    "type": ["Alignment"],
+                    "targetType": "ceterms:Competency",
+                    "targetName": p.title,
+                    "targetUrl": p.competency_uri,
+                    "targetCode": p.code,
+                    "targetFramework": framework.name,
+                    "targetFrameworkUrl": framework.source_uri,
+                    "https://keiko-dapp.xyz/xapi/extensions/proficiency": {
+                        "level": p.level,
+                        "coverage": p.coverage,
+                        "evidence": p.evidence.iter().map(|e| e.interaction_id).collect::<Vec<_>>(),
+                    },
+                })
+            })
+            .collect();
+
+        serde_json::Value::Array(alignments)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{InteractionAuthority, LearningInteractionId, LearningPassportId, CURRENT_PAYLOAD_VERSION};
+
+    const ROOT: &str = "https://example.org/competencies/programming";
+    const LOOPS: &str = "https://example.org/competencies/loops";
+    const RECURSION: &str = "https://example.org/competencies/recursion";
+
+    fn competency(uri: &str, parent_uri: Option<&str>) -> Competency {
+        Competency {
+            uri: uri.to_string(),
+            code: None,
+            title: uri.rsplit('/').next().unwrap_or(uri).to_string(),
+            description: None,
+            parent_uri: parent_uri.map(str::to_string),
+        }
+    }
+
+    fn framework() -> CompetencyFramework {
+        CompetencyFramework {
+            id: FrameworkId(Uuid::nil()),
+            name: "Programación".to_string(),
+            source: FrameworkSource::Custom,
+            source_uri: "https://example.org/frameworks/programming".to_string(),
+            version: None,
+            language: "es".to_string(),
+            competencies: vec![
+                competency(ROOT, None),
+                competency(LOOPS, Some(ROOT)),
+                competency(RECURSION, Some(ROOT)),
+            ],
+            imported_at: DateTime::<Utc>::UNIX_EPOCH,
+            updated_at: DateTime::<Utc>::UNIX_EPOCH,
+        }
+    }
+
+    fn mapping(activity_iri: &str, competency_uri: &str, weight: f64) -> ActivityMapping {
+        ActivityMapping {
+            framework_id: FrameworkId(Uuid::nil()),
+            activity_iri: activity_iri.to_string(),
+            competency_uri: competency_uri.to_string(),
+            weight,
+        }
+    }
+
+    fn interaction(object: &str, score: f64, self_reported: bool) -> LearningInteraction {
+        LearningInteraction {
+            id: LearningInteractionId::new(),
+            passport_id: LearningPassportId(Uuid::nil()),
+            actor: "0xabc".to_string(),
+            verb: "http://adlnet.gov/expapi/verbs/passed".to_string(),
+            object: object.to_string(),
+            activity_type: None,
+            result: Some(LearningResult {
+                success: score >= 0.5,
+                completion: Some(1.0),
+                score: Some(score),
+                duration: None,
+                response: None,
+            }),
+            context: None,
+            timestamp: DateTime::<Utc>::UNIX_EPOCH,
+            verifying_key: "key".to_string(),
+            signature: None,
+            authority: Some(InteractionAuthority {
+                issuer_id: "platform".to_string(),
+                key_id: "k1".to_string(),
+                signature: Some("firma".to_string()),
+            }),
+            stored_in_blockchain: false,
+            payload_version: CURRENT_PAYLOAD_VERSION,
+            self_reported,
+        }
+    }
+
+    fn proficiency<'a>(profile: &'a CompetencyProfile, uri: &str) -> Option<&'a CompetencyProficiency> {
+        profile.proficiencies.iter().find(|p| p.competency_uri == uri)
+    }
+
+    #[test]
+    fn evidence_uses_the_best_value_per_activity_and_propagates_to_ancestors() {
+        let mappings = vec![mapping("quiz-loops", LOOPS, 1.0), mapping("quiz-recursion", RECURSION, 0.5)];
+        let interactions = vec![
+            interaction("quiz-loops", 0.4, false),
+            interaction("quiz-loops", 0.8, false),
+            interaction("quiz-unmapped", 1.0, false),
+        ];
+
+        let profile = CompetencyProfile::compute("0xabc", &framework(), &mappings, &interactions);
+
+        let loops = proficiency(&profile, LOOPS).unwrap();
+        assert_eq!((loops.level, loops.coverage), (0.8, 1.0));
+        assert_eq!(loops.evidence.len(), 2);
+
+        // La competencia amplia ve una de sus dos actividades, con peso 1 de 1.5
+        let root = proficiency(&profile, ROOT).unwrap();
+        assert_eq!(root.level, 0.8);
+        assert!((root.coverage - 1.0 / 1.5).abs() < 1e-9);
+
+        assert!(proficiency(&profile, RECURSION).is_none());
+    }
+
+    #[test]
+    fn self_reported_interactions_are_not_evidence() {
+        let mappings = vec![mapping("quiz-loops", LOOPS, 1.0)];
+        let mut unsigned = interaction("quiz-loops", 1.0, false);
+        unsigned.authority = None;
+        let interactions = vec![interaction("quiz-loops", 1.0, true), unsigned];
+
+        let profile = CompetencyProfile::compute("0xabc", &framework(), &mappings, &interactions);
+
+        assert!(profile.proficiencies.is_empty());
+        assert_eq!(profile.credential_alignments(&framework()), serde_json::json!([]));
+    }
+
+    #[test]
+    fn mappings_to_competencies_outside_the_framework_are_ignored() {
+        let mappings = vec![mapping("quiz-loops", "https://example.org/other", 1.0)];
+        let interactions = vec![interaction("quiz-loops", 1.0, false)];
+
+        let profile = CompetencyProfile::compute("0xabc", &framework(), &mappings, &interactions);
+
+        assert!(profile.proficiencies.is_empty());
+    }
+}
@@ -5,6 +5,18 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
 
+pub mod competency;
+pub mod vocabulary;
+
+pub use competency::*;
+pub use vocabulary::*;
+
+/// Formato de `signing_payload` anterior a la contrafirma obligatoria (interacción serializada entera)
+pub const LEGACY_PAYLOAD_VERSION: u16 = 0;
+
+/// Formato vigente de `signing_payload`
+pub const CURRENT_PAYLOAD_VERSION: u16 = 1;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LearningPassportId(pub Uuid);
 
@@ -31,12 +43,98 @@ pub struct LearningInteraction {
     pub actor: String,           // Identificador del actor (usuario)
     pub verb: String,            // Acción realizada (ej: "completed", "attempted")
     pub object: String,          // Objeto de la interacción (curso, lección, etc.)
+    pub activity_type: Option<String>, // Tipo de actividad del objeto (IRI del vocabulario)
     pub result: Option<LearningResult>,
     pub context: Option<LearningContext>,
     pub timestamp: DateTime<Utc>,
-    pub humanity_proof_key: String,  // Clave de verificación de humanidad
-    pub signature: Option<String>,   // Firma Ed25519 de la interacción
+    pub verifying_key: String,       // Clave pública Ed25519 del estudiante
+    pub signature: Option<String>,   // Firma Ed25519 del estudiante, hecha en su cartera
+    pub authority: Option<InteractionAuthority>, // Emisor que contrafirma (xAPI authority)
     pub stored_in_blockchain: bool,  // Indica si ya está en Keikochain
+    #[serde(default)]
+    pub payload_version: u16,        // Formato del contenido firmado (`CURRENT_PAYLOAD_VERSION` al crearla)
+    #[serde(default)]
+    pub self_reported: bool,         // Declarada por el propio estudiante, sin evaluación de terceros
+}
+
+impl LearningInteraction {
+    /// Respaldada por un tercero: un emisor institucional o un servicio de la plataforma
+    /// (marketplace, evaluador de guías) que la registró tras comprobarla
+    pub fn is_attested(&self) -> bool {
+        self.authority.is_some() && !self.self_reported
+    }
+
+
+    /// Registrada antes de versionar el contenido firmado; no lleva contrafirma
+    pub fn is_legacy(&self) -> bool {
+        self.payload_version == LEGACY_PAYLOAD_VERSION
+    }
+
+    /// Contenido canónico que firman tanto el estudiante como el emisor
+    ///
+    /// Excluye las firmas y los campos que cambian después de firmar
+    /// (pasaporte asignado, estado de sincronización con Keikochain).
+    pub fn signing_payload(&self) -> serde_json::Result<Vec<u8>> {
+        serde_json::to_vec(&serde_json::json!({
+            "version": CURRENT_PAYLOAD_VERSION,
+            "id": self.id.0,
+            "actor": self.actor,
+            "verb": self.verb,
+            "object": self.object,
+            "activity_type": self.activity_type,
+            "result": self.result,
+            "context": self.context,
+            "timestamp": self.timestamp,
+            "authority": self.authority.as_ref().map(|a| serde_json::json!({
+                "issuer_id": a.issuer_id,
+                "key_id": a.key_id,
+            })),
+        }))
+    }
+
+    /// Contenido que firmaban las interacciones `LEGACY_PAYLOAD_VERSION`
+    ///
+    /// Era la interacción entera, sin firma y antes de anclarla, con el compromiso de
+    /// humanidad del pasaporte (`humanity_proof_key`) en lugar de la clave pública.
+    pub fn legacy_signing_payload(&self, humanity_proof_key: &str) -> serde_json::Result<Vec<u8>> {
+        serde_json::to_vec(&LegacySigningPayload {
+            id: &self.id,
+            passport_id: &self.passport_id,
+            actor: &self.actor,
+            verb: &self.verb,
+            object: &self.object,
+            result: &self.result,
+            context: &self.context,
+            timestamp: &self.timestamp,
+            humanity_proof_key,
+            signature: None,
+            stored_in_blockchain: false,
+        })
+    }
+}
+
+/// Interacción tal como se serializaba al firmar en el formato heredado; el orden de los campos es parte de la firma
+#[derive(Serialize)]
+struct LegacySigningPayload<'a> {
+    id: &'a LearningInteractionId,
+    passport_id: &'a LearningPassportId,
+    actor: &'a str,
+    verb: &'a str,
+    object: &'a str,
+    result: &'a Option<LearningResult>,
+    context: &'a Option<LearningContext>,
+    timestamp: &'a DateTime<Utc>,
+    humanity_proof_key: &'a str,
+    signature: Option<String>,
+    stored_in_blockchain: bool,
+}
+
+/// Emisor (xAPI `authority`) que contrafirma una interacción de aprendizaje
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InteractionAuthority {
+    pub issuer_id: String,          // Emisor registrado en el módulo identity
+    pub key_id: String,             // Clave del emisor vigente al firmar
+    pub signature: Option<String>,  // Contrafirma Ed25519 del emisor
 }
 
 /// Resultado de una interacción de aprendizaje
@@ -64,7 +162,7 @@ pub struct LearningContext {
 pub struct LifeLearningPassport {
     pub id: LearningPassportId,
     pub user_address: String,    // Dirección blockchain del usuario
-    pub humanity_proof_key: String, // Clave de verificación de humanidad
+    pub verifying_key: String,   // Clave pública Ed25519 de la humanidad verificada
     pub interactions: Vec<LearningInteraction>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
@@ -124,6 +222,50 @@ pub struct PassportStatistics {
     pub last_activity: Option<DateTime<Utc>>,
 }
 
+/// Resumen de un pasaporte para listados, sin cargar sus interacciones
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PassportSummary {
+    pub id: LearningPassportId,
+    pub user_address: String,
+    pub interaction_count: i64,
+    pub anchored_interaction_count: i64, // Interacciones ya almacenadas en Keikochain
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub blockchain_hash: Option<String>,
+}
+
+/// Resultado de volver a verificar una interacción ya registrada
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InteractionVerification {
+    pub interaction_id: LearningInteractionId,
+    pub signature_valid: Option<bool>,  // Firma Ed25519 del estudiante; `None` si no la firmó
+    pub authority_valid: Option<bool>,  // Contrafirma del emisor; `None` si no tiene emisor
+    pub stored_in_blockchain: bool,     // Según la sincronización registrada; no se consulta Keikochain
+    pub legacy: bool,                   // Formato heredado: sólo firma del estudiante
+}
+
+impl InteractionVerification {
+    /// La contrafirma es válida y, si el estudiante firmó, también su firma
+    /// (el anclaje pendiente no invalida la interacción)
+    ///
+    /// Las interacciones heredadas no tienen contrafirma: basta con la firma del estudiante.
+    pub fn is_valid(&self) -> bool {
+        if self.legacy {
+            return self.signature_valid == Some(true) && self.authority_valid.unwrap_or(true);
+        }
+        self.signature_valid.unwrap_or(true) && self.authority_valid.unwrap_or(false)
+    }
+}
+
+/// Criterios para listar las interacciones de un pasaporte; los campos vacíos no filtran
+#[derive(Debug, Clone, Default)]
+pub struct InteractionFilter {
+    pub verb: Option<String>,
+    pub since: Option<DateTime<Utc>>,   // Inclusive
+    pub until: Option<DateTime<Utc>>,   // Exclusive
+    pub success: Option<bool>,          // Sin resultado cuenta como no superada, igual que en las estadísticas
+}
+
 /// Eventos de dominio para learning_passport
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum LearningPassportEvent {
@@ -134,12 +276,94 @@ pub enum LearningPassportEvent {
     },
     InteractionAdded {
         passport_id: LearningPassportId,
+        user_address: String,
+        interaction_id: LearningInteractionId,
+        timestamp: DateTime<Utc>,
+    },
+    InteractionStoredInBlockchain {
+        passport_id: LearningPassportId,
+        user_address: String,
         interaction_id: LearningInteractionId,
         timestamp: DateTime<Utc>,
     },
     PassportUpdated {
         passport_id: LearningPassportId,
+        user_address: String,
         blockchain_hash: String,
         timestamp: DateTime<Utc>,
     },
 }
+
+impl LearningPassportEvent {
+    /// Titular del pasaporte al que se refiere el evento
+    pub fn user_address(&self) -> &str {
+        match self {
+            LearningPassportEvent::PassportCreated { user_address, .. }
+            | LearningPassportEvent::InteractionAdded { user_address, .. }
+            | LearningPassportEvent::InteractionStoredInBlockchain { user_address, .. }
+            | LearningPassportEvent::PassportUpdated { user_address, .. } => user_address,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interaction(payload_version: u16) -> LearningInteraction {
+        LearningInteraction {
+            id: LearningInteractionId(Uuid::nil()),
+            passport_id: LearningPassportId(Uuid::nil()),
+            actor: "0xabc".to_string(),
+            verb: "completed".to_string(),
+            object: "lesson-1".to_string(),
+            activity_type: Some("https://keiko-dapp.xyz/activities/lesson".to_string()),
+            result: None,
+            context: None,
+            timestamp: DateTime::<Utc>::UNIX_EPOCH,
+            verifying_key: "key".to_string(),
+            signature: Some("firma".to_string()),
+            authority: None,
+            stored_in_blockchain: true,
+            payload_version,
+            self_reported: false,
+        }
+    }
+
+    #[test]
+    fn legacy_payload_keeps_the_original_serialization() {
+        let payload = interaction(LEGACY_PAYLOAD_VERSION).legacy_signing_payload("commitment").unwrap();
+        let nil = Uuid::nil();
+
+        assert_eq!(
+            String::from_utf8(payload).unwrap(),
+            format!(
+                r#"{{"id":"{nil}","passport_id":"{nil}","actor":"0xabc","verb":"completed","object":"lesson-1","result":null,"context":null,"timestamp":"1970-01-01T00:00:00Z","humanity_proof_key":"commitment","signature":null,"stored_in_blockchain":false}}"#
+            )
+        );
+    }
+
+    #[test]
+    fn current_payload_is_versioned() {
+        let payload: serde_json::Value =
+            serde_json::from_slice(&interaction(CURRENT_PAYLOAD_VERSION).signing_payload().unwrap()).unwrap();
+
+        assert_eq!(payload["version"], CURRENT_PAYLOAD_VERSION);
+        assert!(!interaction(CURRENT_PAYLOAD_VERSION).is_legacy());
+    }
+
+    #[test]
+    fn legacy_interactions_are_valid_without_countersignature() {
+        let verification = |signature_valid, legacy| InteractionVerification {
+            interaction_id: LearningInteractionId(Uuid::nil()),
+            signature_valid,
+            authority_valid: None,
+            stored_in_blockchain: false,
+            legacy,
+        };
+
+        assert!(verification(Some(true), true).is_valid());
+        assert!(!verification(None, true).is_valid());
+        assert!(!verification(Some(true), false).is_valid());
+    }
+}
@@ -0,0 +1,244 @@
+// Vocabulario xAPI curado por la comunidad
+// Verbos y tipos de actividad canónicos con nombres visibles en español, portugués e inglés
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Tipo de término del vocabulario
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum VocabularyKind {
+    Verb,
+    ActivityType,
+}
+
+impl VocabularyKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VocabularyKind::Verb => "verb",
+            VocabularyKind::ActivityType => "activity_type",
+        }
+    }
+}
+
+impl FromStr for VocabularyKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "verb" => Ok(VocabularyKind::Verb),
+            "activity_type" => Ok(VocabularyKind::ActivityType),
+            other => Err(anyhow!("Tipo de vocabulario desconocido: {}", other)),
+        }
+    }
+}
+
+/// Nombres visibles de un término
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DisplayNames {
+    pub es: String,
+    pub pt: String,
+    pub en: String,
+}
+
+impl DisplayNames {
+    /// Nombre en el idioma indicado (código BCP 47, ej: "es-PE"); inglés por defecto
+    pub fn for_language(&self, language: &str) -> &str {
+        match language.split(['-', '_']).next().unwrap_or("").to_ascii_lowercase().as_str() {
+            "es" => &self.es,
+            "pt" => &self.pt,
+            _ => &self.en,
+        }
+    }
+}
+
+/// Término canónico del vocabulario
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VocabularyEntry {
+    pub iri: String,                   // IRI canónico (ej: http://adlnet.gov/expapi/verbs/completed)
+    pub kind: VocabularyKind,
+    pub aliases: Vec<String>,          // Nombres cortos e IRIs alternativos que se normalizan a este término
+    pub display: DisplayNames,
+    pub description: Option<String>,
+    pub proposal_id: Option<Uuid>,     // Propuesta de gobernanza que lo aprobó (None para el vocabulario base)
+    pub added_at: DateTime<Utc>,
+}
+
+impl VocabularyEntry {
+    /// Claves por las que se reconoce el término: IRI, último segmento del IRI y alias
+    pub fn lookup_keys(&self) -> Vec<String> {
+        let mut keys = vec![normalize_key(&self.iri)];
+
+        if let Some(segment) = self.iri.rsplit(['/', '#']).next() {
+            if !segment.is_empty() {
+                keys.push(normalize_key(segment));
+            }
+        }
+
+        keys.extend(self.aliases.iter().map(|alias| normalize_key(alias)));
+        keys.sort();
+        keys.dedup();
+        keys
+    }
+}
+
+/// Forma comparable de un término: sin espacios laterales y en minúsculas
+pub fn normalize_key(term: &str) -> String {
+    term.trim().to_lowercase()
+}
+
+/// Qué hacer al ingerir un término que no está en el vocabulario
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UnknownTermPolicy {
+    Warn,   // Aceptar tal cual y registrar una advertencia
+    Reject, // Rechazar la interacción
+}
+
+/// Resultado de normalizar un término
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NormalizedTerm {
+    Canonical(String), // Ya era el IRI canónico
+    Aliased(String),   // Se reconoció por un alias; contiene el IRI canónico
+    Unknown(String),   // No figura en el vocabulario; contiene el término original
+}
+
+impl NormalizedTerm {
+    /// Término resultante tras normalizar
+    pub fn value(&self) -> &str {
+        match self {
+            NormalizedTerm::Canonical(iri) | NormalizedTerm::Aliased(iri) => iri,
+            NormalizedTerm::Unknown(term) => term,
+        }
+    }
+}
+
+/// Índice en memoria del vocabulario para normalizar términos
+#[derive(Debug, Clone, Default)]
+pub struct VocabularyRegistry {
+    entries: HashMap<String, VocabularyEntry>,                  // Por IRI canónico
+    index: HashMap<(VocabularyKind, String), String>,          // Clave normalizada → IRI canónico
+}
+
+impl VocabularyRegistry {
+    pub fn new(entries: Vec<VocabularyEntry>) -> Result<Self> {
+        let mut registry = Self::default();
+
+        for entry in entries {
+            registry.insert(entry)?;
+        }
+
+        Ok(registry)
+    }
+
+    /// Agregar o reemplazar un término, rechazando alias que ya pertenecen a otro
+    pub fn insert(&mut self, entry: VocabularyEntry) -> Result<()> {
+        for key in entry.lookup_keys() {
+            if let Some(existing) = self.index.get(&(entry.kind, key.clone())) {
+                if *existing != entry.iri {
+                    bail!("'{}' ya identifica al término {}", key, existing);
+                }
+            }
+        }
+
+        if let Some(previous) = self.entries.remove(&entry.iri) {
+            for key in previous.lookup_keys() {
+                self.index.remove(&(previous.kind, key));
+            }
+        }
+
+        for key in entry.lookup_keys() {
+            self.index.insert((entry.kind, key), entry.iri.clone());
+        }
+        self.entries.insert(entry.iri.clone(), entry);
+
+        Ok(())
+    }
+
+    /// Buscar el término canónico que corresponde a un término recibido
+    pub fn resolve(&self, kind: VocabularyKind, term: &str) -> Option<&VocabularyEntry> {
+        self.index
+            .get(&(kind, normalize_key(term)))
+            .and_then(|iri| self.entries.get(iri))
+    }
+
+    /// Normalizar un término al IRI canónico del vocabulario
+    pub fn normalize(&self, kind: VocabularyKind, term: &str) -> NormalizedTerm {
+        match self.resolve(kind, term) {
+            Some(entry) if entry.iri == term => NormalizedTerm::Canonical(entry.iri.clone()),
+            Some(entry) => NormalizedTerm::Aliased(entry.iri.clone()),
+            None => NormalizedTerm::Unknown(term.to_string()),
+        }
+    }
+
+    /// Términos de un tipo ordenados por IRI
+    pub fn entries(&self, kind: VocabularyKind) -> Vec<&VocabularyEntry> {
+        let mut entries: Vec<&VocabularyEntry> = self.entries.values().filter(|e| e.kind == kind).collect();
+        entries.sort_by(|a, b| a.iri.cmp(&b.iri));
+        entries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COMPLETED: &str = "http://adlnet.gov/expapi/verbs/completed";
+
+    fn entry(iri: &str, kind: VocabularyKind, aliases: &[&str]) -> VocabularyEntry {
+        VocabularyEntry {
+            iri: iri.to_string(),
+            kind,
+            aliases: aliases.iter().map(|alias| alias.to_string()).collect(),
+            display: DisplayNames { es: "completó".into(), pt: "completou".into(), en: "completed".into() },
+            description: None,
+            proposal_id: None,
+            added_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn lookup_keys_are_normalized_and_unique() {
+        let entry = entry(COMPLETED, VocabularyKind::Verb, &["Completed", " terminado ", "completed"]);
+
+        assert_eq!(entry.lookup_keys(), vec!["completed".to_string(), COMPLETED.into(), "terminado".into()]);
+    }
+
+    #[test]
+    fn normalize_distinguishes_canonical_aliased_and_unknown_terms() {
+        let registry = VocabularyRegistry::new(vec![entry(COMPLETED, VocabularyKind::Verb, &["terminado"])]).unwrap();
+
+        assert_eq!(
+            registry.normalize(VocabularyKind::Verb, COMPLETED),
+            NormalizedTerm::Canonical(COMPLETED.to_string())
+        );
+        assert_eq!(
+            registry.normalize(VocabularyKind::Verb, " Terminado"),
+            NormalizedTerm::Aliased(COMPLETED.to_string())
+        );
+        assert_eq!(
+            registry.normalize(VocabularyKind::ActivityType, "terminado"),
+            NormalizedTerm::Unknown("terminado".to_string())
+        );
+    }
+
+    #[test]
+    fn insert_rejects_aliases_of_other_terms_and_replaces_its_own() {
+        let mut registry = VocabularyRegistry::new(vec![entry(COMPLETED, VocabularyKind::Verb, &["done"])]).unwrap();
+
+        let other = entry("http://example.org/verbs/finished", VocabularyKind::Verb, &["Done"]);
+        assert!(registry.insert(other).is_err());
+
+        // El mismo alias puede usarse en otro tipo de término
+        let activity = entry("http://example.org/activities/done", VocabularyKind::ActivityType, &["done"]);
+        registry.insert(activity).unwrap();
+
+        // Reemplazar un término libera los alias que ya no tiene
+        registry.insert(entry(COMPLETED, VocabularyKind::Verb, &["terminado"])).unwrap();
+        assert!(registry.resolve(VocabularyKind::Verb, "done").is_none());
+        assert_eq!(registry.resolve(VocabularyKind::Verb, "terminado").unwrap().iri, COMPLETED);
+    }
+}
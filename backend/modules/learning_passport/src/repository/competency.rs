@@ -0,0 +1,171 @@
+// Persistencia de marcos de competencias y de sus correspondencias con actividades
+
+use anyhow::Result;
+use sqlx::PgPool;
+
+use crate::domain::{ActivityMapping, CompetencyFramework, FrameworkId};
+
+pub struct CompetencyRepository {
+    pool: PgPool,
+}
+
+impl CompetencyRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Crear o actualizar un marco; las competencias se guardan como JSON
+    pub async fn upsert_framework(&self, framework: &CompetencyFramework) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO competency_frameworks (
+                id, name, source, source_uri, version, language, competencies, imported_at, updated_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            ON CONFLICT (id) DO UPDATE
+            SET name = EXCLUDED.name, version = EXCLUDED.version, language = EXCLUDED.language,
+                competencies = EXCLUDED.competencies, updated_at = EXCLUDED.updated_at
+            "#,
+            framework.id.0,
+            framework.name,
+            framework.source.as_str(),
+            framework.source_uri,
+            framework.version,
+            framework.language,
+            serde_json::to_value(&framework.competencies)?,
+            framework.imported_at,
+            framework.updated_at
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Obtener un marco por ID
+    pub async fn get_framework(&self, framework_id: &FrameworkId) -> Result<Option<CompetencyFramework>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT id, name, source, source_uri, version, language, competencies, imported_at, updated_at
+            FROM competency_frameworks
+            WHERE id = $1
+            "#,
+            framework_id.0
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match row {
+            Some(row) => Ok(Some(CompetencyFramework {
+                id: FrameworkId(row.id),
+                name: row.name,
+                source: row.source.parse()?,
+                source_uri: row.source_uri,
+                version: row.version,
+                language: row.language,
+                competencies: serde_json::from_value(row.competencies)?,
+                imported_at: row.imported_at,
+                updated_at: row.updated_at,
+            })),
+            None => Ok(None),
+        }
+    }
+
+    /// Buscar el marco importado desde un documento de origen
+    pub async fn get_framework_id_by_source_uri(&self, source_uri: &str) -> Result<Option<FrameworkId>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT id
+            FROM competency_frameworks
+            WHERE source_uri = $1
+            "#,
+            source_uri
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|row| FrameworkId(row.id)))
+    }
+
+    /// Obtener los IDs de todos los marcos
+    pub async fn get_framework_ids(&self) -> Result<Vec<FrameworkId>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT id
+            FROM competency_frameworks
+            ORDER BY name ASC
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(|row| FrameworkId(row.id)).collect())
+    }
+
+    /// Crear o actualizar la correspondencia entre una actividad y una competencia
+    pub async fn upsert_mapping(&self, mapping: &ActivityMapping) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO competency_mappings (framework_id, activity_iri, competency_uri, weight)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (framework_id, activity_iri, competency_uri) DO UPDATE
+            SET weight = EXCLUDED.weight
+            "#,
+            mapping.framework_id.0,
+            mapping.activity_iri,
+            mapping.competency_uri,
+            mapping.weight
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Eliminar una correspondencia; devuelve si existía
+    pub async fn delete_mapping(
+        &self,
+        framework_id: &FrameworkId,
+        activity_iri: &str,
+        competency_uri: &str,
+    ) -> Result<bool> {
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM competency_mappings
+            WHERE framework_id = $1 AND activity_iri = $2 AND competency_uri = $3
+            "#,
+            framework_id.0,
+            activity_iri,
+            competency_uri
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Obtener las correspondencias de un marco
+    pub async fn get_mappings(&self, framework_id: &FrameworkId) -> Result<Vec<ActivityMapping>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT framework_id, activity_iri, competency_uri, weight
+            FROM competency_mappings
+            WHERE framework_id = $1
+            ORDER BY competency_uri ASC, activity_iri ASC
+            "#,
+            framework_id.0
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| ActivityMapping {
+                framework_id: FrameworkId(row.framework_id),
+                activity_iri: row.activity_iri,
+                competency_uri: row.competency_uri,
+                weight: row.weight,
+            })
+            .collect())
+    }
+}
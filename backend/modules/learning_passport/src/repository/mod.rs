@@ -1,15 +1,23 @@
 // Repositorios para persistencia del módulo learning_passport
 
+use std::collections::HashMap;
+
 use anyhow::Result;
 use sqlx::PgPool;
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
 
 use crate::domain::{
-    LearningInteraction, LifeLearningPassport, LearningPassportId, 
-    LearningInteractionId, LearningPassportEvent
+    InteractionFilter, LearningInteraction, LifeLearningPassport, LearningPassportId, 
+    LearningInteractionId, LearningPassportEvent, PassportSummary
 };
 
+pub mod competency;
+pub mod vocabulary;
+
+pub use competency::CompetencyRepository;
+pub use vocabulary::VocabularyRepository;
+
 pub struct LearningPassportRepository {
     pool: PgPool,
 }
@@ -24,12 +32,12 @@ impl LearningPassportRepository {
         sqlx::query!(
             r#"
             INSERT INTO learning_passports (
-                id, user_address, humanity_proof_key, created_at, updated_at, blockchain_hash
+                id, user_address, verifying_key, created_at, updated_at, blockchain_hash
             ) VALUES ($1, $2, $3, $4, $5, $6)
             "#,
             passport.id.0,
             passport.user_address,
-            passport.humanity_proof_key,
+            passport.verifying_key,
             passport.created_at,
             passport.updated_at,
             passport.blockchain_hash
@@ -44,7 +52,7 @@ impl LearningPassportRepository {
     pub async fn get_passport_by_id(&self, passport_id: &LearningPassportId) -> Result<Option<LifeLearningPassport>> {
         let passport_row = sqlx::query!(
             r#"
-            SELECT id, user_address, humanity_proof_key, created_at, updated_at, blockchain_hash
+            SELECT id, user_address, verifying_key, created_at, updated_at, blockchain_hash
             FROM learning_passports
             WHERE id = $1
             "#,
@@ -60,7 +68,7 @@ impl LearningPassportRepository {
             let passport = LifeLearningPassport {
                 id: LearningPassportId(row.id),
                 user_address: row.user_address,
-                humanity_proof_key: row.humanity_proof_key,
+                verifying_key: row.verifying_key,
                 interactions,
                 created_at: row.created_at,
                 updated_at: row.updated_at,
@@ -77,7 +85,7 @@ impl LearningPassportRepository {
     pub async fn get_passport_by_user_address(&self, user_address: &str) -> Result<Option<LifeLearningPassport>> {
         let passport_row = sqlx::query!(
             r#"
-            SELECT id, user_address, humanity_proof_key, created_at, updated_at, blockchain_hash
+            SELECT id, user_address, verifying_key, created_at, updated_at, blockchain_hash
             FROM learning_passports
             WHERE user_address = $1
             "#,
@@ -93,7 +101,7 @@ impl LearningPassportRepository {
             let passport = LifeLearningPassport {
                 id: passport_id,
                 user_address: row.user_address,
-                humanity_proof_key: row.humanity_proof_key,
+                verifying_key: row.verifying_key,
                 interactions,
                 created_at: row.created_at,
                 updated_at: row.updated_at,
@@ -106,6 +114,78 @@ impl LearningPassportRepository {
         }
     }
     
+    /// Obtener los pasaportes de varios usuarios con sus interacciones, en dos consultas
+    pub async fn get_passports_by_user_addresses(&self, user_addresses: &[String]) -> Result<Vec<LifeLearningPassport>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT id, user_address, verifying_key, created_at, updated_at, blockchain_hash
+            FROM learning_passports
+            WHERE user_address = ANY($1)
+            "#,
+            user_addresses
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        
+        let passport_ids: Vec<Uuid> = rows.iter().map(|row| row.id).collect();
+        let mut interactions: HashMap<Uuid, Vec<LearningInteraction>> = HashMap::new();
+        for interaction in self.get_interactions_by_passport_ids(&passport_ids).await? {
+            interactions.entry(interaction.passport_id.0).or_default().push(interaction);
+        }
+        
+        Ok(rows
+            .into_iter()
+            .map(|row| LifeLearningPassport {
+                id: LearningPassportId(row.id),
+                user_address: row.user_address,
+                verifying_key: row.verifying_key,
+                interactions: interactions.remove(&row.id).unwrap_or_default(),
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+                blockchain_hash: row.blockchain_hash,
+            })
+            .collect())
+    }
+    
+    /// Buscar pasaportes cuya dirección de usuario empieza por `address_prefix`
+    pub async fn search_passports_by_user_address(&self, address_prefix: &str, limit: i64) -> Result<Vec<PassportSummary>> {
+        // Los comodines de LIKE que escriba el usuario se buscan literalmente
+        let pattern = format!(
+            "{}%",
+            address_prefix.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+        );
+        let rows = sqlx::query!(
+            r#"
+            SELECT p.id, p.user_address, p.created_at, p.updated_at, p.blockchain_hash,
+                   COUNT(i.id) AS "interaction_count!",
+                   COUNT(i.id) FILTER (WHERE i.stored_in_blockchain) AS "anchored_interaction_count!"
+            FROM learning_passports p
+            LEFT JOIN learning_interactions i ON i.passport_id = p.id
+            WHERE p.user_address ILIKE $1
+            GROUP BY p.id
+            ORDER BY p.user_address
+            LIMIT $2
+            "#,
+            pattern,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        
+        Ok(rows
+            .into_iter()
+            .map(|row| PassportSummary {
+                id: LearningPassportId(row.id),
+                user_address: row.user_address,
+                interaction_count: row.interaction_count,
+                anchored_interaction_count: row.anchored_interaction_count,
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+                blockchain_hash: row.blockchain_hash,
+            })
+            .collect())
+    }
+    
     /// Actualizar pasaporte
     pub async fn update_passport(&self, passport: &LifeLearningPassport) -> Result<()> {
         sqlx::query!(
@@ -129,21 +209,26 @@ impl LearningPassportRepository {
         sqlx::query!(
             r#"
             INSERT INTO learning_interactions (
-                id, passport_id, actor, verb, object, result, context, 
-                timestamp, humanity_proof_key, signature, stored_in_blockchain
-            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+                id, passport_id, actor, verb, object, activity_type, result, context, 
+                timestamp, verifying_key, signature, authority, stored_in_blockchain, payload_version,
+                self_reported
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
             "#,
             interaction.id.0,
             interaction.passport_id.0,
             interaction.actor,
             interaction.verb,
             interaction.object,
+            interaction.activity_type,
             serde_json::to_value(&interaction.result)?,
             serde_json::to_value(&interaction.context)?,
             interaction.timestamp,
-            interaction.humanity_proof_key,
+            interaction.verifying_key,
             interaction.signature,
-            interaction.stored_in_blockchain
+            serde_json::to_value(&interaction.authority)?,
+            interaction.stored_in_blockchain,
+            interaction.payload_version as i16,
+            interaction.self_reported
         )
         .execute(&self.pool)
         .await?;
@@ -153,15 +238,20 @@ impl LearningPassportRepository {
     
     /// Obtener interacciones por ID de pasaporte
     pub async fn get_interactions_by_passport_id(&self, passport_id: &LearningPassportId) -> Result<Vec<LearningInteraction>> {
+        self.get_interactions_by_passport_ids(&[passport_id.0]).await
+    }
+    
+    /// Obtener las interacciones de varios pasaportes, ordenadas cronológicamente
+    pub async fn get_interactions_by_passport_ids(&self, passport_ids: &[Uuid]) -> Result<Vec<LearningInteraction>> {
         let rows = sqlx::query!(
             r#"
-            SELECT id, passport_id, actor, verb, object, result, context,
-                   timestamp, humanity_proof_key, signature, stored_in_blockchain
+            SELECT id, passport_id, actor, verb, object, activity_type, result, context,
+                   timestamp, verifying_key, signature, authority, stored_in_blockchain, payload_version, self_reported
             FROM learning_interactions
-            WHERE passport_id = $1
+            WHERE passport_id = ANY($1)
             ORDER BY timestamp ASC
             "#,
-            passport_id.0
+            passport_ids
         )
         .fetch_all(&self.pool)
         .await?;
@@ -175,6 +265,7 @@ impl LearningPassportRepository {
                 actor: row.actor,
                 verb: row.verb,
                 object: row.object,
+                activity_type: row.activity_type,
                 result: if let Some(result_json) = row.result {
                     serde_json::from_value(result_json)?
                 } else {
@@ -186,9 +277,172 @@ impl LearningPassportRepository {
                     None
                 },
                 timestamp: row.timestamp,
-                humanity_proof_key: row.humanity_proof_key,
+                verifying_key: row.verifying_key,
                 signature: row.signature,
+                authority: if let Some(authority_json) = row.authority {
+                    serde_json::from_value(authority_json)?
+                } else {
+                    None
+                },
                 stored_in_blockchain: row.stored_in_blockchain,
+                payload_version: row.payload_version as u16,
+                self_reported: row.self_reported,
+            };
+            
+            interactions.push(interaction);
+        }
+        
+        Ok(interactions)
+    }
+    
+    /// Obtener interacciones por ID
+    pub async fn get_interactions_by_ids(&self, interaction_ids: &[Uuid]) -> Result<Vec<LearningInteraction>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT id, passport_id, actor, verb, object, activity_type, result, context,
+                   timestamp, verifying_key, signature, authority, stored_in_blockchain, payload_version, self_reported
+            FROM learning_interactions
+            WHERE id = ANY($1)
+            "#,
+            interaction_ids
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        
+        let mut interactions = Vec::new();
+        
+        for row in rows {
+            let interaction = LearningInteraction {
+                id: LearningInteractionId(row.id),
+                passport_id: LearningPassportId(row.passport_id),
+                actor: row.actor,
+                verb: row.verb,
+                object: row.object,
+                activity_type: row.activity_type,
+                result: if let Some(result_json) = row.result {
+                    serde_json::from_value(result_json)?
+                } else {
+                    None
+                },
+                context: if let Some(context_json) = row.context {
+                    serde_json::from_value(context_json)?
+                } else {
+                    None
+                },
+                timestamp: row.timestamp,
+                verifying_key: row.verifying_key,
+                signature: row.signature,
+                authority: if let Some(authority_json) = row.authority {
+                    serde_json::from_value(authority_json)?
+                } else {
+                    None
+                },
+                stored_in_blockchain: row.stored_in_blockchain,
+                payload_version: row.payload_version as u16,
+                self_reported: row.self_reported,
+            };
+            
+            interactions.push(interaction);
+        }
+        
+        Ok(interactions)
+    }
+    
+    /// Contar las interacciones de un usuario que cumplen el filtro
+    pub async fn count_interactions_by_user_address(&self, user_address: &str, filter: &InteractionFilter) -> Result<i64> {
+        let row = sqlx::query!(
+            r#"
+            SELECT COUNT(*) AS "count!"
+            FROM learning_interactions i
+            JOIN learning_passports p ON p.id = i.passport_id
+            WHERE p.user_address = $1
+              AND ($2::text IS NULL OR i.verb = $2)
+              AND ($3::timestamptz IS NULL OR i.timestamp >= $3)
+              AND ($4::timestamptz IS NULL OR i.timestamp < $4)
+              AND ($5::boolean IS NULL OR COALESCE((i.result->>'success')::boolean, false) = $5)
+            "#,
+            user_address,
+            filter.verb,
+            filter.since,
+            filter.until,
+            filter.success
+        )
+        .fetch_one(&self.pool)
+        .await?;
+        
+        Ok(row.count)
+    }
+    
+    /// Obtener una página de las interacciones de un usuario que cumplen el filtro
+    ///
+    /// Se ordenan por fecha y, a igualdad, por ID, para que las páginas sean estables.
+    pub async fn list_interactions_by_user_address(
+        &self,
+        user_address: &str,
+        filter: &InteractionFilter,
+        descending: bool,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<LearningInteraction>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT i.id, i.passport_id, i.actor, i.verb, i.object, i.activity_type, i.result, i.context,
+                   i.timestamp, i.verifying_key, i.signature, i.authority, i.stored_in_blockchain, i.payload_version, i.self_reported
+            FROM learning_interactions i
+            JOIN learning_passports p ON p.id = i.passport_id
+            WHERE p.user_address = $1
+              AND ($2::text IS NULL OR i.verb = $2)
+              AND ($3::timestamptz IS NULL OR i.timestamp >= $3)
+              AND ($4::timestamptz IS NULL OR i.timestamp < $4)
+              AND ($5::boolean IS NULL OR COALESCE((i.result->>'success')::boolean, false) = $5)
+            ORDER BY CASE WHEN $6 THEN i.timestamp END DESC,
+                     CASE WHEN NOT $6 THEN i.timestamp END ASC,
+                     i.id
+            LIMIT $7 OFFSET $8
+            "#,
+            user_address,
+            filter.verb,
+            filter.since,
+            filter.until,
+            filter.success,
+            descending,
+            limit,
+            offset
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        
+        let mut interactions = Vec::new();
+        
+        for row in rows {
+            let interaction = LearningInteraction {
+                id: LearningInteractionId(row.id),
+                passport_id: LearningPassportId(row.passport_id),
+                actor: row.actor,
+                verb: row.verb,
+                object: row.object,
+                activity_type: row.activity_type,
+                result: if let Some(result_json) = row.result {
+                    serde_json::from_value(result_json)?
+                } else {
+                    None
+                },
+                context: if let Some(context_json) = row.context {
+                    serde_json::from_value(context_json)?
+                } else {
+                    None
+                },
+                timestamp: row.timestamp,
+                verifying_key: row.verifying_key,
+                signature: row.signature,
+                authority: if let Some(authority_json) = row.authority {
+                    serde_json::from_value(authority_json)?
+                } else {
+                    None
+                },
+                stored_in_blockchain: row.stored_in_blockchain,
+                payload_version: row.payload_version as u16,
+                self_reported: row.self_reported,
             };
             
             interactions.push(interaction);
@@ -217,8 +471,8 @@ impl LearningPassportRepository {
     pub async fn get_pending_blockchain_interactions(&self) -> Result<Vec<LearningInteraction>> {
         let rows = sqlx::query!(
             r#"
-            SELECT id, passport_id, actor, verb, object, result, context,
-                   timestamp, humanity_proof_key, signature, stored_in_blockchain
+            SELECT id, passport_id, actor, verb, object, activity_type, result, context,
+                   timestamp, verifying_key, signature, authority, stored_in_blockchain, payload_version, self_reported
             FROM learning_interactions
             WHERE stored_in_blockchain = false
             ORDER BY timestamp ASC
@@ -236,6 +490,7 @@ impl LearningPassportRepository {
                 actor: row.actor,
                 verb: row.verb,
                 object: row.object,
+                activity_type: row.activity_type,
                 result: if let Some(result_json) = row.result {
                     serde_json::from_value(result_json)?
                 } else {
@@ -247,9 +502,16 @@ impl LearningPassportRepository {
                     None
                 },
                 timestamp: row.timestamp,
-                humanity_proof_key: row.humanity_proof_key,
+                verifying_key: row.verifying_key,
                 signature: row.signature,
+                authority: if let Some(authority_json) = row.authority {
+                    serde_json::from_value(authority_json)?
+                } else {
+                    None
+                },
                 stored_in_blockchain: row.stored_in_blockchain,
+                payload_version: row.payload_version as u16,
+                self_reported: row.self_reported,
             };
             
             interactions.push(interaction);
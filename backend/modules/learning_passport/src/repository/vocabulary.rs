@@ -0,0 +1,88 @@
+// Persistencia del vocabulario xAPI curado por la comunidad
+
+use anyhow::Result;
+use sqlx::postgres::PgListener;
+use sqlx::PgPool;
+
+use crate::domain::VocabularyEntry;
+
+/// Canal de Postgres por el que las instancias se avisan de cambios en el vocabulario
+const CHANGES_CHANNEL: &str = "vocabulary_changed";
+
+pub struct VocabularyRepository {
+    pool: PgPool,
+}
+
+impl VocabularyRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Agregar o actualizar un término del vocabulario
+    pub async fn upsert_entry(&self, entry: &VocabularyEntry) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO vocabulary_entries (iri, kind, aliases, display_names, description, proposal_id, added_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (iri) DO UPDATE
+            SET kind = EXCLUDED.kind, aliases = EXCLUDED.aliases, display_names = EXCLUDED.display_names,
+                description = EXCLUDED.description, proposal_id = EXCLUDED.proposal_id, added_at = EXCLUDED.added_at
+            "#,
+            entry.iri,
+            entry.kind.as_str(),
+            serde_json::to_value(&entry.aliases)?,
+            serde_json::to_value(&entry.display)?,
+            entry.description,
+            entry.proposal_id,
+            entry.added_at
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Obtener todos los términos del vocabulario
+    pub async fn get_entries(&self) -> Result<Vec<VocabularyEntry>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT iri, kind, aliases, display_names, description, proposal_id, added_at
+            FROM vocabulary_entries
+            ORDER BY added_at ASC
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut entries = Vec::new();
+
+        for row in rows {
+            entries.push(VocabularyEntry {
+                iri: row.iri,
+                kind: row.kind.parse()?,
+                aliases: serde_json::from_value(row.aliases)?,
+                display: serde_json::from_value(row.display_names)?,
+                description: row.description,
+                proposal_id: row.proposal_id,
+                added_at: row.added_at,
+            });
+        }
+
+        Ok(entries)
+    }
+
+    /// Avisar a todas las instancias de que el vocabulario cambió
+    pub async fn publish_change(&self) -> Result<()> {
+        sqlx::query("SELECT pg_notify($1, '')").bind(CHANGES_CHANNEL).execute(&self.pool).await?;
+
+        Ok(())
+    }
+
+    /// Escuchar los avisos de cambios del vocabulario
+    pub async fn listen_changes(&self) -> Result<PgListener> {
+        let mut listener = PgListener::connect_with(&self.pool).await?;
+        listener.listen(CHANGES_CHANNEL).await?;
+
+        Ok(listener)
+    }
+}
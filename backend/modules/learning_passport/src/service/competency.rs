@@ -0,0 +1,347 @@
+// Marcos de competencias: importación desde ESCO y CASE, correspondencias con
+// actividades y perfil de competencias calculado desde el pasaporte
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use anyhow::{anyhow, bail, Result};
+use chrono::Utc;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::domain::{
+    ActivityMapping, Competency, CompetencyFramework, CompetencyProfile, FrameworkId, FrameworkSource,
+};
+use crate::repository::CompetencyRepository;
+use crate::service::LearningPassportService;
+
+/// Datos del marco que ESCO no incluye en los recursos de capacidades
+#[derive(Debug, Clone)]
+pub struct EscoImport {
+    pub name: String,
+    pub source_uri: String,             // Ej: esquema de conceptos de capacidades de ESCO
+    pub version: Option<String>,        // Ej: "v1.2.0"
+    pub language: String,               // Idioma de las etiquetas a importar
+}
+
+/// Recurso de capacidad de la API de ESCO
+#[derive(Debug, Deserialize)]
+struct EscoSkill {
+    uri: String,
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(default, rename = "preferredLabel")]
+    preferred_label: HashMap<String, String>,
+    #[serde(default)]
+    description: HashMap<String, EscoLiteral>,
+    #[serde(default, rename = "_links")]
+    links: EscoLinks,
+}
+
+#[derive(Debug, Deserialize)]
+struct EscoLiteral {
+    literal: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct EscoLinks {
+    #[serde(default, rename = "broaderSkill")]
+    broader_skill: Vec<EscoLink>,
+    #[serde(default, rename = "broaderHierarchyConcept")]
+    broader_hierarchy_concept: Vec<EscoLink>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EscoLink {
+    uri: String,
+}
+
+/// Paquete CFPackage de CASE
+#[derive(Debug, Deserialize)]
+struct CasePackage {
+    #[serde(rename = "CFDocument")]
+    cf_document: CaseDocument,
+    #[serde(rename = "CFItems", default)]
+    cf_items: Vec<CaseItem>,
+    #[serde(rename = "CFAssociations", default)]
+    cf_associations: Vec<CaseAssociation>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CaseDocument {
+    uri: String,
+    title: String,
+    #[serde(default)]
+    version: Option<String>,
+    #[serde(default)]
+    language: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CaseItem {
+    uri: String,
+    full_statement: String,
+    #[serde(default)]
+    abbreviated_statement: Option<String>,
+    #[serde(default)]
+    human_coding_scheme: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CaseAssociation {
+    association_type: String,
+    #[serde(rename = "originNodeURI")]
+    origin_node_uri: CaseNodeReference,
+    #[serde(rename = "destinationNodeURI")]
+    destination_node_uri: CaseNodeReference,
+}
+
+#[derive(Debug, Deserialize)]
+struct CaseNodeReference {
+    uri: String,
+}
+
+pub struct CompetencyService {
+    repository: CompetencyRepository,
+    passport: Arc<LearningPassportService>,
+}
+
+impl CompetencyService {
+    pub fn new(repository: CompetencyRepository, passport: Arc<LearningPassportService>) -> Self {
+        Self { repository, passport }
+    }
+
+    /// Importar capacidades de ESCO
+    ///
+    /// Acepta un recurso, una lista de recursos o una respuesta HAL de la API
+    /// (`_embedded.results` o `_embedded` indexado por URI).
+    pub async fn import_esco(&self, import: EscoImport, document: &Value) -> Result<CompetencyFramework> {
+        let resources: Vec<Value> = match document {
+            Value::Array(items) => items.clone(),
+            Value::Object(object) => match object.get("_embedded") {
+                Some(Value::Object(embedded)) => match embedded.get("results") {
+                    Some(Value::Array(results)) => results.clone(),
+                    _ => embedded.values().cloned().collect(),
+                },
+                _ => vec![document.clone()],
+            },
+            _ => bail!("Documento ESCO no reconocido"),
+        };
+
+        let skills: Vec<EscoSkill> = resources
+            .into_iter()
+            .map(serde_json::from_value)
+            .collect::<Result<_, _>>()
+            .map_err(|e| anyhow!("Recurso ESCO no válido: {}", e))?;
+        let uris: HashSet<&str> = skills.iter().map(|s| s.uri.as_str()).collect();
+
+        let competencies = skills
+            .iter()
+            .map(|skill| {
+                let title = skill
+                    .preferred_label
+                    .get(&import.language)
+                    .or_else(|| skill.preferred_label.get("en"))
+                    .cloned()
+                    .or_else(|| skill.title.clone())
+                    .unwrap_or_default();
+                let description = skill
+                    .description
+                    .get(&import.language)
+                    .or_else(|| skill.description.get("en"))
+                    .map(|d| d.literal.clone());
+                // Solo se conserva el padre si forma parte de lo importado
+                let parent_uri = skill
+                    .links
+                    .broader_skill
+                    .iter()
+                    .chain(&skill.links.broader_hierarchy_concept)
+                    .map(|link| link.uri.as_str())
+                    .find(|uri| uris.contains(uri))
+                    .map(str::to_string);
+
+                Competency { uri: skill.uri.clone(), code: None, title, description, parent_uri }
+            })
+            .collect();
+
+        self.save_framework(
+            FrameworkSource::Esco,
+            import.name,
+            import.source_uri,
+            import.version,
+            import.language,
+            competencies,
+        )
+        .await
+    }
+
+    /// Importar un paquete CFPackage de CASE
+    ///
+    /// La jerarquía sale de las asociaciones `isChildOf` entre ítems.
+    pub async fn import_case(&self, document: &Value) -> Result<CompetencyFramework> {
+        let package: CasePackage =
+            serde_json::from_value(document.clone()).map_err(|e| anyhow!("Paquete CASE no válido: {}", e))?;
+
+        let item_uris: HashSet<&str> = package.cf_items.iter().map(|i| i.uri.as_str()).collect();
+        let parents: HashMap<&str, &str> = package
+            .cf_associations
+            .iter()
+            .filter(|a| a.association_type == "isChildOf")
+            .filter(|a| item_uris.contains(a.destination_node_uri.uri.as_str()))
+            .map(|a| (a.origin_node_uri.uri.as_str(), a.destination_node_uri.uri.as_str()))
+            .collect();
+
+        let competencies = package
+            .cf_items
+            .iter()
+            .map(|item| {
+                let (title, description) = match &item.abbreviated_statement {
+                    Some(abbreviated) if !abbreviated.trim().is_empty() => {
+                        (abbreviated.clone(), Some(item.full_statement.clone()))
+                    }
+                    _ => (item.full_statement.clone(), None),
+                };
+
+                Competency {
+                    uri: item.uri.clone(),
+                    code: item.human_coding_scheme.clone(),
+                    title,
+                    description,
+                    parent_uri: parents.get(item.uri.as_str()).map(|p| p.to_string()),
+                }
+            })
+            .collect();
+
+        self.save_framework(
+            FrameworkSource::Case,
+            package.cf_document.title,
+            package.cf_document.uri,
+            package.cf_document.version,
+            package.cf_document.language.unwrap_or_else(|| "en".to_string()),
+            competencies,
+        )
+        .await
+    }
+
+    /// Guardar un marco; reimportar el mismo documento conserva el ID y las correspondencias
+    async fn save_framework(
+        &self,
+        source: FrameworkSource,
+        name: String,
+        source_uri: String,
+        version: Option<String>,
+        language: String,
+        competencies: Vec<Competency>,
+    ) -> Result<CompetencyFramework> {
+        let now = Utc::now();
+        let existing = match self.repository.get_framework_id_by_source_uri(&source_uri).await? {
+            Some(framework_id) => self.repository.get_framework(&framework_id).await?,
+            None => None,
+        };
+
+        let framework = CompetencyFramework {
+            id: existing.as_ref().map(|f| f.id.clone()).unwrap_or_else(FrameworkId::new),
+            name,
+            source,
+            source_uri,
+            version,
+            language,
+            competencies,
+            imported_at: existing.as_ref().map(|f| f.imported_at).unwrap_or(now),
+            updated_at: now,
+        };
+        framework.validate()?;
+
+        self.repository.upsert_framework(&framework).await?;
+
+        Ok(framework)
+    }
+
+    /// Relacionar una actividad xAPI con una competencia del marco
+    pub async fn map_activity(
+        &self,
+        framework_id: &FrameworkId,
+        activity_iri: &str,
+        competency_uri: &str,
+        weight: f64,
+    ) -> Result<ActivityMapping> {
+        let framework = self.get_existing_framework(framework_id).await?;
+
+        if framework.competency(competency_uri).is_none() {
+            bail!("La competencia {} no pertenece al marco {}", competency_uri, framework.name);
+        }
+        if !activity_iri.starts_with("http://") && !activity_iri.starts_with("https://") {
+            bail!("La actividad debe identificarse con un IRI absoluto");
+        }
+        if !(weight > 0.0 && weight <= 1.0) {
+            bail!("El peso de la correspondencia debe estar en (0, 1]");
+        }
+
+        let mapping = ActivityMapping {
+            framework_id: framework_id.clone(),
+            activity_iri: activity_iri.to_string(),
+            competency_uri: competency_uri.to_string(),
+            weight,
+        };
+
+        self.repository.upsert_mapping(&mapping).await?;
+
+        Ok(mapping)
+    }
+
+    pub async fn unmap_activity(
+        &self,
+        framework_id: &FrameworkId,
+        activity_iri: &str,
+        competency_uri: &str,
+    ) -> Result<bool> {
+        self.repository.delete_mapping(framework_id, activity_iri, competency_uri).await
+    }
+
+    pub async fn get_framework(&self, framework_id: &FrameworkId) -> Result<Option<CompetencyFramework>> {
+        self.repository.get_framework(framework_id).await
+    }
+
+    pub async fn get_frameworks(&self) -> Result<Vec<CompetencyFramework>> {
+        let mut frameworks = Vec::new();
+
+        for framework_id in self.repository.get_framework_ids().await? {
+            if let Some(framework) = self.repository.get_framework(&framework_id).await? {
+                frameworks.push(framework);
+            }
+        }
+
+        Ok(frameworks)
+    }
+
+    pub async fn get_mappings(&self, framework_id: &FrameworkId) -> Result<Vec<ActivityMapping>> {
+        self.repository.get_mappings(framework_id).await
+    }
+
+    /// Perfil de competencias de un usuario, con la evidencia de cada competencia
+    pub async fn compute_profile(&self, user_address: &str, framework_id: &FrameworkId) -> Result<CompetencyProfile> {
+        let framework = self.get_existing_framework(framework_id).await?;
+        let mappings = self.repository.get_mappings(framework_id).await?;
+        let interactions = self.passport.get_user_learning_history(user_address).await?;
+
+        Ok(CompetencyProfile::compute(user_address, &framework, &mappings, &interactions))
+    }
+
+    /// Alineaciones de competencias para incluir en una credencial exportada
+    pub async fn credential_alignments(&self, user_address: &str, framework_id: &FrameworkId) -> Result<Value> {
+        let framework = self.get_existing_framework(framework_id).await?;
+        let profile = self.compute_profile(user_address, framework_id).await?;
+
+        Ok(profile.credential_alignments(&framework))
+    }
+
+    async fn get_existing_framework(&self, framework_id: &FrameworkId) -> Result<CompetencyFramework> {
+        self.repository
+            .get_framework(framework_id)
+            .await?
+            .ok_or_else(|| anyhow!("Marco de competencias no encontrado: {}", framework_id.0))
+    }
+}
@@ -1,32 +1,70 @@
 // Servicios de aplicación para el módulo learning_passport
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use uuid::Uuid;
-use chrono::Utc;
-use ed25519_dalek::{SigningKey, VerifyingKey, Signature, Signer, Verifier};
-use serde_json;
+use chrono::{Duration, Utc};
+use std::sync::Arc;
+use tokio::sync::broadcast;
+
+use identity::domain::{parse_verifying_key, verify_signature, HumanityRecord};
+use identity::service::{HumanityVerifier, IssuerSigner, IssuerVerifier};
 
 use crate::repository::LearningPassportRepository;
 use crate::domain::{
-    LearningInteraction, LifeLearningPassport, LearningPassportId, 
-    LearningInteractionId, LearningPassportEvent, PassportStatistics
+    InteractionAuthority, InteractionFilter, InteractionVerification, LearningInteraction, LifeLearningPassport, LearningPassportId, 
+    LearningInteractionId, LearningPassportEvent, NormalizedTerm, PassportStatistics, PassportSummary, VocabularyKind,
+    CURRENT_PAYLOAD_VERSION,
 };
 
+pub mod competency;
+pub mod vocabulary;
+
+pub use competency::CompetencyService;
+pub use vocabulary::VocabularyService;
+
+/// Eventos retenidos para suscriptores lentos antes de que pierdan mensajes
+pub const EVENT_CHANNEL_CAPACITY: usize = 256;
+
+/// Desfase máximo entre el timestamp que firma la cartera y la recepción de la interacción
+pub const SIGNED_INTERACTION_MAX_SKEW_MINUTES: i64 = 10;
+
 pub struct LearningPassportService {
     repository: LearningPassportRepository,
+    humanity_verifier: Arc<dyn HumanityVerifier>,
+    issuer_verifier: Arc<dyn IssuerVerifier>,
+    platform_signer: IssuerSigner, // Contrafirma las interacciones autodeclaradas
+    vocabulary: Arc<VocabularyService>,
+    events: broadcast::Sender<LearningPassportEvent>,
 }
 
 impl LearningPassportService {
-    pub fn new(repository: LearningPassportRepository) -> Self {
-        Self { repository }
+    pub fn new(
+        repository: LearningPassportRepository,
+        humanity_verifier: Arc<dyn HumanityVerifier>,
+        issuer_verifier: Arc<dyn IssuerVerifier>,
+        platform_signer: IssuerSigner,
+        vocabulary: Arc<VocabularyService>,
+    ) -> Self {
+        let (events, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+        Self { repository, humanity_verifier, issuer_verifier, platform_signer, vocabulary, events }
+    }
+    
+    /// Suscribirse a los eventos de dominio emitidos a partir de ahora
+    pub fn subscribe(&self) -> broadcast::Receiver<LearningPassportEvent> {
+        self.events.subscribe()
+    }
+    
+    /// Publicar un evento de dominio; sin suscriptores se descarta
+    fn emit_event(&self, event: LearningPassportEvent) {
+        let _ = self.events.send(event);
     }
     
     /// Crear un nuevo pasaporte de aprendizaje para un usuario
-    pub async fn create_passport(&self, user_address: &str, humanity_proof_key: &str) -> Result<LifeLearningPassport> {
+    pub async fn create_passport(&self, user_address: &str, verifying_key: &str) -> Result<LifeLearningPassport> {
         let passport = LifeLearningPassport {
             id: LearningPassportId::new(),
             user_address: user_address.to_string(),
-            humanity_proof_key: humanity_proof_key.to_string(),
+            verifying_key: verifying_key.to_string(),
             interactions: Vec::new(),
             created_at: Utc::now(),
             updated_at: Utc::now(),
@@ -35,8 +73,11 @@ impl LearningPassportService {
         
         self.repository.create_passport(&passport).await?;
         
-        // TODO: Emitir evento de dominio
-        // self.emit_event(LearningPassportEvent::PassportCreated { ... }).await?;
+        self.emit_event(LearningPassportEvent::PassportCreated {
+            passport_id: passport.id.clone(),
+            user_address: passport.user_address.clone(),
+            timestamp: passport.created_at,
+        });
         
         Ok(passport)
     }
@@ -46,95 +87,302 @@ impl LearningPassportService {
         self.repository.get_passport_by_user_address(user_address).await
     }
     
-    /// Agregar nueva interacción de aprendizaje al pasaporte
+    /// Obtener los pasaportes de varios usuarios; los que no tienen pasaporte no aparecen
+    pub async fn get_passports_by_users(&self, user_addresses: &[String]) -> Result<Vec<LifeLearningPassport>> {
+        self.repository.get_passports_by_user_addresses(user_addresses).await
+    }
+    
+    /// Obtener interacciones por ID, de cualquier pasaporte
+    pub async fn get_interactions(&self, interaction_ids: &[LearningInteractionId]) -> Result<Vec<LearningInteraction>> {
+        let ids: Vec<Uuid> = interaction_ids.iter().map(|id| id.0).collect();
+        self.repository.get_interactions_by_ids(&ids).await
+    }
+    
+    /// Agregar una interacción registrada por la plataforma, que la contrafirma
+    ///
+    /// No lleva firma del estudiante: la plataforma no tiene su clave privada.
+    /// Las interacciones firmadas en la cartera entran por `add_signed_learning_interaction`.
+    #[allow(clippy::too_many_arguments)]
     pub async fn add_learning_interaction(
         &self,
         user_address: &str,
         actor: &str,
         verb: &str,
         object: &str,
+        activity_type: Option<&str>,
         result: Option<crate::domain::LearningResult>,
         context: Option<crate::domain::LearningContext>,
     ) -> Result<LearningInteraction> {
-        // Obtener o crear pasaporte del usuario
-        let mut passport = self.repository.get_passport_by_user_address(user_address).await?;
-        
-        if passport.is_none() {
-            // Crear pasaporte si no existe
-            let new_passport = self.create_passport(user_address, "").await?; // TODO: Obtener humanity_proof_key
-            passport = Some(new_passport);
-        }
+        self.record_interaction(user_address, actor, verb, object, activity_type, result, context, false).await
+    }
+    
+    /// Agregar una interacción que declara el propio estudiante
+    ///
+    /// La plataforma la contrafirma igual, pero queda marcada como autodeclarada y no
+    /// cuenta como evidencia de competencias.
+    pub async fn add_self_reported_interaction(
+        &self,
+        user_address: &str,
+        verb: &str,
+        object: &str,
+        activity_type: Option<&str>,
+        result: Option<crate::domain::LearningResult>,
+        context: Option<crate::domain::LearningContext>,
+    ) -> Result<LearningInteraction> {
+        self.record_interaction(user_address, user_address, verb, object, activity_type, result, context, true).await
+    }
+    
+    #[allow(clippy::too_many_arguments)]
+    async fn record_interaction(
+        &self,
+        user_address: &str,
+        actor: &str,
+        verb: &str,
+        object: &str,
+        activity_type: Option<&str>,
+        result: Option<crate::domain::LearningResult>,
+        context: Option<crate::domain::LearningContext>,
+        self_reported: bool,
+    ) -> Result<LearningInteraction> {
+        // Normalizar verbo y tipo de actividad al vocabulario antes de firmar
+        let verb = self.vocabulary.normalize(VocabularyKind::Verb, verb).await?;
+        let activity_type = match activity_type {
+            Some(activity_type) => Some(self.vocabulary.normalize(VocabularyKind::ActivityType, activity_type).await?),
+            None => None,
+        };
         
-        let passport = passport.unwrap();
+        let passport = self.get_or_create_passport(user_address).await?;
         
         // Crear nueva interacción
         let interaction = LearningInteraction {
             id: LearningInteractionId::new(),
             passport_id: passport.id.clone(),
             actor: actor.to_string(),
-            verb: verb.to_string(),
+            verb: verb.value().to_string(),
             object: object.to_string(),
+            activity_type: activity_type.map(|t| t.value().to_string()),
             result,
             context,
             timestamp: Utc::now(),
-            humanity_proof_key: passport.humanity_proof_key.clone(),
-            signature: None, // Se firmará después
+            verifying_key: passport.verifying_key.clone(),
+            signature: None,
+            authority: Some(self.platform_authority()),
             stored_in_blockchain: false,
+            payload_version: CURRENT_PAYLOAD_VERSION,
+            self_reported,
         };
         
-        // Firmar la interacción con la clave de humanidad
-        let signed_interaction = self.sign_interaction(&interaction, &passport.humanity_proof_key)?;
+        self.countersign_and_store(&passport, interaction).await
+    }
+    
+    /// Agregar una interacción firmada por el estudiante en su cartera
+    ///
+    /// La cartera fija id, timestamp y emisor (`platform_authority`) antes de firmar, así
+    /// que el contenido no puede reescribirse: debe usar los IRIs canónicos del vocabulario.
+    /// La plataforma contrafirma después de verificar la firma con la clave del pasaporte.
+    pub async fn add_signed_learning_interaction(
+        &self,
+        user_address: &str,
+        interaction: LearningInteraction,
+    ) -> Result<LearningInteraction> {
+        if interaction.payload_version != CURRENT_PAYLOAD_VERSION {
+            bail!("La interacción debe firmarse con el formato {}", CURRENT_PAYLOAD_VERSION);
+        }
+        
+        let platform = self.platform_authority();
+        match &interaction.authority {
+            Some(authority) if authority.issuer_id == platform.issuer_id && authority.key_id == platform.key_id => {}
+            _ => bail!("La interacción debe indicar como emisor la clave vigente de la plataforma"),
+        }
+        
+        let skew = (Utc::now() - interaction.timestamp).abs();
+        if skew > Duration::minutes(SIGNED_INTERACTION_MAX_SKEW_MINUTES) {
+            bail!("El timestamp de la interacción difiere más de {} minutos de la hora del servidor", SIGNED_INTERACTION_MAX_SKEW_MINUTES);
+        }
+        
+        self.require_canonical_terms(&interaction).await?;
+        
+        let passport = self.get_or_create_passport(user_address).await?;
+        
+        let mut interaction = interaction;
+        interaction.passport_id = passport.id.clone();
+        interaction.verifying_key = passport.verifying_key.clone();
+        interaction.stored_in_blockchain = false;
+        interaction.self_reported = true;
+        
+        if self.verify_interaction_signature(&interaction).await? != Some(true) {
+            bail!("La interacción no está firmada con la clave del pasaporte de {}", user_address);
+        }
+        
+        self.countersign_and_store(&passport, interaction).await
+    }
+    
+    /// Emisor con el que la plataforma contrafirma, tal como debe figurar en `authority`
+    pub fn platform_authority(&self) -> InteractionAuthority {
+        InteractionAuthority {
+            issuer_id: self.platform_signer.issuer_id().0.to_string(),
+            key_id: self.platform_signer.key_id().to_string(),
+            signature: None,
+        }
+    }
+    
+    /// Contrafirmar como plataforma emisora, guardar y publicar la interacción
+    async fn countersign_and_store(
+        &self,
+        passport: &LifeLearningPassport,
+        interaction: LearningInteraction,
+    ) -> Result<LearningInteraction> {
+        let mut interaction = interaction;
+        let countersignature = self.platform_signer.sign(&interaction.signing_payload()?);
+        if let Some(authority) = interaction.authority.as_mut() {
+            authority.signature = Some(countersignature);
+        }
         
         // Guardar en base de datos
-        self.repository.add_interaction(&signed_interaction).await?;
+        self.repository.add_interaction(&interaction).await?;
+        
+        self.emit_event(LearningPassportEvent::InteractionAdded {
+            passport_id: passport.id.clone(),
+            user_address: passport.user_address.clone(),
+            interaction_id: interaction.id.clone(),
+            timestamp: interaction.timestamp,
+        });
         
-        // TODO: Emitir evento de dominio
-        // self.emit_event(LearningPassportEvent::InteractionAdded { ... }).await?;
+        Ok(interaction)
+    }
+    
+    /// Rechazar términos que el vocabulario reescribiría: el contenido ya está firmado
+    async fn require_canonical_terms(&self, interaction: &LearningInteraction) -> Result<()> {
+        let mut terms = vec![(VocabularyKind::Verb, interaction.verb.as_str())];
+        if let Some(activity_type) = &interaction.activity_type {
+            terms.push((VocabularyKind::ActivityType, activity_type.as_str()));
+        }
+        for (kind, term) in terms {
+            if let NormalizedTerm::Aliased(iri) = self.vocabulary.normalize(kind, term).await? {
+                bail!("Debe usarse el IRI canónico {} en lugar de '{}'", iri, term);
+            }
+        }
         
-        Ok(signed_interaction)
+        Ok(())
     }
     
-    /// Firmar una interacción de aprendizaje
-    fn sign_interaction(&self, interaction: &LearningInteraction, humanity_proof_key: &str) -> Result<LearningInteraction> {
-        // Convertir humanity_proof_key a SigningKey
-        let key_bytes = hex::decode(humanity_proof_key)?;
-        let signing_key = SigningKey::from_bytes(&key_bytes[..32].try_into()?);
+    /// Ingerir una interacción enviada por un emisor institucional en nombre de un estudiante
+    ///
+    /// El emisor aporta el statement completo (id, timestamp y `authority` contrafirmada)
+    /// y se guarda tras verificar la contrafirma, sin firma del estudiante.
+    /// Como el contenido contrafirmado no puede reescribirse, el emisor debe usar
+    /// los IRIs canónicos del vocabulario. El emisor debe pertenecer a la institución
+    /// en cuyo nombre se envía y el actor debe ser el titular del pasaporte.
+    pub async fn ingest_issued_interaction(
+        &self,
+        user_address: &str,
+        institution_id: &str,
+        interaction: LearningInteraction,
+    ) -> Result<LearningInteraction> {
+        let Some(authority) = &interaction.authority else {
+            bail!("La interacción no incluye emisor (authority)");
+        };
+        if interaction.payload_version != CURRENT_PAYLOAD_VERSION {
+            bail!("La interacción debe contrafirmarse con el formato {}", CURRENT_PAYLOAD_VERSION);
+        }
+        if interaction.actor != user_address {
+            bail!("El actor de la interacción ({}) no es el titular del pasaporte {}", interaction.actor, user_address);
+        }
+        if !self.issuer_verifier.issuer_belongs_to(&authority.issuer_id, institution_id).await? {
+            bail!("El emisor {} no pertenece a la institución {}", authority.issuer_id, institution_id);
+        }
         
-        // Crear datos para firmar (interacción serializada)
-        let interaction_data = serde_json::to_string(&interaction)?;
-        let message = interaction_data.as_bytes();
+        self.require_canonical_terms(&interaction).await?;
         
-        // Firmar la interacción
-        let signature = signing_key.sign(message);
-        let signature_hex = hex::encode(signature.to_bytes());
+        if !self.verify_authority_signature(&interaction).await? {
+            bail!("Contrafirma inválida del emisor {}", authority.issuer_id);
+        }
+        
+        let passport = self.get_or_create_passport(user_address).await?;
         
-        // Crear interacción firmada
-        let mut signed_interaction = interaction.clone();
-        signed_interaction.signature = Some(signature_hex);
+        let mut interaction = interaction;
+        interaction.passport_id = passport.id.clone();
+        interaction.verifying_key = passport.verifying_key.clone();
+        interaction.signature = None;
+        interaction.stored_in_blockchain = false;
+        interaction.self_reported = false;
         
-        Ok(signed_interaction)
+        self.repository.add_interaction(&interaction).await?;
+        
+        self.emit_event(LearningPassportEvent::InteractionAdded {
+            passport_id: passport.id.clone(),
+            user_address: passport.user_address.clone(),
+            interaction_id: interaction.id.clone(),
+            timestamp: interaction.timestamp,
+        });
+        
+        Ok(interaction)
     }
     
-    /// Verificar firma de una interacción
-    pub fn verify_interaction_signature(&self, interaction: &LearningInteraction, humanity_proof_key: &str) -> Result<bool> {
-        let key_bytes = hex::decode(humanity_proof_key)?;
-        let verifying_key = VerifyingKey::from_bytes(&key_bytes[..32].try_into()?)?;
+    /// Obtener el pasaporte del usuario, creándolo si todavía no existe
+    ///
+    /// Sólo se crea para humanos verificados y con la clave pública de su registro, de modo
+    /// que nunca se guarda un pasaporte sin clave con la que verificar sus firmas.
+    async fn get_or_create_passport(&self, user_address: &str) -> Result<LifeLearningPassport> {
+        if let Some(passport) = self.repository.get_passport_by_user_address(user_address).await? {
+            return Ok(passport);
+        }
         
-        // Recrear datos de la interacción (sin la firma)
-        let mut interaction_without_signature = interaction.clone();
-        interaction_without_signature.signature = None;
-        let interaction_data = serde_json::to_string(&interaction_without_signature)?;
-        let message = interaction_data.as_bytes();
+        match self.humanity_verifier.get_humanity_record(user_address).await? {
+            Some(record) if record.is_active() => self.create_passport(user_address, &record.verifying_key).await,
+            _ => bail!("{} no tiene una humanidad verificada con la que crear su pasaporte", user_address),
+        }
+    }
+    
+    /// Verificar la firma del estudiante con la clave de su pasaporte; `None` si no firmó
+    ///
+    /// Las interacciones heredadas se verifican con el formato y la clave de entonces,
+    /// derivada del compromiso de humanidad del titular.
+    pub async fn verify_interaction_signature(&self, interaction: &LearningInteraction) -> Result<Option<bool>> {
+        let Some(signature) = &interaction.signature else {
+            return Ok(None);
+        };
         
-        // Verificar firma
-        if let Some(signature_hex) = &interaction.signature {
-            let signature_bytes = hex::decode(signature_hex)?;
-            let signature = Signature::from_bytes(&signature_bytes[..64].try_into()?)?;
-            
-            Ok(verifying_key.verify(message, &signature).is_ok())
-        } else {
-            Ok(false)
+        if interaction.is_legacy() {
+            let Some(record) = self.legacy_humanity_record(interaction).await? else {
+                return Ok(Some(false));
+            };
+            let verifying_key = record.commitment.legacy_verifying_key()?;
+            let payload = interaction.legacy_signing_payload(&record.commitment.0)?;
+            return Ok(Some(verify_signature(&verifying_key, &payload, signature)));
         }
+        
+        let verifying_key = parse_verifying_key(&interaction.verifying_key)?;
+        Ok(Some(verify_signature(&verifying_key, &interaction.signing_payload()?, signature)))
+    }
+    
+    /// Registro de humanidad del titular del pasaporte de una interacción heredada
+    async fn legacy_humanity_record(&self, interaction: &LearningInteraction) -> Result<Option<HumanityRecord>> {
+        let Some(passport) = self.repository.get_passport_by_id(&interaction.passport_id).await? else {
+            return Ok(None);
+        };
+        
+        self.humanity_verifier.get_humanity_record(&passport.user_address).await
+    }
+    
+    /// Verificar la contrafirma del emisor con la clave vigente en el momento de la interacción
+    pub async fn verify_authority_signature(&self, interaction: &LearningInteraction) -> Result<bool> {
+        let Some(authority) = &interaction.authority else {
+            return Ok(false);
+        };
+        let Some(signature) = &authority.signature else {
+            return Ok(false);
+        };
+        
+        self.issuer_verifier
+            .verify_countersignature(
+                &authority.issuer_id,
+                &authority.key_id,
+                &interaction.signing_payload()?,
+                signature,
+                interaction.timestamp,
+            )
+            .await
     }
     
     /// Obtener estadísticas del pasaporte
@@ -157,6 +405,15 @@ impl LearningPassportService {
             
             // Marcar como almacenada en blockchain
             self.repository.mark_interaction_stored_in_blockchain(&interaction.id).await?;
+            
+            if let Some(passport) = self.repository.get_passport_by_id(&interaction.passport_id).await? {
+                self.emit_event(LearningPassportEvent::InteractionStoredInBlockchain {
+                    passport_id: passport.id,
+                    user_address: passport.user_address,
+                    interaction_id: interaction.id,
+                    timestamp: Utc::now(),
+                });
+            }
         }
         
         Ok(())
@@ -171,20 +428,94 @@ impl LearningPassportService {
         }
     }
     
-    /// Validar interacción de aprendizaje (verificar humanidad y firma)
+    pub async fn count_user_interactions(&self, user_address: &str, filter: &InteractionFilter) -> Result<i64> {
+        self.repository.count_interactions_by_user_address(user_address, filter).await
+    }
+    
+    /// Página del historial de un usuario con las interacciones que cumplen el filtro
+    pub async fn list_user_interactions(
+        &self,
+        user_address: &str,
+        filter: &InteractionFilter,
+        descending: bool,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<LearningInteraction>> {
+        self.repository.list_interactions_by_user_address(user_address, filter, descending, limit, offset).await
+    }
+    
+    /// Validar interacción de aprendizaje (verificar humanidad, firma y contrafirma)
+    ///
+    /// Las interacciones heredadas no tienen contrafirma: se validan como entonces,
+    /// con la firma del estudiante y su humanidad verificada.
     pub async fn validate_interaction(&self, interaction: &LearningInteraction) -> Result<bool> {
-        // Verificar que la interacción tenga firma
-        if interaction.signature.is_none() {
-            return Ok(false);
+        if interaction.is_legacy() {
+            let signature_valid = self.verify_interaction_signature(interaction).await? == Some(true);
+            let humanity_valid = self.legacy_humanity_record(interaction).await?.is_some_and(|r| r.is_active());
+            return Ok(signature_valid && humanity_valid);
         }
         
-        // Verificar firma de la interacción
-        let signature_valid = self.verify_interaction_signature(interaction, &interaction.humanity_proof_key)?;
+        // La firma del estudiante es opcional, pero si existe debe ser válida
+        let signature_valid = self.verify_interaction_signature(interaction).await?.unwrap_or(true);
+        
+        // Verificar la contrafirma del emisor (xAPI authority)
+        let authority_valid = self.verify_authority_signature(interaction).await?;
+        
+        // Verificar que la clave pertenece a un humano verificado
+        let humanity_valid = self.humanity_verifier.is_verified_human(&interaction.verifying_key).await?;
         
-        // TODO: Verificar humanidad del usuario (iris, genome, etc.)
-        // let humanity_valid = self.verify_humanity(&interaction.humanity_proof_key).await?;
+        Ok(signature_valid && authority_valid && humanity_valid)
+    }
+    
+    /// Buscar pasaportes por el inicio de la dirección del usuario
+    pub async fn search_passports(&self, address_prefix: &str, limit: i64) -> Result<Vec<PassportSummary>> {
+        self.repository.search_passports_by_user_address(address_prefix.trim(), limit).await
+    }
+    
+    /// Volver a verificar firma y contrafirma de una interacción registrada
+    ///
+    /// El anclaje no se verifica: se informa el que registró la sincronización con Keikochain.
+    /// Una firma malformada cuenta como no válida en lugar de abortar la verificación.
+    pub async fn verify_interaction(&self, interaction: &LearningInteraction) -> Result<InteractionVerification> {
+        let signature_valid = self.verify_interaction_signature(interaction).await.unwrap_or(Some(false));
+        let authority_valid = match &interaction.authority {
+            Some(_) => Some(self.verify_authority_signature(interaction).await.unwrap_or(false)),
+            None => None,
+        };
+        
+        // TODO: Consultar el anclaje en Keikochain a través del gRPC Gateway;
+        // mientras tanto se usa el estado que registra la sincronización
+        Ok(InteractionVerification {
+            interaction_id: interaction.id.clone(),
+            signature_valid,
+            authority_valid,
+            stored_in_blockchain: interaction.stored_in_blockchain,
+            legacy: interaction.is_legacy(),
+        })
+    }
+    
+    /// Verificar varias interacciones por ID; las que no existen se omiten
+    pub async fn verify_interactions(&self, interaction_ids: &[Uuid]) -> Result<Vec<InteractionVerification>> {
+        let mut verifications = Vec::new();
+        for interaction in self.repository.get_interactions_by_ids(interaction_ids).await? {
+            verifications.push(self.verify_interaction(&interaction).await?);
+        }
+        
+        Ok(verifications)
+    }
+    
+    /// Verificar todas las interacciones del pasaporte de un usuario, en orden cronológico
+    pub async fn verify_user_interactions(&self, user_address: &str) -> Result<Vec<InteractionVerification>> {
+        let Some(passport) = self.repository.get_passport_by_user_address(user_address).await? else {
+            bail!("No existe pasaporte para el usuario {}", user_address);
+        };
+        
+        let mut verifications = Vec::new();
+        for interaction in passport.get_interactions_chronological() {
+            verifications.push(self.verify_interaction(interaction).await?);
+        }
         
-        Ok(signature_valid) // && humanity_valid)
+        Ok(verifications)
     }
     
     /// Generar enlace verificable para compartir pasaporte
@@ -0,0 +1,163 @@
+// Registro de vocabulario xAPI: normalización de verbos y tipos de actividad en la ingesta
+// Los términos nuevos llegan a través de propuestas de gobernanza aprobadas
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{bail, Result};
+use chrono::Utc;
+use serde::Deserialize;
+use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+use crate::domain::{
+    DisplayNames, NormalizedTerm, UnknownTermPolicy, VocabularyEntry, VocabularyKind, VocabularyRegistry,
+};
+use crate::repository::VocabularyRepository;
+
+/// Espera antes de volver a escuchar avisos tras un fallo de la conexión
+const LISTENER_RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// Vocabulario base (verbos y tipos de actividad ADL) sembrado en un registro vacío
+const BASE_VOCABULARY: &str = include_str!("../../vocabulary/base_vocabulary.json");
+
+/// Entrada del fichero de vocabulario base
+#[derive(Debug, Deserialize)]
+struct BaseVocabularyEntry {
+    iri: String,
+    kind: VocabularyKind,
+    aliases: Vec<String>,
+    display: DisplayNames,
+    #[serde(default)]
+    description: Option<String>,
+}
+
+pub struct VocabularyService {
+    repository: VocabularyRepository,
+    policy: UnknownTermPolicy,
+    registry: RwLock<VocabularyRegistry>,
+}
+
+impl VocabularyService {
+    /// Cargar el vocabulario persistido, sembrando el vocabulario base si está vacío
+    pub async fn load(repository: VocabularyRepository, policy: UnknownTermPolicy) -> Result<Self> {
+        let mut entries = repository.get_entries().await?;
+
+        if entries.is_empty() {
+            let base: Vec<BaseVocabularyEntry> = serde_json::from_str(BASE_VOCABULARY)?;
+            for base_entry in base {
+                let entry = VocabularyEntry {
+                    iri: base_entry.iri,
+                    kind: base_entry.kind,
+                    aliases: base_entry.aliases,
+                    display: base_entry.display,
+                    description: base_entry.description,
+                    proposal_id: None,
+                    added_at: Utc::now(),
+                };
+                repository.upsert_entry(&entry).await?;
+                entries.push(entry);
+            }
+        }
+
+        let registry = VocabularyRegistry::new(entries)?;
+
+        Ok(Self { repository, policy, registry: RwLock::new(registry) })
+    }
+
+    /// Volver a leer el vocabulario persistido (tras cambios hechos por otra instancia)
+    pub async fn reload(&self) -> Result<()> {
+        let registry = VocabularyRegistry::new(self.repository.get_entries().await?)?;
+        *self.registry.write().await = registry;
+
+        Ok(())
+    }
+
+    /// Recargar el vocabulario en segundo plano cada vez que una instancia publica cambios
+    ///
+    /// Tras perder la conexión también se recarga, por si se perdió algún aviso mientras tanto.
+    pub async fn spawn_change_listener(self: Arc<Self>) -> Result<JoinHandle<()>> {
+        let mut listener = self.repository.listen_changes().await?;
+
+        Ok(tokio::spawn(async move {
+            loop {
+                // `None` indica que la conexión se perdió; se restablece en la siguiente espera
+                if let Err(err) = listener.try_recv().await {
+                    tracing::warn!("No se pudieron escuchar los cambios del vocabulario: {:#}", err);
+                    tokio::time::sleep(LISTENER_RETRY_DELAY).await;
+                    continue;
+                }
+                if let Err(err) = self.reload().await {
+                    tracing::error!("No se pudo recargar el vocabulario: {:#}", err);
+                }
+            }
+        }))
+    }
+
+    /// Agregar o actualizar términos aprobados por gobernanza
+    pub async fn add_entries(&self, entries: Vec<VocabularyEntry>, proposal_id: Option<Uuid>) -> Result<()> {
+        // Validar el lote completo sobre una copia antes de persistir nada
+        let mut candidate = self.registry.read().await.clone();
+
+        for entry in &entries {
+            validate_entry(entry)?;
+            candidate.insert(entry.clone())?;
+        }
+
+        for mut entry in entries {
+            entry.proposal_id = proposal_id;
+            entry.added_at = Utc::now();
+            self.repository.upsert_entry(&entry).await?;
+        }
+
+        self.reload().await?;
+        self.repository.publish_change().await
+    }
+
+    /// Normalizar un término recibido según el vocabulario y la política de términos desconocidos
+    pub async fn normalize(&self, kind: VocabularyKind, term: &str) -> Result<NormalizedTerm> {
+        let normalized = self.registry.read().await.normalize(kind, term);
+
+        if let NormalizedTerm::Unknown(term) = &normalized {
+            match self.policy {
+                UnknownTermPolicy::Warn => {
+                    tracing::warn!("Término xAPI fuera del vocabulario ({}): {}", kind.as_str(), term);
+                }
+                UnknownTermPolicy::Reject => {
+                    bail!("Término xAPI fuera del vocabulario ({}): {}", kind.as_str(), term);
+                }
+            }
+        }
+
+        Ok(normalized)
+    }
+
+    /// Nombre visible de un término en un idioma
+    pub async fn display_name(&self, kind: VocabularyKind, term: &str, language: &str) -> Option<String> {
+        self.registry
+            .read()
+            .await
+            .resolve(kind, term)
+            .map(|entry| entry.display.for_language(language).to_string())
+    }
+
+    /// Términos de un tipo
+    pub async fn get_entries(&self, kind: VocabularyKind) -> Vec<VocabularyEntry> {
+        self.registry.read().await.entries(kind).into_iter().cloned().collect()
+    }
+}
+
+/// Un término debe tener IRI absoluto y nombre visible en los tres idiomas
+fn validate_entry(entry: &VocabularyEntry) -> Result<()> {
+    if url::Url::parse(&entry.iri).is_err() {
+        bail!("El IRI del término no es absoluto: {}", entry.iri);
+    }
+
+    let names = [&entry.display.es, &entry.display.pt, &entry.display.en];
+    if names.iter().any(|name| name.trim().is_empty()) {
+        bail!("El término {} necesita nombre visible en español, portugués e inglés", entry.iri);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+-- Perfiles de tutores y sesiones de tutoría
+
+CREATE TABLE tutor_profiles (
+    user_address TEXT PRIMARY KEY,
+    display_name TEXT NOT NULL,
+    bio TEXT NOT NULL,
+    subjects JSONB NOT NULL,
+    languages JSONB NOT NULL,
+    availability JSONB NOT NULL,
+    active BOOLEAN NOT NULL,
+    verification TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE TABLE tutoring_sessions (
+    id UUID PRIMARY KEY,
+    tutor TEXT NOT NULL,
+    learner TEXT NOT NULL,
+    subject JSONB NOT NULL,
+    scheduled_start TIMESTAMPTZ NOT NULL,
+    scheduled_end TIMESTAMPTZ NOT NULL,
+    status TEXT NOT NULL,
+    message TEXT,
+    cancelled_by TEXT,
+    cancellation_reason TEXT,
+    cancelled_at TIMESTAMPTZ,
+    absent_party TEXT,
+    no_show_report JSONB,
+    accepted_at TIMESTAMPTZ,
+    started_at TIMESTAMPTZ,
+    completed_at TIMESTAMPTZ,
+    tutor_interaction_id UUID,
+    learner_interaction_id UUID,
+    requested_at TIMESTAMPTZ NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX tutoring_sessions_tutor_idx ON tutoring_sessions (tutor, scheduled_start);
+CREATE INDEX tutoring_sessions_learner_idx ON tutoring_sessions (learner, scheduled_start);
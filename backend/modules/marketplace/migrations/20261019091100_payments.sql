@@ -0,0 +1,30 @@
+-- Pagos en escrow de las sesiones y movimientos de su libro mayor
+-- Cada movimiento se registra como pendiente, con su transferencia, antes de enviarse;
+-- `idempotency_key` impide registrar dos veces el mismo tramo
+
+CREATE TABLE payments (
+    id UUID PRIMARY KEY,
+    session_id UUID NOT NULL UNIQUE REFERENCES tutoring_sessions(id),
+    payer TEXT NOT NULL,
+    payee TEXT NOT NULL,
+    amount BIGINT NOT NULL,
+    status TEXT NOT NULL,
+    deposit_reference TEXT NOT NULL UNIQUE,
+    plan JSONB,
+    created_at TIMESTAMPTZ NOT NULL,
+    settled_at TIMESTAMPTZ
+);
+
+CREATE TABLE payment_ledger (
+    id UUID PRIMARY KEY,
+    payment_id UUID NOT NULL REFERENCES payments(id),
+    kind TEXT NOT NULL,
+    account TEXT NOT NULL,
+    amount BIGINT NOT NULL,
+    status TEXT NOT NULL,
+    idempotency_key TEXT NOT NULL UNIQUE,
+    transaction_hash TEXT NOT NULL,
+    payout JSONB,
+    created_at TIMESTAMPTZ NOT NULL,
+    UNIQUE (payment_id, kind)
+);
@@ -0,0 +1,32 @@
+-- Espacios de aprendizaje seguros y validaciones de la comunidad
+
+CREATE TABLE learning_spaces (
+    id UUID PRIMARY KEY,
+    host TEXT NOT NULL,
+    name TEXT NOT NULL,
+    description TEXT NOT NULL,
+    address TEXT NOT NULL,
+    latitude DOUBLE PRECISION NOT NULL,
+    longitude DOUBLE PRECISION NOT NULL,
+    capacity INTEGER NOT NULL,
+    accessibility JSONB NOT NULL,
+    opening_hours JSONB NOT NULL,
+    timezone TEXT NOT NULL,
+    status TEXT NOT NULL,
+    status_reason TEXT,
+    status_proposal_id UUID,
+    status_changed_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX learning_spaces_location_idx ON learning_spaces (latitude, longitude);
+
+CREATE TABLE space_validations (
+    space_id UUID NOT NULL REFERENCES learning_spaces(id),
+    member TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    comment TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (space_id, member)
+);
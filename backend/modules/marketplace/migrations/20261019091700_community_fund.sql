@@ -0,0 +1,12 @@
+-- Desembolsos del fondo comunitario aprobados por gobernanza, uno por propuesta
+-- Como los movimientos de pagos, se registran como pendientes antes de enviar la transferencia
+
+CREATE TABLE community_fund_disbursements (
+    proposal_id UUID PRIMARY KEY,
+    recipient TEXT NOT NULL,
+    amount BIGINT NOT NULL,
+    status TEXT NOT NULL,
+    transaction_hash TEXT NOT NULL,
+    payout JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL
+);
@@ -0,0 +1,20 @@
+// Fondo comunitario: transferencias a iniciativas de aprendizaje aprobadas en gobernanza
+// Cada propuesta financiada genera un único desembolso, registrado antes de enviarse
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{LedgerEntryStatus, PreparedPayout};
+
+/// Desembolso del fondo comunitario para una propuesta aprobada
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FundDisbursement {
+    pub proposal_id: Uuid,                  // Una propuesta se financia una sola vez
+    pub recipient: String,
+    pub amount: u64,
+    pub status: LedgerEntryStatus,
+    pub transaction_hash: String,
+    pub payout: PreparedPayout,
+    pub created_at: DateTime<Utc>,
+}
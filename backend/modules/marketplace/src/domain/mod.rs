@@ -0,0 +1,11 @@
+// Entidades de dominio para el módulo marketplace
+
+pub mod fund;
+pub mod payment;
+pub mod space;
+pub mod tutoring;
+
+pub use fund::*;
+pub use payment::*;
+pub use space::*;
+pub use tutoring::*;
@@ -0,0 +1,405 @@
+// Pagos de tutorías: escrow al reservar, liberación al completar y reembolsos por cancelación
+// Los importes se expresan en unidades base del token ERC-20 de liquidación
+
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{SessionId, SessionParty, SessionStatus, TutoringSession};
+
+/// Denominador de los porcentajes expresados en puntos básicos
+pub const BASIS_POINTS: u32 = 10_000;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PaymentId(pub Uuid);
+
+impl PaymentId {
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+/// Estado del escrow de una sesión
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PaymentStatus {
+    Escrowed,            // Fondos retenidos hasta que la sesión se cierre
+    Released,            // Pagado al tutor (menos la comisión de la plataforma)
+    Refunded,            // Devuelto íntegramente al estudiante
+    PartiallyRefunded,   // Reembolso parcial según la política de cancelación
+}
+
+impl PaymentStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PaymentStatus::Escrowed => "escrowed",
+            PaymentStatus::Released => "released",
+            PaymentStatus::Refunded => "refunded",
+            PaymentStatus::PartiallyRefunded => "partially_refunded",
+        }
+    }
+}
+
+impl fmt::Display for PaymentStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for PaymentStatus {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "escrowed" => Ok(PaymentStatus::Escrowed),
+            "released" => Ok(PaymentStatus::Released),
+            "refunded" => Ok(PaymentStatus::Refunded),
+            "partially_refunded" => Ok(PaymentStatus::PartiallyRefunded),
+            other => Err(anyhow!("Estado de pago desconocido: {}", other)),
+        }
+    }
+}
+
+/// Comisión de la plataforma sobre lo que recibe el tutor
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeSchedule {
+    pub platform_fee_bps: u32,
+}
+
+impl Default for FeeSchedule {
+    fn default() -> Self {
+        Self { platform_fee_bps: 500 } // 5%
+    }
+}
+
+impl FeeSchedule {
+    /// Repartir un importe entre tutor y plataforma: (tutor, comisión)
+    pub fn split(&self, amount: u64) -> (u64, u64) {
+        let fee = apply_bps(amount, self.platform_fee_bps);
+        (amount - fee, fee)
+    }
+}
+
+/// Política de reembolso cuando el estudiante cancela una sesión aceptada
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CancellationPolicy {
+    pub full_refund_hours: i64,       // Con esta antelación o más se devuelve todo
+    pub partial_refund_hours: i64,    // Con esta antelación o más se devuelve una parte
+    pub partial_refund_bps: u32,
+}
+
+impl Default for CancellationPolicy {
+    fn default() -> Self {
+        Self {
+            full_refund_hours: 24,
+            partial_refund_hours: 2,
+            partial_refund_bps: 5_000,
+        }
+    }
+}
+
+impl CancellationPolicy {
+    /// Porción reembolsable al estudiante, en puntos básicos, según cómo se cerró la sesión
+    pub fn refund_bps(&self, session: &TutoringSession) -> Result<u32> {
+        match session.status {
+            SessionStatus::Completed => Ok(0),
+            SessionStatus::NoShow => match session.absent_party {
+                Some(SessionParty::Tutor) => Ok(BASIS_POINTS),
+                Some(SessionParty::Learner) => Ok(0),
+                None => bail!("La sesión {} no indica quién no se presentó", session.id.0),
+            },
+            SessionStatus::Cancelled => {
+                // Si el tutor cancela, o nunca aceptó la reserva, el estudiante no pierde nada
+                let cancelled_by_tutor = session.cancelled_by.as_deref() == Some(session.tutor.as_str());
+                if cancelled_by_tutor || session.accepted_at.is_none() {
+                    return Ok(BASIS_POINTS);
+                }
+                let cancelled_at = session
+                    .cancelled_at
+                    .ok_or_else(|| anyhow!("La sesión {} no indica cuándo se canceló", session.id.0))?;
+                Ok(self.learner_refund_bps(session.scheduled_start, cancelled_at))
+            }
+            other => bail!("La sesión sigue abierta ({}), todavía no se puede liquidar", other),
+        }
+    }
+
+    /// Reembolso al estudiante según la antelación con la que canceló
+    pub fn learner_refund_bps(&self, scheduled_start: DateTime<Utc>, cancelled_at: DateTime<Utc>) -> u32 {
+        let hours_before = (scheduled_start - cancelled_at).num_hours();
+
+        if hours_before >= self.full_refund_hours {
+            BASIS_POINTS
+        } else if hours_before >= self.partial_refund_hours {
+            self.partial_refund_bps
+        } else {
+            0
+        }
+    }
+}
+
+/// Reparto del escrow al liquidar una sesión
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SettlementPlan {
+    pub refund: u64,
+    pub tutor: u64,
+    pub platform_fee: u64,
+}
+
+impl SettlementPlan {
+    /// Reembolsar la porción indicada y repartir el resto entre tutor y plataforma
+    pub fn new(amount: u64, refund_bps: u32, fees: &FeeSchedule) -> Self {
+        let refund = apply_bps(amount, refund_bps);
+        let (tutor, platform_fee) = fees.split(amount - refund);
+
+        Self { refund, tutor, platform_fee }
+    }
+
+    /// Estado final del pago tras ejecutar el plan
+    pub fn resulting_status(&self) -> PaymentStatus {
+        match (self.refund, self.tutor + self.platform_fee) {
+            (0, _) => PaymentStatus::Released,
+            (_, 0) => PaymentStatus::Refunded,
+            _ => PaymentStatus::PartiallyRefunded,
+        }
+    }
+}
+
+fn apply_bps(amount: u64, bps: u32) -> u64 {
+    (amount as u128 * bps.min(BASIS_POINTS) as u128 / BASIS_POINTS as u128) as u64
+}
+
+/// Escrow de una sesión de tutoría
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Payment {
+    pub id: PaymentId,
+    pub session_id: SessionId,
+    pub payer: String,                 // Estudiante
+    pub payee: String,                 // Tutor
+    pub amount: u64,
+    pub status: PaymentStatus,
+    pub deposit_reference: String,     // Referencia del depósito (ej: hash de la transferencia ERC-20)
+    pub plan: Option<SettlementPlan>,  // Reparto acordado al liquidar
+    pub created_at: DateTime<Utc>,
+    pub settled_at: Option<DateTime<Utc>>,
+}
+
+/// Tipo de movimiento del libro contable de pagos
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum LedgerEntryKind {
+    Deposit,       // Estudiante → escrow
+    TutorPayout,   // Escrow → tutor
+    PlatformFee,   // Escrow → tesorería de la plataforma
+    Refund,        // Escrow → estudiante
+}
+
+impl LedgerEntryKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LedgerEntryKind::Deposit => "deposit",
+            LedgerEntryKind::TutorPayout => "tutor_payout",
+            LedgerEntryKind::PlatformFee => "platform_fee",
+            LedgerEntryKind::Refund => "refund",
+        }
+    }
+}
+
+impl FromStr for LedgerEntryKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "deposit" => Ok(LedgerEntryKind::Deposit),
+            "tutor_payout" => Ok(LedgerEntryKind::TutorPayout),
+            "platform_fee" => Ok(LedgerEntryKind::PlatformFee),
+            "refund" => Ok(LedgerEntryKind::Refund),
+            other => Err(anyhow!("Tipo de movimiento desconocido: {}", other)),
+        }
+    }
+}
+
+/// Estado de un movimiento del libro contable
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LedgerEntryStatus {
+    Pending,   // Transferencia registrada antes de enviarse; falta confirmar que se ejecutó
+    Settled,
+}
+
+impl LedgerEntryStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LedgerEntryStatus::Pending => "pending",
+            LedgerEntryStatus::Settled => "settled",
+        }
+    }
+}
+
+impl FromStr for LedgerEntryStatus {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "pending" => Ok(LedgerEntryStatus::Pending),
+            "settled" => Ok(LedgerEntryStatus::Settled),
+            other => Err(anyhow!("Estado de movimiento desconocido: {}", other)),
+        }
+    }
+}
+
+/// Transferencia firmada y lista para enviar
+///
+/// Su hash se conoce antes del envío: se guarda en el ledger antes de mover fondos,
+/// y un reintento comprueba con él si la transferencia ya se ejecutó.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PreparedPayout {
+    pub transaction_hash: String,
+    pub transaction: serde_json::Value, // Transacción firmada, en el formato del backend
+}
+
+/// Movimiento del libro contable, con la transacción que lo liquidó
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LedgerEntry {
+    pub id: Uuid,
+    pub payment_id: PaymentId,
+    pub kind: LedgerEntryKind,
+    pub account: String,
+    pub amount: u64,
+    pub status: LedgerEntryStatus,
+    pub idempotency_key: String,            // Un único movimiento por pago y tramo
+    pub transaction_hash: String,
+    pub payout: Option<PreparedPayout>,     // Transacción firmada de las transferencias salientes
+    pub created_at: DateTime<Utc>,
+}
+
+impl LedgerEntry {
+    /// Clave de idempotencia de un tramo de un pago
+    pub fn idempotency_key(payment_id: &PaymentId, kind: LedgerEntryKind) -> String {
+        format!("{}:{}", payment_id.0, kind.as_str())
+    }
+}
+
+/// Eventos de dominio de pagos
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum PaymentEvent {
+    PaymentEscrowed {
+        payment_id: PaymentId,
+        session_id: SessionId,
+        amount: u64,
+        timestamp: DateTime<Utc>,
+    },
+    PaymentSettled {
+        payment_id: PaymentId,
+        status: PaymentStatus,
+        plan: SettlementPlan,
+        timestamp: DateTime<Utc>,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Subject;
+    use chrono::{Duration, TimeZone};
+
+    fn at(hours: i64) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap() + Duration::hours(hours)
+    }
+
+    fn session(status: SessionStatus) -> TutoringSession {
+        TutoringSession {
+            id: SessionId::new(),
+            tutor: "tutor".to_string(),
+            learner: "learner".to_string(),
+            subject: Subject { code: "math.algebra".to_string(), name: "Álgebra".to_string(), level: None },
+            scheduled_start: at(48),
+            scheduled_end: at(49),
+            status,
+            message: None,
+            cancelled_by: None,
+            cancellation_reason: None,
+            cancelled_at: None,
+            absent_party: None,
+            no_show_report: None,
+            accepted_at: Some(at(0)),
+            started_at: None,
+            completed_at: None,
+            tutor_interaction_id: None,
+            learner_interaction_id: None,
+            requested_at: at(0),
+            updated_at: at(0),
+        }
+    }
+
+    fn cancelled_by(who: &str, cancelled_at: DateTime<Utc>) -> TutoringSession {
+        let mut session = session(SessionStatus::Cancelled);
+        session.cancelled_by = Some(who.to_string());
+        session.cancelled_at = Some(cancelled_at);
+        session
+    }
+
+    #[test]
+    fn completed_session_releases_everything_minus_fee() {
+        let policy = CancellationPolicy::default();
+        let plan = SettlementPlan::new(1_000, policy.refund_bps(&session(SessionStatus::Completed)).unwrap(), &FeeSchedule::default());
+
+        assert_eq!(plan, SettlementPlan { refund: 0, tutor: 950, platform_fee: 50 });
+        assert_eq!(plan.resulting_status(), PaymentStatus::Released);
+    }
+
+    #[test]
+    fn learner_cancellation_refund_depends_on_notice() {
+        let policy = CancellationPolicy::default();
+
+        assert_eq!(policy.refund_bps(&cancelled_by("learner", at(0))).unwrap(), BASIS_POINTS);
+        assert_eq!(policy.refund_bps(&cancelled_by("learner", at(40))).unwrap(), 5_000);
+        assert_eq!(policy.refund_bps(&cancelled_by("learner", at(47))).unwrap(), 0);
+    }
+
+    #[test]
+    fn later_updates_do_not_move_the_cancellation_time() {
+        let policy = CancellationPolicy::default();
+        let mut session = cancelled_by("learner", at(0));
+        session.updated_at = at(47);
+
+        assert_eq!(policy.refund_bps(&session).unwrap(), BASIS_POINTS);
+    }
+
+    #[test]
+    fn tutor_cancellation_or_unaccepted_request_refunds_in_full() {
+        let policy = CancellationPolicy::default();
+        assert_eq!(policy.refund_bps(&cancelled_by("tutor", at(47))).unwrap(), BASIS_POINTS);
+
+        let mut unaccepted = cancelled_by("learner", at(47));
+        unaccepted.accepted_at = None;
+        assert_eq!(policy.refund_bps(&unaccepted).unwrap(), BASIS_POINTS);
+    }
+
+    #[test]
+    fn no_show_penalizes_the_absent_party() {
+        let policy = CancellationPolicy::default();
+        let mut no_show = session(SessionStatus::NoShow);
+
+        no_show.absent_party = Some(SessionParty::Tutor);
+        assert_eq!(policy.refund_bps(&no_show).unwrap(), BASIS_POINTS);
+
+        no_show.absent_party = Some(SessionParty::Learner);
+        assert_eq!(policy.refund_bps(&no_show).unwrap(), 0);
+    }
+
+    #[test]
+    fn open_session_cannot_be_settled() {
+        let policy = CancellationPolicy::default();
+        assert!(policy.refund_bps(&session(SessionStatus::Accepted)).is_err());
+    }
+
+    #[test]
+    fn partial_refund_splits_the_remainder() {
+        let plan = SettlementPlan::new(1_001, 5_000, &FeeSchedule::default());
+
+        assert_eq!(plan.refund + plan.tutor + plan.platform_fee, 1_001);
+        assert_eq!(plan, SettlementPlan { refund: 500, tutor: 476, platform_fee: 25 });
+        assert_eq!(plan.resulting_status(), PaymentStatus::PartiallyRefunded);
+    }
+}
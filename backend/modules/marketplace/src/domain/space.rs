@@ -0,0 +1,356 @@
+// Espacios de aprendizaje seguros: registro de espacios físicos validados por la comunidad
+
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Result};
+use chrono::{DateTime, NaiveTime, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Avales de humanos verificados necesarios para verificar un espacio
+pub const VOUCHES_TO_VERIFY: usize = 3;
+
+/// Reportes necesarios para poner un espacio en revisión
+pub const REPORTS_TO_REVIEW: usize = 2;
+
+/// Radio medio de la Tierra, en kilómetros
+pub const EARTH_RADIUS_KM: f64 = 6371.0;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct LearningSpaceId(pub Uuid);
+
+impl LearningSpaceId {
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+/// Coordenadas WGS 84
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GeoPoint {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+impl GeoPoint {
+    pub fn new(latitude: f64, longitude: f64) -> Result<Self> {
+        if !(-90.0..=90.0).contains(&latitude) || !(-180.0..=180.0).contains(&longitude) {
+            bail!("Coordenadas fuera de rango: ({}, {})", latitude, longitude);
+        }
+
+        Ok(Self { latitude, longitude })
+    }
+
+    /// Distancia de círculo máximo (haversine) en kilómetros
+    pub fn distance_km(&self, other: &GeoPoint) -> f64 {
+        let (lat1, lat2) = (self.latitude.to_radians(), other.latitude.to_radians());
+        let d_lat = lat2 - lat1;
+        let d_lon = (other.longitude - self.longitude).to_radians();
+
+        let a = (d_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (d_lon / 2.0).sin().powi(2);
+        2.0 * EARTH_RADIUS_KM * a.sqrt().min(1.0).asin()
+    }
+
+    /// Rectángulo de coordenadas que contiene el círculo de radio `radius_km` centrado en el punto
+    pub fn bounding_box(&self, radius_km: f64) -> BoundingBox {
+        let angular_radius = radius_km / EARTH_RADIUS_KM;
+        let delta_latitude = angular_radius.to_degrees();
+        let min_latitude = self.latitude - delta_latitude;
+        let max_latitude = self.latitude + delta_latitude;
+
+        // Si el círculo contiene un polo o abarca todos los meridianos no se acota la longitud
+        let longitude = if min_latitude <= -90.0 || max_latitude >= 90.0 {
+            None
+        } else {
+            let ratio = angular_radius.sin() / self.latitude.to_radians().cos();
+            (ratio < 1.0).then(|| {
+                let delta_longitude = ratio.asin().to_degrees();
+                (self.longitude - delta_longitude, self.longitude + delta_longitude)
+            })
+        };
+
+        BoundingBox { min_latitude, max_latitude, longitude }
+    }
+}
+
+/// Rectángulo que acota una búsqueda por radio antes de calcular distancias
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundingBox {
+    pub min_latitude: f64,
+    pub max_latitude: f64,
+    pub longitude: Option<(f64, f64)>, // Puede salirse de [-180, 180] si cruza el antimeridiano
+}
+
+/// Características de accesibilidad de un espacio
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AccessibilityFeature {
+    StepFreeAccess,
+    AccessibleRestroom,
+    Elevator,
+    HearingLoop,
+    SignLanguageSupport,
+    BrailleSignage,
+    QuietArea,
+    ChildFriendly,
+}
+
+/// Franja semanal de apertura, en la hora local del espacio
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpeningPeriod {
+    pub weekday: Weekday,
+    pub opens: NaiveTime,
+    pub closes: NaiveTime,
+}
+
+/// Estado de validación de un espacio
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SpaceStatus {
+    Pending,       // Registrado, a la espera de avales
+    Verified,      // Avalado por la comunidad o aprobado por gobernanza
+    UnderReview,   // Reportado; se resuelve con una propuesta de gobernanza
+    Suspended,     // Suspendido por gobernanza
+}
+
+impl SpaceStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SpaceStatus::Pending => "pending",
+            SpaceStatus::Verified => "verified",
+            SpaceStatus::UnderReview => "under_review",
+            SpaceStatus::Suspended => "suspended",
+        }
+    }
+}
+
+impl SpaceStatus {
+    /// Cambios que puede decidir un moderador sin propuesta de gobernanza
+    ///
+    /// Aprobar un espacio pendiente o descartar los reportes de uno en revisión, y
+    /// suspender cualquier espacio visible. Reactivar un espacio suspendido sigue
+    /// requiriendo una propuesta de gobernanza.
+    pub fn can_moderate_to(&self, next: SpaceStatus) -> bool {
+        matches!(
+            (self, next),
+            (SpaceStatus::Pending | SpaceStatus::UnderReview, SpaceStatus::Verified)
+                | (SpaceStatus::Pending | SpaceStatus::Verified | SpaceStatus::UnderReview, SpaceStatus::Suspended)
+        )
+    }
+
+    /// Cambios que puede decidir una propuesta de gobernanza
+    ///
+    /// Además de los de un moderador, reactivar un espacio suspendido. Una propuesta no
+    /// devuelve un espacio a revisión ni repite el estado que ya tiene.
+    pub fn can_govern_to(&self, next: SpaceStatus) -> bool {
+        self.can_moderate_to(next) || matches!((self, next), (SpaceStatus::Suspended, SpaceStatus::Verified))
+    }
+}
+
+impl fmt::Display for SpaceStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SpaceStatus {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "pending" => Ok(SpaceStatus::Pending),
+            "verified" => Ok(SpaceStatus::Verified),
+            "under_review" => Ok(SpaceStatus::UnderReview),
+            "suspended" => Ok(SpaceStatus::Suspended),
+            other => Err(anyhow!("Estado de espacio desconocido: {}", other)),
+        }
+    }
+}
+
+/// Datos de un espacio que aporta su anfitrión
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LearningSpaceDetails {
+    pub name: String,
+    pub description: String,
+    pub address: String,
+    pub location: GeoPoint,
+    pub capacity: u32,
+    pub accessibility: Vec<AccessibilityFeature>,
+    pub opening_hours: Vec<OpeningPeriod>,
+    pub timezone: String,                 // Zona IANA de las horas de apertura (ej: "America/Bogota")
+}
+
+impl LearningSpaceDetails {
+    pub fn validate(&self) -> Result<()> {
+        if self.name.trim().is_empty() {
+            bail!("El espacio necesita un nombre");
+        }
+        if self.address.trim().is_empty() {
+            bail!("El espacio necesita una dirección");
+        }
+        GeoPoint::new(self.location.latitude, self.location.longitude)?;
+        if self.capacity == 0 {
+            bail!("La capacidad del espacio debe ser mayor que cero");
+        }
+        if self.opening_hours.iter().any(|period| period.opens >= period.closes) {
+            bail!("Cada franja de apertura debe cerrar después de abrir");
+        }
+
+        Ok(())
+    }
+}
+
+/// Espacio físico de aprendizaje
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LearningSpace {
+    pub id: LearningSpaceId,
+    pub host: String,                          // Usuario que registra y gestiona el espacio
+    pub details: LearningSpaceDetails,
+    pub status: SpaceStatus,
+    pub status_reason: Option<String>,
+    pub status_proposal_id: Option<Uuid>,      // Propuesta de gobernanza que fijó el estado
+    pub status_changed_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl LearningSpace {
+    /// Cambiar de estado; los avales y reportes anteriores dejan de contar
+    pub fn set_status(&mut self, status: SpaceStatus, reason: Option<String>, proposal_id: Option<Uuid>) {
+        let now = Utc::now();
+
+        self.status = status;
+        self.status_reason = reason;
+        self.status_proposal_id = proposal_id;
+        self.status_changed_at = now;
+        self.updated_at = now;
+    }
+
+    /// Estado que resulta de los avales y reportes recibidos desde el último cambio
+    ///
+    /// La comunidad solo verifica espacios pendientes y pone en revisión los visibles;
+    /// salir de la revisión requiere una decisión de moderación o de gobernanza, y salir
+    /// de una suspensión, una propuesta de gobernanza.
+    pub fn status_after_validations(&self, vouches: usize, reports: usize) -> SpaceStatus {
+        match self.status {
+            SpaceStatus::Pending | SpaceStatus::Verified if reports >= REPORTS_TO_REVIEW => SpaceStatus::UnderReview,
+            SpaceStatus::Pending if vouches >= VOUCHES_TO_VERIFY => SpaceStatus::Verified,
+            status => status,
+        }
+    }
+}
+
+/// Tipo de validación comunitaria
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ValidationKind {
+    Vouch,   // Aval: el miembro conoce el espacio y lo considera seguro
+    Report,  // Reporte de un problema de seguridad o de datos falsos
+}
+
+impl ValidationKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ValidationKind::Vouch => "vouch",
+            ValidationKind::Report => "report",
+        }
+    }
+}
+
+impl FromStr for ValidationKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "vouch" => Ok(ValidationKind::Vouch),
+            "report" => Ok(ValidationKind::Report),
+            other => Err(anyhow!("Tipo de validación desconocido: {}", other)),
+        }
+    }
+}
+
+/// Aval o reporte de un miembro verificado sobre un espacio
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpaceValidation {
+    pub space_id: LearningSpaceId,
+    pub member: String,
+    pub kind: ValidationKind,
+    pub comment: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Filtros de búsqueda geográfica
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SpaceSearchFilter {
+    pub min_capacity: Option<u32>,
+    pub accessibility: Vec<AccessibilityFeature>,
+    pub include_unverified: bool,          // Incluir espacios pendientes de aval
+}
+
+/// Eventos de dominio de espacios de aprendizaje
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum SpaceEvent {
+    SpaceRegistered {
+        space_id: LearningSpaceId,
+        host: String,
+        timestamp: DateTime<Utc>,
+    },
+    SpaceValidated {
+        space_id: LearningSpaceId,
+        member: String,
+        kind: ValidationKind,
+        timestamp: DateTime<Utc>,
+    },
+    SpaceStatusChanged {
+        space_id: LearningSpaceId,
+        from: SpaceStatus,
+        to: SpaceStatus,
+        proposal_id: Option<Uuid>,
+        timestamp: DateTime<Utc>,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn moderators_cannot_reinstate_suspended_spaces() {
+        assert!(SpaceStatus::UnderReview.can_moderate_to(SpaceStatus::Verified));
+        assert!(SpaceStatus::Pending.can_moderate_to(SpaceStatus::Verified));
+        assert!(SpaceStatus::Verified.can_moderate_to(SpaceStatus::Suspended));
+        assert!(!SpaceStatus::Suspended.can_moderate_to(SpaceStatus::Verified));
+        assert!(!SpaceStatus::Verified.can_moderate_to(SpaceStatus::Verified));
+        assert!(!SpaceStatus::Verified.can_moderate_to(SpaceStatus::Pending));
+    }
+
+    #[test]
+    fn governance_can_reinstate_but_not_repeat_a_status() {
+        assert!(SpaceStatus::Suspended.can_govern_to(SpaceStatus::Verified));
+        assert!(SpaceStatus::UnderReview.can_govern_to(SpaceStatus::Suspended));
+        assert!(!SpaceStatus::Suspended.can_govern_to(SpaceStatus::Suspended));
+        assert!(!SpaceStatus::Verified.can_govern_to(SpaceStatus::Verified));
+        assert!(!SpaceStatus::Verified.can_govern_to(SpaceStatus::UnderReview));
+    }
+
+    #[test]
+    fn bounding_box_contains_the_search_circle() {
+        let center = GeoPoint::new(60.0, 10.0).unwrap();
+        let bounds = center.bounding_box(100.0);
+        let (min_longitude, max_longitude) = bounds.longitude.unwrap();
+
+        // El punto más al este del círculo queda dentro y uno algo más allá, fuera
+        let east = GeoPoint::new(60.0, max_longitude - 0.01).unwrap();
+        assert!(center.distance_km(&east) < 100.0);
+        assert!(center.distance_km(&GeoPoint::new(60.0, max_longitude + 0.5).unwrap()) > 100.0);
+        assert!(min_longitude < 10.0 - 1.0 && max_longitude > 10.0 + 1.0);
+        assert!(bounds.min_latitude < 59.2 && bounds.max_latitude > 60.8);
+    }
+
+    #[test]
+    fn bounding_box_does_not_bound_longitude_around_the_poles() {
+        assert_eq!(GeoPoint::new(89.5, 0.0).unwrap().bounding_box(100.0).longitude, None);
+
+        let antimeridian = GeoPoint::new(0.0, 179.9).unwrap().bounding_box(50.0);
+        assert!(antimeridian.longitude.unwrap().1 > 180.0);
+    }
+}
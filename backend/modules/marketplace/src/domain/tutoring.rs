@@ -0,0 +1,500 @@
+// Tutorías: perfiles de tutores, disponibilidad semanal y ciclo de vida de sesiones
+
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Result};
+use chrono::{DateTime, Datelike, Duration, NaiveTime, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Margen antes del inicio programado en el que se puede iniciar una sesión
+pub const SESSION_START_WINDOW_MINUTES: i64 = 15;
+
+/// Espera tras el inicio programado antes de poder marcar una inasistencia
+pub const NO_SHOW_GRACE_MINUTES: i64 = 15;
+
+/// Materia que imparte un tutor
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Subject {
+    pub code: String,                 // Identificador estable (ej: "math.algebra")
+    pub name: String,
+    pub level: Option<String>,        // Nivel (ej: "secundaria", "universitario")
+}
+
+/// Franja semanal recurrente de disponibilidad, en UTC
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AvailabilitySlot {
+    pub weekday: Weekday,
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl AvailabilitySlot {
+    /// Comprobar si un intervalo cabe por completo dentro de la franja
+    pub fn contains(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> bool {
+        start.weekday() == self.weekday
+            && start.date_naive() == end.date_naive()
+            && start.time() >= self.start
+            && end.time() <= self.end
+    }
+}
+
+/// Verificación de un tutor por los moderadores
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TutorVerification {
+    #[default]
+    Pending,     // Perfil publicado, a la espera de revisión
+    Approved,    // Visible en las búsquedas y reservable
+    Suspended,   // Retirado por moderación
+}
+
+impl TutorVerification {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TutorVerification::Pending => "pending",
+            TutorVerification::Approved => "approved",
+            TutorVerification::Suspended => "suspended",
+        }
+    }
+}
+
+impl fmt::Display for TutorVerification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for TutorVerification {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "pending" => Ok(TutorVerification::Pending),
+            "approved" => Ok(TutorVerification::Approved),
+            "suspended" => Ok(TutorVerification::Suspended),
+            other => Err(anyhow!("Estado de verificación de tutor desconocido: {}", other)),
+        }
+    }
+}
+
+/// Perfil público de un tutor en el marketplace
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TutorProfile {
+    pub user_address: String,
+    pub display_name: String,
+    pub bio: String,
+    pub subjects: Vec<Subject>,
+    pub languages: Vec<String>,           // Códigos BCP 47 (ej: "es", "pt-BR")
+    pub availability: Vec<AvailabilitySlot>,
+    pub active: bool,                     // Acepta nuevas reservas
+    pub verification: TutorVerification,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl TutorProfile {
+    /// Puede recibir reservas: verificado por moderación y aceptando reservas
+    pub fn is_bookable(&self) -> bool {
+        self.active && self.verification == TutorVerification::Approved
+    }
+
+    pub fn teaches(&self, subject_code: &str) -> bool {
+        self.subject(subject_code).is_some()
+    }
+
+    /// Materia del perfil con el código dado
+    pub fn subject(&self, subject_code: &str) -> Option<&Subject> {
+        self.subjects.iter().find(|s| s.code == subject_code)
+    }
+
+    /// Comprobar si un intervalo cae dentro de alguna franja de disponibilidad
+    pub fn is_available(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> bool {
+        self.availability.iter().any(|slot| slot.contains(start, end))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SessionId(pub Uuid);
+
+impl SessionId {
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+/// Estado de una sesión de tutoría
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SessionStatus {
+    Requested,   // Solicitud de reserva pendiente de respuesta del tutor
+    Accepted,
+    InProgress,
+    Completed,
+    Cancelled,
+    NoShow,
+}
+
+impl SessionStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SessionStatus::Requested => "requested",
+            SessionStatus::Accepted => "accepted",
+            SessionStatus::InProgress => "in_progress",
+            SessionStatus::Completed => "completed",
+            SessionStatus::Cancelled => "cancelled",
+            SessionStatus::NoShow => "no_show",
+        }
+    }
+
+    /// Transiciones permitidas de la máquina de estados
+    pub fn can_transition_to(&self, next: SessionStatus) -> bool {
+        matches!(
+            (self, next),
+            (SessionStatus::Requested, SessionStatus::Accepted)
+                | (SessionStatus::Requested, SessionStatus::Cancelled)
+                | (SessionStatus::Accepted, SessionStatus::InProgress)
+                | (SessionStatus::Accepted, SessionStatus::Cancelled)
+                | (SessionStatus::Accepted, SessionStatus::NoShow)
+                | (SessionStatus::InProgress, SessionStatus::Completed)
+        )
+    }
+
+    /// Estados que ocupan la agenda del tutor
+    pub fn blocks_calendar(&self) -> bool {
+        matches!(self, SessionStatus::Accepted | SessionStatus::InProgress)
+    }
+
+    /// Estados finales, a partir de los cuales se liquida el pago
+    pub fn is_closed(&self) -> bool {
+        matches!(self, SessionStatus::Completed | SessionStatus::Cancelled | SessionStatus::NoShow)
+    }
+}
+
+impl fmt::Display for SessionStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SessionStatus {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "requested" => Ok(SessionStatus::Requested),
+            "accepted" => Ok(SessionStatus::Accepted),
+            "in_progress" => Ok(SessionStatus::InProgress),
+            "completed" => Ok(SessionStatus::Completed),
+            "cancelled" => Ok(SessionStatus::Cancelled),
+            "no_show" => Ok(SessionStatus::NoShow),
+            other => Err(anyhow!("Estado de sesión desconocido: {}", other)),
+        }
+    }
+}
+
+/// Participante de una sesión
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SessionParty {
+    Tutor,
+    Learner,
+}
+
+impl SessionParty {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SessionParty::Tutor => "tutor",
+            SessionParty::Learner => "learner",
+        }
+    }
+
+    /// La otra parte de la sesión
+    pub fn other(&self) -> SessionParty {
+        match self {
+            SessionParty::Tutor => SessionParty::Learner,
+            SessionParty::Learner => SessionParty::Tutor,
+        }
+    }
+}
+
+impl FromStr for SessionParty {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "tutor" => Ok(SessionParty::Tutor),
+            "learner" => Ok(SessionParty::Learner),
+            other => Err(anyhow!("Participante desconocido: {}", other)),
+        }
+    }
+}
+
+/// Reporte de inasistencia pendiente
+///
+/// Decide reembolsos, así que no cuenta hasta que lo confirma la parte señalada o lo
+/// resuelve moderación. Si la parte señalada reporta a su vez a la otra, queda en disputa.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NoShowReport {
+    pub absent_party: SessionParty,   // Parte señalada como ausente
+    pub reported_at: DateTime<Utc>,
+    pub disputed: bool,
+}
+
+/// Sesión de tutoría, desde la solicitud de reserva hasta su cierre
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TutoringSession {
+    pub id: SessionId,
+    pub tutor: String,
+    pub learner: String,
+    pub subject: Subject,
+    pub scheduled_start: DateTime<Utc>,
+    pub scheduled_end: DateTime<Utc>,
+    pub status: SessionStatus,
+    pub message: Option<String>,               // Mensaje del estudiante al solicitar la reserva
+    pub cancelled_by: Option<String>,
+    pub cancellation_reason: Option<String>,
+    pub cancelled_at: Option<DateTime<Utc>>,   // Momento de la cancelación, base del reembolso
+    pub absent_party: Option<SessionParty>,    // Quién no se presentó (NoShow)
+    pub no_show_report: Option<NoShowReport>,  // Inasistencia reportada y aún sin confirmar
+    pub accepted_at: Option<DateTime<Utc>>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub tutor_interaction_id: Option<Uuid>,    // Interacción registrada en el pasaporte del tutor
+    pub learner_interaction_id: Option<Uuid>,  // Interacción registrada en el pasaporte del estudiante
+    pub requested_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl TutoringSession {
+    /// Parte que representa un usuario en la sesión
+    pub fn party_of(&self, user_address: &str) -> Option<SessionParty> {
+        if user_address == self.tutor {
+            Some(SessionParty::Tutor)
+        } else if user_address == self.learner {
+            Some(SessionParty::Learner)
+        } else {
+            None
+        }
+    }
+
+    /// Comprobar si la sesión se solapa con un intervalo
+    pub fn overlaps(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> bool {
+        self.scheduled_start < end && start < self.scheduled_end
+    }
+
+    /// Comprobar si ya se puede iniciar la sesión
+    pub fn can_start_at(&self, at: DateTime<Utc>) -> bool {
+        at >= self.scheduled_start - Duration::minutes(SESSION_START_WINDOW_MINUTES) && at < self.scheduled_end
+    }
+
+    /// Comprobar si ya se puede marcar una inasistencia
+    pub fn can_mark_no_show_at(&self, at: DateTime<Utc>) -> bool {
+        at >= self.scheduled_start + Duration::minutes(NO_SHOW_GRACE_MINUTES)
+    }
+
+    /// Registrar que una parte reporta la inasistencia de la otra
+    pub fn report_no_show(&mut self, reporter: SessionParty, at: DateTime<Utc>) -> Result<()> {
+        if self.status != SessionStatus::Accepted {
+            bail!("Solo se puede reportar una inasistencia en sesiones aceptadas");
+        }
+        if !self.can_mark_no_show_at(at) {
+            bail!("Todavía no ha pasado el margen de espera de la sesión");
+        }
+
+        let absent_party = reporter.other();
+        match &mut self.no_show_report {
+            None => {
+                self.no_show_report = Some(NoShowReport { absent_party, reported_at: at, disputed: false });
+            }
+            Some(report) if report.disputed => bail!("La inasistencia ya está en disputa"),
+            Some(report) if report.absent_party == absent_party => bail!("La inasistencia ya fue reportada"),
+            // La parte señalada acusa a su vez a la otra: lo resuelve moderación
+            Some(report) => report.disputed = true,
+        }
+
+        Ok(())
+    }
+
+    /// Confirmar la inasistencia reportada; solo puede hacerlo la parte señalada
+    pub fn confirm_no_show(&mut self, confirmer: SessionParty) -> Result<()> {
+        let Some(report) = self.no_show_report else {
+            bail!("No hay ninguna inasistencia reportada");
+        };
+        if report.disputed {
+            bail!("La inasistencia está en disputa y debe resolverla moderación");
+        }
+        if report.absent_party != confirmer {
+            bail!("Solo la parte señalada puede confirmar su inasistencia");
+        }
+
+        self.absent_party = Some(report.absent_party);
+        self.no_show_report = None;
+        Ok(())
+    }
+}
+
+/// Criterios para listar las sesiones de un usuario; los campos vacíos no filtran
+#[derive(Debug, Clone, Default)]
+pub struct SessionFilter {
+    pub statuses: Vec<SessionStatus>,
+    pub subject_code: Option<String>,
+    pub scheduled_after: Option<DateTime<Utc>>,   // Inicio programado igual o posterior
+    pub scheduled_before: Option<DateTime<Utc>>,  // Inicio programado anterior
+}
+
+/// Campo por el que se ordenan los listados de sesiones
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SessionOrderField {
+    #[default]
+    ScheduledStart,
+    RequestedAt,
+}
+
+/// Orden de los listados de sesiones; por defecto, de la más reciente a la más antigua
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SessionOrder {
+    pub field: SessionOrderField,
+    pub descending: bool,
+}
+
+impl Default for SessionOrder {
+    fn default() -> Self {
+        Self { field: SessionOrderField::default(), descending: true }
+    }
+}
+
+/// Eventos de dominio de tutorías
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum TutoringEvent {
+    BookingRequested {
+        session_id: SessionId,
+        tutor: String,
+        learner: String,
+        timestamp: DateTime<Utc>,
+    },
+    SessionStatusChanged {
+        session_id: SessionId,
+        tutor: String,
+        learner: String,
+        from: SessionStatus,
+        to: SessionStatus,
+        changed_by: String,
+        timestamp: DateTime<Utc>,
+    },
+    SessionCompleted {
+        session_id: SessionId,
+        tutor: String,
+        learner: String,
+        tutor_interaction_id: Uuid,
+        learner_interaction_id: Uuid,
+        timestamp: DateTime<Utc>,
+    },
+    /// Perfil publicado o modificado, incluida su disponibilidad
+    TutorProfileUpdated {
+        user_address: String,
+        timestamp: DateTime<Utc>,
+    },
+}
+
+impl TutoringEvent {
+    /// ¿Participa el usuario en la sesión del evento? Los eventos de perfil no son de ninguna sesión
+    pub fn involves(&self, user_address: &str) -> bool {
+        let (tutor, learner) = match self {
+            TutoringEvent::BookingRequested { tutor, learner, .. }
+            | TutoringEvent::SessionStatusChanged { tutor, learner, .. }
+            | TutoringEvent::SessionCompleted { tutor, learner, .. } => (tutor, learner),
+            TutoringEvent::TutorProfileUpdated { .. } => return false,
+        };
+        tutor == user_address || learner == user_address
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(minutes: i64) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 1, 1, 10, 0, 0).unwrap() + Duration::minutes(minutes)
+    }
+
+    fn accepted_session() -> TutoringSession {
+        TutoringSession {
+            id: SessionId::new(),
+            tutor: "tutor".to_string(),
+            learner: "learner".to_string(),
+            subject: Subject { code: "math.algebra".to_string(), name: "Álgebra".to_string(), level: None },
+            scheduled_start: at(0),
+            scheduled_end: at(60),
+            status: SessionStatus::Accepted,
+            message: None,
+            cancelled_by: None,
+            cancellation_reason: None,
+            cancelled_at: None,
+            absent_party: None,
+            no_show_report: None,
+            accepted_at: Some(at(-60)),
+            started_at: None,
+            completed_at: None,
+            tutor_interaction_id: None,
+            learner_interaction_id: None,
+            requested_at: at(-120),
+            updated_at: at(-60),
+        }
+    }
+
+    #[test]
+    fn no_show_counts_only_once_the_absent_party_confirms() {
+        let mut session = accepted_session();
+
+        assert!(session.report_no_show(SessionParty::Tutor, at(5)).is_err());
+        session.report_no_show(SessionParty::Tutor, at(20)).unwrap();
+        assert_eq!(session.absent_party, None);
+        assert!(session.report_no_show(SessionParty::Tutor, at(25)).is_err());
+
+        // Quien reporta no puede confirmar en nombre de la otra parte
+        assert!(session.confirm_no_show(SessionParty::Tutor).is_err());
+        session.confirm_no_show(SessionParty::Learner).unwrap();
+        assert_eq!(session.absent_party, Some(SessionParty::Learner));
+        assert_eq!(session.no_show_report, None);
+    }
+
+    #[test]
+    fn crossed_no_show_reports_become_a_dispute() {
+        let mut session = accepted_session();
+
+        session.report_no_show(SessionParty::Learner, at(20)).unwrap();
+        session.report_no_show(SessionParty::Tutor, at(21)).unwrap();
+
+        let report = session.no_show_report.unwrap();
+        assert!(report.disputed);
+        assert_eq!(report.absent_party, SessionParty::Tutor);
+        assert!(session.confirm_no_show(SessionParty::Tutor).is_err());
+        assert!(session.report_no_show(SessionParty::Learner, at(22)).is_err());
+    }
+
+    #[test]
+    fn only_approved_active_tutors_teaching_the_subject_are_bookable() {
+        let mut profile = TutorProfile {
+            user_address: "tutor".to_string(),
+            display_name: "Tutor".to_string(),
+            bio: String::new(),
+            subjects: vec![Subject { code: "math.algebra".to_string(), name: "Álgebra".to_string(), level: None }],
+            languages: vec!["es".to_string()],
+            availability: Vec::new(),
+            active: true,
+            verification: TutorVerification::Approved,
+            created_at: at(0),
+            updated_at: at(0),
+        };
+
+        assert!(profile.is_bookable());
+        assert!(profile.teaches("math.algebra"));
+        assert!(!profile.teaches("physics"));
+
+        profile.verification = TutorVerification::Pending;
+        assert!(!profile.is_bookable());
+        profile.verification = TutorVerification::Approved;
+        profile.active = false;
+        assert!(!profile.is_bookable());
+    }
+}
@@ -0,0 +1,30 @@
+use anyhow::Result;
+
+pub mod domain;
+pub mod repository;
+pub mod service;
+
+/// Inicializar el módulo marketplace
+pub async fn init() -> Result<()> {
+    tracing::info!("Inicializando módulo marketplace");
+    
+    // TODO: Implementar inicialización del módulo
+    // - Conexión a base de datos
+    // - Conexión al servicio de pasaportes de aprendizaje
+    // - Selección del backend de liquidación (Starknet o local)
+    // - Tarea periódica de liquidación de pagos de sesiones cerradas
+    // - Registro del ejecutor de propuestas de espacios en gobernanza
+    
+    Ok(())
+}
+
+/// Cerrar el módulo marketplace
+pub async fn shutdown() -> Result<()> {
+    tracing::info!("Cerrando módulo marketplace");
+    
+    // TODO: Implementar limpieza del módulo
+    // - Cerrar conexiones
+    // - Detener la tarea de liquidación de pagos
+    
+    Ok(())
+}
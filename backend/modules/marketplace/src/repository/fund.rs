@@ -0,0 +1,84 @@
+// Persistencia de los desembolsos del fondo comunitario
+
+use anyhow::Result;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::FundDisbursement;
+
+pub struct CommunityFundRepository {
+    pool: PgPool,
+}
+
+impl CommunityFundRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Registrar un desembolso pendiente; falla si la propuesta ya tiene uno
+    pub async fn create_disbursement(&self, disbursement: &FundDisbursement) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO community_fund_disbursements
+                (proposal_id, recipient, amount, status, transaction_hash, payout, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#,
+            disbursement.proposal_id,
+            disbursement.recipient,
+            i64::try_from(disbursement.amount)?,
+            disbursement.status.as_str(),
+            disbursement.transaction_hash,
+            serde_json::to_value(&disbursement.payout)?,
+            disbursement.created_at
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Actualizar el estado y la transferencia de un desembolso
+    pub async fn update_disbursement(&self, disbursement: &FundDisbursement) -> Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE community_fund_disbursements
+            SET status = $2, transaction_hash = $3, payout = $4
+            WHERE proposal_id = $1
+            "#,
+            disbursement.proposal_id,
+            disbursement.status.as_str(),
+            disbursement.transaction_hash,
+            serde_json::to_value(&disbursement.payout)?
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_disbursement(&self, proposal_id: Uuid) -> Result<Option<FundDisbursement>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT proposal_id, recipient, amount, status, transaction_hash, payout, created_at
+            FROM community_fund_disbursements
+            WHERE proposal_id = $1
+            "#,
+            proposal_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|row| {
+            Ok(FundDisbursement {
+                proposal_id: row.proposal_id,
+                recipient: row.recipient,
+                amount: u64::try_from(row.amount)?,
+                status: row.status.parse()?,
+                transaction_hash: row.transaction_hash,
+                payout: serde_json::from_value(row.payout)?,
+                created_at: row.created_at,
+            })
+        })
+        .transpose()
+    }
+}
@@ -0,0 +1,11 @@
+// Repositorios para persistencia del módulo marketplace
+
+pub mod fund;
+pub mod payment;
+pub mod space;
+pub mod tutoring;
+
+pub use fund::CommunityFundRepository;
+pub use payment::PaymentRepository;
+pub use space::LearningSpaceRepository;
+pub use tutoring::TutoringRepository;
@@ -0,0 +1,211 @@
+// Persistencia del escrow de sesiones y del libro contable de pagos
+
+use anyhow::Result;
+use sqlx::{PgExecutor, PgPool};
+
+use crate::domain::{LedgerEntry, Payment, PaymentId, PaymentStatus, SessionId};
+
+pub struct PaymentRepository {
+    pool: PgPool,
+}
+
+impl PaymentRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Registrar el escrow de una sesión junto con su movimiento de depósito
+    pub async fn create_payment(&self, payment: &Payment, deposit: &LedgerEntry) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO payments (id, session_id, payer, payee, amount, status, deposit_reference, plan, created_at, settled_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            "#,
+            payment.id.0,
+            payment.session_id.0,
+            payment.payer,
+            payment.payee,
+            i64::try_from(payment.amount)?,
+            payment.status.as_str(),
+            payment.deposit_reference,
+            payment.plan.map(serde_json::to_value).transpose()?,
+            payment.created_at,
+            payment.settled_at
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        Self::insert_entry(&mut *tx, deposit).await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// Registrar un movimiento del libro contable
+    ///
+    /// La clave de idempotencia es única: dos liquidaciones simultáneas no registran el mismo tramo.
+    pub async fn add_ledger_entry(&self, entry: &LedgerEntry) -> Result<()> {
+        Self::insert_entry(&self.pool, entry).await
+    }
+
+    async fn insert_entry(executor: impl PgExecutor<'_>, entry: &LedgerEntry) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO payment_ledger (
+                id, payment_id, kind, account, amount, status, idempotency_key, transaction_hash, payout, created_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            "#,
+            entry.id,
+            entry.payment_id.0,
+            entry.kind.as_str(),
+            entry.account,
+            i64::try_from(entry.amount)?,
+            entry.status.as_str(),
+            entry.idempotency_key,
+            entry.transaction_hash,
+            entry.payout.as_ref().map(serde_json::to_value).transpose()?,
+            entry.created_at
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Guardar el estado y la transacción vigente de un movimiento
+    pub async fn update_ledger_entry(&self, entry: &LedgerEntry) -> Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE payment_ledger
+            SET status = $2, transaction_hash = $3, payout = $4
+            WHERE id = $1
+            "#,
+            entry.id,
+            entry.status.as_str(),
+            entry.transaction_hash,
+            entry.payout.as_ref().map(serde_json::to_value).transpose()?
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Guardar el plan de reparto y el estado de liquidación de un escrow
+    pub async fn update_settlement(&self, payment: &Payment) -> Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE payments
+            SET status = $2, plan = $3, settled_at = $4
+            WHERE id = $1
+            "#,
+            payment.id.0,
+            payment.status.as_str(),
+            payment.plan.map(serde_json::to_value).transpose()?,
+            payment.settled_at
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Obtener el escrow de una sesión
+    pub async fn get_payment_by_session(&self, session_id: &SessionId) -> Result<Option<Payment>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT id, session_id, payer, payee, amount, status, deposit_reference, plan, created_at, settled_at
+            FROM payments
+            WHERE session_id = $1
+            "#,
+            session_id.0
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match row {
+            Some(row) => Ok(Some(Payment {
+                id: PaymentId(row.id),
+                session_id: SessionId(row.session_id),
+                payer: row.payer,
+                payee: row.payee,
+                amount: u64::try_from(row.amount)?,
+                status: row.status.parse()?,
+                deposit_reference: row.deposit_reference,
+                plan: row.plan.map(serde_json::from_value).transpose()?,
+                created_at: row.created_at,
+                settled_at: row.settled_at,
+            })),
+            None => Ok(None),
+        }
+    }
+
+    /// Obtener los IDs de sesión de los escrows en un estado
+    pub async fn get_session_ids_by_status(&self, status: PaymentStatus) -> Result<Vec<SessionId>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT session_id
+            FROM payments
+            WHERE status = $1
+            ORDER BY created_at ASC
+            "#,
+            status.as_str()
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(|row| SessionId(row.session_id)).collect())
+    }
+
+    /// Obtener los movimientos de un pago en orden cronológico
+    pub async fn get_ledger(&self, payment_id: &PaymentId) -> Result<Vec<LedgerEntry>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT id, payment_id, kind, account, amount, status, idempotency_key, transaction_hash, payout, created_at
+            FROM payment_ledger
+            WHERE payment_id = $1
+            ORDER BY created_at ASC
+            "#,
+            payment_id.0
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut entries = Vec::new();
+
+        for row in rows {
+            entries.push(LedgerEntry {
+                id: row.id,
+                payment_id: PaymentId(row.payment_id),
+                kind: row.kind.parse()?,
+                account: row.account,
+                amount: u64::try_from(row.amount)?,
+                status: row.status.parse()?,
+                idempotency_key: row.idempotency_key,
+                transaction_hash: row.transaction_hash,
+                payout: row.payout.map(serde_json::from_value).transpose()?,
+                created_at: row.created_at,
+            });
+        }
+
+        Ok(entries)
+    }
+
+    /// Comprobar si una referencia de depósito ya respalda otro escrow
+    pub async fn deposit_reference_exists(&self, deposit_reference: &str) -> Result<bool> {
+        let row = sqlx::query!(
+            r#"
+            SELECT EXISTS (SELECT 1 FROM payments WHERE deposit_reference = $1) AS "exists!"
+            "#,
+            deposit_reference
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(row.exists)
+    }
+}
@@ -0,0 +1,281 @@
+// Persistencia de espacios de aprendizaje y de sus validaciones comunitarias
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+use crate::domain::{
+    GeoPoint, LearningSpace, LearningSpaceDetails, LearningSpaceId, SpaceSearchFilter, SpaceStatus, SpaceValidation,
+    EARTH_RADIUS_KM,
+};
+
+pub struct LearningSpaceRepository {
+    pool: PgPool,
+}
+
+impl LearningSpaceRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Crear o actualizar un espacio
+    pub async fn upsert_space(&self, space: &LearningSpace) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO learning_spaces (
+                id, host, name, description, address, latitude, longitude, capacity, accessibility,
+                opening_hours, timezone, status, status_reason, status_proposal_id, status_changed_at,
+                created_at, updated_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17)
+            ON CONFLICT (id) DO UPDATE
+            SET name = EXCLUDED.name, description = EXCLUDED.description, address = EXCLUDED.address,
+                latitude = EXCLUDED.latitude, longitude = EXCLUDED.longitude, capacity = EXCLUDED.capacity,
+                accessibility = EXCLUDED.accessibility, opening_hours = EXCLUDED.opening_hours,
+                timezone = EXCLUDED.timezone, status = EXCLUDED.status, status_reason = EXCLUDED.status_reason,
+                status_proposal_id = EXCLUDED.status_proposal_id, status_changed_at = EXCLUDED.status_changed_at,
+                updated_at = EXCLUDED.updated_at
+            "#,
+            space.id.0,
+            space.host,
+            space.details.name,
+            space.details.description,
+            space.details.address,
+            space.details.location.latitude,
+            space.details.location.longitude,
+            i32::try_from(space.details.capacity)?,
+            serde_json::to_value(&space.details.accessibility)?,
+            serde_json::to_value(&space.details.opening_hours)?,
+            space.details.timezone,
+            space.status.as_str(),
+            space.status_reason,
+            space.status_proposal_id,
+            space.status_changed_at,
+            space.created_at,
+            space.updated_at
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Obtener un espacio por ID
+    pub async fn get_space(&self, space_id: &LearningSpaceId) -> Result<Option<LearningSpace>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT id, host, name, description, address, latitude, longitude, capacity, accessibility,
+                   opening_hours, timezone, status, status_reason, status_proposal_id, status_changed_at,
+                   created_at, updated_at
+            FROM learning_spaces
+            WHERE id = $1
+            "#,
+            space_id.0
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match row {
+            Some(row) => Ok(Some(LearningSpace {
+                id: LearningSpaceId(row.id),
+                host: row.host,
+                details: LearningSpaceDetails {
+                    name: row.name,
+                    description: row.description,
+                    address: row.address,
+                    location: GeoPoint { latitude: row.latitude, longitude: row.longitude },
+                    capacity: u32::try_from(row.capacity)?,
+                    accessibility: serde_json::from_value(row.accessibility)?,
+                    opening_hours: serde_json::from_value(row.opening_hours)?,
+                    timezone: row.timezone,
+                },
+                status: row.status.parse()?,
+                status_reason: row.status_reason,
+                status_proposal_id: row.status_proposal_id,
+                status_changed_at: row.status_changed_at,
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+            })),
+            None => Ok(None),
+        }
+    }
+
+    /// Buscar espacios dentro de un radio, ordenados por distancia
+    ///
+    /// Devuelve los espacios con su distancia en kilómetros al centro.
+    pub async fn search_within_radius(
+        &self,
+        center: &GeoPoint,
+        radius_km: f64,
+        filter: &SpaceSearchFilter,
+    ) -> Result<Vec<(LearningSpace, f64)>> {
+        let statuses: Vec<String> = if filter.include_unverified {
+            vec![SpaceStatus::Verified.as_str().to_string(), SpaceStatus::Pending.as_str().to_string()]
+        } else {
+            vec![SpaceStatus::Verified.as_str().to_string()]
+        };
+        let min_capacity = filter.min_capacity.map(i32::try_from).transpose()?;
+
+        // El rectángulo descarta candidatos por índice; la distancia exacta se calcula después
+        let bounds = center.bounding_box(radius_km);
+        let (min_longitude, max_longitude) = bounds.longitude.unzip();
+
+        let rows = sqlx::query!(
+            r#"
+            SELECT id, host, name, description, address, latitude, longitude, capacity, accessibility,
+                   opening_hours, timezone, status, status_reason, status_proposal_id, status_changed_at,
+                   created_at, updated_at, distance_km AS "distance_km!"
+            FROM (
+                SELECT *,
+                       2 * $3::float8 * asin(LEAST(1.0, sqrt(
+                           power(sin(radians(latitude - $1) / 2), 2)
+                           + cos(radians($1)) * cos(radians(latitude)) * power(sin(radians(longitude - $2) / 2), 2)
+                       ))) AS distance_km
+                FROM learning_spaces
+                WHERE latitude BETWEEN $4::float8 AND $5::float8
+                  AND (
+                      $6::float8 IS NULL
+                      OR longitude BETWEEN $6 AND $7::float8
+                      OR longitude BETWEEN $6 + 360 AND $7 + 360
+                      OR longitude BETWEEN $6 - 360 AND $7 - 360
+                  )
+            ) AS candidates
+            WHERE distance_km <= $8
+              AND ($9::int4 IS NULL OR capacity >= $9)
+              AND accessibility @> $10
+              AND status = ANY($11)
+            ORDER BY distance_km ASC
+            "#,
+            center.latitude,
+            center.longitude,
+            EARTH_RADIUS_KM,
+            bounds.min_latitude,
+            bounds.max_latitude,
+            min_longitude,
+            max_longitude,
+            radius_km,
+            min_capacity,
+            serde_json::to_value(&filter.accessibility)?,
+            &statuses
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut spaces = Vec::new();
+
+        for row in rows {
+            let space = LearningSpace {
+                id: LearningSpaceId(row.id),
+                host: row.host,
+                details: LearningSpaceDetails {
+                    name: row.name,
+                    description: row.description,
+                    address: row.address,
+                    location: GeoPoint { latitude: row.latitude, longitude: row.longitude },
+                    capacity: u32::try_from(row.capacity)?,
+                    accessibility: serde_json::from_value(row.accessibility)?,
+                    opening_hours: serde_json::from_value(row.opening_hours)?,
+                    timezone: row.timezone,
+                },
+                status: row.status.parse()?,
+                status_reason: row.status_reason,
+                status_proposal_id: row.status_proposal_id,
+                status_changed_at: row.status_changed_at,
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+            };
+            spaces.push((space, row.distance_km));
+        }
+
+        Ok(spaces)
+    }
+
+    /// Obtener los IDs de los espacios en un estado
+    pub async fn get_space_ids_by_status(&self, status: SpaceStatus) -> Result<Vec<LearningSpaceId>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT id
+            FROM learning_spaces
+            WHERE status = $1
+            ORDER BY status_changed_at ASC
+            "#,
+            status.as_str()
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(|row| LearningSpaceId(row.id)).collect())
+    }
+
+    /// Registrar el aval o reporte de un miembro (reemplaza su validación anterior)
+    pub async fn upsert_validation(&self, validation: &SpaceValidation) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO space_validations (space_id, member, kind, comment, created_at)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (space_id, member) DO UPDATE
+            SET kind = EXCLUDED.kind, comment = EXCLUDED.comment, created_at = EXCLUDED.created_at
+            "#,
+            validation.space_id.0,
+            validation.member,
+            validation.kind.as_str(),
+            validation.comment,
+            validation.created_at
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Contar avales y reportes recibidos desde un momento: (avales, reportes)
+    pub async fn count_validations_since(
+        &self,
+        space_id: &LearningSpaceId,
+        since: DateTime<Utc>,
+    ) -> Result<(usize, usize)> {
+        let row = sqlx::query!(
+            r#"
+            SELECT COUNT(*) FILTER (WHERE kind = 'vouch') AS "vouches!",
+                   COUNT(*) FILTER (WHERE kind = 'report') AS "reports!"
+            FROM space_validations
+            WHERE space_id = $1 AND created_at >= $2
+            "#,
+            space_id.0,
+            since
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok((usize::try_from(row.vouches)?, usize::try_from(row.reports)?))
+    }
+
+    /// Obtener las validaciones de un espacio, de la más reciente a la más antigua
+    pub async fn get_validations(&self, space_id: &LearningSpaceId) -> Result<Vec<SpaceValidation>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT space_id, member, kind, comment, created_at
+            FROM space_validations
+            WHERE space_id = $1
+            ORDER BY created_at DESC
+            "#,
+            space_id.0
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut validations = Vec::new();
+
+        for row in rows {
+            validations.push(SpaceValidation {
+                space_id: LearningSpaceId(row.space_id),
+                member: row.member,
+                kind: row.kind.parse()?,
+                comment: row.comment,
+                created_at: row.created_at,
+            });
+        }
+
+        Ok(validations)
+    }
+}
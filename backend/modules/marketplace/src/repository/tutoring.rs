@@ -0,0 +1,456 @@
+// Persistencia de perfiles de tutores y sesiones de tutoría
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+use crate::domain::{
+    SessionFilter, SessionId, SessionOrder, SessionOrderField, SessionStatus, TutorProfile, TutorVerification,
+    TutoringSession,
+};
+
+pub struct TutoringRepository {
+    pool: PgPool,
+}
+
+impl TutoringRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Crear o actualizar el perfil de un tutor
+    pub async fn upsert_profile(&self, profile: &TutorProfile) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO tutor_profiles (
+                user_address, display_name, bio, subjects, languages, availability, active, verification,
+                created_at, updated_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            ON CONFLICT (user_address) DO UPDATE
+            SET display_name = EXCLUDED.display_name, bio = EXCLUDED.bio, subjects = EXCLUDED.subjects,
+                languages = EXCLUDED.languages, availability = EXCLUDED.availability,
+                active = EXCLUDED.active, verification = EXCLUDED.verification, updated_at = EXCLUDED.updated_at
+            "#,
+            profile.user_address,
+            profile.display_name,
+            profile.bio,
+            serde_json::to_value(&profile.subjects)?,
+            serde_json::to_value(&profile.languages)?,
+            serde_json::to_value(&profile.availability)?,
+            profile.active,
+            profile.verification.as_str(),
+            profile.created_at,
+            profile.updated_at
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Obtener el perfil de un tutor
+    pub async fn get_profile(&self, user_address: &str) -> Result<Option<TutorProfile>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT user_address, display_name, bio, subjects, languages, availability, active, verification,
+                   created_at, updated_at
+            FROM tutor_profiles
+            WHERE user_address = $1
+            "#,
+            user_address
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match row {
+            Some(row) => Ok(Some(TutorProfile {
+                user_address: row.user_address,
+                display_name: row.display_name,
+                bio: row.bio,
+                subjects: serde_json::from_value(row.subjects)?,
+                languages: serde_json::from_value(row.languages)?,
+                availability: serde_json::from_value(row.availability)?,
+                active: row.active,
+                verification: row.verification.parse()?,
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+            })),
+            None => Ok(None),
+        }
+    }
+
+    /// Obtener los perfiles de varios usuarios en una sola consulta
+    pub async fn get_profiles(&self, user_addresses: &[String]) -> Result<Vec<TutorProfile>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT user_address, display_name, bio, subjects, languages, availability, active, verification,
+                   created_at, updated_at
+            FROM tutor_profiles
+            WHERE user_address = ANY($1)
+            "#,
+            user_addresses
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(TutorProfile {
+                    user_address: row.user_address,
+                    display_name: row.display_name,
+                    bio: row.bio,
+                    subjects: serde_json::from_value(row.subjects)?,
+                    languages: serde_json::from_value(row.languages)?,
+                    availability: serde_json::from_value(row.availability)?,
+                    active: row.active,
+                    verification: row.verification.parse()?,
+                    created_at: row.created_at,
+                    updated_at: row.updated_at,
+                })
+            })
+            .collect()
+    }
+
+    /// Buscar tutores reservables que imparten una materia, opcionalmente en un idioma
+    pub async fn search_profiles(&self, subject_code: &str, language: Option<&str>) -> Result<Vec<TutorProfile>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT user_address, display_name, bio, subjects, languages, availability, active, verification,
+                   created_at, updated_at
+            FROM tutor_profiles
+            WHERE active = true
+              AND verification = 'approved'
+              AND subjects @> jsonb_build_array(jsonb_build_object('code', $1::text))
+              AND ($2::text IS NULL OR languages ? $2)
+            ORDER BY display_name ASC
+            "#,
+            subject_code,
+            language
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut profiles = Vec::new();
+
+        for row in rows {
+            profiles.push(TutorProfile {
+                user_address: row.user_address,
+                display_name: row.display_name,
+                bio: row.bio,
+                subjects: serde_json::from_value(row.subjects)?,
+                languages: serde_json::from_value(row.languages)?,
+                availability: serde_json::from_value(row.availability)?,
+                active: row.active,
+                verification: row.verification.parse()?,
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+            });
+        }
+
+        Ok(profiles)
+    }
+
+    /// Perfiles en un estado de verificación, del más antiguo al más reciente (cola de moderación)
+    pub async fn get_profiles_by_verification(&self, verification: TutorVerification) -> Result<Vec<TutorProfile>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT user_address, display_name, bio, subjects, languages, availability, active, verification,
+                   created_at, updated_at
+            FROM tutor_profiles
+            WHERE verification = $1
+            ORDER BY created_at ASC
+            "#,
+            verification.as_str()
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(TutorProfile {
+                    user_address: row.user_address,
+                    display_name: row.display_name,
+                    bio: row.bio,
+                    subjects: serde_json::from_value(row.subjects)?,
+                    languages: serde_json::from_value(row.languages)?,
+                    availability: serde_json::from_value(row.availability)?,
+                    active: row.active,
+                    verification: row.verification.parse()?,
+                    created_at: row.created_at,
+                    updated_at: row.updated_at,
+                })
+            })
+            .collect()
+    }
+
+    /// Crear una sesión (solicitud de reserva)
+    pub async fn insert_session(&self, session: &TutoringSession) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO tutoring_sessions (
+                id, tutor, learner, subject, scheduled_start, scheduled_end, status, message,
+                requested_at, updated_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            "#,
+            session.id.0,
+            session.tutor,
+            session.learner,
+            serde_json::to_value(&session.subject)?,
+            session.scheduled_start,
+            session.scheduled_end,
+            session.status.as_str(),
+            session.message,
+            session.requested_at,
+            session.updated_at
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Guardar los cambios de una sesión si nadie la modificó desde que se leyó
+    ///
+    /// `expected_status` y `expected_updated_at` son los valores leídos; devuelve `false`
+    /// si otra petición cambió la sesión antes.
+    pub async fn update_session(
+        &self,
+        session: &TutoringSession,
+        expected_status: SessionStatus,
+        expected_updated_at: DateTime<Utc>,
+    ) -> Result<bool> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE tutoring_sessions
+            SET status = $2, cancelled_by = $3, cancellation_reason = $4, absent_party = $5,
+                accepted_at = $6, started_at = $7, completed_at = $8, tutor_interaction_id = $9,
+                learner_interaction_id = $10, updated_at = $11, cancelled_at = $13, no_show_report = $14
+            WHERE id = $1 AND status = $12 AND updated_at = $15
+            "#,
+            session.id.0,
+            session.status.as_str(),
+            session.cancelled_by,
+            session.cancellation_reason,
+            session.absent_party.map(|p| p.as_str()),
+            session.accepted_at,
+            session.started_at,
+            session.completed_at,
+            session.tutor_interaction_id,
+            session.learner_interaction_id,
+            session.updated_at,
+            expected_status.as_str(),
+            session.cancelled_at,
+            session.no_show_report.map(serde_json::to_value).transpose()?,
+            expected_updated_at
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Obtener una sesión por ID
+    pub async fn get_session(&self, session_id: &SessionId) -> Result<Option<TutoringSession>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT id, tutor, learner, subject, scheduled_start, scheduled_end, status, message,
+                   cancelled_by, cancellation_reason, cancelled_at, absent_party, accepted_at, started_at,
+                   completed_at, tutor_interaction_id, learner_interaction_id, requested_at, updated_at,
+                   no_show_report
+            FROM tutoring_sessions
+            WHERE id = $1
+            "#,
+            session_id.0
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match row {
+            Some(row) => Ok(Some(TutoringSession {
+                id: SessionId(row.id),
+                tutor: row.tutor,
+                learner: row.learner,
+                subject: serde_json::from_value(row.subject)?,
+                scheduled_start: row.scheduled_start,
+                scheduled_end: row.scheduled_end,
+                status: row.status.parse()?,
+                message: row.message,
+                cancelled_by: row.cancelled_by,
+                cancellation_reason: row.cancellation_reason,
+                cancelled_at: row.cancelled_at,
+                absent_party: row.absent_party.map(|p| p.parse()).transpose()?,
+                no_show_report: row.no_show_report.map(serde_json::from_value).transpose()?,
+                accepted_at: row.accepted_at,
+                started_at: row.started_at,
+                completed_at: row.completed_at,
+                tutor_interaction_id: row.tutor_interaction_id,
+                learner_interaction_id: row.learner_interaction_id,
+                requested_at: row.requested_at,
+                updated_at: row.updated_at,
+            })),
+            None => Ok(None),
+        }
+    }
+
+    /// Sesiones con una inasistencia reportada y sin confirmar, de la más antigua a la más reciente
+    pub async fn get_sessions_with_no_show_report(&self) -> Result<Vec<TutoringSession>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT id
+            FROM tutoring_sessions
+            WHERE no_show_report IS NOT NULL
+            ORDER BY no_show_report->>'reported_at' ASC, id
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut sessions = Vec::new();
+
+        for row in rows {
+            if let Some(session) = self.get_session(&SessionId(row.id)).await? {
+                sessions.push(session);
+            }
+        }
+
+        Ok(sessions)
+    }
+
+    /// Obtener las sesiones en las que participa un usuario, de la más reciente a la más antigua
+    pub async fn get_sessions_by_user(&self, user_address: &str) -> Result<Vec<TutoringSession>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT id
+            FROM tutoring_sessions
+            WHERE tutor = $1 OR learner = $1
+            ORDER BY scheduled_start DESC
+            "#,
+            user_address
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut sessions = Vec::new();
+
+        for row in rows {
+            if let Some(session) = self.get_session(&SessionId(row.id)).await? {
+                sessions.push(session);
+            }
+        }
+
+        Ok(sessions)
+    }
+
+    /// Contar las sesiones que cumplen el filtro; con `user_address`, solo las de ese usuario
+    pub async fn count_sessions(&self, user_address: Option<&str>, filter: &SessionFilter) -> Result<i64> {
+        let statuses = status_filter(filter);
+        let row = sqlx::query!(
+            r#"
+            SELECT COUNT(*) AS "count!"
+            FROM tutoring_sessions
+            WHERE ($1::text IS NULL OR tutor = $1 OR learner = $1)
+              AND ($2::text[] IS NULL OR status = ANY($2))
+              AND ($3::text IS NULL OR subject->>'code' = $3)
+              AND ($4::timestamptz IS NULL OR scheduled_start >= $4)
+              AND ($5::timestamptz IS NULL OR scheduled_start < $5)
+            "#,
+            user_address,
+            statuses.as_deref(),
+            filter.subject_code,
+            filter.scheduled_after,
+            filter.scheduled_before
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(row.count)
+    }
+
+    /// Obtener una página de las sesiones que cumplen el filtro; con `user_address`, solo las de ese usuario
+    ///
+    /// A igualdad del campo de orden se desempata por ID, para que las páginas sean estables.
+    pub async fn list_sessions(
+        &self,
+        user_address: Option<&str>,
+        filter: &SessionFilter,
+        order: SessionOrder,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<TutoringSession>> {
+        let statuses = status_filter(filter);
+        let by_requested_at = order.field == SessionOrderField::RequestedAt;
+        let rows = sqlx::query!(
+            r#"
+            SELECT id
+            FROM tutoring_sessions
+            WHERE ($1::text IS NULL OR tutor = $1 OR learner = $1)
+              AND ($2::text[] IS NULL OR status = ANY($2))
+              AND ($3::text IS NULL OR subject->>'code' = $3)
+              AND ($4::timestamptz IS NULL OR scheduled_start >= $4)
+              AND ($5::timestamptz IS NULL OR scheduled_start < $5)
+            ORDER BY CASE WHEN $7 THEN CASE WHEN $6 THEN requested_at ELSE scheduled_start END END DESC,
+                     CASE WHEN NOT $7 THEN CASE WHEN $6 THEN requested_at ELSE scheduled_start END END ASC,
+                     id
+            LIMIT $8 OFFSET $9
+            "#,
+            user_address,
+            statuses.as_deref(),
+            filter.subject_code,
+            filter.scheduled_after,
+            filter.scheduled_before,
+            by_requested_at,
+            order.descending,
+            limit,
+            offset
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut sessions = Vec::new();
+
+        for row in rows {
+            if let Some(session) = self.get_session(&SessionId(row.id)).await? {
+                sessions.push(session);
+            }
+        }
+
+        Ok(sessions)
+    }
+
+    /// Comprobar si un usuario tiene sesiones que ocupan su agenda en un intervalo
+    pub async fn has_conflicting_session(
+        &self,
+        user_address: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<bool> {
+        let row = sqlx::query!(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM tutoring_sessions
+                WHERE (tutor = $1 OR learner = $1)
+                  AND status IN ('accepted', 'in_progress')
+                  AND scheduled_start < $3 AND $2 < scheduled_end
+            ) AS "exists!"
+            "#,
+            user_address,
+            start,
+            end
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(row.exists)
+    }
+}
+
+/// Estados del filtro como texto; sin estados no se filtra
+fn status_filter(filter: &SessionFilter) -> Option<Vec<String>> {
+    if filter.statuses.is_empty() {
+        None
+    } else {
+        Some(filter.statuses.iter().map(|s| s.as_str().to_string()).collect())
+    }
+}
@@ -0,0 +1,74 @@
+// Fondo comunitario: financia las iniciativas de aprendizaje aprobadas en gobernanza
+// El desembolso se registra como pendiente, con su transferencia firmada, antes de enviarse;
+// reintentar la ejecución de la propuesta lo reconcilia con el backend en lugar de repetirlo
+
+use std::sync::Arc;
+
+use anyhow::{bail, Result};
+use async_trait::async_trait;
+use chrono::Utc;
+
+use governance::domain::ProposalId;
+use governance::service::CommunityFund;
+
+use crate::domain::{FundDisbursement, LedgerEntryStatus};
+use crate::repository::CommunityFundRepository;
+
+use super::payout::{reconcile_payout, start_payout};
+use super::settlement::SettlementBackend;
+
+pub struct CommunityFundService {
+    repository: CommunityFundRepository,
+    settlement: Arc<dyn SettlementBackend>, // Backend cuya cuenta de escrow es la del fondo
+}
+
+impl CommunityFundService {
+    pub fn new(repository: CommunityFundRepository, settlement: Arc<dyn SettlementBackend>) -> Self {
+        Self { repository, settlement }
+    }
+
+    /// Obtener el desembolso de una propuesta
+    pub async fn get_disbursement(&self, proposal_id: &ProposalId) -> Result<Option<FundDisbursement>> {
+        self.repository.get_disbursement(proposal_id.0).await
+    }
+}
+
+#[async_trait]
+impl CommunityFund for CommunityFundService {
+    async fn balance(&self) -> Result<u64> {
+        self.settlement.balance().await
+    }
+
+    async fn disburse(&self, proposal_id: &ProposalId, recipient: &str, amount: u64) -> Result<()> {
+        if let Some(disbursement) = self.repository.get_disbursement(proposal_id.0).await? {
+            return match disbursement.status {
+                LedgerEntryStatus::Settled => Ok(()),
+                LedgerEntryStatus::Pending => {
+                    reconcile_payout(self.settlement.as_ref(), &self.repository, disbursement).await
+                }
+            };
+        }
+
+        if amount == 0 {
+            bail!("El importe a financiar debe ser mayor que cero");
+        }
+
+        let balance = self.settlement.balance().await?;
+        if balance < amount {
+            bail!("Saldo insuficiente en el fondo comunitario: {} disponible, {} solicitado", balance, amount);
+        }
+
+        let payout = self.settlement.prepare_payout(recipient, amount).await?;
+        let disbursement = FundDisbursement {
+            proposal_id: proposal_id.0,
+            recipient: recipient.to_string(),
+            amount,
+            status: LedgerEntryStatus::Pending,
+            transaction_hash: payout.transaction_hash.clone(),
+            payout,
+            created_at: Utc::now(),
+        };
+
+        start_payout(self.settlement.as_ref(), &self.repository, disbursement).await
+    }
+}
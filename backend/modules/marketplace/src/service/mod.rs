@@ -0,0 +1,16 @@
+// Servicios de aplicación para el módulo marketplace
+
+pub mod fund;
+pub mod payment;
+mod payout;
+pub mod settlement;
+pub mod space;
+pub mod tutoring;
+
+pub use fund::CommunityFundService;
+pub use payment::PaymentService;
+pub use settlement::{
+    LocalSettlement, PayoutStatus, SettlementBackend, SettlementReceipt, StarknetErc20Settlement,
+};
+pub use space::{LearningSpaceExecutor, LearningSpaceService};
+pub use tutoring::TutoringService;
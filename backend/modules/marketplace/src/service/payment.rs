@@ -0,0 +1,237 @@
+// Escrow de sesiones de tutoría: depósito al reservar y liquidación al cerrar la sesión
+// Cada tramo (reembolso, pago al tutor, comisión) se registra en el ledger como pendiente,
+// con el hash de su transferencia, antes de enviarla; un reintento la reconcilia con el backend
+
+use std::sync::Arc;
+
+use anyhow::{anyhow, bail, Result};
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::domain::{
+    CancellationPolicy, FeeSchedule, LedgerEntry, LedgerEntryKind, LedgerEntryStatus, Payment, PaymentId,
+    PaymentStatus, SessionId, SessionStatus, SettlementPlan,
+};
+use crate::repository::PaymentRepository;
+
+use super::payout::{reconcile_payout, start_payout};
+use super::settlement::SettlementBackend;
+use super::TutoringService;
+
+pub struct PaymentService {
+    repository: PaymentRepository,
+    tutoring: Arc<TutoringService>,
+    settlement: Arc<dyn SettlementBackend>,
+    fees: FeeSchedule,
+    cancellation_policy: CancellationPolicy,
+    treasury_account: String, // Cuenta de la plataforma que recibe las comisiones
+}
+
+impl PaymentService {
+    pub fn new(
+        repository: PaymentRepository,
+        tutoring: Arc<TutoringService>,
+        settlement: Arc<dyn SettlementBackend>,
+        fees: FeeSchedule,
+        cancellation_policy: CancellationPolicy,
+        treasury_account: &str,
+    ) -> Self {
+        Self {
+            repository,
+            tutoring,
+            settlement,
+            fees,
+            cancellation_policy,
+            treasury_account: treasury_account.to_string(),
+        }
+    }
+
+    /// Retener en escrow el pago de una reserva pendiente
+    ///
+    /// El tutor ve el importe retenido al decidir si acepta la reserva.
+    pub async fn escrow_booking(
+        &self,
+        session_id: &SessionId,
+        payer: &str,
+        amount: u64,
+        deposit_reference: &str,
+    ) -> Result<Payment> {
+        if amount == 0 {
+            bail!("El importe del escrow debe ser mayor que cero");
+        }
+
+        let session = self
+            .tutoring
+            .get_session(session_id)
+            .await?
+            .ok_or_else(|| anyhow!("Sesión no encontrada: {}", session_id.0))?;
+
+        if session.learner != payer {
+            bail!("Solo el estudiante de la sesión puede pagarla");
+        }
+        if session.status != SessionStatus::Requested {
+            bail!("Solo se pueden pagar reservas pendientes (estado actual: {})", session.status);
+        }
+        if self.repository.get_payment_by_session(session_id).await?.is_some() {
+            bail!("La sesión {} ya tiene un pago en escrow", session_id.0);
+        }
+        if self.repository.deposit_reference_exists(deposit_reference).await? {
+            bail!("El depósito {} ya respalda otro pago", deposit_reference);
+        }
+
+        let receipt = self.settlement.confirm_deposit(payer, amount, deposit_reference).await?;
+
+        let now = Utc::now();
+        let payment = Payment {
+            id: PaymentId::new(),
+            session_id: session_id.clone(),
+            payer: payer.to_string(),
+            payee: session.tutor.clone(),
+            amount,
+            status: PaymentStatus::Escrowed,
+            deposit_reference: deposit_reference.to_string(),
+            plan: None,
+            created_at: now,
+            settled_at: None,
+        };
+        let deposit = LedgerEntry {
+            id: Uuid::new_v4(),
+            payment_id: payment.id.clone(),
+            kind: LedgerEntryKind::Deposit,
+            account: payer.to_string(),
+            amount,
+            status: LedgerEntryStatus::Settled,
+            idempotency_key: LedgerEntry::idempotency_key(&payment.id, LedgerEntryKind::Deposit),
+            transaction_hash: receipt.transaction_hash,
+            payout: None,
+            created_at: now,
+        };
+
+        self.repository.create_payment(&payment, &deposit).await?;
+
+        // TODO: Emitir evento de dominio
+        // self.emit_event(PaymentEvent::PaymentEscrowed { ... }).await?;
+
+        Ok(payment)
+    }
+
+    /// Liquidar el escrow de una sesión cerrada según cómo terminó
+    ///
+    /// Se puede reintentar tras cualquier fallo: cada tramo ya registrado se reconcilia con el
+    /// backend de liquidación por el hash de su transferencia en lugar de enviarse de nuevo.
+    pub async fn settle_session(&self, session_id: &SessionId) -> Result<Payment> {
+        let mut payment = self
+            .repository
+            .get_payment_by_session(session_id)
+            .await?
+            .ok_or_else(|| anyhow!("La sesión {} no tiene pago en escrow", session_id.0))?;
+
+        if payment.status != PaymentStatus::Escrowed {
+            return Ok(payment);
+        }
+
+        // Fijar el reparto antes de mover fondos para que un reintento use el mismo
+        let plan = match payment.plan {
+            Some(plan) => plan,
+            None => {
+                let session = self
+                    .tutoring
+                    .get_session(session_id)
+                    .await?
+                    .ok_or_else(|| anyhow!("Sesión no encontrada: {}", session_id.0))?;
+                let refund_bps = self.cancellation_policy.refund_bps(&session)?;
+                let plan = SettlementPlan::new(payment.amount, refund_bps, &self.fees);
+
+                payment.plan = Some(plan);
+                self.repository.update_settlement(&payment).await?;
+                plan
+            }
+        };
+
+        let ledger = self.repository.get_ledger(&payment.id).await?;
+
+        let legs = [
+            (LedgerEntryKind::Refund, payment.payer.clone(), plan.refund),
+            (LedgerEntryKind::TutorPayout, payment.payee.clone(), plan.tutor),
+            (LedgerEntryKind::PlatformFee, self.treasury_account.clone(), plan.platform_fee),
+        ];
+
+        for (kind, account, amount) in legs {
+            if amount == 0 {
+                continue;
+            }
+
+            match ledger.iter().find(|entry| entry.kind == kind) {
+                Some(entry) if entry.status == LedgerEntryStatus::Settled => {}
+                Some(entry) => reconcile_payout(self.settlement.as_ref(), &self.repository, entry.clone()).await?,
+                None => self.start_leg(&payment.id, kind, &account, amount).await?,
+            }
+        }
+
+        payment.status = plan.resulting_status();
+        payment.settled_at = Some(Utc::now());
+        self.repository.update_settlement(&payment).await?;
+
+        // TODO: Emitir evento de dominio
+        // self.emit_event(PaymentEvent::PaymentSettled { ... }).await?;
+
+        Ok(payment)
+    }
+
+    /// Liquidar los escrows de todas las sesiones que ya se cerraron
+    ///
+    /// Pensado para ejecutarse periódicamente; un fallo en una sesión no detiene las demás.
+    pub async fn settle_closed_sessions(&self) -> Result<Vec<Payment>> {
+        let mut settled = Vec::new();
+
+        for session_id in self.repository.get_session_ids_by_status(PaymentStatus::Escrowed).await? {
+            let Some(session) = self.tutoring.get_session(&session_id).await? else {
+                continue;
+            };
+            if !session.status.is_closed() {
+                continue;
+            }
+
+            match self.settle_session(&session_id).await {
+                Ok(payment) => settled.push(payment),
+                Err(e) => tracing::warn!("No se pudo liquidar el pago de la sesión {}: {}", session_id.0, e),
+            }
+        }
+
+        Ok(settled)
+    }
+
+    /// Registrar un tramo como pendiente con su transferencia firmada y enviarla
+    async fn start_leg(
+        &self,
+        payment_id: &PaymentId,
+        kind: LedgerEntryKind,
+        account: &str,
+        amount: u64,
+    ) -> Result<()> {
+        let payout = self.settlement.prepare_payout(account, amount).await?;
+        let entry = LedgerEntry {
+            id: Uuid::new_v4(),
+            payment_id: payment_id.clone(),
+            kind,
+            account: account.to_string(),
+            amount,
+            status: LedgerEntryStatus::Pending,
+            idempotency_key: LedgerEntry::idempotency_key(payment_id, kind),
+            transaction_hash: payout.transaction_hash.clone(),
+            payout: Some(payout),
+            created_at: Utc::now(),
+        };
+
+        start_payout(self.settlement.as_ref(), &self.repository, entry).await
+    }
+
+    pub async fn get_payment_for_session(&self, session_id: &SessionId) -> Result<Option<Payment>> {
+        self.repository.get_payment_by_session(session_id).await
+    }
+
+    /// Movimientos del ledger de un pago
+    pub async fn get_ledger(&self, payment_id: &PaymentId) -> Result<Vec<LedgerEntry>> {
+        self.repository.get_ledger(payment_id).await
+    }
+}
@@ -0,0 +1,146 @@
+// Transferencias salientes del escrow: desembolsos del fondo comunitario y tramos de los pagos
+// Se registran como pendientes, con su transferencia firmada, antes de enviarse; un reintento
+// las reconcilia con el backend de liquidación en lugar de enviarlas de nuevo
+
+use anyhow::{anyhow, bail, Result};
+use async_trait::async_trait;
+
+use crate::domain::{FundDisbursement, LedgerEntry, LedgerEntryStatus, PreparedPayout};
+use crate::repository::{CommunityFundRepository, PaymentRepository};
+
+use super::settlement::{PayoutStatus, SettlementBackend};
+
+/// Registro de una transferencia saliente
+pub(crate) trait PayoutRecord: Send + Sync {
+    fn recipient(&self) -> &str;
+    fn amount(&self) -> u64;
+    fn payout(&self) -> Result<&PreparedPayout>;
+    /// Sustituir la transferencia, junto con el hash que se guarda del registro
+    fn replace_payout(&mut self, payout: PreparedPayout);
+    fn mark_settled(&mut self);
+}
+
+/// Persistencia de los registros de transferencias
+#[async_trait]
+pub(crate) trait PayoutStore<R: PayoutRecord>: Send + Sync {
+    async fn create(&self, record: &R) -> Result<()>;
+    async fn update(&self, record: &R) -> Result<()>;
+}
+
+/// Registrar una transferencia ya preparada como pendiente y enviarla
+pub(crate) async fn start_payout<R: PayoutRecord>(
+    settlement: &dyn SettlementBackend,
+    store: &impl PayoutStore<R>,
+    record: R,
+) -> Result<()> {
+    // Si el registro falla, la transferencia no se ha enviado
+    store.create(&record).await?;
+    submit_payout(settlement, store, record).await
+}
+
+/// Averiguar en qué quedó la transferencia de un registro pendiente y completarlo
+pub(crate) async fn reconcile_payout<R: PayoutRecord>(
+    settlement: &dyn SettlementBackend,
+    store: &impl PayoutStore<R>,
+    mut record: R,
+) -> Result<()> {
+    let payout = record.payout()?.clone();
+
+    match settlement.payout_status(&payout).await? {
+        PayoutStatus::Accepted => {
+            record.mark_settled();
+            store.update(&record).await
+        }
+        PayoutStatus::Pending => {
+            bail!("La transferencia {} sigue pendiente de confirmación", payout.transaction_hash)
+        }
+        PayoutStatus::NotSubmitted => submit_payout(settlement, store, record).await,
+        PayoutStatus::Failed(reason) => {
+            // No movió fondos: se sustituye por una nueva antes de enviarla
+            tracing::warn!("La transferencia {} falló ({}); se prepara otra", payout.transaction_hash, reason);
+            let payout = settlement.prepare_payout(record.recipient(), record.amount()).await?;
+            record.replace_payout(payout);
+            store.update(&record).await?;
+            submit_payout(settlement, store, record).await
+        }
+    }
+}
+
+async fn submit_payout<R: PayoutRecord>(
+    settlement: &dyn SettlementBackend,
+    store: &impl PayoutStore<R>,
+    mut record: R,
+) -> Result<()> {
+    settlement.submit_payout(record.payout()?).await?;
+
+    record.mark_settled();
+    store.update(&record).await
+}
+
+impl PayoutRecord for FundDisbursement {
+    fn recipient(&self) -> &str {
+        &self.recipient
+    }
+
+    fn amount(&self) -> u64 {
+        self.amount
+    }
+
+    fn payout(&self) -> Result<&PreparedPayout> {
+        Ok(&self.payout)
+    }
+
+    fn replace_payout(&mut self, payout: PreparedPayout) {
+        self.transaction_hash = payout.transaction_hash.clone();
+        self.payout = payout;
+    }
+
+    fn mark_settled(&mut self) {
+        self.status = LedgerEntryStatus::Settled;
+    }
+}
+
+impl PayoutRecord for LedgerEntry {
+    fn recipient(&self) -> &str {
+        &self.account
+    }
+
+    fn amount(&self) -> u64 {
+        self.amount
+    }
+
+    fn payout(&self) -> Result<&PreparedPayout> {
+        self.payout.as_ref().ok_or_else(|| anyhow!("El movimiento {} no tiene transferencia", self.id))
+    }
+
+    fn replace_payout(&mut self, payout: PreparedPayout) {
+        self.transaction_hash = payout.transaction_hash.clone();
+        self.payout = Some(payout);
+    }
+
+    fn mark_settled(&mut self) {
+        self.status = LedgerEntryStatus::Settled;
+    }
+}
+
+#[async_trait]
+impl PayoutStore<FundDisbursement> for CommunityFundRepository {
+    async fn create(&self, record: &FundDisbursement) -> Result<()> {
+        self.create_disbursement(record).await
+    }
+
+    async fn update(&self, record: &FundDisbursement) -> Result<()> {
+        self.update_disbursement(record).await
+    }
+}
+
+#[async_trait]
+impl PayoutStore<LedgerEntry> for PaymentRepository {
+    async fn create(&self, record: &LedgerEntry) -> Result<()> {
+        self.add_ledger_entry(record).await
+    }
+
+    async fn update(&self, record: &LedgerEntry) -> Result<()> {
+        self.update_ledger_entry(record).await
+    }
+}
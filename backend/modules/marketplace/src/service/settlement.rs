@@ -0,0 +1,503 @@
+// Liquidación de pagos: backends que mueven fondos hacia y desde la cuenta de escrow
+// El ledger del marketplace registra cada movimiento con la transacción que lo liquidó
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Result};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use starknet_rs::core::crypto::{compute_hash_on_elements, ecdsa_sign};
+use starknet_rs::core::types::FieldElement;
+use starknet_rs::core::utils::{cairo_short_string_to_felt, get_selector_from_name};
+
+use crate::domain::PreparedPayout;
+
+/// Recibo de un movimiento liquidado
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SettlementReceipt {
+    pub transaction_hash: String,
+}
+
+/// Backend de liquidación para el escrow de sesiones
+#[async_trait]
+pub trait SettlementBackend: Send + Sync {
+    /// Comprobar que el pagador depositó el importe en la cuenta de escrow
+    ///
+    /// `reference` identifica el depósito en el backend (ej: hash de la transferencia).
+    async fn confirm_deposit(&self, payer: &str, amount: u64, reference: &str) -> Result<SettlementReceipt>;
+
+    /// Preparar y firmar una transferencia desde la cuenta de escrow, sin enviarla
+    async fn prepare_payout(&self, to: &str, amount: u64) -> Result<PreparedPayout>;
+
+    /// Enviar una transferencia preparada y esperar a que se acepte
+    ///
+    /// Reenviar la misma transferencia no paga dos veces: el backend la identifica por su hash.
+    async fn submit_payout(&self, payout: &PreparedPayout) -> Result<()>;
+
+    /// Consultar en qué quedó una transferencia preparada
+    async fn payout_status(&self, payout: &PreparedPayout) -> Result<PayoutStatus>;
+
+    /// Saldo actual de la cuenta de escrow
+    async fn balance(&self) -> Result<u64>;
+}
+
+/// Situación de una transferencia preparada en el backend de liquidación
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PayoutStatus {
+    Accepted,          // Ejecutada y aceptada: los fondos ya se movieron
+    Pending,           // Enviada, todavía sin aceptar
+    NotSubmitted,      // El backend no la conoce y todavía puede enviarse
+    Failed(String),    // Revertida o descartada: no movió fondos y ya no se ejecutará
+}
+
+/// Backend en memoria para desarrollo local y tests
+///
+/// Los saldos se acreditan con `credit`; cada depósito mueve fondos del pagador al escrow.
+#[derive(Default)]
+pub struct LocalSettlement {
+    state: Mutex<LocalSettlementState>,
+}
+
+#[derive(Default)]
+struct LocalSettlementState {
+    balances: HashMap<String, u64>,
+    escrow: u64,
+    transactions: u64,
+    executed: HashSet<String>, // Transferencias ya ejecutadas, por hash
+}
+
+impl LocalSettlementState {
+    fn next_transaction(&mut self) -> SettlementReceipt {
+        self.transactions += 1;
+        SettlementReceipt { transaction_hash: format!("local-{:08}", self.transactions) }
+    }
+}
+
+impl LocalSettlement {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Acreditar saldo a una cuenta
+    pub fn credit(&self, account: &str, amount: u64) {
+        let mut state = self.state.lock().unwrap();
+        *state.balances.entry(account.to_string()).or_default() += amount;
+    }
+
+    pub fn balance_of(&self, account: &str) -> u64 {
+        self.state.lock().unwrap().balances.get(account).copied().unwrap_or(0)
+    }
+
+    pub fn escrow_balance(&self) -> u64 {
+        self.state.lock().unwrap().escrow
+    }
+}
+
+#[async_trait]
+impl SettlementBackend for LocalSettlement {
+    async fn confirm_deposit(&self, payer: &str, amount: u64, _reference: &str) -> Result<SettlementReceipt> {
+        let mut state = self.state.lock().unwrap();
+
+        let balance = state.balances.entry(payer.to_string()).or_default();
+        if *balance < amount {
+            bail!("Saldo insuficiente de {} para depositar {}", payer, amount);
+        }
+        *balance -= amount;
+        state.escrow += amount;
+
+        Ok(state.next_transaction())
+    }
+
+    async fn prepare_payout(&self, to: &str, amount: u64) -> Result<PreparedPayout> {
+        let receipt = self.state.lock().unwrap().next_transaction();
+
+        Ok(PreparedPayout {
+            transaction_hash: receipt.transaction_hash,
+            transaction: json!({ "to": to, "amount": amount }),
+        })
+    }
+
+    async fn submit_payout(&self, payout: &PreparedPayout) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+
+        if state.executed.contains(&payout.transaction_hash) {
+            return Ok(());
+        }
+
+        let to = payout.transaction["to"]
+            .as_str()
+            .ok_or_else(|| anyhow!("Transferencia local sin destinatario"))?
+            .to_string();
+        let amount = payout.transaction["amount"]
+            .as_u64()
+            .ok_or_else(|| anyhow!("Transferencia local sin importe"))?;
+
+        if state.escrow < amount {
+            bail!("Saldo insuficiente en escrow para pagar {}", amount);
+        }
+        state.escrow -= amount;
+        *state.balances.entry(to).or_default() += amount;
+        state.executed.insert(payout.transaction_hash.clone());
+
+        Ok(())
+    }
+
+    async fn payout_status(&self, payout: &PreparedPayout) -> Result<PayoutStatus> {
+        let state = self.state.lock().unwrap();
+
+        Ok(if state.executed.contains(&payout.transaction_hash) {
+            PayoutStatus::Accepted
+        } else {
+            PayoutStatus::NotSubmitted
+        })
+    }
+
+    async fn balance(&self) -> Result<u64> {
+        Ok(self.escrow_balance())
+    }
+}
+
+/// Intentos de consulta del recibo antes de dar por perdida una transacción enviada
+const RECEIPT_POLL_ATTEMPTS: u32 = 20;
+const RECEIPT_POLL_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Código de error JSON-RPC de Starknet para una transacción desconocida
+const TXN_HASH_NOT_FOUND: i64 = 29;
+
+/// Liquidación con un token ERC-20 en Starknet
+///
+/// El estudiante transfiere el importe a la cuenta de escrow desde su wallet y
+/// aporta el hash de la transacción; los pagos salen de la cuenta de escrow con
+/// transacciones INVOKE firmadas por la plataforma.
+pub struct StarknetErc20Settlement {
+    http: reqwest::Client,
+    rpc_url: String,
+    chain_id: FieldElement,
+    token: FieldElement,
+    escrow_account: FieldElement,
+    escrow_private_key: FieldElement,
+    max_fee: FieldElement,
+    reserved_nonce: tokio::sync::Mutex<Option<FieldElement>>, // Siguiente nonce libre según este proceso
+}
+
+impl StarknetErc20Settlement {
+    pub fn new(
+        rpc_url: &str,
+        chain_id: &str,
+        token_address: &str,
+        escrow_account: &str,
+        escrow_private_key: &str,
+        max_fee: u64,
+    ) -> Result<Self> {
+        Ok(Self {
+            http: reqwest::Client::new(),
+            rpc_url: rpc_url.to_string(),
+            chain_id: cairo_short_string_to_felt(chain_id)?,
+            token: parse_felt(token_address)?,
+            escrow_account: parse_felt(escrow_account)?,
+            escrow_private_key: parse_felt(escrow_private_key)?,
+            max_fee: FieldElement::from(max_fee),
+            reserved_nonce: tokio::sync::Mutex::new(None),
+        })
+    }
+
+    /// Llamada JSON-RPC al nodo de Starknet; un error del nodo se devuelve como `Err` interno
+    async fn rpc_call(&self, method: &str, params: Value) -> Result<std::result::Result<Value, Value>> {
+        let response: Value = self
+            .http
+            .post(&self.rpc_url)
+            .json(&json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params }))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        if let Some(error) = response.get("error") {
+            return Ok(Err(error.clone()));
+        }
+
+        response
+            .get("result")
+            .cloned()
+            .map(Ok)
+            .ok_or_else(|| anyhow!("Respuesta de Starknet sin resultado en {}", method))
+    }
+
+    /// Llamada JSON-RPC al nodo de Starknet
+    async fn rpc(&self, method: &str, params: Value) -> Result<Value> {
+        self.rpc_call(method, params)
+            .await?
+            .map_err(|error| anyhow!("Error de Starknet en {}: {}", method, error))
+    }
+
+    async fn get_receipt(&self, transaction_hash: &str) -> Result<Value> {
+        self.rpc("starknet_getTransactionReceipt", json!([transaction_hash])).await
+    }
+
+    /// Recibo de una transacción; `None` si el nodo no la conoce
+    async fn find_receipt(&self, transaction_hash: &str) -> Result<Option<Value>> {
+        match self.rpc_call("starknet_getTransactionReceipt", json!([transaction_hash])).await? {
+            Ok(receipt) => Ok(Some(receipt)),
+            Err(error) if error["code"].as_i64() == Some(TXN_HASH_NOT_FOUND) => Ok(None),
+            Err(error) => bail!("Error de Starknet consultando {}: {}", transaction_hash, error),
+        }
+    }
+
+    async fn get_nonce(&self, block: &str) -> Result<FieldElement> {
+        let nonce = self
+            .rpc("starknet_getNonce", json!([block, format!("{:#x}", self.escrow_account)]))
+            .await?;
+        parse_felt(nonce.as_str().unwrap_or_default())
+    }
+
+    /// Esperar a que una transacción enviada sea aceptada y se ejecute correctamente
+    async fn wait_for_acceptance(&self, transaction_hash: &str) -> Result<()> {
+        for _ in 0..RECEIPT_POLL_ATTEMPTS {
+            tokio::time::sleep(RECEIPT_POLL_INTERVAL).await;
+
+            // El nodo responde con error mientras la transacción no está en un bloque
+            let Ok(receipt) = self.get_receipt(transaction_hash).await else {
+                continue;
+            };
+            check_receipt_succeeded(&receipt, transaction_hash)?;
+            return Ok(());
+        }
+
+        bail!("La transacción {} no fue aceptada a tiempo", transaction_hash)
+    }
+}
+
+#[async_trait]
+impl SettlementBackend for StarknetErc20Settlement {
+    async fn confirm_deposit(&self, payer: &str, amount: u64, reference: &str) -> Result<SettlementReceipt> {
+        let receipt = self.get_receipt(reference).await?;
+        check_receipt_succeeded(&receipt, reference)?;
+
+        let payer = parse_felt(payer)?;
+        let transfer_selector = get_selector_from_name("Transfer")?;
+        let events = receipt["events"].as_array().cloned().unwrap_or_default();
+
+        for event in events {
+            if parse_felt(event["from_address"].as_str().unwrap_or_default()).ok() != Some(self.token) {
+                continue;
+            }
+            let keys = felts(&event["keys"])?;
+            let data = felts(&event["data"])?;
+            if keys.first() != Some(&transfer_selector) {
+                continue;
+            }
+
+            // Los ERC-20 en Cairo 1 indexan origen y destino; los de Cairo 0 los envían en data
+            let (from, to, low, high) = match (keys.len(), data.len()) {
+                (3, 2) => (keys[1], keys[2], data[0], data[1]),
+                (1, 4) => (data[0], data[1], data[2], data[3]),
+                _ => continue,
+            };
+
+            if from == payer
+                && to == self.escrow_account
+                && low == FieldElement::from(amount)
+                && high == FieldElement::ZERO
+            {
+                return Ok(SettlementReceipt { transaction_hash: reference.to_string() });
+            }
+        }
+
+        bail!("La transacción {} no transfiere {} a la cuenta de escrow", reference, amount)
+    }
+
+    async fn prepare_payout(&self, to: &str, amount: u64) -> Result<PreparedPayout> {
+        // Las transferencias preparadas a la vez no pueden compartir nonce
+        let mut reserved = self.reserved_nonce.lock().await;
+        let pending = self.get_nonce("pending").await?;
+        let nonce = reserved.filter(|reserved| *reserved > pending).unwrap_or(pending);
+        *reserved = Some(nonce + FieldElement::ONE);
+
+        // __execute__ de la cuenta con una única llamada: transfer(to, u256 { low, high })
+        let calldata = vec![
+            FieldElement::ONE,
+            self.token,
+            get_selector_from_name("transfer")?,
+            FieldElement::from(3u8),
+            parse_felt(to)?,
+            FieldElement::from(amount),
+            FieldElement::ZERO,
+        ];
+
+        // Hash de transacción INVOKE v1
+        let transaction_hash = compute_hash_on_elements(&[
+            cairo_short_string_to_felt("invoke")?,
+            FieldElement::ONE,
+            self.escrow_account,
+            FieldElement::ZERO,
+            compute_hash_on_elements(&calldata),
+            self.max_fee,
+            self.chain_id,
+            nonce,
+        ]);
+        let signature = ecdsa_sign(&self.escrow_private_key, &transaction_hash)?;
+
+        let hex = |felt: &FieldElement| format!("{:#x}", felt);
+        Ok(PreparedPayout {
+            transaction_hash: hex(&transaction_hash),
+            transaction: json!({
+                "type": "INVOKE",
+                "version": "0x1",
+                "sender_address": hex(&self.escrow_account),
+                "calldata": calldata.iter().map(hex).collect::<Vec<_>>(),
+                "max_fee": hex(&self.max_fee),
+                "signature": [hex(&signature.r), hex(&signature.s)],
+                "nonce": hex(&nonce),
+            }),
+        })
+    }
+
+    async fn submit_payout(&self, payout: &PreparedPayout) -> Result<()> {
+        let result = self
+            .rpc("starknet_addInvokeTransaction", json!({ "invoke_transaction": payout.transaction }))
+            .await?;
+
+        let transaction_hash = result["transaction_hash"]
+            .as_str()
+            .ok_or_else(|| anyhow!("Starknet no devolvió el hash de la transacción"))?;
+        if parse_felt(transaction_hash)? != parse_felt(&payout.transaction_hash)? {
+            bail!(
+                "Starknet registró la transferencia como {} en lugar de {}",
+                transaction_hash,
+                payout.transaction_hash
+            );
+        }
+
+        self.wait_for_acceptance(&payout.transaction_hash).await
+    }
+
+    async fn payout_status(&self, payout: &PreparedPayout) -> Result<PayoutStatus> {
+        // El nonce se consulta antes que el recibo: si ya estaba consumido y la transferencia
+        // no tiene recibo, la consumió otra transacción y esta nunca se ejecutará
+        let nonce = parse_felt(payout.transaction["nonce"].as_str().unwrap_or_default())?;
+        let nonce_consumed = self.get_nonce("latest").await? > nonce;
+
+        let Some(receipt) = self.find_receipt(&payout.transaction_hash).await? else {
+            return Ok(if nonce_consumed {
+                PayoutStatus::Failed(format!("el nonce {:#x} ya se usó en otra transacción", nonce))
+            } else {
+                PayoutStatus::NotSubmitted
+            });
+        };
+
+        if receipt["execution_status"] == "REVERTED" {
+            let reason = receipt["revert_reason"].as_str().unwrap_or("sin motivo");
+            return Ok(PayoutStatus::Failed(reason.to_string()));
+        }
+
+        Ok(match check_receipt_succeeded(&receipt, &payout.transaction_hash) {
+            Ok(()) => PayoutStatus::Accepted,
+            Err(_) => PayoutStatus::Pending,
+        })
+    }
+
+    async fn balance(&self) -> Result<u64> {
+        let result = self
+            .rpc(
+                "starknet_call",
+                json!([
+                    {
+                        "contract_address": format!("{:#x}", self.token),
+                        "entry_point_selector": format!("{:#x}", get_selector_from_name("balanceOf")?),
+                        "calldata": [format!("{:#x}", self.escrow_account)],
+                    },
+                    "latest"
+                ]),
+            )
+            .await?;
+
+        // balanceOf devuelve un u256 { low, high }
+        let balance = felts(&result)?;
+        match balance.as_slice() {
+            [low, high] if *high == FieldElement::ZERO => {
+                u64::try_from(*low).map_err(|_| anyhow!("El saldo de la cuenta de escrow no cabe en u64"))
+            }
+            [_, _] => bail!("El saldo de la cuenta de escrow no cabe en u64"),
+            _ => bail!("Respuesta inesperada de balanceOf: {}", result),
+        }
+    }
+}
+
+fn parse_felt(value: &str) -> Result<FieldElement> {
+    FieldElement::from_hex_be(value).map_err(|e| anyhow!("Valor de Starknet inválido '{}': {}", value, e))
+}
+
+fn felts(value: &Value) -> Result<Vec<FieldElement>> {
+    value
+        .as_array()
+        .map(|items| items.iter().map(|item| parse_felt(item.as_str().unwrap_or_default())).collect())
+        .unwrap_or_else(|| Ok(Vec::new()))
+}
+
+fn check_receipt_succeeded(receipt: &Value, transaction_hash: &str) -> Result<()> {
+    if receipt["execution_status"] != "SUCCEEDED" {
+        bail!(
+            "La transacción {} no se ejecutó correctamente: {}",
+            transaction_hash,
+            receipt["revert_reason"].as_str().unwrap_or("sin motivo")
+        );
+    }
+    if !matches!(receipt["finality_status"].as_str(), Some("ACCEPTED_ON_L2" | "ACCEPTED_ON_L1")) {
+        bail!("La transacción {} todavía no está aceptada", transaction_hash);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn local_settlement_moves_funds_through_escrow() {
+        let settlement = LocalSettlement::new();
+        settlement.credit("learner", 1_000);
+
+        settlement.confirm_deposit("learner", 800, "ref").await.unwrap();
+        assert_eq!(settlement.balance_of("learner"), 200);
+        assert_eq!(settlement.escrow_balance(), 800);
+
+        let first = settlement.prepare_payout("tutor", 760).await.unwrap();
+        let second = settlement.prepare_payout("treasury", 40).await.unwrap();
+        assert_ne!(first.transaction_hash, second.transaction_hash);
+
+        settlement.submit_payout(&first).await.unwrap();
+        settlement.submit_payout(&second).await.unwrap();
+        assert_eq!(settlement.balance_of("tutor"), 760);
+        assert_eq!(settlement.escrow_balance(), 0);
+    }
+
+    #[tokio::test]
+    async fn resubmitting_a_prepared_payout_pays_once() {
+        let settlement = LocalSettlement::new();
+        settlement.credit("learner", 1_000);
+        settlement.confirm_deposit("learner", 1_000, "ref").await.unwrap();
+
+        let payout = settlement.prepare_payout("tutor", 600).await.unwrap();
+        assert_eq!(settlement.payout_status(&payout).await.unwrap(), PayoutStatus::NotSubmitted);
+
+        settlement.submit_payout(&payout).await.unwrap();
+        settlement.submit_payout(&payout).await.unwrap();
+        assert_eq!(settlement.payout_status(&payout).await.unwrap(), PayoutStatus::Accepted);
+        assert_eq!(settlement.balance_of("tutor"), 600);
+        assert_eq!(settlement.escrow_balance(), 400);
+    }
+
+    #[tokio::test]
+    async fn local_settlement_rejects_overdrafts() {
+        let settlement = LocalSettlement::new();
+        settlement.credit("learner", 100);
+
+        assert!(settlement.confirm_deposit("learner", 101, "ref").await.is_err());
+        let payout = settlement.prepare_payout("tutor", 1).await.unwrap();
+        assert!(settlement.submit_payout(&payout).await.is_err());
+    }
+}
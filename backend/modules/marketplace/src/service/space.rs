@@ -0,0 +1,274 @@
+// Registro de espacios de aprendizaje seguros
+// Registro → avales/reportes de humanos verificados → verificación o revisión → decisión de gobernanza
+
+use std::sync::Arc;
+
+use anyhow::{anyhow, bail, Result};
+use async_trait::async_trait;
+use chrono::Utc;
+use uuid::Uuid;
+
+use governance::domain::{Proposal, ProposalPayload};
+use governance::service::ProposalExecutor;
+use identity::service::HumanityVerifier;
+
+use crate::domain::{
+    GeoPoint, LearningSpace, LearningSpaceDetails, LearningSpaceId, SpaceSearchFilter, SpaceStatus, SpaceValidation,
+    ValidationKind,
+};
+use crate::repository::LearningSpaceRepository;
+
+/// Radio máximo de búsqueda, en kilómetros
+pub const MAX_SEARCH_RADIUS_KM: f64 = 200.0;
+
+pub struct LearningSpaceService {
+    repository: LearningSpaceRepository,
+    humanity_verifier: Arc<dyn HumanityVerifier>,
+}
+
+impl LearningSpaceService {
+    pub fn new(repository: LearningSpaceRepository, humanity_verifier: Arc<dyn HumanityVerifier>) -> Self {
+        Self { repository, humanity_verifier }
+    }
+
+    /// Registrar un espacio; queda pendiente de avales de la comunidad
+    pub async fn register_space(&self, host: &str, details: LearningSpaceDetails) -> Result<LearningSpace> {
+        if !self.humanity_verifier.is_verified_address(host).await? {
+            bail!("Solo humanos verificados pueden registrar espacios");
+        }
+        details.validate()?;
+
+        let now = Utc::now();
+        let space = LearningSpace {
+            id: LearningSpaceId::new(),
+            host: host.to_string(),
+            details,
+            status: SpaceStatus::Pending,
+            status_reason: None,
+            status_proposal_id: None,
+            status_changed_at: now,
+            created_at: now,
+            updated_at: now,
+        };
+
+        self.repository.upsert_space(&space).await?;
+
+        // TODO: Emitir evento de dominio
+        // self.emit_event(SpaceEvent::SpaceRegistered { ... }).await?;
+
+        Ok(space)
+    }
+
+    /// Actualizar los datos de un espacio (solo el anfitrión)
+    ///
+    /// Un cambio de dirección o ubicación invalida los avales: el espacio vuelve a pendiente.
+    pub async fn update_space(
+        &self,
+        space_id: &LearningSpaceId,
+        host: &str,
+        details: LearningSpaceDetails,
+    ) -> Result<LearningSpace> {
+        let mut space = self.get_existing_space(space_id).await?;
+
+        if space.host != host {
+            bail!("Solo el anfitrión puede modificar el espacio");
+        }
+        details.validate()?;
+
+        let moved = details.address != space.details.address || details.location != space.details.location;
+        space.details = details;
+        space.updated_at = Utc::now();
+
+        if moved && space.status == SpaceStatus::Verified {
+            space.set_status(SpaceStatus::Pending, Some("Cambio de ubicación".to_string()), None);
+        }
+
+        self.repository.upsert_space(&space).await?;
+
+        Ok(space)
+    }
+
+    /// Avalar un espacio como seguro
+    pub async fn vouch(&self, space_id: &LearningSpaceId, member: &str, comment: &str) -> Result<LearningSpace> {
+        self.validate(space_id, member, ValidationKind::Vouch, comment).await
+    }
+
+    /// Reportar un problema en un espacio
+    pub async fn report(&self, space_id: &LearningSpaceId, member: &str, reason: &str) -> Result<LearningSpace> {
+        if reason.trim().is_empty() {
+            bail!("El reporte debe explicar el problema");
+        }
+
+        self.validate(space_id, member, ValidationKind::Report, reason).await
+    }
+
+    async fn validate(
+        &self,
+        space_id: &LearningSpaceId,
+        member: &str,
+        kind: ValidationKind,
+        comment: &str,
+    ) -> Result<LearningSpace> {
+        let mut space = self.get_existing_space(space_id).await?;
+
+        if space.host == member {
+            bail!("El anfitrión no puede validar su propio espacio");
+        }
+        if !self.humanity_verifier.is_verified_address(member).await? {
+            bail!("Solo humanos verificados pueden avalar o reportar espacios");
+        }
+
+        self.repository
+            .upsert_validation(&SpaceValidation {
+                space_id: space_id.clone(),
+                member: member.to_string(),
+                kind,
+                comment: comment.to_string(),
+                created_at: Utc::now(),
+            })
+            .await?;
+
+        // TODO: Emitir evento de dominio
+        // self.emit_event(SpaceEvent::SpaceValidated { ... }).await?;
+
+        let (vouches, reports) = self.repository.count_validations_since(space_id, space.status_changed_at).await?;
+        let next = space.status_after_validations(vouches, reports);
+
+        if next != space.status {
+            let reason = match next {
+                SpaceStatus::UnderReview => Some(format!("{} reportes de la comunidad", reports)),
+                _ => None,
+            };
+            space.set_status(next, reason, None);
+            self.repository.upsert_space(&space).await?;
+
+            // TODO: Emitir evento de dominio
+            // self.emit_event(SpaceEvent::SpaceStatusChanged { ... }).await?;
+        }
+
+        Ok(space)
+    }
+
+    /// Aplicar el estado decidido por una propuesta de gobernanza
+    pub async fn apply_governance_decision(
+        &self,
+        space_id: &LearningSpaceId,
+        status: SpaceStatus,
+        reason: Option<String>,
+        proposal_id: Uuid,
+    ) -> Result<LearningSpace> {
+        let mut space = self.get_existing_space(space_id).await?;
+
+        if !space.status.can_govern_to(status) {
+            bail!("La propuesta no puede pasar el espacio de {} a {}", space.status, status);
+        }
+
+        space.set_status(status, reason, Some(proposal_id));
+        self.repository.upsert_space(&space).await?;
+
+        // TODO: Emitir evento de dominio
+        // self.emit_event(SpaceEvent::SpaceStatusChanged { ... }).await?;
+
+        Ok(space)
+    }
+
+    /// Aplicar la decisión de un moderador sobre un espacio
+    pub async fn apply_moderation_decision(
+        &self,
+        space_id: &LearningSpaceId,
+        status: SpaceStatus,
+        reason: Option<String>,
+    ) -> Result<LearningSpace> {
+        let mut space = self.get_existing_space(space_id).await?;
+
+        if !space.status.can_moderate_to(status) {
+            bail!("Un moderador no puede pasar el espacio de {} a {}", space.status, status);
+        }
+        if status == SpaceStatus::Suspended && reason.as_deref().is_none_or(|r| r.trim().is_empty()) {
+            bail!("La suspensión debe indicar un motivo");
+        }
+
+        space.set_status(status, reason, None);
+        self.repository.upsert_space(&space).await?;
+
+        // TODO: Emitir evento de dominio
+        // self.emit_event(SpaceEvent::SpaceStatusChanged { ... }).await?;
+
+        Ok(space)
+    }
+
+    pub async fn get_space(&self, space_id: &LearningSpaceId) -> Result<Option<LearningSpace>> {
+        self.repository.get_space(space_id).await
+    }
+
+    /// Buscar espacios cerca de un punto, del más cercano al más lejano
+    pub async fn search_spaces(
+        &self,
+        center: GeoPoint,
+        radius_km: f64,
+        filter: SpaceSearchFilter,
+    ) -> Result<Vec<(LearningSpace, f64)>> {
+        let center = GeoPoint::new(center.latitude, center.longitude)?;
+        if radius_km <= 0.0 || radius_km > MAX_SEARCH_RADIUS_KM {
+            bail!("El radio de búsqueda debe estar entre 0 y {} km", MAX_SEARCH_RADIUS_KM);
+        }
+
+        self.repository.search_within_radius(&center, radius_km, &filter).await
+    }
+
+    /// Espacios en un estado (ej: cola de revisión para moderadores)
+    pub async fn get_spaces_by_status(&self, status: SpaceStatus) -> Result<Vec<LearningSpace>> {
+        let mut spaces = Vec::new();
+
+        for space_id in self.repository.get_space_ids_by_status(status).await? {
+            if let Some(space) = self.repository.get_space(&space_id).await? {
+                spaces.push(space);
+            }
+        }
+
+        Ok(spaces)
+    }
+
+    pub async fn get_validations(&self, space_id: &LearningSpaceId) -> Result<Vec<SpaceValidation>> {
+        self.repository.get_validations(space_id).await
+    }
+
+    async fn get_existing_space(&self, space_id: &LearningSpaceId) -> Result<LearningSpace> {
+        self.repository
+            .get_space(space_id)
+            .await?
+            .ok_or_else(|| anyhow!("Espacio no encontrado: {}", space_id.0))
+    }
+}
+
+/// Ejecutor de propuestas de aprobación y suspensión de espacios
+///
+/// Se registra en gobernanza para `ApproveLearningSpace` y `SuspendLearningSpace`.
+pub struct LearningSpaceExecutor {
+    spaces: Arc<LearningSpaceService>,
+}
+
+impl LearningSpaceExecutor {
+    pub fn new(spaces: Arc<LearningSpaceService>) -> Self {
+        Self { spaces }
+    }
+}
+
+#[async_trait]
+impl ProposalExecutor for LearningSpaceExecutor {
+    async fn execute(&self, proposal: &Proposal) -> Result<()> {
+        let (space_id, status, reason) = match &proposal.payload {
+            ProposalPayload::ApproveLearningSpace { space_id } => (space_id, SpaceStatus::Verified, None),
+            ProposalPayload::SuspendLearningSpace { space_id, reason } => {
+                (space_id, SpaceStatus::Suspended, Some(reason.clone()))
+            }
+            _ => bail!("La propuesta no cambia el estado de un espacio"),
+        };
+
+        self.spaces
+            .apply_governance_decision(&LearningSpaceId(*space_id), status, reason, proposal.id.0)
+            .await?;
+
+        Ok(())
+    }
+}
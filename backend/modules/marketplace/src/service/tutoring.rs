@@ -0,0 +1,582 @@
+// Flujo de tutorías: perfiles, reservas y ciclo de vida de sesiones
+// Al completar una sesión se registra una interacción firmada en el pasaporte de ambas partes
+
+use std::sync::Arc;
+
+use anyhow::{anyhow, bail, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, SubsecRound, Utc};
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use identity::service::HumanityVerifier;
+use learning_passport::domain::{LearningContext, LearningResult};
+use learning_passport::service::{LearningPassportService, EVENT_CHANNEL_CAPACITY};
+use reputation::domain::CompletedSession;
+use reputation::service::RatedSessions;
+
+use crate::domain::{
+    AvailabilitySlot, SessionFilter, SessionId, SessionOrder, SessionParty, SessionStatus, Subject, TutorProfile,
+    TutorVerification, TutoringEvent, TutoringSession,
+};
+use crate::repository::TutoringRepository;
+
+/// Verbo xAPI registrado en el pasaporte del estudiante
+pub const LEARNER_VERB: &str = "http://adlnet.gov/expapi/verbs/attended";
+
+/// Verbo xAPI registrado en el pasaporte del tutor
+pub const TUTOR_VERB: &str = "http://id.tincanapi.com/verb/mentored";
+
+/// Tipo de actividad xAPI de una sesión de tutoría
+pub const SESSION_ACTIVITY_TYPE: &str = "http://adlnet.gov/expapi/activities/meeting";
+
+pub struct TutoringService {
+    repository: TutoringRepository,
+    humanity_verifier: Arc<dyn HumanityVerifier>,
+    passport: Arc<LearningPassportService>,
+    events: broadcast::Sender<TutoringEvent>,
+}
+
+impl TutoringService {
+    pub fn new(
+        repository: TutoringRepository,
+        humanity_verifier: Arc<dyn HumanityVerifier>,
+        passport: Arc<LearningPassportService>,
+    ) -> Self {
+        let (events, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+        Self { repository, humanity_verifier, passport, events }
+    }
+
+    /// Suscribirse a los eventos de tutorías emitidos a partir de ahora
+    pub fn subscribe(&self) -> broadcast::Receiver<TutoringEvent> {
+        self.events.subscribe()
+    }
+
+    /// Publicar un evento de dominio; sin suscriptores se descarta
+    fn emit_event(&self, event: TutoringEvent) {
+        let _ = self.events.send(event);
+    }
+
+    /// Publicar o actualizar el perfil de tutor de un usuario
+    pub async fn upsert_tutor_profile(
+        &self,
+        user_address: &str,
+        display_name: &str,
+        bio: &str,
+        subjects: Vec<Subject>,
+        languages: Vec<String>,
+        active: bool,
+    ) -> Result<TutorProfile> {
+        if !self.humanity_verifier.is_verified_address(user_address).await? {
+            bail!("Solo humanos verificados pueden ofrecer tutorías");
+        }
+        if display_name.trim().is_empty() {
+            bail!("El perfil necesita un nombre visible");
+        }
+        if subjects.is_empty() {
+            bail!("El perfil debe incluir al menos una materia");
+        }
+
+        let now = Utc::now();
+        let existing = self.repository.get_profile(user_address).await?;
+
+        let profile = TutorProfile {
+            user_address: user_address.to_string(),
+            display_name: display_name.to_string(),
+            bio: bio.to_string(),
+            subjects,
+            languages,
+            availability: existing.as_ref().map(|p| p.availability.clone()).unwrap_or_default(),
+            active,
+            verification: existing.as_ref().map(|p| p.verification).unwrap_or_default(),
+            created_at: existing.as_ref().map(|p| p.created_at).unwrap_or(now),
+            updated_at: now,
+        };
+
+        self.repository.upsert_profile(&profile).await?;
+        self.emit_event(TutoringEvent::TutorProfileUpdated {
+            user_address: profile.user_address.clone(),
+            timestamp: profile.updated_at,
+        });
+
+        Ok(profile)
+    }
+
+    /// Reemplazar el calendario semanal de disponibilidad de un tutor
+    pub async fn set_availability(&self, user_address: &str, availability: Vec<AvailabilitySlot>) -> Result<TutorProfile> {
+        let mut profile = self
+            .repository
+            .get_profile(user_address)
+            .await?
+            .ok_or_else(|| anyhow!("Perfil de tutor no encontrado: {}", user_address))?;
+
+        if availability.iter().any(|slot| slot.start >= slot.end) {
+            bail!("Cada franja de disponibilidad debe terminar después de empezar");
+        }
+
+        profile.availability = availability;
+        profile.updated_at = Utc::now();
+        self.repository.upsert_profile(&profile).await?;
+        self.emit_event(TutoringEvent::TutorProfileUpdated {
+            user_address: profile.user_address.clone(),
+            timestamp: profile.updated_at,
+        });
+
+        Ok(profile)
+    }
+
+    pub async fn get_tutor_profile(&self, user_address: &str) -> Result<Option<TutorProfile>> {
+        self.repository.get_profile(user_address).await
+    }
+
+    /// Perfiles de tutor de varios usuarios; los que no son tutores no aparecen
+    pub async fn get_tutor_profiles(&self, user_addresses: &[String]) -> Result<Vec<TutorProfile>> {
+        self.repository.get_profiles(user_addresses).await
+    }
+
+    /// Cambiar la verificación de un tutor (moderación)
+    pub async fn set_tutor_verification(
+        &self,
+        user_address: &str,
+        verification: TutorVerification,
+    ) -> Result<TutorProfile> {
+        let mut profile = self
+            .repository
+            .get_profile(user_address)
+            .await?
+            .ok_or_else(|| anyhow!("Perfil de tutor no encontrado: {}", user_address))?;
+
+        if profile.verification == verification {
+            bail!("El tutor ya está en estado {}", verification);
+        }
+
+        profile.verification = verification;
+        profile.updated_at = Utc::now();
+        self.repository.upsert_profile(&profile).await?;
+        self.emit_event(TutoringEvent::TutorProfileUpdated {
+            user_address: profile.user_address.clone(),
+            timestamp: profile.updated_at,
+        });
+
+        Ok(profile)
+    }
+
+    /// Tutores en un estado de verificación (ej: pendientes de revisión)
+    pub async fn get_tutors_by_verification(&self, verification: TutorVerification) -> Result<Vec<TutorProfile>> {
+        self.repository.get_profiles_by_verification(verification).await
+    }
+
+    /// Buscar tutores verificados y activos por materia y, opcionalmente, idioma
+    pub async fn search_tutors(&self, subject_code: &str, language: Option<&str>) -> Result<Vec<TutorProfile>> {
+        self.repository.search_profiles(subject_code, language).await
+    }
+
+    /// Solicitar una reserva con un tutor
+    pub async fn request_booking(
+        &self,
+        learner: &str,
+        tutor: &str,
+        subject_code: &str,
+        scheduled_start: DateTime<Utc>,
+        scheduled_end: DateTime<Utc>,
+        message: Option<String>,
+    ) -> Result<TutoringSession> {
+        if learner == tutor {
+            bail!("Un tutor no puede reservar una sesión consigo mismo");
+        }
+        if !self.humanity_verifier.is_verified_address(learner).await? {
+            bail!("Solo humanos verificados pueden reservar tutorías");
+        }
+        if scheduled_start >= scheduled_end {
+            bail!("La sesión debe terminar después de empezar");
+        }
+        if scheduled_start <= Utc::now() {
+            bail!("La sesión debe programarse en el futuro");
+        }
+
+        let profile = self
+            .repository
+            .get_profile(tutor)
+            .await?
+            .ok_or_else(|| anyhow!("Perfil de tutor no encontrado: {}", tutor))?;
+
+        if !profile.is_bookable() {
+            bail!("El tutor no está verificado o no acepta nuevas reservas");
+        }
+        let subject = profile
+            .subject(subject_code)
+            .cloned()
+            .ok_or_else(|| anyhow!("El tutor no imparte la materia {}", subject_code))?;
+        if !profile.is_available(scheduled_start, scheduled_end) {
+            bail!("El horario solicitado está fuera de la disponibilidad del tutor");
+        }
+        if self.repository.has_conflicting_session(tutor, scheduled_start, scheduled_end).await? {
+            bail!("El tutor ya tiene una sesión en ese horario");
+        }
+
+        let now = Utc::now();
+        let session = TutoringSession {
+            id: SessionId::new(),
+            tutor: tutor.to_string(),
+            learner: learner.to_string(),
+            subject,
+            scheduled_start,
+            scheduled_end,
+            status: SessionStatus::Requested,
+            message,
+            cancelled_by: None,
+            cancellation_reason: None,
+            cancelled_at: None,
+            absent_party: None,
+            no_show_report: None,
+            accepted_at: None,
+            started_at: None,
+            completed_at: None,
+            tutor_interaction_id: None,
+            learner_interaction_id: None,
+            requested_at: now,
+            updated_at: now.trunc_subsecs(6),
+        };
+
+        self.repository.insert_session(&session).await?;
+
+        self.emit_event(TutoringEvent::BookingRequested {
+            session_id: session.id.clone(),
+            tutor: session.tutor.clone(),
+            learner: session.learner.clone(),
+            timestamp: session.requested_at,
+        });
+
+        Ok(session)
+    }
+
+    /// Aceptar una solicitud de reserva (solo el tutor)
+    pub async fn accept_booking(&self, session_id: &SessionId, tutor: &str) -> Result<TutoringSession> {
+        let mut session = self.get_existing_session(session_id).await?;
+
+        if session.party_of(tutor) != Some(SessionParty::Tutor) {
+            bail!("Solo el tutor puede aceptar la reserva");
+        }
+        // La agenda pudo ocuparse con otra reserva aceptada desde la solicitud
+        if self
+            .repository
+            .has_conflicting_session(tutor, session.scheduled_start, session.scheduled_end)
+            .await?
+        {
+            bail!("El tutor ya tiene una sesión en ese horario");
+        }
+
+        session.accepted_at = Some(Utc::now());
+        self.transition(&mut session, SessionStatus::Accepted, tutor).await?;
+
+        Ok(session)
+    }
+
+    /// Cancelar una sesión pendiente o aceptada (cualquiera de las partes)
+    pub async fn cancel_session(
+        &self,
+        session_id: &SessionId,
+        cancelled_by: &str,
+        reason: Option<String>,
+    ) -> Result<TutoringSession> {
+        let mut session = self.get_existing_session(session_id).await?;
+
+        if session.party_of(cancelled_by).is_none() {
+            bail!("Solo los participantes pueden cancelar la sesión");
+        }
+
+        session.cancelled_by = Some(cancelled_by.to_string());
+        session.cancellation_reason = reason;
+        session.cancelled_at = Some(Utc::now());
+        self.transition(&mut session, SessionStatus::Cancelled, cancelled_by).await?;
+
+        Ok(session)
+    }
+
+    /// Iniciar una sesión aceptada dentro de su ventana horaria
+    ///
+    /// La inicia el estudiante: su inicio confirma que asistió, así que el tutor no puede
+    /// completar (y cobrar) una sesión a la que el estudiante no se presentó. Si el
+    /// estudiante no aparece, el tutor lo reporta con `mark_no_show`.
+    pub async fn start_session(&self, session_id: &SessionId, started_by: &str) -> Result<TutoringSession> {
+        let mut session = self.get_existing_session(session_id).await?;
+        let now = Utc::now();
+
+        if session.party_of(started_by) != Some(SessionParty::Learner) {
+            bail!("Solo el estudiante puede iniciar la sesión");
+        }
+        if !session.can_start_at(now) {
+            bail!("La sesión solo puede iniciarse cerca de su horario programado");
+        }
+
+        session.started_at = Some(now);
+        self.transition(&mut session, SessionStatus::InProgress, started_by).await?;
+
+        Ok(session)
+    }
+
+    /// Reportar que la otra parte no se presentó a una sesión aceptada
+    ///
+    /// La inasistencia decide el reembolso, así que el reporte no cierra la sesión: la
+    /// parte señalada debe confirmarlo con `confirm_no_show`. Si a su vez reporta a la
+    /// otra parte, el reporte queda en disputa y lo resuelve moderación.
+    pub async fn mark_no_show(&self, session_id: &SessionId, reported_by: &str) -> Result<TutoringSession> {
+        let mut session = self.get_existing_session(session_id).await?;
+        let read = (session.status, session.updated_at);
+
+        let Some(reporter) = session.party_of(reported_by) else {
+            bail!("Solo los participantes pueden reportar una inasistencia");
+        };
+
+        session.report_no_show(reporter, Utc::now())?;
+        self.save(&mut session, read).await?;
+
+        Ok(session)
+    }
+
+    /// Confirmar la propia inasistencia reportada por la otra parte y cerrar la sesión
+    pub async fn confirm_no_show(&self, session_id: &SessionId, confirmed_by: &str) -> Result<TutoringSession> {
+        let mut session = self.get_existing_session(session_id).await?;
+
+        let Some(confirmer) = session.party_of(confirmed_by) else {
+            bail!("Solo los participantes pueden confirmar una inasistencia");
+        };
+
+        session.confirm_no_show(confirmer)?;
+        self.transition(&mut session, SessionStatus::NoShow, confirmed_by).await?;
+
+        Ok(session)
+    }
+
+    /// Resolver un reporte de inasistencia (moderación)
+    ///
+    /// Con `absent_party` la sesión se cierra como inasistencia de esa parte; sin ella se
+    /// descarta el reporte y la sesión sigue aceptada.
+    pub async fn resolve_no_show(
+        &self,
+        session_id: &SessionId,
+        absent_party: Option<SessionParty>,
+        resolved_by: &str,
+    ) -> Result<TutoringSession> {
+        let mut session = self.get_existing_session(session_id).await?;
+        let read = (session.status, session.updated_at);
+
+        if session.no_show_report.take().is_none() {
+            bail!("La sesión no tiene ninguna inasistencia reportada");
+        }
+
+        match absent_party {
+            Some(absent_party) => {
+                session.absent_party = Some(absent_party);
+                self.transition(&mut session, SessionStatus::NoShow, resolved_by).await?;
+            }
+            None => self.save(&mut session, read).await?,
+        }
+
+        Ok(session)
+    }
+
+    /// Completar una sesión en curso y registrarla en el pasaporte de ambas partes
+    ///
+    /// La sesión se cierra antes de escribir en los pasaportes para que una petición
+    /// concurrente no registre las interacciones dos veces. Si el registro falla, el tutor
+    /// puede repetir la llamada: solo se escriben las interacciones que falten.
+    pub async fn complete_session(&self, session_id: &SessionId, completed_by: &str) -> Result<TutoringSession> {
+        let mut session = self.get_existing_session(session_id).await?;
+
+        if session.party_of(completed_by) != Some(SessionParty::Tutor) {
+            bail!("Solo el tutor puede completar la sesión");
+        }
+
+        if session.status == SessionStatus::Completed {
+            if session.learner_interaction_id.is_some() && session.tutor_interaction_id.is_some() {
+                bail!("La sesión ya está completada");
+            }
+        } else {
+            session.completed_at = Some(Utc::now());
+            self.transition(&mut session, SessionStatus::Completed, completed_by).await?;
+        }
+
+        let completed_at = session.completed_at.unwrap_or(session.updated_at);
+        let object = format!("https://keiko-dapp.xyz/tutoring/sessions/{}", session.id.0);
+        let result = LearningResult {
+            success: true,
+            completion: Some(1.0),
+            score: None,
+            duration: session.started_at.map(|started| (completed_at - started).num_seconds()),
+            response: None,
+        };
+        let context = LearningContext {
+            platform: "keiko-marketplace".to_string(),
+            language: "es".to_string(), // TODO: Usar el idioma acordado para la sesión
+            instructor: Some(session.tutor.clone()),
+            group: None,
+            extensions: Some(serde_json::json!({
+                "https://keiko-dapp.xyz/xapi/extensions/subject": session.subject.code,
+            })),
+        };
+
+        // Cada interacción se guarda en la sesión en cuanto se escribe, para no repetirla al reintentar
+        let learner_interaction_id = match session.learner_interaction_id {
+            Some(id) => id,
+            None => {
+                let interaction = self
+                    .passport
+                    .add_learning_interaction(
+                        &session.learner,
+                        &session.learner,
+                        LEARNER_VERB,
+                        &object,
+                        Some(SESSION_ACTIVITY_TYPE),
+                        Some(result.clone()),
+                        Some(context.clone()),
+                    )
+                    .await?;
+                let read = (session.status, session.updated_at);
+                session.learner_interaction_id = Some(interaction.id.0);
+                self.save(&mut session, read).await?;
+                interaction.id.0
+            }
+        };
+        let tutor_interaction_id = match session.tutor_interaction_id {
+            Some(id) => id,
+            None => {
+                let interaction = self
+                    .passport
+                    .add_learning_interaction(
+                        &session.tutor,
+                        &session.tutor,
+                        TUTOR_VERB,
+                        &object,
+                        Some(SESSION_ACTIVITY_TYPE),
+                        Some(result),
+                        Some(context),
+                    )
+                    .await?;
+                let read = (session.status, session.updated_at);
+                session.tutor_interaction_id = Some(interaction.id.0);
+                self.save(&mut session, read).await?;
+                interaction.id.0
+            }
+        };
+
+        self.emit_event(TutoringEvent::SessionCompleted {
+            session_id: session.id.clone(),
+            tutor: session.tutor.clone(),
+            learner: session.learner.clone(),
+            tutor_interaction_id,
+            learner_interaction_id,
+            timestamp: completed_at,
+        });
+
+        Ok(session)
+    }
+
+    pub async fn get_session(&self, session_id: &SessionId) -> Result<Option<TutoringSession>> {
+        self.repository.get_session(session_id).await
+    }
+
+    /// Inasistencias reportadas pendientes de confirmación o en disputa (moderación)
+    pub async fn get_no_show_reports(&self) -> Result<Vec<TutoringSession>> {
+        self.repository.get_sessions_with_no_show_report().await
+    }
+
+    /// Sesiones en las que participa un usuario, como tutor o estudiante
+    pub async fn get_sessions_for_user(&self, user_address: &str) -> Result<Vec<TutoringSession>> {
+        self.repository.get_sessions_by_user(user_address).await
+    }
+
+    pub async fn count_sessions_for_user(&self, user_address: &str, filter: &SessionFilter) -> Result<i64> {
+        self.repository.count_sessions(Some(user_address), filter).await
+    }
+
+    /// Página de las sesiones de un usuario que cumplen el filtro
+    pub async fn list_sessions_for_user(
+        &self,
+        user_address: &str,
+        filter: &SessionFilter,
+        order: SessionOrder,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<TutoringSession>> {
+        self.repository.list_sessions(Some(user_address), filter, order, limit, offset).await
+    }
+
+    /// Contar las sesiones de todos los usuarios que cumplen el filtro (moderación)
+    pub async fn count_sessions(&self, filter: &SessionFilter) -> Result<i64> {
+        self.repository.count_sessions(None, filter).await
+    }
+
+    /// Página de las sesiones de todos los usuarios que cumplen el filtro (moderación)
+    pub async fn list_sessions(
+        &self,
+        filter: &SessionFilter,
+        order: SessionOrder,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<TutoringSession>> {
+        self.repository.list_sessions(None, filter, order, limit, offset).await
+    }
+
+    /// Persistir los cambios de una sesión leída con el estado y la fecha `read`
+    ///
+    /// Falla si otra petición la modificó entretanto.
+    async fn save(&self, session: &mut TutoringSession, read: (SessionStatus, DateTime<Utc>)) -> Result<()> {
+        // Postgres guarda microsegundos: se trunca para poder comparar con lo leído en la próxima escritura
+        session.updated_at = Utc::now().trunc_subsecs(6);
+
+        if !self.repository.update_session(session, read.0, read.1).await? {
+            bail!("La sesión {} cambió mientras se procesaba", session.id.0);
+        }
+
+        Ok(())
+    }
+
+    async fn get_existing_session(&self, session_id: &SessionId) -> Result<TutoringSession> {
+        self.repository
+            .get_session(session_id)
+            .await?
+            .ok_or_else(|| anyhow!("Sesión no encontrada: {}", session_id.0))
+    }
+
+    /// Aplicar una transición de la máquina de estados y persistirla
+    async fn transition(&self, session: &mut TutoringSession, next: SessionStatus, changed_by: &str) -> Result<()> {
+        let previous = session.status;
+        let read = (session.status, session.updated_at);
+
+        if !previous.can_transition_to(next) {
+            bail!("No se puede pasar de {} a {}", previous, next);
+        }
+
+        session.status = next;
+        self.save(session, read).await?;
+
+        self.emit_event(TutoringEvent::SessionStatusChanged {
+            session_id: session.id.clone(),
+            tutor: session.tutor.clone(),
+            learner: session.learner.clone(),
+            from: previous,
+            to: next,
+            changed_by: changed_by.to_string(),
+            timestamp: session.updated_at,
+        });
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl RatedSessions for TutoringService {
+    async fn completed_session(&self, session_id: &str) -> Result<Option<CompletedSession>> {
+        let Ok(id) = Uuid::parse_str(session_id) else {
+            return Ok(None);
+        };
+
+        Ok(self
+            .repository
+            .get_session(&SessionId(id))
+            .await?
+            .filter(|session| session.status == SessionStatus::Completed)
+            .map(|session| CompletedSession { tutor: session.tutor, learner: session.learner }))
+    }
+}
@@ -0,0 +1,38 @@
+-- Valoraciones firmadas, eventos de pasaporte consumidos, parámetros del motor y puntuaciones
+-- Los eventos se guardan en orden de llegada; las puntuaciones se recalculan a partir de ellos
+
+CREATE TABLE rating_events (
+    sequence BIGSERIAL PRIMARY KEY,
+    event JSONB NOT NULL,
+    timestamp TIMESTAMPTZ NOT NULL
+);
+
+CREATE TABLE reputation_passport_events (
+    sequence BIGSERIAL PRIMARY KEY,
+    event JSONB NOT NULL
+);
+
+CREATE TABLE reputation_parameters (
+    id INT PRIMARY KEY,
+    parameters JSONB NOT NULL
+);
+
+CREATE TABLE reputation_scores (
+    user_address TEXT NOT NULL,
+    role TEXT NOT NULL,
+    score DOUBLE PRECISION NOT NULL,
+    rating_count BIGINT NOT NULL,
+    computed_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (user_address, role)
+);
+
+CREATE TABLE reputation_history (
+    id BIGSERIAL PRIMARY KEY,
+    user_address TEXT NOT NULL,
+    role TEXT NOT NULL,
+    score DOUBLE PRECISION NOT NULL,
+    rating_count BIGINT NOT NULL,
+    computed_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX reputation_history_user_idx ON reputation_history (user_address, role, computed_at);
@@ -0,0 +1,19 @@
+-- Disputas de valoraciones: eventos auditables y estado actual de cada disputa
+
+CREATE TABLE dispute_events (
+    sequence BIGSERIAL PRIMARY KEY,
+    dispute_id UUID NOT NULL,
+    event JSONB NOT NULL,
+    timestamp TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX dispute_events_dispute_id_idx ON dispute_events (dispute_id, sequence);
+
+CREATE TABLE disputes (
+    id UUID PRIMARY KEY,
+    rating_id UUID NOT NULL,
+    status TEXT NOT NULL,
+    state JSONB NOT NULL,
+    opened_at TIMESTAMPTZ NOT NULL,
+    resolved_at TIMESTAMPTZ
+);
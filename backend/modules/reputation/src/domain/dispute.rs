@@ -0,0 +1,411 @@
+// Entidades de dominio para disputas y apelaciones de calificaciones
+// El estado de cada disputa se reconstruye a partir de su historial de eventos
+
+use std::collections::{HashMap, HashSet};
+
+use anyhow::{bail, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{RatingEvent, RatingId};
+
+/// Tamaño mínimo de un jurado comunitario
+pub const MIN_JURY_SIZE: usize = 3;
+
+/// Miembros que se sortean para cada jurado (impar para evitar empates)
+pub const JURY_SIZE: usize = 5;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct DisputeId(pub Uuid);
+
+impl DisputeId {
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+/// Parte implicada en una disputa
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DisputeParty {
+    Rater,
+    Ratee,
+}
+
+/// Estado de una disputa
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DisputeStatus {
+    Flagged,           // Calificación impugnada, recogiendo pruebas
+    EvidenceComplete,  // Pruebas de ambas partes (o plazo cerrado), pendiente de decisión
+    Resolved,
+}
+
+impl DisputeStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DisputeStatus::Flagged => "flagged",
+            DisputeStatus::EvidenceComplete => "evidence_complete",
+            DisputeStatus::Resolved => "resolved",
+        }
+    }
+}
+
+/// Resultado de una disputa sobre la calificación impugnada
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DisputeOutcome {
+    Upheld,
+    Reduced { stars: u8 },
+    Removed,
+}
+
+impl DisputeOutcome {
+    /// Evento que aplica el resultado a la calificación; mantenerla no genera ninguno
+    pub fn rating_event(&self, rating_id: &RatingId, dispute_id: &DisputeId) -> Option<RatingEvent> {
+        match *self {
+            DisputeOutcome::Upheld => None,
+            DisputeOutcome::Reduced { stars } => Some(RatingEvent::RatingAdjusted {
+                rating_id: rating_id.clone(),
+                stars,
+                dispute_id: dispute_id.clone(),
+                timestamp: Utc::now(),
+            }),
+            DisputeOutcome::Removed => Some(RatingEvent::RatingRemoved {
+                rating_id: rating_id.clone(),
+                dispute_id: dispute_id.clone(),
+                timestamp: Utc::now(),
+            }),
+        }
+    }
+}
+
+/// Forma de resolución que solicita quien abre la disputa
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AdjudicationKind {
+    Moderator,
+    Jury,
+}
+
+/// Quién decide la disputa
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Adjudication {
+    Moderator,
+    Jury { jurors: Vec<String> }, // Miembros de la comunidad con humanidad verificada
+}
+
+impl Adjudication {
+    /// Formar un jurado con los candidatos sorteados
+    ///
+    /// Descarta a las partes y los duplicados; las partes nunca eligen a sus jurados.
+    pub fn jury(candidates: Vec<String>, rater: &str, ratee: &str) -> Result<Self> {
+        let mut seen = HashSet::new();
+        let jurors: Vec<String> = candidates
+            .into_iter()
+            .filter(|juror| juror != rater && juror != ratee)
+            .filter(|juror| seen.insert(juror.clone()))
+            .take(JURY_SIZE)
+            .collect();
+
+        if jurors.len() < MIN_JURY_SIZE {
+            bail!(
+                "No hay suficientes humanos verificados para formar un jurado de {} miembros",
+                MIN_JURY_SIZE
+            );
+        }
+
+        Ok(Adjudication::Jury { jurors })
+    }
+}
+
+/// Prueba aportada por una de las partes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Evidence {
+    pub party: DisputeParty,
+    pub submitted_by: String,
+    pub content: String,
+    pub attachments: Vec<String>, // URIs de adjuntos
+    pub submitted_at: DateTime<Utc>,
+}
+
+/// Voto de un miembro del jurado comunitario
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JuryVote {
+    pub juror: String,
+    pub outcome: DisputeOutcome,
+    pub cast_at: DateTime<Utc>,
+}
+
+/// Eventos de disputa: cada transición queda registrada para auditar la reputación
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum DisputeEvent {
+    DisputeOpened {
+        dispute_id: DisputeId,
+        rating_id: RatingId,
+        rater: String,
+        ratee: String,
+        opened_by: String,
+        reason: String,
+        adjudication: Adjudication,
+        timestamp: DateTime<Utc>,
+    },
+    EvidenceSubmitted {
+        dispute_id: DisputeId,
+        evidence: Evidence,
+    },
+    EvidenceClosed {
+        dispute_id: DisputeId,
+        closed_by: String,
+        timestamp: DateTime<Utc>,
+    },
+    JuryVoteCast {
+        dispute_id: DisputeId,
+        vote: JuryVote,
+    },
+    DisputeResolved {
+        dispute_id: DisputeId,
+        outcome: DisputeOutcome,
+        decided_by: String, // Moderador o "jury"
+        timestamp: DateTime<Utc>,
+    },
+}
+
+impl DisputeEvent {
+    pub fn dispute_id(&self) -> &DisputeId {
+        match self {
+            DisputeEvent::DisputeOpened { dispute_id, .. }
+            | DisputeEvent::EvidenceSubmitted { dispute_id, .. }
+            | DisputeEvent::EvidenceClosed { dispute_id, .. }
+            | DisputeEvent::JuryVoteCast { dispute_id, .. }
+            | DisputeEvent::DisputeResolved { dispute_id, .. } => dispute_id,
+        }
+    }
+
+    pub fn timestamp(&self) -> DateTime<Utc> {
+        match self {
+            DisputeEvent::DisputeOpened { timestamp, .. }
+            | DisputeEvent::EvidenceClosed { timestamp, .. }
+            | DisputeEvent::DisputeResolved { timestamp, .. } => *timestamp,
+            DisputeEvent::EvidenceSubmitted { evidence, .. } => evidence.submitted_at,
+            DisputeEvent::JuryVoteCast { vote, .. } => vote.cast_at,
+        }
+    }
+}
+
+/// Disputa sobre una calificación
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Dispute {
+    pub id: DisputeId,
+    pub rating_id: RatingId,
+    pub rater: String,
+    pub ratee: String,
+    pub opened_by: String,
+    pub reason: String,
+    pub adjudication: Adjudication,
+    pub status: DisputeStatus,
+    pub evidence: Vec<Evidence>,
+    pub votes: Vec<JuryVote>,
+    pub outcome: Option<DisputeOutcome>,
+    pub decided_by: Option<String>,
+    pub opened_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+impl Dispute {
+    /// Reconstruir una disputa a partir de sus eventos en orden
+    pub fn from_events(events: &[DisputeEvent]) -> Option<Self> {
+        let mut dispute: Option<Dispute> = None;
+
+        for event in events {
+            match event {
+                DisputeEvent::DisputeOpened {
+                    dispute_id,
+                    rating_id,
+                    rater,
+                    ratee,
+                    opened_by,
+                    reason,
+                    adjudication,
+                    timestamp,
+                } => {
+                    dispute = Some(Dispute {
+                        id: dispute_id.clone(),
+                        rating_id: rating_id.clone(),
+                        rater: rater.clone(),
+                        ratee: ratee.clone(),
+                        opened_by: opened_by.clone(),
+                        reason: reason.clone(),
+                        adjudication: adjudication.clone(),
+                        status: DisputeStatus::Flagged,
+                        evidence: Vec::new(),
+                        votes: Vec::new(),
+                        outcome: None,
+                        decided_by: None,
+                        opened_at: *timestamp,
+                        resolved_at: None,
+                    });
+                }
+                other => {
+                    if let Some(dispute) = dispute.as_mut() {
+                        dispute.apply(other);
+                    }
+                }
+            }
+        }
+
+        dispute
+    }
+
+    /// Aplicar un evento posterior a la apertura
+    pub fn apply(&mut self, event: &DisputeEvent) {
+        match event {
+            DisputeEvent::DisputeOpened { .. } => {}
+            DisputeEvent::EvidenceSubmitted { evidence, .. } => {
+                self.evidence.push(evidence.clone());
+                if self.has_evidence_from(DisputeParty::Rater) && self.has_evidence_from(DisputeParty::Ratee) {
+                    self.status = DisputeStatus::EvidenceComplete;
+                }
+            }
+            DisputeEvent::EvidenceClosed { .. } => {
+                self.status = DisputeStatus::EvidenceComplete;
+            }
+            DisputeEvent::JuryVoteCast { vote, .. } => {
+                self.votes.push(vote.clone());
+            }
+            DisputeEvent::DisputeResolved { outcome, decided_by, timestamp, .. } => {
+                self.status = DisputeStatus::Resolved;
+                self.outcome = Some(*outcome);
+                self.decided_by = Some(decided_by.clone());
+                self.resolved_at = Some(*timestamp);
+            }
+        }
+    }
+
+    /// Parte que representa un usuario en la disputa
+    pub fn party_of(&self, user_address: &str) -> Option<DisputeParty> {
+        if user_address == self.rater {
+            Some(DisputeParty::Rater)
+        } else if user_address == self.ratee {
+            Some(DisputeParty::Ratee)
+        } else {
+            None
+        }
+    }
+
+    pub fn has_evidence_from(&self, party: DisputeParty) -> bool {
+        self.evidence.iter().any(|e| e.party == party)
+    }
+
+    /// Veredicto del jurado, si ya se alcanzó
+    ///
+    /// Gana el resultado con mayoría absoluta del jurado; si todos votaron sin
+    /// mayoría, la calificación se mantiene. Para reducciones se aplica la
+    /// mediana de las estrellas propuestas.
+    pub fn jury_verdict(&self) -> Option<DisputeOutcome> {
+        let Adjudication::Jury { jurors } = &self.adjudication else {
+            return None;
+        };
+
+        let mut counts: HashMap<&'static str, usize> = HashMap::new();
+        let mut reduced_stars: Vec<u8> = Vec::new();
+
+        for vote in &self.votes {
+            let kind = match vote.outcome {
+                DisputeOutcome::Upheld => "upheld",
+                DisputeOutcome::Reduced { stars } => {
+                    reduced_stars.push(stars);
+                    "reduced"
+                }
+                DisputeOutcome::Removed => "removed",
+            };
+            *counts.entry(kind).or_default() += 1;
+        }
+
+        let majority = |kind: &str| counts.get(kind).copied().unwrap_or(0) * 2 > jurors.len();
+
+        if majority("removed") {
+            Some(DisputeOutcome::Removed)
+        } else if majority("reduced") {
+            reduced_stars.sort_unstable();
+            Some(DisputeOutcome::Reduced { stars: reduced_stars[reduced_stars.len() / 2] })
+        } else if majority("upheld") || self.votes.len() >= jurors.len() {
+            Some(DisputeOutcome::Upheld)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidates(addresses: &[&str]) -> Vec<String> {
+        addresses.iter().map(|a| a.to_string()).collect()
+    }
+
+    fn jury_dispute(jurors: &[&str]) -> Dispute {
+        let event = DisputeEvent::DisputeOpened {
+            dispute_id: DisputeId::new(),
+            rating_id: RatingId(Uuid::new_v4()),
+            rater: "0xrater".to_string(),
+            ratee: "0xratee".to_string(),
+            opened_by: "0xratee".to_string(),
+            reason: "Calificación injusta".to_string(),
+            adjudication: Adjudication::Jury { jurors: candidates(jurors) },
+            timestamp: Utc::now(),
+        };
+        Dispute::from_events(&[event]).unwrap()
+    }
+
+    fn vote(dispute: &mut Dispute, juror: &str, outcome: DisputeOutcome) {
+        dispute.apply(&DisputeEvent::JuryVoteCast {
+            dispute_id: dispute.id.clone(),
+            vote: JuryVote { juror: juror.to_string(), outcome, cast_at: Utc::now() },
+        });
+    }
+
+    #[test]
+    fn drawn_juries_exclude_the_parties_and_duplicates() {
+        let drawn = candidates(&["0xrater", "0xa", "0xb", "0xa", "0xratee", "0xc", "0xd", "0xe", "0xf"]);
+
+        let Adjudication::Jury { jurors } = Adjudication::jury(drawn, "0xrater", "0xratee").unwrap() else {
+            panic!("se esperaba un jurado");
+        };
+        assert_eq!(jurors, candidates(&["0xa", "0xb", "0xc", "0xd", "0xe"]));
+    }
+
+    #[test]
+    fn a_jury_needs_enough_verified_humans() {
+        let drawn = candidates(&["0xa", "0xrater", "0xb"]);
+
+        assert!(Adjudication::jury(drawn, "0xrater", "0xratee").is_err());
+    }
+
+    #[test]
+    fn only_reductions_and_removals_change_the_rating() {
+        let rating_id = RatingId(Uuid::new_v4());
+        let dispute_id = DisputeId::new();
+
+        assert!(DisputeOutcome::Upheld.rating_event(&rating_id, &dispute_id).is_none());
+        assert!(matches!(
+            DisputeOutcome::Reduced { stars: 2 }.rating_event(&rating_id, &dispute_id),
+            Some(RatingEvent::RatingAdjusted { stars: 2, .. })
+        ));
+        assert!(matches!(
+            DisputeOutcome::Removed.rating_event(&rating_id, &dispute_id),
+            Some(RatingEvent::RatingRemoved { .. })
+        ));
+    }
+
+    #[test]
+    fn jury_majority_decides_and_reductions_use_the_median() {
+        let mut dispute = jury_dispute(&["0xa", "0xb", "0xc"]);
+        vote(&mut dispute, "0xa", DisputeOutcome::Reduced { stars: 1 });
+        assert_eq!(dispute.jury_verdict(), None);
+
+        vote(&mut dispute, "0xb", DisputeOutcome::Upheld);
+        assert_eq!(dispute.jury_verdict(), None);
+
+        vote(&mut dispute, "0xc", DisputeOutcome::Reduced { stars: 2 });
+        assert_eq!(dispute.jury_verdict(), Some(DisputeOutcome::Reduced { stars: 2 }));
+    }
+}
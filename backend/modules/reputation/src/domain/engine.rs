@@ -0,0 +1,324 @@
+// Motor de cálculo de reputación
+// Función pura: mismas calificaciones, actividad y parámetros producen siempre las mismas puntuaciones
+
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+
+use chrono::{DateTime, Utc};
+use learning_passport::domain::LearningPassportEvent;
+
+use super::{Rating, RatedRole, ReputationParameters, ReputationScore};
+
+/// Datos de entrada del motor de reputación
+pub struct ReputationInputs<'a> {
+    pub ratings: &'a [Rating],
+    pub learner_activity: &'a HashMap<String, Vec<DateTime<Utc>>>, // Marcas de tiempo de interacciones por usuario
+    pub verified_humans: &'a HashSet<String>,                      // Usuarios con humanidad verificada
+    pub now: DateTime<Utc>,
+}
+
+/// Extraer la actividad de aprendizaje por usuario a partir de los eventos del pasaporte
+///
+/// Cada evento indica su titular, así que no hace falta haber visto la creación del pasaporte.
+pub fn learner_activity_from_events(events: &[LearningPassportEvent]) -> HashMap<String, Vec<DateTime<Utc>>> {
+    let mut activity: HashMap<String, Vec<DateTime<Utc>>> = HashMap::new();
+
+    for event in events {
+        if let LearningPassportEvent::InteractionAdded { user_address, timestamp, .. } = event {
+            activity.entry(user_address.clone()).or_default().push(*timestamp);
+        }
+    }
+
+    activity
+}
+
+/// Factor de decaimiento exponencial según la antigüedad
+fn decay(params: &ReputationParameters, at: DateTime<Utc>, now: DateTime<Utc>) -> f64 {
+    let age_days = (now - at).num_seconds().max(0) as f64 / 86_400.0;
+    0.5_f64.powf(age_days / params.half_life_days)
+}
+
+/// Detectar calificaciones positivas que cierran un anillo de calificaciones recíprocas
+///
+/// Una calificación positiva a → b forma parte de un anillo si b alcanza a a
+/// través de calificaciones positivas en menos de `ring_max_length` saltos. Que tutor y
+/// estudiante se califiquen mutuamente tras una sesión es lo normal: la calificación
+/// directa b → a solo cierra el anillo si procede de otra sesión.
+fn detect_collusive_ratings(params: &ReputationParameters, ratings: &[Rating]) -> HashSet<usize> {
+    // Calificador → calificado → sesiones en que lo calificó positivamente
+    let mut graph: HashMap<&str, HashMap<&str, HashSet<&str>>> = HashMap::new();
+    for rating in ratings.iter().filter(|r| r.stars >= params.positive_threshold) {
+        graph
+            .entry(rating.rater.as_str())
+            .or_default()
+            .entry(rating.ratee.as_str())
+            .or_default()
+            .insert(rating.session_id.as_str());
+    }
+
+    let max_hops = params.ring_max_length.saturating_sub(1);
+    let mut collusive = HashSet::new();
+
+    for (index, rating) in ratings.iter().enumerate() {
+        if rating.stars < params.positive_threshold || max_hops == 0 {
+            continue;
+        }
+
+        let mut queue = VecDeque::from([(rating.ratee.as_str(), 0usize)]);
+        let mut visited = HashSet::from([rating.ratee.as_str()]);
+
+        while let Some((node, hops)) = queue.pop_front() {
+            if hops == max_hops {
+                continue;
+            }
+            for (next, sessions) in graph.get(node).into_iter().flatten() {
+                if *next == rating.rater {
+                    let mutual_in_same_session = hops == 0 && sessions.iter().all(|s| *s == rating.session_id);
+                    if mutual_in_same_session {
+                        continue;
+                    }
+                    collusive.insert(index);
+                    queue.clear();
+                    break;
+                }
+                if visited.insert(next) {
+                    queue.push_back((next, hops + 1));
+                }
+            }
+        }
+    }
+
+    collusive
+}
+
+/// Calcular la reputación de todos los usuarios
+///
+/// El peso de cada calificación combina decaimiento temporal, verificación de
+/// humanidad del calificador, amortiguación anti-colusión y la propia reputación
+/// del calificador, que se refina durante `iterations` rondas.
+pub fn compute_reputation(params: &ReputationParameters, inputs: &ReputationInputs) -> Vec<ReputationScore> {
+    let collusive = detect_collusive_ratings(params, inputs.ratings);
+
+    // Peso base de cada calificación (independiente de la reputación del calificador)
+    let base_weights: Vec<f64> = inputs
+        .ratings
+        .iter()
+        .enumerate()
+        .map(|(index, rating)| {
+            let mut weight = decay(params, rating.created_at, inputs.now);
+            if !inputs.verified_humans.contains(&rating.rater) {
+                weight *= params.unverified_rater_factor;
+            }
+            if collusive.contains(&index) {
+                weight *= params.collusion_dampening;
+            }
+            weight
+        })
+        .collect();
+
+    // Actividad de aprendizaje con decaimiento, saturada en [0, 1]
+    let activity: HashMap<&str, f64> = inputs
+        .learner_activity
+        .iter()
+        .map(|(user, timestamps)| {
+            let decayed: f64 = timestamps.iter().map(|t| decay(params, *t, inputs.now)).sum();
+            (user.as_str(), 1.0 - (-decayed / params.activity_scale).exp())
+        })
+        .collect();
+
+    let mut subjects: BTreeMap<(&str, RatedRole), usize> = BTreeMap::new();
+    for rating in inputs.ratings {
+        *subjects.entry((rating.ratee.as_str(), rating.ratee_role)).or_default() += 1;
+    }
+    for user in activity.keys() {
+        subjects.entry((user, RatedRole::Learner)).or_default();
+    }
+
+    let mut rater_reputation: HashMap<&str, f64> = HashMap::new();
+    let mut scores: BTreeMap<(&str, RatedRole), f64> = BTreeMap::new();
+
+    for _ in 0..params.iterations.max(1) {
+        let mut weighted_sum: HashMap<(&str, RatedRole), f64> = HashMap::new();
+        let mut weight_total: HashMap<(&str, RatedRole), f64> = HashMap::new();
+
+        for (rating, base_weight) in inputs.ratings.iter().zip(&base_weights) {
+            let rater_rep = rater_reputation
+                .get(rating.rater.as_str())
+                .copied()
+                .unwrap_or(params.prior_mean);
+            let weight = base_weight * (params.min_rater_weight + (1.0 - params.min_rater_weight) * rater_rep);
+            let key = (rating.ratee.as_str(), rating.ratee_role);
+
+            *weighted_sum.entry(key).or_default() += weight * rating.normalized_score();
+            *weight_total.entry(key).or_default() += weight;
+        }
+
+        for key in subjects.keys() {
+            let sum = weighted_sum.get(key).copied().unwrap_or(0.0);
+            let total = weight_total.get(key).copied().unwrap_or(0.0);
+            let rating_component =
+                (params.prior_weight * params.prior_mean + sum) / (params.prior_weight + total);
+
+            let score = match key.1 {
+                RatedRole::Tutor => rating_component,
+                RatedRole::Learner => {
+                    let learner_activity = activity.get(key.0).copied().unwrap_or(0.0);
+                    (1.0 - params.activity_weight) * rating_component + params.activity_weight * learner_activity
+                }
+            };
+            scores.insert(*key, score);
+        }
+
+        // La reputación de un usuario como calificador es la mejor de sus roles
+        rater_reputation.clear();
+        for ((user, _), score) in &scores {
+            let entry = rater_reputation.entry(user).or_insert(0.0);
+            *entry = entry.max(*score);
+        }
+    }
+
+    scores
+        .into_iter()
+        .map(|((user, role), score)| ReputationScore {
+            user_address: user.to_string(),
+            role,
+            score: score * 100.0,
+            rating_count: subjects.get(&(user, role)).copied().unwrap_or(0),
+            computed_at: inputs.now,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use learning_passport::domain::{LearningInteractionId, LearningPassportId};
+
+    use super::*;
+    use crate::domain::RatingId;
+
+    fn now() -> DateTime<Utc> {
+        DateTime::<Utc>::UNIX_EPOCH + Duration::days(1_000)
+    }
+
+    fn rating(rater: &str, ratee: &str, stars: u8, days_ago: i64) -> Rating {
+        Rating {
+            id: RatingId::new(),
+            session_id: format!("{rater}-{ratee}"),
+            rater: rater.to_string(),
+            ratee: ratee.to_string(),
+            ratee_role: RatedRole::Tutor,
+            stars,
+            comment: None,
+            rater_public_key: String::new(),
+            signature: String::new(),
+            created_at: now() - Duration::days(days_ago),
+        }
+    }
+
+    fn in_session(rating: Rating, session_id: &str) -> Rating {
+        Rating { session_id: session_id.to_string(), ..rating }
+    }
+
+    fn scores(ratings: &[Rating], verified: &[&str]) -> HashMap<String, f64> {
+        let verified_humans = verified.iter().map(|v| v.to_string()).collect();
+        let inputs = ReputationInputs {
+            ratings,
+            learner_activity: &HashMap::new(),
+            verified_humans: &verified_humans,
+            now: now(),
+        };
+
+        compute_reputation(&ReputationParameters::default(), &inputs)
+            .into_iter()
+            .map(|score| (score.user_address, score.score))
+            .collect()
+    }
+
+    #[test]
+    fn reciprocal_and_three_way_rings_are_collusive() {
+        let params = ReputationParameters::default();
+        let ratings = vec![
+            rating("a", "b", 5, 0),
+            rating("b", "a", 5, 0),
+            rating("c", "d", 5, 0),
+            rating("d", "e", 5, 0),
+            rating("e", "c", 5, 0),
+            rating("f", "g", 5, 0),
+        ];
+
+        assert_eq!(detect_collusive_ratings(&params, &ratings), HashSet::from([0, 1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn negative_ratings_and_long_rings_are_not_collusive() {
+        let params = ReputationParameters { ring_max_length: 2, ..Default::default() };
+        let ratings = vec![
+            rating("a", "b", 5, 0),
+            rating("b", "a", 2, 0),
+            rating("c", "d", 5, 0),
+            rating("d", "e", 5, 0),
+            rating("e", "c", 5, 0),
+        ];
+
+        assert!(detect_collusive_ratings(&params, &ratings).is_empty());
+    }
+
+    #[test]
+    fn mutual_ratings_are_collusive_only_when_repeated_across_sessions() {
+        let params = ReputationParameters::default();
+        let one_session = vec![in_session(rating("t", "l", 5, 0), "s1"), in_session(rating("l", "t", 5, 0), "s1")];
+        assert!(detect_collusive_ratings(&params, &one_session).is_empty());
+
+        let mut repeated = one_session;
+        repeated.push(in_session(rating("t", "l", 5, 0), "s2"));
+        repeated.push(in_session(rating("l", "t", 5, 0), "s2"));
+        assert_eq!(detect_collusive_ratings(&params, &repeated), HashSet::from([0, 1, 2, 3]));
+    }
+
+    #[test]
+    fn activity_is_keyed_by_the_event_owner() {
+        let event = LearningPassportEvent::InteractionAdded {
+            passport_id: LearningPassportId::new(),
+            user_address: "learner".to_string(),
+            interaction_id: LearningInteractionId::new(),
+            timestamp: now(),
+        };
+
+        let activity = learner_activity_from_events(&[event]);
+
+        assert_eq!(activity.get("learner"), Some(&vec![now()]));
+    }
+
+    #[test]
+    fn unrated_users_start_at_the_prior_and_good_ratings_raise_the_score() {
+        let scores = scores(&[rating("r1", "tutor", 5, 0), rating("r2", "tutor", 5, 0)], &["r1", "r2"]);
+
+        assert!(scores["tutor"] > ReputationParameters::default().prior_mean * 100.0);
+        assert!(scores["tutor"] <= 100.0);
+    }
+
+    #[test]
+    fn unverified_raters_count_less() {
+        let verified = scores(&[rating("r1", "tutor", 1, 0)], &["r1"]);
+        let unverified = scores(&[rating("r1", "tutor", 1, 0)], &[]);
+
+        assert!(verified["tutor"] < unverified["tutor"]);
+    }
+
+    #[test]
+    fn old_ratings_decay() {
+        let recent = scores(&[rating("r1", "tutor", 1, 0)], &["r1"]);
+        let old = scores(&[rating("r1", "tutor", 1, 720)], &["r1"]);
+
+        assert!(recent["tutor"] < old["tutor"]);
+    }
+
+    #[test]
+    fn collusive_ratings_are_dampened() {
+        let honest = scores(&[rating("a", "b", 5, 0)], &["a", "b"]);
+        let ring = scores(&[rating("a", "b", 5, 0), rating("b", "a", 5, 0)], &["a", "b"]);
+
+        assert!(ring["b"] < honest["b"]);
+    }
+}
@@ -0,0 +1,223 @@
+// Entidades de dominio para el módulo reputation
+// Calificaciones firmadas tras sesiones de tutoría y puntuaciones de reputación derivadas
+
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub mod dispute;
+pub mod engine;
+
+pub use dispute::*;
+pub use engine::*;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct RatingId(pub Uuid);
+
+impl RatingId {
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+/// Rol en el que se califica a un usuario
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum RatedRole {
+    Tutor,
+    Learner,
+}
+
+impl RatedRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RatedRole::Tutor => "tutor",
+            RatedRole::Learner => "learner",
+        }
+    }
+}
+
+impl fmt::Display for RatedRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for RatedRole {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "tutor" => Ok(RatedRole::Tutor),
+            "learner" => Ok(RatedRole::Learner),
+            other => Err(anyhow!("Rol calificado desconocido: {}", other)),
+        }
+    }
+}
+
+/// Calificación firmada emitida tras una sesión de tutoría
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Rating {
+    pub id: RatingId,
+    pub session_id: String,          // Sesión de tutoría calificada
+    pub rater: String,               // Dirección de quien califica
+    pub ratee: String,               // Dirección de quien es calificado
+    pub ratee_role: RatedRole,
+    pub stars: u8,                   // Puntuación de 1 a 5
+    pub comment: Option<String>,
+    pub rater_public_key: String,    // Clave pública Ed25519 del calificador
+    pub signature: String,           // Firma Ed25519 del calificador
+    pub created_at: DateTime<Utc>,
+}
+
+impl Rating {
+    /// Contenido canónico firmado por el calificador
+    pub fn signing_payload(&self) -> serde_json::Result<Vec<u8>> {
+        serde_json::to_vec(&serde_json::json!({
+            "id": self.id.0,
+            "session_id": self.session_id,
+            "rater": self.rater,
+            "ratee": self.ratee,
+            "ratee_role": self.ratee_role,
+            "stars": self.stars,
+            "comment": self.comment,
+            "created_at": self.created_at,
+        }))
+    }
+
+    /// Puntuación normalizada en [0, 1]
+    pub fn normalized_score(&self) -> f64 {
+        (self.stars.clamp(1, 5) - 1) as f64 / 4.0
+    }
+}
+
+/// Sesión de tutoría completada, tal como la informa marketplace
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompletedSession {
+    pub tutor: String,
+    pub learner: String,
+}
+
+impl CompletedSession {
+    /// Rol en el que `rater` puede calificar a `ratee`: cada participante califica al otro
+    pub fn rated_role(&self, rater: &str, ratee: &str) -> Option<RatedRole> {
+        if rater == self.learner && ratee == self.tutor {
+            Some(RatedRole::Tutor)
+        } else if rater == self.tutor && ratee == self.learner {
+            Some(RatedRole::Learner)
+        } else {
+            None
+        }
+    }
+}
+
+/// Eventos de calificaciones: fuente de verdad para recalcular la reputación
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum RatingEvent {
+    RatingSubmitted {
+        rating: Rating,
+        timestamp: DateTime<Utc>,
+    },
+    RatingAdjusted {
+        rating_id: RatingId,
+        stars: u8,
+        dispute_id: DisputeId,  // Disputa que motivó el ajuste
+        timestamp: DateTime<Utc>,
+    },
+    RatingRemoved {
+        rating_id: RatingId,
+        dispute_id: DisputeId,
+        timestamp: DateTime<Utc>,
+    },
+}
+
+impl RatingEvent {
+    pub fn timestamp(&self) -> DateTime<Utc> {
+        match self {
+            RatingEvent::RatingSubmitted { timestamp, .. }
+            | RatingEvent::RatingAdjusted { timestamp, .. }
+            | RatingEvent::RatingRemoved { timestamp, .. } => *timestamp,
+        }
+    }
+}
+
+/// Reconstruir las calificaciones vigentes a partir del historial de eventos
+pub fn fold_rating_events(events: &[RatingEvent]) -> Vec<Rating> {
+    let mut ratings = Vec::new();
+
+    for event in events {
+        match event {
+            RatingEvent::RatingSubmitted { rating, .. } => ratings.push(rating.clone()),
+            RatingEvent::RatingAdjusted { rating_id, stars, .. } => {
+                if let Some(rating) = ratings.iter_mut().find(|r| r.id == *rating_id) {
+                    rating.stars = *stars;
+                }
+            }
+            RatingEvent::RatingRemoved { rating_id, .. } => ratings.retain(|r| r.id != *rating_id),
+        }
+    }
+
+    ratings
+}
+
+/// Parámetros del motor de reputación (ajustables por gobernanza)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReputationParameters {
+    pub half_life_days: f64,           // Vida media del decaimiento temporal
+    pub prior_mean: f64,               // Reputación inicial en [0, 1]
+    pub prior_weight: f64,             // Peso equivalente del valor inicial (en calificaciones)
+    pub min_rater_weight: f64,         // Peso mínimo de un calificador sin reputación
+    pub unverified_rater_factor: f64,  // Multiplicador para calificadores sin humanidad verificada
+    pub collusion_dampening: f64,      // Multiplicador para calificaciones dentro de anillos recíprocos
+    pub ring_max_length: usize,        // Longitud máxima de anillo detectado (2 = recíproco directo)
+    pub positive_threshold: u8,        // Estrellas a partir de las que una calificación es positiva
+    pub activity_weight: f64,          // Peso de la actividad de aprendizaje en la reputación de estudiantes
+    pub activity_scale: f64,           // Interacciones (con decaimiento) para saturar la actividad
+    pub iterations: usize,             // Iteraciones de ponderación por reputación del calificador
+}
+
+impl Default for ReputationParameters {
+    fn default() -> Self {
+        Self {
+            half_life_days: 180.0,
+            prior_mean: 0.6,
+            prior_weight: 3.0,
+            min_rater_weight: 0.2,
+            unverified_rater_factor: 0.25,
+            collusion_dampening: 0.3,
+            ring_max_length: 3,
+            positive_threshold: 4,
+            activity_weight: 0.3,
+            activity_scale: 20.0,
+            iterations: 5,
+        }
+    }
+}
+
+/// Puntuación de reputación de un usuario en un rol
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReputationScore {
+    pub user_address: String,
+    pub role: RatedRole,
+    pub score: f64,                  // Reputación en [0, 100]
+    pub rating_count: usize,         // Calificaciones consideradas
+    pub computed_at: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_session_participants_rate_each_other() {
+        let session = CompletedSession { tutor: "0xtutor".to_string(), learner: "0xlearner".to_string() };
+
+        assert_eq!(session.rated_role("0xlearner", "0xtutor"), Some(RatedRole::Tutor));
+        assert_eq!(session.rated_role("0xtutor", "0xlearner"), Some(RatedRole::Learner));
+        assert_eq!(session.rated_role("0xother", "0xtutor"), None);
+        assert_eq!(session.rated_role("0xtutor", "0xother"), None);
+    }
+}
@@ -0,0 +1,28 @@
+use anyhow::Result;
+
+pub mod domain;
+pub mod repository;
+pub mod service;
+
+/// Inicializar el módulo reputation
+pub async fn init() -> Result<()> {
+    tracing::info!("Inicializando módulo reputation");
+    
+    // TODO: Implementar inicialización del módulo
+    // - Conexión a base de datos
+    // - Suscripción a eventos de learning_passport en Redis Streams
+    // - Registro de eventos de dominio de calificaciones
+    
+    Ok(())
+}
+
+/// Cerrar el módulo reputation
+pub async fn shutdown() -> Result<()> {
+    tracing::info!("Cerrando módulo reputation");
+    
+    // TODO: Implementar limpieza del módulo
+    // - Cerrar conexiones
+    // - Finalizar streams
+    
+    Ok(())
+}
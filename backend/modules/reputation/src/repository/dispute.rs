@@ -0,0 +1,201 @@
+// Persistencia de disputas de calificaciones
+// Los eventos son la fuente de verdad; la tabla disputes es una proyección para consultas
+
+use anyhow::{bail, Result};
+use sqlx::{PgPool, Postgres, Transaction};
+
+use crate::domain::{Dispute, DisputeEvent, DisputeId, DisputeStatus, RatingEvent, RatingId};
+
+pub struct DisputeRepository {
+    pool: PgPool,
+}
+
+impl DisputeRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Registrar la apertura de una disputa y crear su proyección
+    pub async fn record_opening(&self, event: &DisputeEvent, dispute: &Dispute) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO disputes (id, rating_id, status, state, opened_at, resolved_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+            dispute.id.0,
+            dispute.rating_id.0,
+            dispute.status.as_str(),
+            serde_json::to_value(dispute)?,
+            dispute.opened_at,
+            dispute.resolved_at
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        Self::insert_event(&mut tx, event).await?;
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// Registrar una transición de la disputa y actualizar su proyección
+    ///
+    /// `previous` es la disputa tal como se leyó antes de aplicar el evento; si otra
+    /// petición la cambió entretanto, no se registra nada y se devuelve un error.
+    pub async fn record_event(&self, event: &DisputeEvent, previous: &Dispute, dispute: &Dispute) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        Self::update_projection(&mut tx, previous, dispute).await?;
+        Self::insert_event(&mut tx, event).await?;
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// Registrar la resolución junto con su efecto sobre la calificación
+    ///
+    /// Ambos eventos se confirman en la misma transacción: una calificación nunca queda
+    /// ajustada por una disputa que no consta como resuelta, ni al revés.
+    pub async fn record_resolution(
+        &self,
+        event: &DisputeEvent,
+        previous: &Dispute,
+        dispute: &Dispute,
+        rating_event: Option<&RatingEvent>,
+    ) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+
+        Self::update_projection(&mut tx, previous, dispute).await?;
+
+        if let Some(rating_event) = rating_event {
+            sqlx::query!(
+                r#"
+                INSERT INTO rating_events (event, timestamp)
+                VALUES ($1, $2)
+                "#,
+                serde_json::to_value(rating_event)?,
+                rating_event.timestamp()
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        Self::insert_event(&mut tx, event).await?;
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// Actualizar la proyección sólo si sigue como se leyó
+    ///
+    /// Cada evento cambia el estado o añade pruebas o votos, así que esos tres valores
+    /// identifican la versión leída. La fila queda bloqueada hasta el final de la
+    /// transacción y la petición concurrente vuelve a evaluar la condición sobre ella.
+    async fn update_projection(tx: &mut Transaction<'_, Postgres>, previous: &Dispute, dispute: &Dispute) -> Result<()> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE disputes
+            SET status = $2, state = $3, resolved_at = $4
+            WHERE id = $1
+              AND status = $5
+              AND jsonb_array_length(state->'evidence') = $6
+              AND jsonb_array_length(state->'votes') = $7
+            "#,
+            dispute.id.0,
+            dispute.status.as_str(),
+            serde_json::to_value(dispute)?,
+            dispute.resolved_at,
+            previous.status.as_str(),
+            previous.evidence.len() as i32,
+            previous.votes.len() as i32
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            bail!("La disputa cambió mientras se procesaba la petición; vuelve a intentarlo");
+        }
+
+        Ok(())
+    }
+
+    async fn insert_event(tx: &mut Transaction<'_, Postgres>, event: &DisputeEvent) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO dispute_events (dispute_id, event, timestamp)
+            VALUES ($1, $2, $3)
+            "#,
+            event.dispute_id().0,
+            serde_json::to_value(event)?,
+            event.timestamp()
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Obtener el historial de transiciones de una disputa
+    pub async fn get_events(&self, dispute_id: &DisputeId) -> Result<Vec<DisputeEvent>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT event
+            FROM dispute_events
+            WHERE dispute_id = $1
+            ORDER BY sequence ASC
+            "#,
+            dispute_id.0
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut events = Vec::new();
+
+        for row in rows {
+            events.push(serde_json::from_value(row.event)?);
+        }
+
+        Ok(events)
+    }
+
+    /// Obtener la disputa abierta sobre una calificación, si existe
+    pub async fn get_open_dispute_for_rating(&self, rating_id: &RatingId) -> Result<Option<DisputeId>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT id
+            FROM disputes
+            WHERE rating_id = $1 AND status <> $2
+            "#,
+            rating_id.0,
+            DisputeStatus::Resolved.as_str()
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|row| DisputeId(row.id)))
+    }
+
+    /// Listar disputas en un estado, de la más antigua a la más reciente
+    pub async fn get_disputes_by_status(&self, status: DisputeStatus) -> Result<Vec<Dispute>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT state
+            FROM disputes
+            WHERE status = $1
+            ORDER BY opened_at ASC
+            "#,
+            status.as_str()
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut disputes = Vec::new();
+
+        for row in rows {
+            disputes.push(serde_json::from_value(row.state)?);
+        }
+
+        Ok(disputes)
+    }
+}
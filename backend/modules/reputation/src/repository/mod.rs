@@ -0,0 +1,243 @@
+// Repositorios para persistencia del módulo reputation
+
+use anyhow::Result;
+use sqlx::PgPool;
+
+pub mod dispute;
+
+pub use dispute::DisputeRepository;
+
+use learning_passport::domain::LearningPassportEvent;
+
+use crate::domain::{RatedRole, RatingEvent, ReputationParameters, ReputationScore};
+
+pub struct ReputationRepository {
+    pool: PgPool,
+}
+
+impl ReputationRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Agregar un evento de calificación al historial
+    pub async fn append_rating_event(&self, event: &RatingEvent) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO rating_events (event, timestamp)
+            VALUES ($1, $2)
+            "#,
+            serde_json::to_value(event)?,
+            event.timestamp()
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Obtener todos los eventos de calificación en orden de llegada
+    pub async fn get_rating_events(&self) -> Result<Vec<RatingEvent>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT event
+            FROM rating_events
+            ORDER BY sequence ASC
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut events = Vec::new();
+
+        for row in rows {
+            events.push(serde_json::from_value(row.event)?);
+        }
+
+        Ok(events)
+    }
+
+    /// Comprobar si un usuario ya calificó una sesión
+    pub async fn rating_exists(&self, session_id: &str, rater: &str) -> Result<bool> {
+        let row = sqlx::query!(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM rating_events
+                WHERE event->'RatingSubmitted'->'rating'->>'session_id' = $1
+                  AND event->'RatingSubmitted'->'rating'->>'rater' = $2
+            ) AS "exists!"
+            "#,
+            session_id,
+            rater
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(row.exists)
+    }
+
+    /// Registrar un evento de learning_passport consumido por el módulo
+    pub async fn append_passport_event(&self, event: &LearningPassportEvent) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO reputation_passport_events (event)
+            VALUES ($1)
+            "#,
+            serde_json::to_value(event)?
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Obtener los eventos de learning_passport consumidos, en orden de llegada
+    pub async fn get_passport_events(&self) -> Result<Vec<LearningPassportEvent>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT event
+            FROM reputation_passport_events
+            ORDER BY sequence ASC
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut events = Vec::new();
+
+        for row in rows {
+            events.push(serde_json::from_value(row.event)?);
+        }
+
+        Ok(events)
+    }
+
+    /// Obtener los parámetros vigentes del motor de reputación
+    pub async fn get_parameters(&self) -> Result<Option<ReputationParameters>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT parameters
+            FROM reputation_parameters
+            WHERE id = 1
+            "#
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match row {
+            Some(row) => Ok(Some(serde_json::from_value(row.parameters)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Guardar los parámetros del motor de reputación
+    pub async fn save_parameters(&self, parameters: &ReputationParameters) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO reputation_parameters (id, parameters)
+            VALUES (1, $1)
+            ON CONFLICT (id) DO UPDATE SET parameters = EXCLUDED.parameters
+            "#,
+            serde_json::to_value(parameters)?
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Guardar una puntuación como vigente y agregarla al historial
+    pub async fn save_score(&self, score: &ReputationScore) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO reputation_scores (user_address, role, score, rating_count, computed_at)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (user_address, role) DO UPDATE
+            SET score = EXCLUDED.score, rating_count = EXCLUDED.rating_count, computed_at = EXCLUDED.computed_at
+            "#,
+            score.user_address,
+            score.role.as_str(),
+            score.score,
+            score.rating_count as i64,
+            score.computed_at
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO reputation_history (user_address, role, score, rating_count, computed_at)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+            score.user_address,
+            score.role.as_str(),
+            score.score,
+            score.rating_count as i64,
+            score.computed_at
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// Obtener la puntuación vigente de un usuario en un rol
+    pub async fn get_current_score(&self, user_address: &str, role: RatedRole) -> Result<Option<ReputationScore>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT user_address, role, score, rating_count, computed_at
+            FROM reputation_scores
+            WHERE user_address = $1 AND role = $2
+            "#,
+            user_address,
+            role.as_str()
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match row {
+            Some(row) => Ok(Some(ReputationScore {
+                user_address: row.user_address,
+                role: row.role.parse()?,
+                score: row.score,
+                rating_count: row.rating_count as usize,
+                computed_at: row.computed_at,
+            })),
+            None => Ok(None),
+        }
+    }
+
+    /// Obtener el historial de puntuaciones de un usuario en un rol
+    pub async fn get_score_history(&self, user_address: &str, role: RatedRole) -> Result<Vec<ReputationScore>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT user_address, role, score, rating_count, computed_at
+            FROM reputation_history
+            WHERE user_address = $1 AND role = $2
+            ORDER BY computed_at ASC
+            "#,
+            user_address,
+            role.as_str()
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut history = Vec::new();
+
+        for row in rows {
+            history.push(ReputationScore {
+                user_address: row.user_address,
+                role: row.role.parse()?,
+                score: row.score,
+                rating_count: row.rating_count as usize,
+                computed_at: row.computed_at,
+            });
+        }
+
+        Ok(history)
+    }
+}
@@ -0,0 +1,422 @@
+// Flujo de disputas y apelaciones de calificaciones
+// Calificación impugnada → pruebas de ambas partes → decisión de moderador o jurado → resolución
+
+use std::sync::Arc;
+
+use anyhow::{anyhow, bail, Result};
+use chrono::Utc;
+
+use identity::domain::{Action, Resource, ResourceKind};
+use identity::service::{Authorizer, HumanityVerifier};
+
+use crate::domain::{
+    Adjudication, AdjudicationKind, Dispute, DisputeEvent, DisputeId, DisputeOutcome, DisputeStatus, Evidence,
+    JuryVote, RatingId, JURY_SIZE,
+};
+use crate::repository::DisputeRepository;
+
+use super::ReputationService;
+
+pub struct DisputeService {
+    repository: DisputeRepository,
+    reputation: Arc<ReputationService>,
+    humanity_verifier: Arc<dyn HumanityVerifier>,
+    authorizer: Arc<dyn Authorizer>,
+}
+
+impl DisputeService {
+    pub fn new(
+        repository: DisputeRepository,
+        reputation: Arc<ReputationService>,
+        humanity_verifier: Arc<dyn HumanityVerifier>,
+        authorizer: Arc<dyn Authorizer>,
+    ) -> Self {
+        Self { repository, reputation, humanity_verifier, authorizer }
+    }
+
+    /// Impugnar una calificación
+    ///
+    /// Quien abre la disputa sólo elige entre moderación y jurado; los jurados los sortea
+    /// el servicio entre humanos verificados ajenos a la calificación.
+    pub async fn open_dispute(
+        &self,
+        rating_id: &RatingId,
+        opened_by: &str,
+        reason: &str,
+        kind: AdjudicationKind,
+    ) -> Result<Dispute> {
+        let rating = self
+            .reputation
+            .get_rating(rating_id)
+            .await?
+            .ok_or_else(|| anyhow!("Calificación no encontrada: {:?}", rating_id))?;
+
+        if opened_by != rating.rater && opened_by != rating.ratee {
+            bail!("Sólo las partes de la calificación pueden impugnarla");
+        }
+
+        if reason.trim().is_empty() {
+            bail!("La disputa debe indicar un motivo");
+        }
+
+        if self.repository.get_open_dispute_for_rating(rating_id).await?.is_some() {
+            bail!("La calificación ya tiene una disputa abierta");
+        }
+
+        let adjudication = match kind {
+            AdjudicationKind::Moderator => Adjudication::Moderator,
+            AdjudicationKind::Jury => self.draw_jury(&rating.rater, &rating.ratee).await?,
+        };
+
+        let event = DisputeEvent::DisputeOpened {
+            dispute_id: DisputeId::new(),
+            rating_id: rating_id.clone(),
+            rater: rating.rater,
+            ratee: rating.ratee,
+            opened_by: opened_by.to_string(),
+            reason: reason.to_string(),
+            adjudication,
+            timestamp: Utc::now(),
+        };
+
+        let dispute = Dispute::from_events(std::slice::from_ref(&event))
+            .ok_or_else(|| anyhow!("No se pudo abrir la disputa"))?;
+        self.repository.record_opening(&event, &dispute).await?;
+
+        Ok(dispute)
+    }
+
+    /// Aportar pruebas a una disputa en curso
+    pub async fn submit_evidence(
+        &self,
+        dispute_id: &DisputeId,
+        submitted_by: &str,
+        content: &str,
+        attachments: Vec<String>,
+    ) -> Result<Dispute> {
+        let mut dispute = self.load(dispute_id).await?;
+
+        if dispute.status != DisputeStatus::Flagged {
+            bail!("El plazo de pruebas de la disputa está cerrado");
+        }
+
+        let Some(party) = dispute.party_of(submitted_by) else {
+            bail!("Sólo las partes de la disputa pueden aportar pruebas");
+        };
+
+        let event = DisputeEvent::EvidenceSubmitted {
+            dispute_id: dispute_id.clone(),
+            evidence: Evidence {
+                party,
+                submitted_by: submitted_by.to_string(),
+                content: content.to_string(),
+                attachments,
+                submitted_at: Utc::now(),
+            },
+        };
+
+        self.record(&mut dispute, event).await?;
+
+        Ok(dispute)
+    }
+
+    /// Cerrar el plazo de pruebas aunque alguna parte no haya respondido
+    pub async fn close_evidence(&self, dispute_id: &DisputeId, moderator: &str) -> Result<Dispute> {
+        let mut dispute = self.load(dispute_id).await?;
+        self.ensure_moderator(&dispute, moderator).await?;
+
+        if dispute.status != DisputeStatus::Flagged {
+            bail!("El plazo de pruebas de la disputa ya está cerrado");
+        }
+
+        let event = DisputeEvent::EvidenceClosed {
+            dispute_id: dispute_id.clone(),
+            closed_by: moderator.to_string(),
+            timestamp: Utc::now(),
+        };
+
+        self.record(&mut dispute, event).await?;
+
+        Ok(dispute)
+    }
+
+    /// Decisión de un moderador sobre una disputa
+    pub async fn decide(&self, dispute_id: &DisputeId, moderator: &str, outcome: DisputeOutcome) -> Result<Dispute> {
+        let mut dispute = self.load(dispute_id).await?;
+
+        if dispute.adjudication != Adjudication::Moderator {
+            bail!("La disputa se resuelve por jurado comunitario");
+        }
+
+        if dispute.status != DisputeStatus::EvidenceComplete {
+            bail!("La disputa no está lista para decisión");
+        }
+
+        if dispute.party_of(moderator).is_some() {
+            bail!("Una parte de la disputa no puede decidirla");
+        }
+
+        self.ensure_moderator(&dispute, moderator).await?;
+
+        self.validate_outcome(&dispute, outcome).await?;
+        self.resolve(&mut dispute, outcome, moderator).await?;
+
+        Ok(dispute)
+    }
+
+    /// Voto de un miembro del jurado; la disputa se resuelve al alcanzarse el veredicto
+    pub async fn cast_jury_vote(&self, dispute_id: &DisputeId, juror: &str, outcome: DisputeOutcome) -> Result<Dispute> {
+        let mut dispute = self.load(dispute_id).await?;
+
+        let Adjudication::Jury { jurors } = &dispute.adjudication else {
+            bail!("La disputa se resuelve por moderación");
+        };
+
+        if !jurors.iter().any(|j| j == juror) {
+            bail!("{} no forma parte del jurado", juror);
+        }
+
+        if dispute.status != DisputeStatus::EvidenceComplete {
+            bail!("La disputa no está lista para votación");
+        }
+
+        if dispute.votes.iter().any(|v| v.juror == juror) {
+            bail!("{} ya votó en esta disputa", juror);
+        }
+
+        self.validate_outcome(&dispute, outcome).await?;
+
+        let event = DisputeEvent::JuryVoteCast {
+            dispute_id: dispute_id.clone(),
+            vote: JuryVote {
+                juror: juror.to_string(),
+                outcome,
+                cast_at: Utc::now(),
+            },
+        };
+
+        self.record(&mut dispute, event).await?;
+
+        if let Some(verdict) = dispute.jury_verdict() {
+            self.resolve(&mut dispute, verdict, "jury").await?;
+        }
+
+        Ok(dispute)
+    }
+
+    /// Obtener el estado actual de una disputa
+    pub async fn get_dispute(&self, dispute_id: &DisputeId) -> Result<Option<Dispute>> {
+        Ok(Dispute::from_events(&self.repository.get_events(dispute_id).await?))
+    }
+
+    /// Obtener el historial completo de transiciones de una disputa
+    pub async fn get_dispute_history(&self, dispute_id: &DisputeId) -> Result<Vec<DisputeEvent>> {
+        self.repository.get_events(dispute_id).await
+    }
+
+    /// Listar disputas en un estado (cola de moderación)
+    pub async fn get_disputes_by_status(&self, status: DisputeStatus) -> Result<Vec<Dispute>> {
+        self.repository.get_disputes_by_status(status).await
+    }
+
+    async fn load(&self, dispute_id: &DisputeId) -> Result<Dispute> {
+        self.get_dispute(dispute_id)
+            .await?
+            .ok_or_else(|| anyhow!("Disputa no encontrada: {:?}", dispute_id))
+    }
+
+    /// Aplicar y registrar un evento; falla si la disputa cambió desde que se leyó
+    async fn record(&self, dispute: &mut Dispute, event: DisputeEvent) -> Result<()> {
+        let previous = dispute.clone();
+        dispute.apply(&event);
+        self.repository.record_event(&event, &previous, dispute).await
+    }
+
+    /// Aplicar el resultado a la calificación y cerrar la disputa en una sola transacción
+    async fn resolve(&self, dispute: &mut Dispute, outcome: DisputeOutcome, decided_by: &str) -> Result<()> {
+        let rating_event = outcome.rating_event(&dispute.rating_id, &dispute.id);
+        let event = DisputeEvent::DisputeResolved {
+            dispute_id: dispute.id.clone(),
+            outcome,
+            decided_by: decided_by.to_string(),
+            timestamp: Utc::now(),
+        };
+
+        let previous = dispute.clone();
+        dispute.apply(&event);
+        self.repository
+            .record_resolution(&event, &previous, dispute, rating_event.as_ref())
+            .await?;
+
+        if rating_event.is_some() {
+            self.reputation.recompute_users(&[dispute.ratee.as_str(), dispute.rater.as_str()]).await?;
+        }
+
+        // TODO: Emitir evento de dominio
+        // self.emit_event(ReputationEvent::DisputeResolved { ... }).await?;
+
+        Ok(())
+    }
+
+    /// Sólo los moderadores cierran pruebas y deciden disputas
+    async fn ensure_moderator(&self, dispute: &Dispute, moderator: &str) -> Result<()> {
+        let principal = self.authorizer.load_principal(moderator).await?;
+        let resource = Resource::new(ResourceKind::Dispute).with_id(&dispute.id.0.to_string());
+        let decision = self
+            .authorizer
+            .authorize(Some(&principal), Action::Moderate, &resource)
+            .await?;
+
+        if !decision.allowed {
+            bail!("{} no puede moderar disputas: {}", moderator, decision.reason);
+        }
+
+        Ok(())
+    }
+
+    /// Una reducción debe dejar la calificación por debajo de la actual
+    async fn validate_outcome(&self, dispute: &Dispute, outcome: DisputeOutcome) -> Result<()> {
+        if let DisputeOutcome::Reduced { stars } = outcome {
+            let rating = self
+                .reputation
+                .get_rating(&dispute.rating_id)
+                .await?
+                .ok_or_else(|| anyhow!("La calificación impugnada ya no existe"))?;
+
+            if stars < 1 || stars >= rating.stars {
+                bail!("Una reducción debe quedar entre 1 y {} estrellas", rating.stars.saturating_sub(1));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Sortear el jurado entre humanos verificados que no sean parte de la calificación
+    async fn draw_jury(&self, rater: &str, ratee: &str) -> Result<Adjudication> {
+        let parties = [rater.to_string(), ratee.to_string()];
+        let candidates = self.humanity_verifier.sample_verified_humans(JURY_SIZE, &parties).await?;
+
+        Adjudication::jury(candidates, rater, ratee)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use identity::domain::{AuthorizationDecision, AuthorizationRequest, HumanityProof, HumanityRecord, Principal, Role};
+    use identity::service::PolicyEngine;
+    use sqlx::PgPool;
+    use uuid::Uuid;
+
+    use crate::domain::CompletedSession;
+    use crate::repository::ReputationRepository;
+    use crate::service::RatedSessions;
+
+    /// Registro que devuelve siempre los mismos candidatos, sin excluir a nadie
+    struct Registry(Vec<String>);
+
+    #[async_trait]
+    impl HumanityVerifier for Registry {
+        async fn register_humanity(&self, _: &str, _: &HumanityProof) -> Result<HumanityRecord> {
+            bail!("no soportado")
+        }
+
+        async fn is_verified_human(&self, _: &str) -> Result<bool> {
+            Ok(true)
+        }
+
+        async fn get_humanity_record(&self, _: &str) -> Result<Option<HumanityRecord>> {
+            Ok(None)
+        }
+
+        async fn sample_verified_humans(&self, _: usize, _: &[String]) -> Result<Vec<String>> {
+            Ok(self.0.clone())
+        }
+    }
+
+    /// Sólo 0xmod tiene el rol de moderador; las decisiones las toma el motor de políticas real
+    struct Roles;
+
+    #[async_trait]
+    impl Authorizer for Roles {
+        async fn load_principal(&self, user_address: &str) -> Result<Principal> {
+            let roles = if user_address == "0xmod" { vec![Role::Moderator] } else { Vec::new() };
+            Ok(Principal { user_address: user_address.to_string(), roles, memberships: Vec::new() })
+        }
+
+        async fn authorize(
+            &self,
+            principal: Option<&Principal>,
+            action: Action,
+            resource: &Resource,
+        ) -> Result<AuthorizationDecision> {
+            let Some(principal) = principal else {
+                return Ok(AuthorizationDecision::deny("Usuario no autenticado"));
+            };
+            Ok(PolicyEngine::default().evaluate(&AuthorizationRequest {
+                principal: principal.clone(),
+                action,
+                resource: resource.clone(),
+                owner_memberships: Vec::new(),
+            }))
+        }
+    }
+
+    struct NoSessions;
+
+    #[async_trait]
+    impl RatedSessions for NoSessions {
+        async fn completed_session(&self, _: &str) -> Result<Option<CompletedSession>> {
+            Ok(None)
+        }
+    }
+
+    fn disputes(candidates: &[&str]) -> DisputeService {
+        // Las comprobaciones probadas no llegan a consultar la base de datos
+        let pool = PgPool::connect_lazy("postgres://localhost/keiko").unwrap();
+        let humanity: Arc<dyn HumanityVerifier> =
+            Arc::new(Registry(candidates.iter().map(|c| c.to_string()).collect()));
+        let reputation = Arc::new(ReputationService::new(
+            ReputationRepository::new(pool.clone()),
+            humanity.clone(),
+            Arc::new(NoSessions),
+        ));
+        DisputeService::new(DisputeRepository::new(pool), reputation, humanity, Arc::new(Roles))
+    }
+
+    fn dispute() -> Dispute {
+        Dispute::from_events(&[DisputeEvent::DisputeOpened {
+            dispute_id: DisputeId::new(),
+            rating_id: RatingId(Uuid::new_v4()),
+            rater: "0xrater".to_string(),
+            ratee: "0xratee".to_string(),
+            opened_by: "0xratee".to_string(),
+            reason: "Calificación injusta".to_string(),
+            adjudication: Adjudication::Moderator,
+            timestamp: Utc::now(),
+        }])
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn only_moderators_decide_disputes() {
+        let service = disputes(&[]);
+
+        assert!(service.ensure_moderator(&dispute(), "0xmod").await.is_ok());
+        assert!(service.ensure_moderator(&dispute(), "0xuser").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn the_service_draws_the_jury_without_the_parties() {
+        let service = disputes(&["0xratee", "0xa", "0xb", "0xrater", "0xc"]);
+
+        let jury = service.draw_jury("0xrater", "0xratee").await.unwrap();
+        assert_eq!(
+            jury,
+            Adjudication::Jury { jurors: vec!["0xa".to_string(), "0xb".to_string(), "0xc".to_string()] }
+        );
+
+        assert!(disputes(&["0xa", "0xrater"]).draw_jury("0xrater", "0xratee").await.is_err());
+    }
+}
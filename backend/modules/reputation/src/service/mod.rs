@@ -0,0 +1,217 @@
+// Servicios de aplicación para el módulo reputation
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use anyhow::{bail, Result};
+use async_trait::async_trait;
+use chrono::Utc;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::task::JoinHandle;
+
+use identity::domain::{parse_verifying_key, verify_signature};
+use identity::service::HumanityVerifier;
+use learning_passport::domain::LearningPassportEvent;
+
+use crate::domain::{
+    compute_reputation, fold_rating_events, learner_activity_from_events, CompletedSession, RatedRole, Rating,
+    RatingEvent, RatingId, ReputationInputs, ReputationParameters, ReputationScore,
+};
+use crate::repository::ReputationRepository;
+
+pub mod dispute;
+
+pub use dispute::DisputeService;
+
+/// Sesiones de tutoría que se pueden calificar (las implementa marketplace)
+#[async_trait]
+pub trait RatedSessions: Send + Sync {
+    /// Participantes de una sesión completada; `None` si no existe o no se completó
+    async fn completed_session(&self, session_id: &str) -> Result<Option<CompletedSession>>;
+}
+
+pub struct ReputationService {
+    repository: ReputationRepository,
+    humanity_verifier: Arc<dyn HumanityVerifier>,
+    sessions: Arc<dyn RatedSessions>,
+}
+
+impl ReputationService {
+    pub fn new(
+        repository: ReputationRepository,
+        humanity_verifier: Arc<dyn HumanityVerifier>,
+        sessions: Arc<dyn RatedSessions>,
+    ) -> Self {
+        Self { repository, humanity_verifier, sessions }
+    }
+
+    /// Registrar una calificación firmada tras una sesión de tutoría y recalcular la reputación
+    ///
+    /// Sólo califican los participantes de una sesión completada, uno al otro, y con
+    /// humanidad verificada: la firma se comprueba con la clave de su registro.
+    pub async fn submit_rating(&self, rating: Rating) -> Result<Rating> {
+        if !(1..=5).contains(&rating.stars) {
+            bail!("La calificación debe estar entre 1 y 5 estrellas");
+        }
+
+        if rating.rater == rating.ratee {
+            bail!("Un usuario no puede calificarse a sí mismo");
+        }
+
+        let Some(session) = self.sessions.completed_session(&rating.session_id).await? else {
+            bail!("La sesión {} no existe o no se ha completado", rating.session_id);
+        };
+        if session.rated_role(&rating.rater, &rating.ratee) != Some(rating.ratee_role) {
+            bail!(
+                "{} no puede calificar a {} como {} en la sesión {}",
+                rating.rater, rating.ratee, rating.ratee_role, rating.session_id
+            );
+        }
+
+        let record = match self.humanity_verifier.get_humanity_record(&rating.rater).await? {
+            Some(record) if record.is_active() => record,
+            _ => bail!("Sólo pueden calificar usuarios con humanidad verificada"),
+        };
+        if record.verifying_key != rating.rater_public_key {
+            bail!("La clave pública no corresponde a la humanidad verificada del calificador");
+        }
+
+        let verifying_key = parse_verifying_key(&record.verifying_key)?;
+        if !verify_signature(&verifying_key, &rating.signing_payload()?, &rating.signature) {
+            bail!("Firma de calificación inválida");
+        }
+
+        if self.repository.rating_exists(&rating.session_id, &rating.rater).await? {
+            bail!("La sesión {} ya fue calificada por {}", rating.session_id, rating.rater);
+        }
+
+        self.repository
+            .append_rating_event(&RatingEvent::RatingSubmitted {
+                rating: rating.clone(),
+                timestamp: Utc::now(),
+            })
+            .await?;
+        self.recompute_users(&[rating.ratee.as_str(), rating.rater.as_str()]).await?;
+
+        Ok(rating)
+    }
+
+    /// Obtener una calificación vigente (con los ajustes por disputas aplicados)
+    pub async fn get_rating(&self, rating_id: &RatingId) -> Result<Option<Rating>> {
+        let ratings = fold_rating_events(&self.repository.get_rating_events().await?);
+        Ok(ratings.into_iter().find(|r| r.id == *rating_id))
+    }
+
+    /// Consumir un evento de learning_passport para el cálculo de reputación de estudiantes
+    pub async fn handle_passport_event(&self, event: &LearningPassportEvent) -> Result<()> {
+        self.repository.append_passport_event(event).await
+    }
+
+    /// Ingerir en segundo plano los eventos de learning_passport
+    ///
+    /// La actividad se guarda al llegar y la reputación se recalcula con la próxima
+    /// calificación o ajuste. Si el canal se desborda, los eventos perdidos se registran en el log.
+    pub fn spawn_passport_ingestion(
+        self: Arc<Self>,
+        mut events: broadcast::Receiver<LearningPassportEvent>,
+    ) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                match events.recv().await {
+                    Ok(event) => {
+                        if let Err(err) = self.handle_passport_event(&event).await {
+                            tracing::error!("No se pudo ingerir el evento de pasaporte {:?}: {:#}", event, err);
+                        }
+                    }
+                    Err(RecvError::Lagged(missed)) => {
+                        tracing::warn!("Reputación: se perdieron {} eventos de pasaporte", missed);
+                    }
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+
+    /// Obtener los parámetros vigentes del motor de reputación
+    pub async fn get_parameters(&self) -> Result<ReputationParameters> {
+        Ok(self.repository.get_parameters().await?.unwrap_or_default())
+    }
+
+    /// Actualizar los parámetros del motor de reputación
+    pub async fn update_parameters(&self, parameters: &ReputationParameters) -> Result<()> {
+        if parameters.half_life_days <= 0.0 || parameters.activity_scale <= 0.0 {
+            bail!("La vida media y la escala de actividad deben ser positivas");
+        }
+
+        let factors = [
+            parameters.prior_mean,
+            parameters.min_rater_weight,
+            parameters.unverified_rater_factor,
+            parameters.collusion_dampening,
+            parameters.activity_weight,
+        ];
+        if factors.iter().any(|f| !(0.0..=1.0).contains(f)) {
+            bail!("Los factores de reputación deben estar entre 0 y 1");
+        }
+
+        self.repository.save_parameters(parameters).await
+    }
+
+    /// Recalcular la reputación de todos los usuarios a partir del historial de eventos
+    pub async fn recompute(&self) -> Result<Vec<ReputationScore>> {
+        self.recompute_scores(None).await
+    }
+
+    /// Recalcular y guardar sólo la reputación de los usuarios afectados por un cambio
+    ///
+    /// El motor sigue necesitando todas las calificaciones (el peso de cada calificador
+    /// depende de su propia reputación), pero sólo se guardan las puntuaciones de `users`.
+    pub async fn recompute_users(&self, users: &[&str]) -> Result<Vec<ReputationScore>> {
+        self.recompute_scores(Some(users)).await
+    }
+
+    async fn recompute_scores(&self, users: Option<&[&str]>) -> Result<Vec<ReputationScore>> {
+        let parameters = self.get_parameters().await?;
+        let ratings = fold_rating_events(&self.repository.get_rating_events().await?);
+        let learner_activity = learner_activity_from_events(&self.repository.get_passport_events().await?);
+
+        let mut verified_humans = HashSet::new();
+        let raters: HashSet<&str> = ratings.iter().map(|r| r.rater.as_str()).collect();
+        for rater in raters {
+            if let Some(record) = self.humanity_verifier.get_humanity_record(rater).await? {
+                if record.is_active() {
+                    verified_humans.insert(rater.to_string());
+                }
+            }
+        }
+
+        let scores: Vec<ReputationScore> = compute_reputation(
+            &parameters,
+            &ReputationInputs {
+                ratings: &ratings,
+                learner_activity: &learner_activity,
+                verified_humans: &verified_humans,
+                now: Utc::now(),
+            },
+        )
+        .into_iter()
+        .filter(|score| users.is_none_or(|users| users.contains(&score.user_address.as_str())))
+        .collect();
+
+        for score in &scores {
+            self.repository.save_score(score).await?;
+        }
+
+        Ok(scores)
+    }
+
+    /// Obtener la reputación vigente de un usuario en un rol
+    pub async fn get_reputation(&self, user_address: &str, role: RatedRole) -> Result<Option<ReputationScore>> {
+        self.repository.get_current_score(user_address, role).await
+    }
+
+    /// Obtener la evolución histórica de la reputación de un usuario en un rol
+    pub async fn get_reputation_history(&self, user_address: &str, role: RatedRole) -> Result<Vec<ReputationScore>> {
+        self.repository.get_score_history(user_address, role).await
+    }
+}
@@ -0,0 +1,15 @@
+-- Guías de autoestudio; las unidades y sus actividades se guardan con la guía
+
+CREATE TABLE selfstudy_guides (
+    id UUID PRIMARY KEY,
+    author TEXT NOT NULL,
+    title TEXT NOT NULL,
+    description TEXT NOT NULL,
+    language TEXT NOT NULL,
+    subject TEXT,
+    status TEXT NOT NULL,
+    version INT NOT NULL,
+    units JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL
+);
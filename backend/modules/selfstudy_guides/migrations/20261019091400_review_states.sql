@@ -0,0 +1,18 @@
+-- Estado de repaso SM-2 de cada actividad por estudiante
+
+CREATE TABLE review_states (
+    learner TEXT NOT NULL,
+    activity_iri TEXT NOT NULL,
+    repetitions INT NOT NULL,
+    ease_factor DOUBLE PRECISION NOT NULL,
+    interval_days INT NOT NULL,
+    lapses INT NOT NULL,
+    reviews INT NOT NULL,
+    last_quality SMALLINT NOT NULL,
+    last_reviewed_at TIMESTAMPTZ NOT NULL,
+    last_interaction_id UUID NOT NULL,
+    due_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (learner, activity_iri)
+);
+
+CREATE INDEX review_states_due_idx ON review_states (learner, due_at);
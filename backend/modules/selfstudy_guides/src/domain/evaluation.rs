@@ -0,0 +1,197 @@
+// Evaluación de respuestas de estudiantes contra la rúbrica de un objetivo
+
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{GuideId, UnitId};
+
+/// Criterio de una rúbrica
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RubricCriterion {
+    pub id: String,
+    pub description: String,
+    pub weight: f64,                    // Peso relativo dentro de la rúbrica
+    pub keywords: Vec<String>,          // Términos que evidencian el criterio (evaluación determinista)
+    pub min_matches: Option<usize>,     // Términos necesarios para la puntuación completa; por defecto todos
+}
+
+/// Rúbrica con la que se evalúan las respuestas a un objetivo
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Rubric {
+    pub criteria: Vec<RubricCriterion>,
+    pub passing_score: f64,             // Puntuación escalada mínima [0, 1] para aprobar
+}
+
+impl Rubric {
+    pub fn validate(&self) -> Result<()> {
+        if self.criteria.is_empty() {
+            bail!("La rúbrica necesita al menos un criterio");
+        }
+        if !(0.0..=1.0).contains(&self.passing_score) {
+            bail!("La puntuación para aprobar debe estar entre 0 y 1");
+        }
+
+        let mut ids = std::collections::HashSet::new();
+        for criterion in &self.criteria {
+            if !ids.insert(criterion.id.as_str()) {
+                bail!("Criterio repetido en la rúbrica: {}", criterion.id);
+            }
+            if !criterion.weight.is_finite() || criterion.weight <= 0.0 {
+                bail!("El peso del criterio {} debe ser positivo", criterion.id);
+            }
+            if criterion.keywords.is_empty() {
+                bail!("El criterio {} necesita al menos un término clave", criterion.id);
+            }
+            // Un término sin letras ni números no puede aparecer en ninguna respuesta
+            let blank = |keyword: &String| !keyword.split('|').any(|alt| alt.chars().any(char::is_alphanumeric));
+            if criterion.keywords.iter().any(blank) {
+                bail!("El criterio {} tiene un término clave vacío", criterion.id);
+            }
+            if criterion.min_matches.is_some_and(|n| n == 0 || n > criterion.keywords.len()) {
+                bail!("El criterio {} exige más coincidencias que términos tiene", criterion.id);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn criterion(&self, id: &str) -> Option<&RubricCriterion> {
+        self.criteria.iter().find(|c| c.id == id)
+    }
+
+    /// Puntuación ponderada a partir de las puntuaciones de cada criterio
+    ///
+    /// Los criterios sin puntuación cuentan como cero.
+    pub fn weighted_score(&self, scores: &[CriterionScore]) -> f64 {
+        let total_weight: f64 = self.criteria.iter().map(|c| c.weight).sum();
+        if total_weight <= 0.0 {
+            return 0.0;
+        }
+
+        let earned: f64 = self
+            .criteria
+            .iter()
+            .map(|c| {
+                let score = scores.iter().find(|s| s.criterion_id == c.id).map(|s| s.score).unwrap_or(0.0);
+                c.weight * score.clamp(0.0, 1.0)
+            })
+            .sum();
+
+        earned / total_weight
+    }
+}
+
+/// Tipo de respuesta entregada
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SubmissionKind {
+    FreeText,
+    Code { language: String },
+}
+
+impl SubmissionKind {
+    pub fn as_str(&self) -> &str {
+        match self {
+            SubmissionKind::FreeText => "free_text",
+            SubmissionKind::Code { language } => language,
+        }
+    }
+}
+
+/// Respuesta de un estudiante a un objetivo de una guía
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Submission {
+    pub guide_id: GuideId,
+    pub unit_id: UnitId,
+    pub objective_code: String,
+    pub learner: String,
+    pub kind: SubmissionKind,
+    pub prompt: String,                 // Enunciado o descripción del objetivo
+    pub answer: String,
+    pub submitted_at: DateTime<Utc>,
+}
+
+/// Puntuación de un criterio
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CriterionScore {
+    pub criterion_id: String,
+    pub score: f64,                     // [0, 1]
+    pub feedback: String,
+}
+
+/// Tipo de evaluador
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EvaluatorKind {
+    Rubric,         // Determinista, por términos clave
+    LanguageModel,  // Modelo de lenguaje tras una API compatible con OpenAI
+}
+
+impl EvaluatorKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EvaluatorKind::Rubric => "rubric",
+            EvaluatorKind::LanguageModel => "language_model",
+        }
+    }
+}
+
+impl fmt::Display for EvaluatorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for EvaluatorKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "rubric" => Ok(EvaluatorKind::Rubric),
+            "language_model" => Ok(EvaluatorKind::LanguageModel),
+            other => Err(anyhow!("Tipo de evaluador desconocido: {}", other)),
+        }
+    }
+}
+
+/// Identidad del evaluador, registrada junto a cada evaluación
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EvaluatorIdentity {
+    pub kind: EvaluatorKind,
+    pub name: String,                   // Ej: "keyword-rubric" o el nombre del modelo
+    pub version: String,
+}
+
+/// Resultado estructurado de evaluar una respuesta
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Evaluation {
+    pub score: f64,                     // Puntuación ponderada [0, 1]
+    pub passed: bool,
+    pub criteria: Vec<CriterionScore>,
+    pub feedback: String,
+    pub evaluator: EvaluatorIdentity,
+    pub evaluated_at: DateTime<Utc>,
+}
+
+impl Evaluation {
+    /// Construir la evaluación a partir de las puntuaciones por criterio
+    pub fn from_criteria(
+        rubric: &Rubric,
+        criteria: Vec<CriterionScore>,
+        feedback: String,
+        evaluator: EvaluatorIdentity,
+    ) -> Self {
+        let score = rubric.weighted_score(&criteria);
+
+        Self {
+            score,
+            passed: score >= rubric.passing_score,
+            criteria,
+            feedback,
+            evaluator,
+            evaluated_at: Utc::now(),
+        }
+    }
+}
@@ -0,0 +1,309 @@
+// Entidades de dominio para el módulo selfstudy_guides
+// Guías de autoestudio formadas por unidades con prerrequisitos y objetivos ligados a actividades xAPI
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub mod evaluation;
+pub mod progress;
+pub mod review;
+
+pub use evaluation::*;
+pub use progress::*;
+pub use review::*;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct GuideId(pub Uuid);
+
+impl GuideId {
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct UnitId(pub Uuid);
+
+impl UnitId {
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+/// Objetivo de aprendizaje, cumplido a través de una actividad xAPI del pasaporte
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LearningObjective {
+    pub code: String,                   // Identificador dentro de la guía (ej: "fracciones.suma")
+    pub description: String,
+    pub activity_iri: String,           // IRI del objeto xAPI que evidencia el objetivo
+    pub mastery_score: Option<f64>,     // Puntuación escalada mínima [0, 1], si el objetivo la exige
+    #[serde(default)]
+    pub rubric: Option<Rubric>,         // Rúbrica para evaluar respuestas abiertas o de código
+}
+
+/// Unidad de una guía
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GuideUnit {
+    pub id: UnitId,
+    pub title: String,
+    pub summary: String,
+    pub prerequisites: Vec<UnitId>,     // Unidades que deben completarse antes
+    pub objectives: Vec<LearningObjective>,
+    pub estimated_minutes: Option<u32>,
+}
+
+/// Estado editorial de una guía
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GuideStatus {
+    Draft,
+    Published,
+    Archived,
+}
+
+impl GuideStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GuideStatus::Draft => "draft",
+            GuideStatus::Published => "published",
+            GuideStatus::Archived => "archived",
+        }
+    }
+}
+
+impl fmt::Display for GuideStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for GuideStatus {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "draft" => Ok(GuideStatus::Draft),
+            "published" => Ok(GuideStatus::Published),
+            "archived" => Ok(GuideStatus::Archived),
+            other => Err(anyhow!("Estado de guía desconocido: {}", other)),
+        }
+    }
+}
+
+/// Guía de autoestudio
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Guide {
+    pub id: GuideId,
+    pub author: String,
+    pub title: String,
+    pub description: String,
+    pub language: String,               // Código BCP 47 (ej: "es")
+    pub subject: Option<String>,
+    pub status: GuideStatus,
+    pub units: Vec<GuideUnit>,          // En el orden sugerido por el autor
+    pub version: u32,                   // Aumenta con cada cambio de unidades de una guía publicada
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Guide {
+    pub fn unit(&self, unit_id: &UnitId) -> Option<&GuideUnit> {
+        self.units.iter().find(|u| &u.id == unit_id)
+    }
+
+    /// Buscar un objetivo por código, junto con la unidad que lo contiene
+    pub fn find_objective(&self, code: &str) -> Option<(&GuideUnit, &LearningObjective)> {
+        self.units
+            .iter()
+            .find_map(|unit| unit.objectives.iter().find(|o| o.code == code).map(|o| (unit, o)))
+    }
+
+    /// Posición de una unidad en el orden sugerido por el autor
+    pub fn position_of(&self, unit_id: &UnitId) -> Option<usize> {
+        self.units.iter().position(|u| &u.id == unit_id)
+    }
+
+    /// Validar la estructura de unidades: IDs y objetivos únicos, prerrequisitos existentes y sin ciclos
+    pub fn validate_units(&self) -> Result<()> {
+        let mut unit_ids = HashSet::new();
+        let mut objective_codes = HashSet::new();
+
+        for unit in &self.units {
+            if !unit_ids.insert(&unit.id) {
+                bail!("Unidad repetida en la guía: {}", unit.id.0);
+            }
+            if unit.title.trim().is_empty() {
+                bail!("La unidad {} necesita un título", unit.id.0);
+            }
+            if unit.objectives.is_empty() {
+                bail!("La unidad '{}' necesita al menos un objetivo de aprendizaje", unit.title);
+            }
+
+            for objective in &unit.objectives {
+                if !objective_codes.insert(objective.code.as_str()) {
+                    bail!("Objetivo repetido en la guía: {}", objective.code);
+                }
+                if !objective.activity_iri.starts_with("http://") && !objective.activity_iri.starts_with("https://") {
+                    bail!("El objetivo {} debe apuntar a un IRI de actividad absoluto", objective.code);
+                }
+                if let Some(score) = objective.mastery_score {
+                    if !(0.0..=1.0).contains(&score) {
+                        bail!("La puntuación de dominio del objetivo {} debe estar entre 0 y 1", objective.code);
+                    }
+                }
+                if let Some(rubric) = &objective.rubric {
+                    rubric
+                        .validate()
+                        .map_err(|e| anyhow!("Rúbrica inválida en el objetivo {}: {}", objective.code, e))?;
+                }
+            }
+        }
+
+        for unit in &self.units {
+            let mut seen = HashSet::new();
+            for prerequisite in &unit.prerequisites {
+                if !seen.insert(prerequisite) {
+                    bail!("La unidad '{}' repite el prerrequisito {}", unit.title, prerequisite.0);
+                }
+                if !unit_ids.contains(prerequisite) {
+                    bail!("La unidad '{}' requiere una unidad inexistente: {}", unit.title, prerequisite.0);
+                }
+            }
+        }
+
+        self.check_acyclic()
+    }
+
+    /// Detectar ciclos de prerrequisitos (algoritmo de Kahn)
+    fn check_acyclic(&self) -> Result<()> {
+        let mut pending: HashMap<&UnitId, usize> =
+            self.units.iter().map(|u| (&u.id, u.prerequisites.len())).collect();
+        let mut ready: Vec<&UnitId> = pending.iter().filter(|(_, n)| **n == 0).map(|(id, _)| *id).collect();
+        let mut visited = 0;
+
+        while let Some(unit_id) = ready.pop() {
+            visited += 1;
+            for dependent in self.units.iter().filter(|u| u.prerequisites.contains(unit_id)) {
+                let count = pending
+                    .get_mut(&dependent.id)
+                    .ok_or_else(|| anyhow!("La unidad '{}' no está en la guía", dependent.title))?;
+                *count -= 1;
+                if *count == 0 {
+                    ready.push(&dependent.id);
+                }
+            }
+        }
+
+        if visited != self.units.len() {
+            bail!("Los prerrequisitos de la guía forman un ciclo");
+        }
+
+        Ok(())
+    }
+}
+
+/// Eventos de dominio para selfstudy_guides
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum GuideEvent {
+    GuideCreated {
+        guide_id: GuideId,
+        author: String,
+        timestamp: DateTime<Utc>,
+    },
+    GuideUnitsUpdated {
+        guide_id: GuideId,
+        version: u32,
+        timestamp: DateTime<Utc>,
+    },
+    GuidePublished {
+        guide_id: GuideId,
+        version: u32,
+        timestamp: DateTime<Utc>,
+    },
+    GuideArchived {
+        guide_id: GuideId,
+        timestamp: DateTime<Utc>,
+    },
+    SubmissionEvaluated {
+        guide_id: GuideId,
+        objective_code: String,
+        learner: String,
+        score: f64,
+        passed: bool,
+        evaluator: EvaluatorIdentity,
+        timestamp: DateTime<Utc>,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unit(title: &str, prerequisites: &[&UnitId]) -> GuideUnit {
+        GuideUnit {
+            id: UnitId::new(),
+            title: title.to_string(),
+            summary: String::new(),
+            prerequisites: prerequisites.iter().map(|id| (*id).clone()).collect(),
+            objectives: vec![LearningObjective {
+                code: title.to_string(),
+                description: String::new(),
+                activity_iri: format!("https://keiko-dapp.xyz/activities/{title}"),
+                mastery_score: None,
+                rubric: None,
+            }],
+            estimated_minutes: None,
+        }
+    }
+
+    fn guide(units: Vec<GuideUnit>) -> Guide {
+        Guide {
+            id: GuideId::new(),
+            author: "author".to_string(),
+            title: "Fracciones".to_string(),
+            description: String::new(),
+            language: "es".to_string(),
+            subject: None,
+            status: GuideStatus::Draft,
+            units,
+            version: 1,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn prerequisite_chains_are_valid() {
+        let basics = unit("basics", &[]);
+        let sums = unit("sums", &[&basics.id]);
+        let products = unit("products", &[&basics.id, &sums.id]);
+
+        assert!(guide(vec![products, sums, basics]).validate_units().is_ok());
+    }
+
+    #[test]
+    fn prerequisite_cycles_are_rejected() {
+        let mut first = unit("first", &[]);
+        let second = unit("second", &[&first.id]);
+        first.prerequisites.push(second.id.clone());
+        let err = guide(vec![first, second]).validate_units().unwrap_err();
+        assert!(err.to_string().contains("ciclo"));
+
+        let mut alone = unit("alone", &[]);
+        alone.prerequisites.push(alone.id.clone());
+        assert!(guide(vec![alone]).validate_units().is_err());
+    }
+
+    #[test]
+    fn missing_prerequisites_are_rejected() {
+        let orphan = unit("orphan", &[&UnitId::new()]);
+
+        let err = guide(vec![orphan]).validate_units().unwrap_err();
+        assert!(err.to_string().contains("inexistente"));
+    }
+}
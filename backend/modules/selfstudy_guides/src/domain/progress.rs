@@ -0,0 +1,366 @@
+// Progreso de un estudiante en una guía, derivado de las interacciones de su pasaporte
+// y recomendación de la siguiente unidad
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use learning_passport::domain::LearningInteraction;
+
+use super::{Guide, GuideId, GuideUnit, LearningObjective, UnitId};
+
+/// Verbos xAPI que cuentan como cumplimiento de un objetivo
+pub const COMPLETION_VERBS: [&str; 3] = [
+    "http://adlnet.gov/expapi/verbs/completed",
+    "http://adlnet.gov/expapi/verbs/passed",
+    "http://adlnet.gov/expapi/verbs/mastered",
+];
+
+/// Puntuación media por debajo de la cual conviene repasar un prerrequisito
+pub const REVIEW_SCORE_THRESHOLD: f64 = 0.6;
+
+/// Progreso en un objetivo
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObjectiveProgress {
+    pub code: String,
+    pub attempts: usize,
+    pub best_score: Option<f64>,        // Mejor puntuación escalada registrada
+    pub completed: bool,
+    pub last_activity_at: Option<DateTime<Utc>>,
+}
+
+impl ObjectiveProgress {
+    /// Evaluar un objetivo con las interacciones sobre su actividad
+    pub fn evaluate(objective: &LearningObjective, interactions: &[LearningInteraction]) -> Self {
+        let mut progress = Self {
+            code: objective.code.clone(),
+            attempts: 0,
+            best_score: None,
+            completed: false,
+            last_activity_at: None,
+        };
+
+        for interaction in interactions.iter().filter(|i| i.object == objective.activity_iri) {
+            progress.attempts += 1;
+            progress.last_activity_at = progress.last_activity_at.max(Some(interaction.timestamp));
+
+            let score = interaction.result.as_ref().and_then(|r| r.score);
+            if let Some(score) = score {
+                progress.best_score = Some(progress.best_score.map_or(score, |best| best.max(score)));
+            }
+
+            let succeeded = COMPLETION_VERBS.contains(&interaction.verb.as_str())
+                || interaction.result.as_ref().map(|r| r.success).unwrap_or(false);
+            let mastered = match objective.mastery_score {
+                Some(mastery) => score.map(|s| s >= mastery).unwrap_or(false),
+                None => true,
+            };
+            if succeeded && mastered {
+                progress.completed = true;
+            }
+        }
+
+        progress
+    }
+}
+
+/// Estado de una unidad para un estudiante
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UnitState {
+    Locked,       // Faltan prerrequisitos
+    Available,    // Prerrequisitos cumplidos, sin actividad
+    InProgress,   // Con actividad, objetivos pendientes
+    Completed,    // Todos los objetivos cumplidos
+}
+
+/// Progreso en una unidad
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnitProgress {
+    pub unit_id: UnitId,
+    pub state: UnitState,
+    pub objectives: Vec<ObjectiveProgress>,
+    pub average_score: Option<f64>,     // Media de las mejores puntuaciones de sus objetivos
+}
+
+/// Progreso de un estudiante en una guía
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GuideProgress {
+    pub guide_id: GuideId,
+    pub guide_version: u32,
+    pub learner: String,
+    pub units: Vec<UnitProgress>,
+    pub completed_units: usize,
+    pub computed_at: DateTime<Utc>,
+}
+
+impl GuideProgress {
+    /// Derivar el progreso a partir de las interacciones del pasaporte del estudiante
+    pub fn derive(guide: &Guide, learner: &str, interactions: &[LearningInteraction]) -> Self {
+        // Primero los objetivos; el estado de cada unidad depende del de sus prerrequisitos
+        let mut evaluated: HashMap<&UnitId, (Vec<ObjectiveProgress>, Option<f64>)> = HashMap::new();
+        for unit in &guide.units {
+            let objectives: Vec<ObjectiveProgress> =
+                unit.objectives.iter().map(|o| ObjectiveProgress::evaluate(o, interactions)).collect();
+            let scores: Vec<f64> = objectives.iter().filter_map(|o| o.best_score).collect();
+            let average_score =
+                (!scores.is_empty()).then(|| scores.iter().sum::<f64>() / scores.len() as f64);
+            evaluated.insert(&unit.id, (objectives, average_score));
+        }
+
+        let completed = |unit: &GuideUnit| evaluated[&unit.id].0.iter().all(|o| o.completed);
+
+        let units: Vec<UnitProgress> = guide
+            .units
+            .iter()
+            .map(|unit| {
+                let (objectives, average_score) = evaluated[&unit.id].clone();
+                let prerequisites_met = unit
+                    .prerequisites
+                    .iter()
+                    .all(|p| guide.unit(p).map(completed).unwrap_or(false));
+
+                let state = if completed(unit) {
+                    UnitState::Completed
+                } else if !prerequisites_met {
+                    UnitState::Locked
+                } else if objectives.iter().any(|o| o.attempts > 0) {
+                    UnitState::InProgress
+                } else {
+                    UnitState::Available
+                };
+
+                UnitProgress { unit_id: unit.id.clone(), state, objectives, average_score }
+            })
+            .collect();
+
+        Self {
+            guide_id: guide.id.clone(),
+            guide_version: guide.version,
+            learner: learner.to_string(),
+            completed_units: units.iter().filter(|u| u.state == UnitState::Completed).count(),
+            units,
+            computed_at: Utc::now(),
+        }
+    }
+
+    pub fn unit(&self, unit_id: &UnitId) -> Option<&UnitProgress> {
+        self.units.iter().find(|u| &u.unit_id == unit_id)
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.completed_units == self.units.len()
+    }
+
+    /// Recomendar la siguiente unidad
+    ///
+    /// Se continúa primero lo empezado; entre las unidades disponibles se prefiere la que
+    /// tiene prerrequisitos mejor dominados y, a igualdad, el orden del autor. Si el
+    /// prerrequisito más débil de la unidad elegida quedó por debajo de
+    /// `REVIEW_SCORE_THRESHOLD`, se recomienda repasarlo antes.
+    pub fn next_recommended_unit(&self, guide: &Guide) -> Option<UnitRecommendation> {
+        let weakest_prerequisite = |unit: &GuideUnit| -> Option<(UnitId, f64)> {
+            unit.prerequisites
+                .iter()
+                .filter_map(|p| self.unit(p).and_then(|u| u.average_score).map(|s| (p.clone(), s)))
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+        };
+        let readiness = |unit: &GuideUnit| weakest_prerequisite(unit).map(|(_, s)| s).unwrap_or(1.0);
+
+        let mut candidates: Vec<(&GuideUnit, &UnitProgress)> = guide
+            .units
+            .iter()
+            .filter_map(|unit| self.unit(&unit.id).map(|progress| (unit, progress)))
+            .filter(|(_, progress)| matches!(progress.state, UnitState::Available | UnitState::InProgress))
+            .collect();
+
+        candidates.sort_by(|(a, pa), (b, pb)| {
+            let in_progress = |p: &UnitProgress| p.state == UnitState::InProgress;
+            in_progress(pb)
+                .cmp(&in_progress(pa))
+                .then(readiness(b).total_cmp(&readiness(a)))
+                .then(guide.position_of(&a.id).cmp(&guide.position_of(&b.id)))
+        });
+
+        let (unit, progress) = candidates.first()?;
+
+        if let Some((prerequisite, score)) = weakest_prerequisite(unit) {
+            if score < REVIEW_SCORE_THRESHOLD {
+                return Some(UnitRecommendation {
+                    unit_id: prerequisite,
+                    reason: RecommendationReason::Review { before: unit.id.clone(), average_score: score },
+                });
+            }
+        }
+
+        let reason = match progress.state {
+            UnitState::InProgress => RecommendationReason::Continue,
+            _ => RecommendationReason::Start,
+        };
+
+        Some(UnitRecommendation { unit_id: unit.id.clone(), reason })
+    }
+}
+
+/// Motivo de una recomendación
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RecommendationReason {
+    Continue,                                         // Unidad empezada y sin terminar
+    Start,                                            // Unidad nueva con prerrequisitos cumplidos
+    Review { before: UnitId, average_score: f64 },    // Repasar un prerrequisito flojo antes de avanzar
+}
+
+/// Siguiente unidad recomendada
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnitRecommendation {
+    pub unit_id: UnitId,
+    pub reason: RecommendationReason,
+}
+
+#[cfg(test)]
+mod tests {
+    use learning_passport::domain::{
+        LearningInteractionId, LearningPassportId, LearningResult, CURRENT_PAYLOAD_VERSION,
+    };
+
+    use super::*;
+    use crate::domain::{GuideStatus, LearningObjective};
+
+    const COMPLETED: &str = "http://adlnet.gov/expapi/verbs/completed";
+    const ATTEMPTED: &str = "http://adlnet.gov/expapi/verbs/attempted";
+
+    fn unit(title: &str, prerequisites: &[&UnitId]) -> GuideUnit {
+        GuideUnit {
+            id: UnitId::new(),
+            title: title.to_string(),
+            summary: String::new(),
+            prerequisites: prerequisites.iter().map(|id| (*id).clone()).collect(),
+            objectives: vec![LearningObjective {
+                code: title.to_string(),
+                description: String::new(),
+                activity_iri: activity(title),
+                mastery_score: None,
+                rubric: None,
+            }],
+            estimated_minutes: None,
+        }
+    }
+
+    fn activity(title: &str) -> String {
+        format!("https://keiko-dapp.xyz/activities/{title}")
+    }
+
+    fn interaction(title: &str, verb: &str, score: f64) -> LearningInteraction {
+        LearningInteraction {
+            id: LearningInteractionId::new(),
+            passport_id: LearningPassportId::new(),
+            actor: "learner".to_string(),
+            verb: verb.to_string(),
+            object: activity(title),
+            activity_type: None,
+            result: Some(LearningResult {
+                success: false,
+                completion: None,
+                score: Some(score),
+                duration: None,
+                response: None,
+            }),
+            context: None,
+            timestamp: Utc::now(),
+            verifying_key: "key".to_string(),
+            signature: None,
+            authority: None,
+            stored_in_blockchain: false,
+            payload_version: CURRENT_PAYLOAD_VERSION,
+            self_reported: false,
+        }
+    }
+
+    /// basics ← sums ← products, y basics ← decimals
+    fn guide() -> Guide {
+        let basics = unit("basics", &[]);
+        let sums = unit("sums", &[&basics.id]);
+        let decimals = unit("decimals", &[&basics.id]);
+        let products = unit("products", &[&sums.id]);
+
+        Guide {
+            id: GuideId::new(),
+            author: "author".to_string(),
+            title: "Fracciones".to_string(),
+            description: String::new(),
+            language: "es".to_string(),
+            subject: None,
+            status: GuideStatus::Published,
+            units: vec![basics, sums, decimals, products],
+            version: 1,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn states(progress: &GuideProgress) -> Vec<UnitState> {
+        progress.units.iter().map(|unit| unit.state).collect()
+    }
+
+    #[test]
+    fn units_unlock_as_their_prerequisites_are_completed() {
+        let guide = guide();
+
+        let progress = GuideProgress::derive(&guide, "learner", &[]);
+        assert_eq!(states(&progress), [UnitState::Available, UnitState::Locked, UnitState::Locked, UnitState::Locked]);
+
+        let progress = GuideProgress::derive(&guide, "learner", &[interaction("basics", COMPLETED, 0.9)]);
+        assert_eq!(
+            states(&progress),
+            [UnitState::Completed, UnitState::Available, UnitState::Available, UnitState::Locked]
+        );
+        assert_eq!(progress.completed_units, 1);
+    }
+
+    #[test]
+    fn recommendations_follow_the_author_order_among_new_units() {
+        let guide = guide();
+        let progress = GuideProgress::derive(&guide, "learner", &[interaction("basics", COMPLETED, 0.9)]);
+
+        let recommendation = progress.next_recommended_unit(&guide).unwrap();
+        assert_eq!(recommendation.unit_id, guide.units[1].id);
+        assert_eq!(recommendation.reason, RecommendationReason::Start);
+    }
+
+    #[test]
+    fn started_units_are_recommended_before_new_ones() {
+        let guide = guide();
+        let interactions = [interaction("basics", COMPLETED, 0.9), interaction("decimals", ATTEMPTED, 0.3)];
+        let progress = GuideProgress::derive(&guide, "learner", &interactions);
+
+        let recommendation = progress.next_recommended_unit(&guide).unwrap();
+        assert_eq!(recommendation.unit_id, guide.units[2].id);
+        assert_eq!(recommendation.reason, RecommendationReason::Continue);
+    }
+
+    #[test]
+    fn weak_prerequisites_are_reviewed_before_moving_on() {
+        let guide = guide();
+        let progress = GuideProgress::derive(&guide, "learner", &[interaction("basics", COMPLETED, 0.4)]);
+
+        let recommendation = progress.next_recommended_unit(&guide).unwrap();
+        assert_eq!(recommendation.unit_id, guide.units[0].id);
+        assert_eq!(
+            recommendation.reason,
+            RecommendationReason::Review { before: guide.units[1].id.clone(), average_score: 0.4 }
+        );
+    }
+
+    #[test]
+    fn finished_guides_have_no_recommendation() {
+        let guide = guide();
+        let interactions: Vec<LearningInteraction> = ["basics", "sums", "decimals", "products"]
+            .into_iter()
+            .map(|title| interaction(title, COMPLETED, 1.0))
+            .collect();
+        let progress = GuideProgress::derive(&guide, "learner", &interactions);
+
+        assert!(progress.is_complete());
+        assert!(progress.next_recommended_unit(&guide).is_none());
+    }
+}
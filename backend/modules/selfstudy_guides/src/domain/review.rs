@@ -0,0 +1,221 @@
+// Repetición espaciada (SM-2) a partir de los resultados registrados en el pasaporte
+// El estado se deriva solo de las interacciones: recalcular el historial da el mismo estado
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use learning_passport::domain::{LearningInteraction, LearningResult};
+
+/// Factor de facilidad inicial y mínimo de SM-2
+pub const INITIAL_EASE_FACTOR: f64 = 2.5;
+pub const MIN_EASE_FACTOR: f64 = 1.3;
+
+/// Calidad mínima (0-5) para considerar un repaso superado
+pub const PASSING_QUALITY: u8 = 3;
+
+/// Intervalo máximo entre repasos (unos 100 años); el factor de facilidad no tiene techo
+pub const MAX_INTERVAL_DAYS: u32 = 36_500;
+
+/// Calidad de recuerdo (0-5) de un resultado; `None` si el resultado no evalúa nada
+pub fn review_quality(result: &LearningResult) -> Option<u8> {
+    match result.score {
+        Some(score) if score.is_finite() => Some((score.clamp(0.0, 1.0) * 5.0).round() as u8),
+        _ if result.success => Some(4),
+        _ if result.completion.is_some() || result.response.is_some() => Some(1),
+        _ => None,
+    }
+}
+
+/// Clave de orden de las interacciones: marca de tiempo y, a igualdad, ID
+fn review_key(interaction: &LearningInteraction) -> (DateTime<Utc>, Uuid) {
+    (interaction.timestamp, interaction.id.0)
+}
+
+/// Estado de memoria de un estudiante sobre una actividad
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MemoryState {
+    pub learner: String,
+    pub activity_iri: String,
+    pub repetitions: u32,               // Repasos superados consecutivos
+    pub ease_factor: f64,
+    pub interval_days: u32,
+    pub lapses: u32,                    // Veces que se olvidó tras haberla superado
+    pub reviews: u32,                   // Interacciones aplicadas
+    pub last_quality: u8,
+    pub last_reviewed_at: DateTime<Utc>,
+    pub last_interaction_id: Uuid,      // Cursor para aplicar solo interacciones nuevas
+    pub due_at: DateTime<Utc>,
+}
+
+impl MemoryState {
+    /// Recalcular el estado desde el historial completo de una actividad
+    pub fn replay(learner: &str, activity_iri: &str, interactions: &[LearningInteraction]) -> Option<Self> {
+        let mut reviews: Vec<&LearningInteraction> = interactions
+            .iter()
+            .filter(|i| i.object == activity_iri && i.result.as_ref().and_then(review_quality).is_some())
+            .collect();
+        reviews.sort_by_key(|i| review_key(i));
+
+        let mut state: Option<Self> = None;
+        for interaction in reviews {
+            match state.as_mut() {
+                Some(state) => {
+                    state.apply(interaction);
+                }
+                None => state = Self::first_review(learner, interaction),
+            }
+        }
+
+        state
+    }
+
+    /// Estado tras la primera interacción evaluada
+    pub fn first_review(learner: &str, interaction: &LearningInteraction) -> Option<Self> {
+        let quality = interaction.result.as_ref().and_then(review_quality)?;
+
+        let mut state = Self {
+            learner: learner.to_string(),
+            activity_iri: interaction.object.clone(),
+            repetitions: 0,
+            ease_factor: INITIAL_EASE_FACTOR,
+            interval_days: 0,
+            lapses: 0,
+            reviews: 0,
+            last_quality: quality,
+            last_reviewed_at: interaction.timestamp,
+            last_interaction_id: interaction.id.0,
+            due_at: interaction.timestamp,
+        };
+        state.schedule(quality, interaction);
+
+        Some(state)
+    }
+
+    /// ¿Es la interacción posterior al cursor del estado?
+    pub fn is_newer(&self, interaction: &LearningInteraction) -> bool {
+        review_key(interaction) > (self.last_reviewed_at, self.last_interaction_id)
+    }
+
+    /// Aplicar una interacción posterior al cursor; devuelve si cambió el estado
+    pub fn apply(&mut self, interaction: &LearningInteraction) -> bool {
+        if interaction.object != self.activity_iri || !self.is_newer(interaction) {
+            return false;
+        }
+        let Some(quality) = interaction.result.as_ref().and_then(review_quality) else {
+            return false;
+        };
+
+        self.schedule(quality, interaction);
+        true
+    }
+
+    /// Paso de SM-2; el próximo repaso se cuenta desde el momento de la interacción
+    fn schedule(&mut self, quality: u8, interaction: &LearningInteraction) {
+        if quality < PASSING_QUALITY {
+            if self.repetitions > 0 {
+                self.lapses += 1;
+            }
+            self.repetitions = 0;
+            self.interval_days = 1;
+        } else {
+            self.repetitions += 1;
+            self.interval_days = match self.repetitions {
+                1 => 1,
+                2 => 6,
+                _ => (f64::from(self.interval_days) * self.ease_factor).round().min(f64::from(MAX_INTERVAL_DAYS)) as u32,
+            };
+        }
+
+        let q = f64::from(5 - quality.min(5));
+        self.ease_factor = (self.ease_factor + 0.1 - q * (0.08 + q * 0.02)).max(MIN_EASE_FACTOR);
+
+        self.reviews += 1;
+        self.last_quality = quality;
+        self.last_reviewed_at = interaction.timestamp;
+        self.last_interaction_id = interaction.id.0;
+        self.due_at = interaction
+            .timestamp
+            .checked_add_signed(Duration::days(i64::from(self.interval_days)))
+            .unwrap_or(DateTime::<Utc>::MAX_UTC);
+    }
+
+    pub fn is_due(&self, at: DateTime<Utc>) -> bool {
+        self.due_at <= at
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use learning_passport::domain::{LearningInteractionId, LearningPassportId, CURRENT_PAYLOAD_VERSION};
+
+    use super::*;
+
+    const ACTIVITY: &str = "https://keiko-dapp.xyz/activities/fracciones";
+
+    fn interaction(days: i64, score: f64) -> LearningInteraction {
+        LearningInteraction {
+            id: LearningInteractionId::new(),
+            passport_id: LearningPassportId::new(),
+            actor: "learner".to_string(),
+            verb: "http://adlnet.gov/expapi/verbs/answered".to_string(),
+            object: ACTIVITY.to_string(),
+            activity_type: None,
+            result: Some(LearningResult {
+                success: score >= 0.6,
+                completion: Some(1.0),
+                score: Some(score),
+                duration: None,
+                response: None,
+            }),
+            context: None,
+            timestamp: DateTime::<Utc>::UNIX_EPOCH + Duration::days(days),
+            verifying_key: "key".to_string(),
+            signature: None,
+            authority: None,
+            stored_in_blockchain: false,
+            payload_version: CURRENT_PAYLOAD_VERSION,
+            self_reported: false,
+        }
+    }
+
+    #[test]
+    fn sm2_grows_intervals_and_resets_on_lapse() {
+        let history = vec![interaction(0, 1.0), interaction(1, 0.8), interaction(7, 1.0)];
+        let state = MemoryState::replay("learner", ACTIVITY, &history).unwrap();
+        assert_eq!(state.repetitions, 3);
+        assert_eq!(state.interval_days, 16);
+        assert_eq!(state.due_at, history[2].timestamp + Duration::days(16));
+
+        let mut lapsed = state.clone();
+        assert!(lapsed.apply(&interaction(22, 0.2)));
+        assert_eq!((lapsed.repetitions, lapsed.interval_days, lapsed.lapses), (0, 1, 1));
+        assert!(lapsed.ease_factor < state.ease_factor);
+    }
+
+    #[test]
+    fn long_perfect_streaks_cap_the_interval() {
+        let history: Vec<LearningInteraction> = (0..40).map(|day| interaction(day, 1.0)).collect();
+
+        let state = MemoryState::replay("learner", ACTIVITY, &history).unwrap();
+
+        assert_eq!(state.repetitions, 40);
+        assert_eq!(state.interval_days, MAX_INTERVAL_DAYS);
+        assert_eq!(state.due_at, history[39].timestamp + Duration::days(i64::from(MAX_INTERVAL_DAYS)));
+    }
+
+    #[test]
+    fn incremental_updates_match_full_replay() {
+        let history = vec![interaction(0, 0.4), interaction(1, 1.0), interaction(2, 0.6), interaction(8, 0.9)];
+
+        let mut incremental = MemoryState::first_review("learner", &history[0]).unwrap();
+        for i in &history[1..] {
+            assert!(incremental.apply(i));
+        }
+        assert!(!incremental.apply(&history[1]));
+
+        let mut shuffled = history.clone();
+        shuffled.reverse();
+        assert_eq!(MemoryState::replay("learner", ACTIVITY, &shuffled), Some(incremental));
+    }
+}
@@ -0,0 +1,119 @@
+// Persistencia de guías de autoestudio; las unidades se guardan como JSON
+
+use anyhow::Result;
+use sqlx::PgPool;
+
+use crate::domain::{Guide, GuideId, GuideStatus};
+
+pub struct GuideRepository {
+    pool: PgPool,
+}
+
+impl GuideRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Crear o actualizar una guía; las unidades se guardan como JSON
+    pub async fn upsert_guide(&self, guide: &Guide) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO selfstudy_guides (
+                id, author, title, description, language, subject, status, version, units,
+                created_at, updated_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            ON CONFLICT (id) DO UPDATE
+            SET title = EXCLUDED.title, description = EXCLUDED.description, language = EXCLUDED.language,
+                subject = EXCLUDED.subject, status = EXCLUDED.status, version = EXCLUDED.version,
+                units = EXCLUDED.units, updated_at = EXCLUDED.updated_at
+            "#,
+            guide.id.0,
+            guide.author,
+            guide.title,
+            guide.description,
+            guide.language,
+            guide.subject,
+            guide.status.as_str(),
+            i32::try_from(guide.version)?,
+            serde_json::to_value(&guide.units)?,
+            guide.created_at,
+            guide.updated_at
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Obtener una guía por ID
+    pub async fn get_guide(&self, guide_id: &GuideId) -> Result<Option<Guide>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT id, author, title, description, language, subject, status, version, units,
+                   created_at, updated_at
+            FROM selfstudy_guides
+            WHERE id = $1
+            "#,
+            guide_id.0
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match row {
+            Some(row) => Ok(Some(Guide {
+                id: GuideId(row.id),
+                author: row.author,
+                title: row.title,
+                description: row.description,
+                language: row.language,
+                subject: row.subject,
+                status: row.status.parse()?,
+                units: serde_json::from_value(row.units)?,
+                version: u32::try_from(row.version)?,
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+            })),
+            None => Ok(None),
+        }
+    }
+
+    /// Obtener los IDs de las guías en un estado, opcionalmente filtradas por materia
+    pub async fn get_guide_ids_by_status(
+        &self,
+        status: GuideStatus,
+        subject: Option<&str>,
+    ) -> Result<Vec<GuideId>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT id
+            FROM selfstudy_guides
+            WHERE status = $1 AND ($2::text IS NULL OR subject = $2)
+            ORDER BY updated_at DESC
+            "#,
+            status.as_str(),
+            subject
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(|row| GuideId(row.id)).collect())
+    }
+
+    /// Obtener los IDs de las guías de un autor
+    pub async fn get_guide_ids_by_author(&self, author: &str) -> Result<Vec<GuideId>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT id
+            FROM selfstudy_guides
+            WHERE author = $1
+            ORDER BY updated_at DESC
+            "#,
+            author
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(|row| GuideId(row.id)).collect())
+    }
+}
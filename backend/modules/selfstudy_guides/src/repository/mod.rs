@@ -0,0 +1,7 @@
+// Repositorios para persistencia del módulo selfstudy_guides
+
+pub mod guide;
+pub mod review;
+
+pub use guide::GuideRepository;
+pub use review::ReviewStateRepository;
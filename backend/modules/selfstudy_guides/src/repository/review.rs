@@ -0,0 +1,144 @@
+// Persistencia del estado de repetición espaciada por estudiante y actividad
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+use crate::domain::MemoryState;
+
+pub struct ReviewStateRepository {
+    pool: PgPool,
+}
+
+impl ReviewStateRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Crear o reemplazar el estado de una actividad
+    pub async fn upsert_state(&self, state: &MemoryState) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO review_states (
+                learner, activity_iri, repetitions, ease_factor, interval_days, lapses, reviews,
+                last_quality, last_reviewed_at, last_interaction_id, due_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            ON CONFLICT (learner, activity_iri) DO UPDATE
+            SET repetitions = EXCLUDED.repetitions, ease_factor = EXCLUDED.ease_factor,
+                interval_days = EXCLUDED.interval_days, lapses = EXCLUDED.lapses, reviews = EXCLUDED.reviews,
+                last_quality = EXCLUDED.last_quality, last_reviewed_at = EXCLUDED.last_reviewed_at,
+                last_interaction_id = EXCLUDED.last_interaction_id, due_at = EXCLUDED.due_at
+            "#,
+            state.learner,
+            state.activity_iri,
+            i32::try_from(state.repetitions)?,
+            state.ease_factor,
+            i32::try_from(state.interval_days)?,
+            i32::try_from(state.lapses)?,
+            i32::try_from(state.reviews)?,
+            i16::from(state.last_quality),
+            state.last_reviewed_at,
+            state.last_interaction_id,
+            state.due_at
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Obtener el estado de un estudiante sobre una actividad
+    pub async fn get_state(&self, learner: &str, activity_iri: &str) -> Result<Option<MemoryState>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT learner, activity_iri, repetitions, ease_factor, interval_days, lapses, reviews,
+                   last_quality, last_reviewed_at, last_interaction_id, due_at
+            FROM review_states
+            WHERE learner = $1 AND activity_iri = $2
+            "#,
+            learner,
+            activity_iri
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match row {
+            Some(row) => Ok(Some(MemoryState {
+                learner: row.learner,
+                activity_iri: row.activity_iri,
+                repetitions: u32::try_from(row.repetitions)?,
+                ease_factor: row.ease_factor,
+                interval_days: u32::try_from(row.interval_days)?,
+                lapses: u32::try_from(row.lapses)?,
+                reviews: u32::try_from(row.reviews)?,
+                last_quality: u8::try_from(row.last_quality)?,
+                last_reviewed_at: row.last_reviewed_at,
+                last_interaction_id: row.last_interaction_id,
+                due_at: row.due_at,
+            })),
+            None => Ok(None),
+        }
+    }
+
+    /// Obtener los estados de un estudiante, de la revisión más próxima a la más lejana
+    pub async fn get_states_by_learner(&self, learner: &str) -> Result<Vec<MemoryState>> {
+        self.get_states_due_before(learner, None).await
+    }
+
+    /// Obtener los estados de un estudiante con repaso pendiente antes de un momento
+    pub async fn get_due_states(&self, learner: &str, until: DateTime<Utc>) -> Result<Vec<MemoryState>> {
+        self.get_states_due_before(learner, Some(until)).await
+    }
+
+    async fn get_states_due_before(&self, learner: &str, until: Option<DateTime<Utc>>) -> Result<Vec<MemoryState>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT learner, activity_iri, repetitions, ease_factor, interval_days, lapses, reviews,
+                   last_quality, last_reviewed_at, last_interaction_id, due_at
+            FROM review_states
+            WHERE learner = $1 AND ($2::timestamptz IS NULL OR due_at <= $2)
+            ORDER BY due_at ASC
+            "#,
+            learner,
+            until
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut states = Vec::new();
+
+        for row in rows {
+            states.push(MemoryState {
+                learner: row.learner,
+                activity_iri: row.activity_iri,
+                repetitions: u32::try_from(row.repetitions)?,
+                ease_factor: row.ease_factor,
+                interval_days: u32::try_from(row.interval_days)?,
+                lapses: u32::try_from(row.lapses)?,
+                reviews: u32::try_from(row.reviews)?,
+                last_quality: u8::try_from(row.last_quality)?,
+                last_reviewed_at: row.last_reviewed_at,
+                last_interaction_id: row.last_interaction_id,
+                due_at: row.due_at,
+            });
+        }
+
+        Ok(states)
+    }
+
+    /// Borrar los estados de un estudiante antes de recalcularlos
+    pub async fn delete_states_by_learner(&self, learner: &str) -> Result<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM review_states
+            WHERE learner = $1
+            "#,
+            learner
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
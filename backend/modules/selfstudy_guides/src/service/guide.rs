@@ -0,0 +1,307 @@
+// Autoría de guías (borrador → publicada → archivada) y progreso derivado del pasaporte
+// Las respuestas a objetivos con rúbrica se evalúan y quedan registradas en el pasaporte
+
+use std::sync::Arc;
+
+use anyhow::{anyhow, bail, Result};
+use chrono::Utc;
+
+use learning_passport::domain::{LearningContext, LearningInteraction, LearningResult};
+use learning_passport::service::LearningPassportService;
+
+use crate::domain::{
+    Evaluation, Guide, GuideId, GuideProgress, GuideStatus, GuideUnit, Submission, SubmissionKind,
+    UnitRecommendation,
+};
+use crate::repository::GuideRepository;
+use crate::service::Evaluator;
+
+/// Verbos xAPI con los que se registra una respuesta evaluada
+pub const PASSED_VERB: &str = "http://adlnet.gov/expapi/verbs/passed";
+pub const FAILED_VERB: &str = "http://adlnet.gov/expapi/verbs/failed";
+
+/// Tipo de actividad xAPI de una respuesta evaluada
+pub const ASSESSMENT_ACTIVITY_TYPE: &str = "http://adlnet.gov/expapi/activities/assessment";
+
+/// Extensiones de contexto xAPI con los datos de la evaluación
+pub const EVALUATOR_EXTENSION: &str = "https://keiko-dapp.xyz/xapi/extensions/evaluator";
+pub const CRITERIA_EXTENSION: &str = "https://keiko-dapp.xyz/xapi/extensions/rubric-criteria";
+pub const GUIDE_EXTENSION: &str = "https://keiko-dapp.xyz/xapi/extensions/selfstudy-guide";
+
+pub struct SelfStudyGuideService {
+    repository: GuideRepository,
+    passport: Arc<LearningPassportService>,
+    evaluator: Arc<dyn Evaluator>,
+}
+
+impl SelfStudyGuideService {
+    pub fn new(
+        repository: GuideRepository,
+        passport: Arc<LearningPassportService>,
+        evaluator: Arc<dyn Evaluator>,
+    ) -> Self {
+        Self { repository, passport, evaluator }
+    }
+
+    /// Crear una guía en borrador, sin unidades
+    pub async fn create_guide(
+        &self,
+        author: &str,
+        title: &str,
+        description: &str,
+        language: &str,
+        subject: Option<String>,
+    ) -> Result<Guide> {
+        if title.trim().is_empty() {
+            bail!("La guía necesita un título");
+        }
+        if language.trim().is_empty() {
+            bail!("La guía necesita un idioma");
+        }
+
+        let now = Utc::now();
+        let guide = Guide {
+            id: GuideId::new(),
+            author: author.to_string(),
+            title: title.to_string(),
+            description: description.to_string(),
+            language: language.to_string(),
+            subject,
+            status: GuideStatus::Draft,
+            units: Vec::new(),
+            version: 1,
+            created_at: now,
+            updated_at: now,
+        };
+
+        self.repository.upsert_guide(&guide).await?;
+
+        // TODO: Emitir evento de dominio
+        // self.emit_event(GuideEvent::GuideCreated { ... }).await?;
+
+        Ok(guide)
+    }
+
+    /// Reemplazar las unidades de una guía (solo el autor)
+    ///
+    /// En una guía publicada el cambio crea una nueva versión.
+    pub async fn save_units(&self, guide_id: &GuideId, author: &str, units: Vec<GuideUnit>) -> Result<Guide> {
+        let mut guide = self.get_existing_guide(guide_id).await?;
+
+        if guide.author != author {
+            bail!("Solo el autor puede modificar la guía");
+        }
+        if guide.status == GuideStatus::Archived {
+            bail!("La guía está archivada");
+        }
+
+        guide.units = units;
+        guide.validate_units()?;
+
+        if guide.status == GuideStatus::Published {
+            guide.version += 1;
+        }
+        guide.updated_at = Utc::now();
+
+        self.repository.upsert_guide(&guide).await?;
+
+        // TODO: Emitir evento de dominio
+        // self.emit_event(GuideEvent::GuideUnitsUpdated { ... }).await?;
+
+        Ok(guide)
+    }
+
+    /// Publicar una guía en borrador
+    pub async fn publish_guide(&self, guide_id: &GuideId, author: &str) -> Result<Guide> {
+        let mut guide = self.get_existing_guide(guide_id).await?;
+
+        if guide.author != author {
+            bail!("Solo el autor puede publicar la guía");
+        }
+        if guide.status != GuideStatus::Draft {
+            bail!("Solo se pueden publicar guías en borrador");
+        }
+        if guide.units.is_empty() {
+            bail!("La guía necesita al menos una unidad para publicarse");
+        }
+        guide.validate_units()?;
+
+        guide.status = GuideStatus::Published;
+        guide.updated_at = Utc::now();
+
+        self.repository.upsert_guide(&guide).await?;
+
+        // TODO: Emitir evento de dominio
+        // self.emit_event(GuideEvent::GuidePublished { ... }).await?;
+
+        Ok(guide)
+    }
+
+    /// Archivar una guía; deja de listarse pero el progreso sigue consultable
+    pub async fn archive_guide(&self, guide_id: &GuideId, author: &str) -> Result<Guide> {
+        let mut guide = self.get_existing_guide(guide_id).await?;
+
+        if guide.author != author {
+            bail!("Solo el autor puede archivar la guía");
+        }
+        if guide.status == GuideStatus::Archived {
+            return Ok(guide);
+        }
+
+        guide.status = GuideStatus::Archived;
+        guide.updated_at = Utc::now();
+
+        self.repository.upsert_guide(&guide).await?;
+
+        // TODO: Emitir evento de dominio
+        // self.emit_event(GuideEvent::GuideArchived { ... }).await?;
+
+        Ok(guide)
+    }
+
+    pub async fn get_guide(&self, guide_id: &GuideId) -> Result<Option<Guide>> {
+        self.repository.get_guide(guide_id).await
+    }
+
+    /// Guías publicadas, opcionalmente de una materia
+    pub async fn get_published_guides(&self, subject: Option<&str>) -> Result<Vec<Guide>> {
+        let mut guides = Vec::new();
+
+        for guide_id in self.repository.get_guide_ids_by_status(GuideStatus::Published, subject).await? {
+            if let Some(guide) = self.repository.get_guide(&guide_id).await? {
+                guides.push(guide);
+            }
+        }
+
+        Ok(guides)
+    }
+
+    /// Guías de un autor en cualquier estado
+    pub async fn get_guides_by_author(&self, author: &str) -> Result<Vec<Guide>> {
+        let mut guides = Vec::new();
+
+        for guide_id in self.repository.get_guide_ids_by_author(author).await? {
+            if let Some(guide) = self.repository.get_guide(&guide_id).await? {
+                guides.push(guide);
+            }
+        }
+
+        Ok(guides)
+    }
+
+    /// Progreso de un estudiante, derivado de las interacciones de su pasaporte
+    pub async fn get_progress(&self, guide_id: &GuideId, learner: &str) -> Result<GuideProgress> {
+        let guide = self.get_existing_guide(guide_id).await?;
+        self.derive_progress(&guide, learner).await
+    }
+
+    /// Siguiente unidad recomendada; `None` si la guía está completa
+    pub async fn next_recommended_unit(
+        &self,
+        guide_id: &GuideId,
+        learner: &str,
+    ) -> Result<Option<UnitRecommendation>> {
+        let guide = self.get_existing_guide(guide_id).await?;
+        let progress = self.derive_progress(&guide, learner).await?;
+
+        Ok(progress.next_recommended_unit(&guide))
+    }
+
+    /// Evaluar la respuesta de un estudiante a un objetivo con rúbrica
+    ///
+    /// La evaluación queda registrada en el pasaporte como interacción sobre la actividad
+    /// del objetivo, con la puntuación en el resultado y el evaluador en las extensiones.
+    pub async fn submit_answer(
+        &self,
+        guide_id: &GuideId,
+        objective_code: &str,
+        learner: &str,
+        kind: SubmissionKind,
+        answer: &str,
+    ) -> Result<(Evaluation, LearningInteraction)> {
+        let guide = self.get_existing_guide(guide_id).await?;
+
+        if guide.status != GuideStatus::Published {
+            bail!("Solo se responden guías publicadas");
+        }
+        if answer.trim().is_empty() {
+            bail!("La respuesta está vacía");
+        }
+
+        let (unit, objective) = guide
+            .find_objective(objective_code)
+            .ok_or_else(|| anyhow!("Objetivo no encontrado en la guía: {}", objective_code))?;
+        let rubric = objective
+            .rubric
+            .as_ref()
+            .ok_or_else(|| anyhow!("El objetivo {} no tiene rúbrica de evaluación", objective_code))?;
+
+        let submission = Submission {
+            guide_id: guide.id.clone(),
+            unit_id: unit.id.clone(),
+            objective_code: objective.code.clone(),
+            learner: learner.to_string(),
+            kind,
+            prompt: objective.description.clone(),
+            answer: answer.to_string(),
+            submitted_at: Utc::now(),
+        };
+
+        let evaluation = self.evaluator.evaluate(&submission, rubric).await?;
+
+        let verb = if evaluation.passed { PASSED_VERB } else { FAILED_VERB };
+        let result = LearningResult {
+            success: evaluation.passed,
+            completion: Some(1.0),
+            score: Some(evaluation.score),
+            duration: None,
+            response: Some(submission.answer.clone()),
+        };
+        let context = LearningContext {
+            platform: "keiko-selfstudy".to_string(),
+            language: guide.language.clone(),
+            instructor: None,
+            group: None,
+            extensions: Some(serde_json::json!({
+                EVALUATOR_EXTENSION: evaluation.evaluator,
+                CRITERIA_EXTENSION: evaluation.criteria,
+                GUIDE_EXTENSION: { "id": guide.id.0, "version": guide.version, "unit": unit.id.0 },
+            })),
+        };
+
+        let interaction = self
+            .passport
+            .add_learning_interaction(
+                learner,
+                learner,
+                verb,
+                &objective.activity_iri,
+                Some(ASSESSMENT_ACTIVITY_TYPE),
+                Some(result),
+                Some(context),
+            )
+            .await?;
+
+        // TODO: Emitir evento de dominio
+        // self.emit_event(GuideEvent::SubmissionEvaluated { ... }).await?;
+
+        Ok((evaluation, interaction))
+    }
+
+    async fn derive_progress(&self, guide: &Guide, learner: &str) -> Result<GuideProgress> {
+        if guide.status == GuideStatus::Draft {
+            bail!("La guía aún no está publicada");
+        }
+
+        let interactions = self.passport.get_user_learning_history(learner).await?;
+
+        Ok(GuideProgress::derive(guide, learner, &interactions))
+    }
+
+    async fn get_existing_guide(&self, guide_id: &GuideId) -> Result<Guide> {
+        self.repository
+            .get_guide(guide_id)
+            .await?
+            .ok_or_else(|| anyhow!("Guía no encontrada: {}", guide_id.0))
+    }
+}
@@ -0,0 +1,9 @@
+// Servicios de aplicación para el módulo selfstudy_guides
+
+pub mod evaluator;
+pub mod guide;
+pub mod review;
+
+pub use evaluator::{Evaluator, OpenAiCompatibleEvaluator, RubricEvaluator};
+pub use guide::SelfStudyGuideService;
+pub use review::ReviewScheduler;
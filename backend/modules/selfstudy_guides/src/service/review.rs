@@ -0,0 +1,188 @@
+// Programación de repasos: mantiene el estado SM-2 de cada estudiante y actividad
+// al día con las interacciones evaluadas de su pasaporte
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Duration, NaiveDate, TimeZone, Utc};
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::task::JoinHandle;
+
+use learning_passport::domain::{LearningInteraction, LearningPassportEvent};
+use learning_passport::service::LearningPassportService;
+
+use crate::domain::{review_quality, MemoryState};
+use crate::repository::ReviewStateRepository;
+
+pub struct ReviewScheduler {
+    repository: ReviewStateRepository,
+    passport: Arc<LearningPassportService>,
+}
+
+impl ReviewScheduler {
+    pub fn new(repository: ReviewStateRepository, passport: Arc<LearningPassportService>) -> Self {
+        Self { repository, passport }
+    }
+
+    /// Aplicar una interacción recién registrada en el pasaporte
+    ///
+    /// Si llega fuera de orden respecto al estado guardado, se recalcula la actividad
+    /// desde el historial para que el resultado sea el mismo que un recálculo completo.
+    pub async fn record_interaction(
+        &self,
+        learner: &str,
+        interaction: &LearningInteraction,
+    ) -> Result<Option<MemoryState>> {
+        if interaction.result.as_ref().and_then(review_quality).is_none() {
+            return Ok(None);
+        }
+
+        let state = match self.repository.get_state(learner, &interaction.object).await? {
+            None => MemoryState::first_review(learner, interaction),
+            Some(mut state) if state.is_newer(interaction) => {
+                state.apply(interaction);
+                Some(state)
+            }
+            Some(_) => {
+                let history = self.passport.get_user_learning_history(learner).await?;
+                MemoryState::replay(learner, &interaction.object, &history)
+            }
+        };
+
+        if let Some(state) = &state {
+            self.repository.upsert_state(state).await?;
+        }
+
+        Ok(state)
+    }
+
+    /// Consumir un evento de learning_passport: cada interacción nueva actualiza su repaso
+    pub async fn handle_passport_event(&self, event: &LearningPassportEvent) -> Result<()> {
+        let LearningPassportEvent::InteractionAdded { user_address, interaction_id, .. } = event else {
+            return Ok(());
+        };
+
+        let interactions = self.passport.get_interactions(std::slice::from_ref(interaction_id)).await?;
+        for interaction in &interactions {
+            self.record_interaction(user_address, interaction).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Mantener en segundo plano los repasos al día con los eventos de learning_passport
+    ///
+    /// Si el canal se desborda, los eventos perdidos se registran en el log; `sync_learner`
+    /// pone al día al estudiante a partir de su historial.
+    pub fn spawn_passport_sync(
+        self: Arc<Self>,
+        mut events: broadcast::Receiver<LearningPassportEvent>,
+    ) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                match events.recv().await {
+                    Ok(event) => {
+                        if let Err(err) = self.handle_passport_event(&event).await {
+                            tracing::error!("No se pudo programar el repaso del evento {:?}: {:#}", event, err);
+                        }
+                    }
+                    Err(RecvError::Lagged(missed)) => {
+                        tracing::warn!("Repasos: se perdieron {} eventos de pasaporte", missed);
+                    }
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+
+    /// Poner al día los estados de un estudiante con su historial
+    ///
+    /// Solo se aplican las interacciones posteriores a cada cursor; una actividad con
+    /// interacciones antiguas aún no aplicadas se recalcula entera.
+    pub async fn sync_learner(&self, learner: &str) -> Result<Vec<MemoryState>> {
+        let history = self.passport.get_user_learning_history(learner).await?;
+        let mut updated = Vec::new();
+
+        for (activity_iri, mut interactions) in Self::reviewable_by_activity(&history) {
+            interactions.sort_by_key(|i| (i.timestamp, i.id.0));
+
+            let state = match self.repository.get_state(learner, activity_iri).await? {
+                Some(mut state) => {
+                    let applied = interactions.iter().filter(|i| !state.is_newer(i)).count();
+                    if applied == state.reviews as usize {
+                        let mut changed = false;
+                        for interaction in &interactions {
+                            changed |= state.apply(interaction);
+                        }
+                        changed.then_some(state)
+                    } else {
+                        MemoryState::replay(learner, activity_iri, &history)
+                    }
+                }
+                None => MemoryState::replay(learner, activity_iri, &history),
+            };
+
+            if let Some(state) = state {
+                self.repository.upsert_state(&state).await?;
+                updated.push(state);
+            }
+        }
+
+        Ok(updated)
+    }
+
+    /// Recalcular desde cero todos los estados de un estudiante
+    pub async fn rebuild_learner(&self, learner: &str) -> Result<Vec<MemoryState>> {
+        let history = self.passport.get_user_learning_history(learner).await?;
+
+        self.repository.delete_states_by_learner(learner).await?;
+
+        let mut states = Vec::new();
+        for activity_iri in Self::reviewable_by_activity(&history).into_keys() {
+            if let Some(state) = MemoryState::replay(learner, activity_iri, &history) {
+                self.repository.upsert_state(&state).await?;
+                states.push(state);
+            }
+        }
+
+        Ok(states)
+    }
+
+    /// Actividades con repaso pendiente hasta el final de un día (UTC)
+    pub async fn due_for_review(&self, learner: &str, day: NaiveDate) -> Result<Vec<MemoryState>> {
+        let next_day = day
+            .succ_opt()
+            .and_then(|next| next.and_hms_opt(0, 0, 0))
+            .ok_or_else(|| anyhow!("Fecha fuera de rango: {}", day))?;
+        let end_of_day = Utc.from_utc_datetime(&next_day) - Duration::microseconds(1);
+
+        self.repository.get_due_states(learner, end_of_day).await
+    }
+
+    /// Actividades con repaso pendiente hoy
+    pub async fn due_today(&self, learner: &str) -> Result<Vec<MemoryState>> {
+        self.due_for_review(learner, Utc::now().date_naive()).await
+    }
+
+    pub async fn get_states(&self, learner: &str) -> Result<Vec<MemoryState>> {
+        self.repository.get_states_by_learner(learner).await
+    }
+
+    /// Próximo repaso de una actividad, si ya tiene estado
+    pub async fn next_review_at(&self, learner: &str, activity_iri: &str) -> Result<Option<DateTime<Utc>>> {
+        Ok(self.repository.get_state(learner, activity_iri).await?.map(|state| state.due_at))
+    }
+
+    fn reviewable_by_activity(history: &[LearningInteraction]) -> BTreeMap<&str, Vec<&LearningInteraction>> {
+        let mut by_activity: BTreeMap<&str, Vec<&LearningInteraction>> = BTreeMap::new();
+
+        for interaction in history {
+            if interaction.result.as_ref().and_then(review_quality).is_some() {
+                by_activity.entry(interaction.object.as_str()).or_default().push(interaction);
+            }
+        }
+
+        by_activity
+    }
+}